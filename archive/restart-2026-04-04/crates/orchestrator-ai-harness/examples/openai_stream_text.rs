@@ -25,7 +25,9 @@ async fn main() -> Result<(), HarnessError> {
             StreamEvent::OutputDelta { text, .. } => print!("{text}"),
             StreamEvent::Completed { .. } => println!(),
             StreamEvent::Error { error, .. } => eprintln!("run error: {error}"),
-            StreamEvent::RunStarted { .. } => {}
+            StreamEvent::RunStarted { .. }
+            | StreamEvent::ToolCallDelta { .. }
+            | StreamEvent::ToolCall { .. } => {}
         }
     }
 
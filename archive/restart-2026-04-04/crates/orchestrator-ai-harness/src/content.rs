@@ -1,3 +1,7 @@
+use std::time::Duration;
+
+use crate::provider::ProviderResponseMeta;
+
 /// Input content sent to a model run.
 ///
 /// `v1` is text-first, but the enum is non-exhaustive so new content kinds can
@@ -9,6 +13,16 @@ pub enum InputPart {
     Text(String),
     /// Structured JSON input.
     Json(serde_json::Value),
+    /// Raw image bytes with their MIME type (for example `image/png`).
+    ImageBytes { mime: String, data: bytes::Bytes },
+    /// Tool call the model made in an earlier run, replayed so its result can follow it.
+    ToolCall {
+        call_id: String,
+        name: String,
+        arguments: String,
+    },
+    /// Result of a tool call the model asked for in an earlier run, matched by `call_id`.
+    ToolResult { call_id: String, content: String },
 }
 
 /// Output content produced by a model run.
@@ -19,6 +33,44 @@ pub enum OutputPart {
     Text(String),
     /// Structured JSON output.
     Json(serde_json::Value),
+    /// Tool the model asked to call; answer with `InputPart::ToolResult` using `call_id`.
+    ToolCall {
+        call_id: String,
+        name: String,
+        arguments: String,
+    },
+}
+
+/// Timing measurements recorded by the harness while streaming a run.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, Default)]
+pub struct RunMetrics {
+    /// Time from run start to the first non-empty text delta. `None` when the
+    /// provider produced no deltas (for example a single completed payload).
+    pub ttft: Option<Duration>,
+    /// Time from run start to the terminal completion event.
+    pub total: Duration,
+}
+
+/// Token counts reported by the provider for one run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, Default)]
+pub struct TokenUsage {
+    /// Prompt tokens consumed.
+    pub input_tokens: u64,
+    /// Completion tokens produced.
+    pub output_tokens: u64,
+}
+
+impl TokenUsage {
+    /// Input plus output tokens.
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+
+    /// Adds `other` to these counts.
+    pub fn add(&mut self, other: &TokenUsage) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+    }
 }
 
 /// Final aggregated output for a completed run.
@@ -28,9 +80,31 @@ pub struct RunOutput {
     pub parts: Vec<OutputPart>,
     /// Vendor-specific finish reason when available (for example `stop`).
     pub finish_reason: Option<String>,
+    /// Harness-measured latency metrics. Providers leave this at its default.
+    #[serde(default)]
+    pub metrics: RunMetrics,
+    /// Provider response metadata (request id, model, system fingerprint).
+    #[serde(default)]
+    pub provider_meta: ProviderResponseMeta,
+    /// Token usage reported by the provider, when available.
+    #[serde(default)]
+    pub usage: Option<TokenUsage>,
+    /// Estimated cost from the harness `CostModel`. `None` when usage or the
+    /// model's price is unknown.
+    #[serde(default)]
+    pub cost_usd: Option<f64>,
+    /// Final provider response JSON as received (the last stream chunk for
+    /// streaming runs). Only set when requested via `RunOptions::raw_response`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_response: Option<serde_json::Value>,
 }
 
 impl RunOutput {
+    /// Whether the provider reported a prompt cache hit for this run.
+    pub fn cache_hit(&self) -> Option<bool> {
+        self.provider_meta.cache_hit
+    }
+
     /// Concatenates all text parts in order and ignores non-text parts.
     pub fn text(&self) -> String {
         let mut out = String::new();
@@ -56,6 +130,9 @@ mod tests {
                 OutputPart::Text(" world".into()),
             ],
             finish_reason: None,
+            metrics: RunMetrics::default(),
+            provider_meta: ProviderResponseMeta::default(),
+            ..RunOutput::default()
         };
         assert_eq!(output.text(), "hello world");
     }
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use crate::content::TokenUsage;
+use crate::model::ModelRef;
+
+/// Price of one model, in USD per 1,000 tokens.
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ModelPrice {
+    /// USD per 1,000 input (prompt) tokens.
+    pub input_per_1k: f64,
+    /// USD per 1,000 output (completion) tokens.
+    pub output_per_1k: f64,
+}
+
+impl ModelPrice {
+    /// Creates a price from per-1k input and output rates.
+    pub fn per_1k(input_per_1k: f64, output_per_1k: f64) -> Self {
+        Self {
+            input_per_1k,
+            output_per_1k,
+        }
+    }
+
+    /// Returns the estimated cost in USD for `usage`.
+    pub fn cost_usd(&self, usage: &TokenUsage) -> f64 {
+        (usage.input_tokens as f64 * self.input_per_1k
+            + usage.output_tokens as f64 * self.output_per_1k)
+            / 1000.0
+    }
+}
+
+/// Price table used to estimate run cost from reported token usage.
+///
+/// Configure it with `HarnessBuilder::cost_model`. Runs on models without a
+/// price keep `RunOutput::cost_usd` at `None`.
+#[derive(Clone, Debug, Default)]
+pub struct CostModel {
+    prices: HashMap<ModelRef, ModelPrice>,
+}
+
+impl CostModel {
+    /// Creates an empty price table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the price for one provider/model pair.
+    pub fn with_price(mut self, model: ModelRef, price: ModelPrice) -> Self {
+        self.prices.insert(model, price);
+        self
+    }
+
+    /// Returns the configured price for `model`, if any.
+    pub fn price(&self, model: &ModelRef) -> Option<&ModelPrice> {
+        self.prices.get(model)
+    }
+
+    /// Returns the estimated cost in USD, or `None` when `model` has no price.
+    pub fn estimate(&self, model: &ModelRef, usage: &TokenUsage) -> Option<f64> {
+        self.price(model).map(|price| price.cost_usd(usage))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_uses_per_1k_rates() {
+        let model = ModelRef::new("openai", "gpt-5-nano");
+        let costs = CostModel::new().with_price(model.clone(), ModelPrice::per_1k(0.05, 0.4));
+        let usage = TokenUsage {
+            input_tokens: 2000,
+            output_tokens: 500,
+        };
+        let cost = costs.estimate(&model, &usage).expect("priced model");
+        assert!((cost - 0.3).abs() < 1e-9, "got {cost}");
+        assert_eq!(
+            costs.estimate(&ModelRef::new("openai", "other"), &usage),
+            None
+        );
+    }
+}
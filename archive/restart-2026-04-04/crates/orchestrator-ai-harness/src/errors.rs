@@ -23,6 +23,12 @@ pub enum ProviderError {
         provider: ProviderId,
         message: String,
     },
+    /// Provider does not implement the requested operation.
+    #[error("unsupported operation ({provider}): {operation}")]
+    Unsupported {
+        provider: ProviderId,
+        operation: String,
+    },
 }
 
 impl ProviderError {
@@ -55,12 +61,21 @@ impl ProviderError {
         }
     }
 
+    /// Creates an unsupported-operation error.
+    pub fn unsupported(provider: impl Into<ProviderId>, operation: impl Into<String>) -> Self {
+        Self::Unsupported {
+            provider: provider.into(),
+            operation: operation.into(),
+        }
+    }
+
     /// Returns the provider associated with this error.
     pub fn provider_id(&self) -> &ProviderId {
         match self {
             Self::Provider { provider, .. }
             | Self::Transport { provider, .. }
-            | Self::Protocol { provider, .. } => provider,
+            | Self::Protocol { provider, .. }
+            | Self::Unsupported { provider, .. } => provider,
         }
     }
 
@@ -70,6 +85,7 @@ impl ProviderError {
             Self::Provider { message, .. }
             | Self::Transport { message, .. }
             | Self::Protocol { message, .. } => message,
+            Self::Unsupported { operation, .. } => operation,
         }
     }
 }
@@ -86,6 +102,9 @@ pub enum RunFailure {
     /// The harness detected a protocol or invariant error.
     #[error("protocol failure: {message}")]
     Protocol { message: String },
+    /// The run did not complete within `RunOptions::timeout`.
+    #[error("run timed out after {timeout_ms}ms")]
+    Timeout { timeout_ms: u64 },
     /// The run was cancelled by the caller.
     #[error("run cancelled")]
     Cancelled,
@@ -109,9 +128,15 @@ pub enum HarnessError {
     /// Transport error surfaced outside the run stream.
     #[error("transport error: {0}")]
     Transport(String),
+    /// Local I/O failed, for example writing streamed output.
+    #[error("io error: {0}")]
+    Io(String),
     /// Terminal failure returned from a started run.
     #[error(transparent)]
     RunFailed(RunFailure),
+    /// Model answer did not match any of the allowed classification labels.
+    #[error("invalid label: got {got:?}, allowed {allowed:?}")]
+    InvalidLabel { got: String, allowed: Vec<String> },
     /// Operation was cancelled before a terminal run result was returned.
     #[error("cancelled")]
     Cancelled,
@@ -151,5 +176,12 @@ pub(crate) fn run_failure_from_provider_error(err: &ProviderError) -> RunFailure
         ProviderError::Protocol { provider, message } => RunFailure::Protocol {
             message: format!("provider={provider}: {message}"),
         },
+        ProviderError::Unsupported {
+            provider,
+            operation,
+        } => RunFailure::Provider {
+            provider: provider.to_string(),
+            message: format!("unsupported operation: {operation}"),
+        },
     }
 }
@@ -1,19 +1,49 @@
 use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+use crate::clock::{Clock, TokioClock};
+use crate::cost::CostModel;
 use crate::errors::HarnessError;
+use crate::middleware::Middleware;
 use crate::model::ProviderId;
 use crate::provider::ProviderAdapter;
+use crate::run_log::RunLogger;
 use crate::session::{Session, SessionConfig};
 
 pub(crate) struct HarnessInner {
     providers: HashMap<ProviderId, Arc<dyn ProviderAdapter>>,
+    clock: Arc<dyn Clock>,
+    model_cache: Option<Mutex<HashMap<ProviderId, Vec<String>>>>,
+    cost_model: Option<CostModel>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    privacy_mode: bool,
+    run_logger: Option<Arc<dyn RunLogger>>,
 }
 
 impl HarnessInner {
     pub(crate) fn provider(&self, id: &ProviderId) -> Option<Arc<dyn ProviderAdapter>> {
         self.providers.get(id).cloned()
     }
+
+    pub(crate) fn clock(&self) -> Arc<dyn Clock> {
+        self.clock.clone()
+    }
+
+    pub(crate) fn cost_model(&self) -> Option<&CostModel> {
+        self.cost_model.as_ref()
+    }
+
+    pub(crate) fn middleware(&self) -> &[Arc<dyn Middleware>] {
+        &self.middleware
+    }
+
+    pub(crate) fn privacy_mode(&self) -> bool {
+        self.privacy_mode
+    }
+
+    pub(crate) fn run_logger(&self) -> Option<Arc<dyn RunLogger>> {
+        self.run_logger.clone()
+    }
 }
 
 /// Entry point for creating sessions and running models.
@@ -32,12 +62,96 @@ impl Harness {
     pub fn session(&self, config: SessionConfig) -> Session {
         Session::new(self.inner.clone(), config)
     }
+
+    /// Lists the models served by a registered provider.
+    ///
+    /// With [`HarnessBuilder::cache_model_lists`] the first successful result per
+    /// provider is reused for the lifetime of the harness.
+    pub async fn list_models(
+        &self,
+        provider_id: impl Into<ProviderId>,
+    ) -> Result<Vec<String>, HarnessError> {
+        let provider_id = provider_id.into();
+        let provider =
+            self.inner
+                .provider(&provider_id)
+                .ok_or_else(|| HarnessError::ProviderNotFound {
+                    provider: provider_id.clone(),
+                })?;
+        if let Some(cache) = &self.inner.model_cache
+            && let Some(models) = cache.lock().expect("model cache lock").get(&provider_id)
+        {
+            return Ok(models.clone());
+        }
+        let models = provider
+            .list_models()
+            .await
+            .map_err(HarnessError::Provider)?;
+        if let Some(cache) = &self.inner.model_cache {
+            cache
+                .lock()
+                .expect("model cache lock")
+                .insert(provider_id, models.clone());
+        }
+        Ok(models)
+    }
+}
+
+/// Constructor for a provider adapter, registered under a name in [`ProviderFactories`].
+pub type ProviderFactory =
+    Arc<dyn Fn() -> Result<Arc<dyn ProviderAdapter>, HarnessError> + Send + Sync>;
+
+/// Provider constructors keyed by name, so an app can pick providers at runtime from config
+/// (for example a plugin loaded by name) and register them with
+/// [`HarnessBuilder::register_provider_by_name`].
+#[derive(Default, Clone)]
+pub struct ProviderFactories {
+    factories: HashMap<String, ProviderFactory>,
+}
+
+impl ProviderFactories {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds (or replaces) the constructor used for `name`.
+    pub fn register<F>(mut self, name: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn() -> Result<Arc<dyn ProviderAdapter>, HarnessError> + Send + Sync + 'static,
+    {
+        self.factories.insert(name.into(), Arc::new(factory));
+        self
+    }
+
+    /// Registered names, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.factories.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Builds the provider registered as `name`.
+    pub fn create(&self, name: &str) -> Result<Arc<dyn ProviderAdapter>, HarnessError> {
+        let factory = self.factories.get(name).ok_or_else(|| {
+            HarnessError::Config(format!(
+                "unknown provider factory: {name} (known: {})",
+                self.names().join(", ")
+            ))
+        })?;
+        factory()
+    }
 }
 
 /// Builder used to register provider adapters before creating a `Harness`.
 #[derive(Default)]
 pub struct HarnessBuilder {
     providers: Vec<Arc<dyn ProviderAdapter>>,
+    clock: Option<Arc<dyn Clock>>,
+    cache_model_lists: bool,
+    cost_model: Option<CostModel>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    privacy_mode: bool,
+    run_logger: Option<Arc<dyn RunLogger>>,
 }
 
 impl HarnessBuilder {
@@ -49,6 +163,68 @@ impl HarnessBuilder {
         self
     }
 
+    /// Registers a boxed provider adapter, e.g. one returned by a plugin loader.
+    pub fn register_provider_boxed(self, provider: Box<dyn ProviderAdapter>) -> Self {
+        self.register_provider(Arc::from(provider))
+    }
+
+    /// Builds the provider registered as `name` in `factories` and registers it.
+    ///
+    /// Fails with `HarnessError::Config` for an unknown name or when the factory fails.
+    pub fn register_provider_by_name(
+        self,
+        factories: &ProviderFactories,
+        name: &str,
+    ) -> Result<Self, HarnessError> {
+        Ok(self.register_provider(factories.create(name)?))
+    }
+
+    /// Replaces the time source used for run timeouts and metrics.
+    ///
+    /// Defaults to [`TokioClock`]; tests can pass a virtual clock instead.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Caches `Harness::list_models` results per provider instead of querying each time.
+    pub fn cache_model_lists(mut self, enabled: bool) -> Self {
+        self.cache_model_lists = enabled;
+        self
+    }
+
+    /// Sets the price table used to fill `RunOutput::cost_usd` and session totals.
+    pub fn cost_model(mut self, cost_model: CostModel) -> Self {
+        self.cost_model = Some(cost_model);
+        self
+    }
+
+    /// Adds a middleware applied around every provider call.
+    ///
+    /// Middleware runs in the order it was added.
+    pub fn with_middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Enables privacy mode for every run: requests are never stored by the provider,
+    /// user identifiers and metadata are stripped, and prompt or output text is kept out of logs.
+    ///
+    /// Overrides per-run options and middleware that try to turn storage back on.
+    pub fn privacy_mode(mut self, enabled: bool) -> Self {
+        self.privacy_mode = enabled;
+        self
+    }
+
+    /// Records every finished run's prompt and response, e.g. with
+    /// [`JsonlRunLogger`](crate::run_log::JsonlRunLogger) for a `runs.jsonl` audit trail.
+    ///
+    /// With privacy mode on, records keep ids, model and usage but no prompt or output text.
+    pub fn with_run_logger(mut self, logger: Arc<dyn RunLogger>) -> Self {
+        self.run_logger = Some(logger);
+        self
+    }
+
     /// Builds the harness and validates provider registration (including duplicates).
     pub fn build(self) -> Result<Harness, HarnessError> {
         let mut map: HashMap<ProviderId, Arc<dyn ProviderAdapter>> = HashMap::new();
@@ -63,7 +239,15 @@ impl HarnessBuilder {
             map.insert(id, provider);
         }
         Ok(Harness {
-            inner: Arc::new(HarnessInner { providers: map }),
+            inner: Arc::new(HarnessInner {
+                providers: map,
+                clock: self.clock.unwrap_or_else(|| Arc::new(TokioClock)),
+                model_cache: self.cache_model_lists.then(Mutex::default),
+                cost_model: self.cost_model,
+                middleware: self.middleware,
+                privacy_mode: self.privacy_mode,
+                run_logger: self.run_logger,
+            }),
         })
     }
 }
@@ -73,6 +257,7 @@ mod tests {
     use super::*;
     use crate::provider::{ProviderAdapter, ProviderRequest, ProviderStreamHandle};
     use crate::{errors::ProviderError, model::ProviderId};
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     struct DummyProvider;
 
@@ -100,4 +285,54 @@ mod tests {
             matches!(result, Err(HarnessError::Config(message)) if message.contains("duplicate provider"))
         );
     }
+
+    struct CatalogProvider {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl ProviderAdapter for CatalogProvider {
+        fn id(&self) -> ProviderId {
+            ProviderId::new("catalog")
+        }
+
+        async fn start_stream(
+            &self,
+            _req: ProviderRequest,
+        ) -> Result<ProviderStreamHandle, ProviderError> {
+            unreachable!("not used in this test")
+        }
+
+        async fn list_models(&self) -> Result<Vec<String>, ProviderError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(vec!["small".into(), "large".into()])
+        }
+    }
+
+    #[tokio::test]
+    async fn list_models_propagates_provider_catalog() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let harness = Harness::builder()
+            .register_provider(Arc::new(CatalogProvider {
+                calls: calls.clone(),
+            }))
+            .register_provider(Arc::new(DummyProvider))
+            .cache_model_lists(true)
+            .build()
+            .expect("build harness");
+
+        for _ in 0..2 {
+            let models = harness.list_models("catalog").await.expect("models");
+            assert_eq!(models, vec!["small".to_string(), "large".to_string()]);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1, "second call is cached");
+
+        let err = harness.list_models("dummy").await.unwrap_err();
+        assert!(matches!(
+            err,
+            HarnessError::Provider(ProviderError::Unsupported { .. })
+        ));
+        let err = harness.list_models("missing").await.unwrap_err();
+        assert!(matches!(err, HarnessError::ProviderNotFound { .. }));
+    }
 }
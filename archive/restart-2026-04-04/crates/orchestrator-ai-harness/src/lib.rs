@@ -32,12 +32,18 @@
 //! # }
 //! ```
 
+/// Injectable time source for timeouts and metrics.
+pub mod clock;
 /// Input/output content types and final run output helpers.
 pub mod content;
+/// Per-model pricing used to estimate run cost.
+pub mod cost;
 /// Public error types used by the harness API.
 pub mod errors;
 /// Harness entry point and builder.
 pub mod harness;
+/// Request/response interception hooks applied around provider calls.
+pub mod middleware;
 /// Model and provider identifiers plus generic run options.
 pub mod model;
 /// Common imports for typical usage.
@@ -46,6 +52,8 @@ pub mod prelude;
 pub mod provider;
 /// Run builder, streaming handle, and cancellation handle.
 pub mod run;
+/// Audit logging of run prompts and responses.
+pub mod run_log;
 /// Session configuration and session handle.
 pub mod session;
 /// Normalized public stream events.
@@ -53,13 +61,18 @@ pub mod stream;
 /// Vendor-specific integrations and extension traits.
 pub mod vendors;
 
-pub use content::{InputPart, OutputPart, RunOutput};
+pub use clock::{Clock, TokioClock};
+pub use content::{InputPart, OutputPart, RunMetrics, RunOutput, TokenUsage};
+pub use cost::{CostModel, ModelPrice};
 pub use errors::{HarnessError, ProviderError, RunFailure};
-pub use harness::{Harness, HarnessBuilder};
+pub use harness::{Harness, HarnessBuilder, ProviderFactories, ProviderFactory};
+pub use middleware::Middleware;
 pub use model::{ModelRef, ProviderId, RunOptions};
 pub use provider::{
     ProviderAdapter, ProviderEvent, ProviderRequest, ProviderResponseMeta, ProviderStreamHandle,
 };
 pub use run::{AbortHandle, RunBuilder, RunStream};
-pub use session::{Session, SessionConfig};
+pub use run_log::{JsonlRunLogger, RunLogger, RunRecord};
+pub use session::{Session, SessionConfig, SessionUsage};
 pub use stream::StreamEvent;
+pub use tokio_util::sync::CancellationToken;
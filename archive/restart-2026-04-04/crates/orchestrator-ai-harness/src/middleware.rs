@@ -0,0 +1,15 @@
+use crate::provider::{ProviderEvent, ProviderRequest};
+
+/// Hook applied around every provider call made by the harness.
+///
+/// Register with `HarnessBuilder::with_middleware`. Middleware runs in
+/// registration order for both hooks; both default to doing nothing.
+pub trait Middleware: Send + Sync {
+    /// Called before the request is handed to the provider adapter.
+    ///
+    /// Use it to inject headers, rewrite options, or sign requests.
+    fn before_request(&self, _req: &mut ProviderRequest) {}
+
+    /// Called for each event the provider emits, before the harness normalizes it.
+    fn after_event(&self, _event: &ProviderEvent) {}
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt;
 use std::time::Duration;
 
@@ -36,7 +37,7 @@ impl From<String> for ProviderId {
 }
 
 /// Model selection for a run.
-#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct ModelRef {
     /// Provider that owns the model.
     pub provider: ProviderId,
@@ -55,12 +56,23 @@ impl ModelRef {
 }
 
 /// Generic run behavior options.
-#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct RunOptions {
     /// Optional per-run timeout.
     pub timeout: Option<Duration>,
     /// Bounded event buffer size used by the streaming channel.
     pub stream_buffer_capacity: usize,
+    /// Optional sampling seed for reproducible runs, when the provider supports it. OpenAI
+    /// rejects it on the Responses API; use the Chat Completions API instead.
+    pub seed: Option<u64>,
+    /// Extra HTTP headers for this run (gateway routing, A/B flags). Values of
+    /// sensitive names are redacted in `Debug` output and logs.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Asks the adapter to attach the final raw provider response to
+    /// `RunOutput::raw_response`. Set by `RunBuilder::complete_raw`.
+    #[serde(default)]
+    pub raw_response: bool,
 }
 
 impl Default for RunOptions {
@@ -68,10 +80,59 @@ impl Default for RunOptions {
         Self {
             timeout: None,
             stream_buffer_capacity: 128,
+            seed: None,
+            headers: BTreeMap::new(),
+            raw_response: false,
         }
     }
 }
 
+impl fmt::Debug for RunOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RunOptions")
+            .field("timeout", &self.timeout)
+            .field("stream_buffer_capacity", &self.stream_buffer_capacity)
+            .field("seed", &self.seed)
+            .field("headers", &redact_headers(&self.headers))
+            .field("raw_response", &self.raw_response)
+            .finish()
+    }
+}
+
+/// Header names whose values must never reach logs.
+const SENSITIVE_HEADER_MARKERS: &[&str] = &[
+    "authorization",
+    "api-key",
+    "apikey",
+    "token",
+    "secret",
+    "cookie",
+    "password",
+];
+
+/// Copy of `headers` safe to log: values of sensitive names are replaced.
+pub(crate) fn redact_headers<'a>(
+    headers: impl IntoIterator<Item = (&'a String, &'a String)>,
+) -> BTreeMap<&'a str, &'a str> {
+    headers
+        .into_iter()
+        .map(|(name, value)| {
+            let lower = name.to_ascii_lowercase();
+            let sensitive = SENSITIVE_HEADER_MARKERS
+                .iter()
+                .any(|marker| lower.contains(marker));
+            (
+                name.as_str(),
+                if sensitive {
+                    "<redacted>"
+                } else {
+                    value.as_str()
+                },
+            )
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,4 +141,16 @@ mod tests {
     fn run_options_default_buffer_capacity() {
         assert_eq!(RunOptions::default().stream_buffer_capacity, 128);
     }
+
+    #[test]
+    fn run_options_debug_redacts_sensitive_headers() {
+        let mut options = RunOptions::default();
+        options.headers.insert("X-Route".into(), "beta".into());
+        options
+            .headers
+            .insert("X-Gateway-Api-Key".into(), "gw-secret-value".into());
+        let debug = format!("{options:?}");
+        assert!(debug.contains("\"X-Route\": \"beta\""), "got: {debug}");
+        assert!(!debug.contains("gw-secret-value"), "got: {debug}");
+    }
 }
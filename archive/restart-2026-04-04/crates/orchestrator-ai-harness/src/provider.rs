@@ -23,15 +23,46 @@ pub struct ProviderRequest {
     pub options: RunOptions,
     /// Provider-specific request options keyed by provider id.
     pub vendor_options: HashMap<ProviderId, serde_json::Value>,
+    /// Extra HTTP headers; adapters that talk HTTP send them with the request.
+    pub headers: HashMap<String, String>,
+    /// Harness-wide privacy mode: adapters must not ask the provider to store the request
+    /// and must not send user identifiers or metadata. Set by the harness after middleware.
+    pub privacy_mode: bool,
 }
 
-/// Optional metadata returned by a provider when the stream starts.
-#[derive(Clone, Debug, Default)]
+/// Optional metadata returned by a provider when the stream starts or completes.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct ProviderResponseMeta {
     /// Provider request identifier, when available.
     pub request_id: Option<String>,
     /// Model name echoed by the provider, when available.
     pub model: Option<String>,
+    /// Backend configuration fingerprint, when available. Changes indicate the
+    /// provider changed the serving backend even for the same model name.
+    #[serde(default)]
+    pub system_fingerprint: Option<String>,
+    /// Whether the provider served part of the prompt from its prompt cache.
+    /// `None` when the provider did not report cached tokens.
+    #[serde(default)]
+    pub cache_hit: Option<bool>,
+}
+
+impl ProviderResponseMeta {
+    /// Fills fields that are still unset from `fallback`.
+    pub(crate) fn fill_missing_from(&mut self, fallback: &ProviderResponseMeta) {
+        if self.request_id.is_none() {
+            self.request_id = fallback.request_id.clone();
+        }
+        if self.model.is_none() {
+            self.model = fallback.model.clone();
+        }
+        if self.system_fingerprint.is_none() {
+            self.system_fingerprint = fallback.system_fingerprint.clone();
+        }
+        if self.cache_hit.is_none() {
+            self.cache_hit = fallback.cache_hit;
+        }
+    }
 }
 
 /// Internal provider events that the harness normalizes into `StreamEvent`.
@@ -39,6 +70,17 @@ pub struct ProviderResponseMeta {
 pub enum ProviderEvent {
     /// Incremental text output chunk.
     TextDelta { text: String },
+    /// Fragment of a tool call's JSON arguments, in stream order.
+    ToolCallDelta {
+        call_id: String,
+        arguments_fragment: String,
+    },
+    /// Tool call with its arguments fully assembled.
+    ToolCall {
+        call_id: String,
+        name: String,
+        arguments: String,
+    },
     /// Provider signaled completion. `output` may be `None` for delta-only streams.
     Completed {
         output: Option<RunOutput>,
@@ -73,4 +115,11 @@ pub trait ProviderAdapter: Send + Sync {
         &self,
         req: ProviderRequest,
     ) -> Result<ProviderStreamHandle, ProviderError>;
+
+    /// Lists the model names this provider can serve.
+    ///
+    /// Defaults to [`ProviderError::Unsupported`] for adapters without a model catalog.
+    async fn list_models(&self) -> Result<Vec<String>, ProviderError> {
+        Err(ProviderError::unsupported(self.id(), "list_models"))
+    }
 }
@@ -1,15 +1,18 @@
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use futures::StreamExt as _;
 use tokio::sync::{mpsc, oneshot, watch};
+use tokio_util::sync::CancellationToken;
 use tracing::debug;
 
-use crate::content::{InputPart, OutputPart, RunOutput};
+use crate::content::{InputPart, OutputPart, RunMetrics, RunOutput};
 use crate::errors::{HarnessError, RunFailure, run_failure_from_provider_error};
 use crate::harness::HarnessInner;
 use crate::model::{ModelRef, ProviderId, RunOptions};
 use crate::provider::{ProviderAdapter, ProviderEvent, ProviderRequest};
+use crate::run_log::RunRecord;
+use crate::session::SessionUsage;
 use crate::stream::StreamEvent;
 
 /// Handle used to request cancellation of a running stream.
@@ -28,19 +31,45 @@ impl AbortHandle {
     }
 }
 
+/// Cancellation sources watched by a running task: the stream's `AbortHandle`
+/// and the token passed to `RunBuilder::with_cancellation_token`.
+struct RunCancellation {
+    abort_rx: watch::Receiver<bool>,
+    token: CancellationToken,
+}
+
+impl RunCancellation {
+    /// Resolves once either source requests cancellation.
+    async fn cancelled(&mut self) {
+        let aborted = async {
+            if self.abort_rx.wait_for(|aborted| *aborted).await.is_err() {
+                // Every `AbortHandle` is gone, so only the token can cancel.
+                std::future::pending::<()>().await;
+            }
+        };
+        tokio::select! {
+            _ = self.token.cancelled() => {}
+            _ = aborted => {}
+        }
+    }
+}
+
 /// Builder for configuring and starting a single model run.
 ///
 /// This is the main user-facing API for providing prompts, inputs, and runtime
 /// options before either streaming events or collecting a final result.
+#[derive(Clone)]
 pub struct RunBuilder {
     harness: Arc<HarnessInner>,
     session_id: uuid::Uuid,
     _session_name: String,
+    session_usage: Arc<Mutex<SessionUsage>>,
     model: ModelRef,
     system_prompt: Option<String>,
     input_parts: Vec<InputPart>,
     options: RunOptions,
     vendor_options: HashMap<ProviderId, serde_json::Value>,
+    cancellation_token: Option<CancellationToken>,
 }
 
 impl RunBuilder {
@@ -48,17 +77,20 @@ impl RunBuilder {
         harness: Arc<HarnessInner>,
         session_id: uuid::Uuid,
         session_name: String,
+        session_usage: Arc<Mutex<SessionUsage>>,
         model: ModelRef,
     ) -> Self {
         Self {
             harness,
             session_id,
             _session_name: session_name,
+            session_usage,
             model,
             system_prompt: None,
             input_parts: Vec::new(),
             options: RunOptions::default(),
             vendor_options: HashMap::new(),
+            cancellation_token: None,
         }
     }
 
@@ -74,6 +106,18 @@ impl RunBuilder {
         self
     }
 
+    /// Appends an image user input part.
+    ///
+    /// Parts keep the order they were added in, so text and images can be
+    /// interleaved.
+    pub fn user_image(mut self, mime: impl Into<String>, data: impl Into<bytes::Bytes>) -> Self {
+        self.input_parts.push(InputPart::ImageBytes {
+            mime: mime.into(),
+            data: data.into(),
+        });
+        self
+    }
+
     /// Appends a JSON user input part.
     ///
     /// This method currently returns `Result` for API consistency with future
@@ -83,6 +127,35 @@ impl RunBuilder {
         Ok(self)
     }
 
+    /// Appends a tool call the model made in an earlier run.
+    ///
+    /// Providers require each tool result to follow the call it answers, so add the call
+    /// (as reported in `OutputPart::ToolCall`) before its [`RunBuilder::tool_result`].
+    pub fn tool_call(
+        mut self,
+        call_id: impl Into<String>,
+        name: impl Into<String>,
+        arguments: impl Into<String>,
+    ) -> Self {
+        self.input_parts.push(InputPart::ToolCall {
+            call_id: call_id.into(),
+            name: name.into(),
+            arguments: arguments.into(),
+        });
+        self
+    }
+
+    /// Appends the result of a tool call, to send back to the model in a follow-up run.
+    ///
+    /// The matching [`RunBuilder::tool_call`] must come earlier in the input.
+    pub fn tool_result(mut self, call_id: impl Into<String>, content: impl Into<String>) -> Self {
+        self.input_parts.push(InputPart::ToolResult {
+            call_id: call_id.into(),
+            content: content.into(),
+        });
+        self
+    }
+
     /// Replaces all input parts with the provided list.
     pub fn input_parts(mut self, parts: Vec<InputPart>) -> Result<Self, HarnessError> {
         self.input_parts = parts;
@@ -95,6 +168,29 @@ impl RunBuilder {
         self
     }
 
+    /// Cancels the run when `token` is cancelled, so runs can join an existing
+    /// cancellation tree. The stream's `AbortHandle` keeps working alongside it.
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Sets a sampling seed so repeated runs are reproducible where the
+    /// provider supports it. OpenAI accepts it only on the Chat Completions
+    /// API; a Responses API request with a seed fails before it is sent.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.options.seed = Some(seed);
+        self
+    }
+
+    /// Adds an HTTP header sent with this run's provider request (for
+    /// example `X-Route: beta` for gateway routing). Setting a name again
+    /// replaces its value; headers added by middleware take precedence.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.options.headers.insert(name.into(), value.into());
+        self
+    }
+
     /// Sets the bounded stream buffer size used between the runtime task and
     /// the consumer.
     pub fn stream_buffer_capacity(mut self, capacity: usize) -> Self {
@@ -122,6 +218,8 @@ impl RunBuilder {
     /// `OutputDelta`, and a terminal `Completed`/`Error` event).
     pub async fn start_stream(self) -> Result<RunStream, HarnessError> {
         let harness = self.harness.clone();
+        let session_usage = self.session_usage.clone();
+        let token = self.cancellation_token.clone().unwrap_or_default();
         let validated = self.validate_and_build_request()?;
         let provider = harness
             .provider(&validated.request.model.provider)
@@ -134,16 +232,50 @@ impl RunBuilder {
         let (abort_tx, abort_rx) = watch::channel(false);
 
         let abort_handle = AbortHandle { tx: abort_tx };
+        let cancellation = RunCancellation { abort_rx, token };
         let run_id = validated.request.run_id;
         let session_id = validated.request.session_id;
         let model = validated.request.model.clone();
-        tokio::spawn(run_task(
-            provider,
-            validated.request,
-            tx,
-            final_tx,
-            abort_rx,
-        ));
+        let accounting = RunAccounting {
+            price: harness
+                .cost_model()
+                .and_then(|costs| costs.price(&model))
+                .copied(),
+            session_usage,
+        };
+        match harness.run_logger() {
+            Some(logger) => {
+                let record = RunRecord::new(&validated.request, harness.privacy_mode());
+                let (task_tx, task_rx) = oneshot::channel();
+                let task = run_task(
+                    provider,
+                    harness.clone(),
+                    accounting,
+                    validated.request,
+                    tx,
+                    task_tx,
+                    cancellation,
+                );
+                tokio::spawn(async move {
+                    task.await;
+                    if let Ok(result) = task_rx.await {
+                        logger.log(record.finish(&result));
+                        let _ = final_tx.send(result);
+                    }
+                });
+            }
+            None => {
+                tokio::spawn(run_task(
+                    provider,
+                    harness.clone(),
+                    accounting,
+                    validated.request,
+                    tx,
+                    final_tx,
+                    cancellation,
+                ));
+            }
+        }
 
         Ok(RunStream {
             run_id,
@@ -163,6 +295,113 @@ impl RunBuilder {
         stream.finish().await
     }
 
+    /// Streams text deltas to stdout, flushing after each one, and returns
+    /// once the run ends; a newline follows the last delta.
+    pub async fn print_stream(self) -> Result<(), HarnessError> {
+        self.print_stream_until(std::future::pending::<()>()).await
+    }
+
+    /// Like [`RunBuilder::print_stream`], but aborts the run when `cancel` completes; the run
+    /// then fails with `RunFailure::Cancelled`.
+    ///
+    /// No signal handler is installed by the harness; a CLI that wants Ctrl-C to stop the run
+    /// passes `tokio::signal::ctrl_c()`.
+    pub async fn print_stream_until<F>(self, cancel: F) -> Result<(), HarnessError>
+    where
+        F: std::future::Future + Send + 'static,
+    {
+        self.print_stream_to(std::io::stdout(), cancel).await
+    }
+
+    pub(crate) async fn print_stream_to<F>(
+        self,
+        mut out: impl std::io::Write,
+        cancel: F,
+    ) -> Result<(), HarnessError>
+    where
+        F: std::future::Future + Send + 'static,
+    {
+        let mut stream = self.start_stream().await?;
+        let abort = stream.abort_handle();
+        let canceller = {
+            let abort = abort.clone();
+            tokio::spawn(async move {
+                cancel.await;
+                abort.abort();
+            })
+        };
+        let write_err = |err: std::io::Error| HarnessError::Io(format!("stdout: {err}"));
+        let mut printed = Ok(());
+        while let Some(event) = stream.next_event().await {
+            if let StreamEvent::OutputDelta { text, .. } = event {
+                printed = out
+                    .write_all(text.as_bytes())
+                    .and_then(|()| out.flush())
+                    .map_err(write_err);
+                if printed.is_err() {
+                    abort.abort();
+                    break;
+                }
+            }
+        }
+        let result = stream.finish().await;
+        canceller.abort();
+        printed?;
+        result?;
+        out.write_all(b"\n")
+            .and_then(|()| out.flush())
+            .map_err(write_err)
+    }
+
+    /// Streams newline-delimited JSON objects from the model, decoding each
+    /// complete line into `T` as soon as it arrives.
+    ///
+    /// Partial lines are buffered until their newline (or the end of the run);
+    /// blank lines are skipped. A line that fails to decode yields a protocol
+    /// error and the stream continues; a failed run yields its error last.
+    pub fn stream_json<T>(self) -> impl futures::Stream<Item = Result<T, HarnessError>>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let state = JsonLines {
+            builder: Some(self),
+            stream: None,
+            buffer: String::new(),
+            ready: std::collections::VecDeque::new(),
+        };
+        futures::stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(item) = state.ready.pop_front() {
+                    return Some((item, state));
+                }
+                if state.stream.is_none() {
+                    let builder = state.builder.take()?;
+                    match builder.start_stream().await {
+                        Ok(stream) => state.stream = Some(stream),
+                        Err(err) => return Some((Err(err), state)),
+                    }
+                }
+                let stream = state.stream.as_mut()?;
+                match stream.next_event().await {
+                    Some(StreamEvent::OutputDelta { text, .. }) => {
+                        state.buffer.push_str(&text);
+                        state.decode_lines(false);
+                    }
+                    Some(StreamEvent::Completed { .. } | StreamEvent::Error { .. }) | None => {
+                        state.decode_lines(true);
+                        // With both `stream` and `builder` taken, the stream ends
+                        // once `ready` is drained.
+                        let stream = state.stream.take()?;
+                        if let Err(err) = stream.finish().await {
+                            state.ready.push_back(Err(err));
+                        }
+                    }
+                    Some(_) => {}
+                }
+            }
+        })
+    }
+
     /// Runs to completion and returns concatenated text output.
     ///
     /// Non-text output parts are ignored.
@@ -170,6 +409,94 @@ impl RunBuilder {
         Ok(self.collect_output().await?.text())
     }
 
+    /// Runs to completion and returns the final output together with the raw
+    /// final response JSON from the provider, for diagnosing how a response
+    /// was mapped.
+    ///
+    /// Fails with a protocol error when the provider adapter does not retain
+    /// raw responses.
+    pub async fn complete_raw(mut self) -> Result<(RunOutput, serde_json::Value), HarnessError> {
+        self.options.raw_response = true;
+        let provider = self.model.provider.clone();
+        let mut output = self.collect_output().await?;
+        let raw = output.raw_response.take().ok_or_else(|| {
+            HarnessError::protocol_msg(format!("provider {provider} returned no raw response"))
+        })?;
+        Ok((output, raw))
+    }
+
+    /// Runs a classification prompt constrained to `labels` and returns the
+    /// matching label as spelled in `labels`.
+    ///
+    /// The allowed labels are appended to the system prompt. The answer is
+    /// matched case-insensitively after trimming; an out-of-set answer is
+    /// retried once before failing with `HarnessError::InvalidLabel`.
+    pub async fn collect_label(self, labels: &[&str]) -> Result<String, HarnessError> {
+        let allowed: Vec<String> = labels
+            .iter()
+            .map(|label| label.trim().to_string())
+            .filter(|label| !label.is_empty())
+            .collect();
+        if allowed.is_empty() {
+            return Err(HarnessError::Validation(
+                "at least one non-empty label is required".into(),
+            ));
+        }
+
+        let instruction = format!(
+            "Respond with exactly one of the following labels and nothing else: {}",
+            allowed.join(", ")
+        );
+        let system_prompt = match self.system_prompt.as_deref().map(str::trim) {
+            Some(existing) if !existing.is_empty() => format!("{existing}\n\n{instruction}"),
+            _ => instruction,
+        };
+        let builder = self.system_prompt(system_prompt);
+
+        let mut got = String::new();
+        for _attempt in 0..2 {
+            got = builder.clone().collect_text().await?;
+            if let Some(label) = match_label(&allowed, &got) {
+                return Ok(label.to_string());
+            }
+            if builder.harness.privacy_mode() {
+                debug!("label not in allowed set");
+            } else {
+                debug!(got = %got.trim(), "label not in allowed set");
+            }
+        }
+        Err(HarnessError::InvalidLabel {
+            got: got.trim().to_string(),
+            allowed,
+        })
+    }
+
+    /// Runs the same request `n` times concurrently and returns the candidate
+    /// text with the highest `scorer` value.
+    ///
+    /// Ties go to the earliest candidate. Any failed run fails the whole call.
+    pub async fn best_of(
+        self,
+        n: usize,
+        scorer: impl Fn(&str) -> f64,
+    ) -> Result<String, HarnessError> {
+        if n == 0 {
+            return Err(HarnessError::Validation(
+                "best_of requires at least one candidate".into(),
+            ));
+        }
+        let runs = (0..n).map(|_| self.clone().collect_text());
+        let candidates = futures::future::try_join_all(runs).await?;
+        let mut best: Option<(f64, String)> = None;
+        for candidate in candidates {
+            let score = scorer(&candidate);
+            if best.as_ref().is_none_or(|(top, _)| score > *top) {
+                best = Some((score, candidate));
+            }
+        }
+        Ok(best.map(|(_, text)| text).unwrap_or_default())
+    }
+
     fn validate_and_build_request(self) -> Result<ValidatedRun, HarnessError> {
         if self.model.provider.as_str().trim().is_empty() {
             return Err(HarnessError::Validation(
@@ -189,13 +516,45 @@ impl RunBuilder {
                 "at least one input part is required".into(),
             ));
         }
+        let mut tool_calls = std::collections::HashSet::new();
         for part in &self.input_parts {
-            if let InputPart::Text(text) = part
-                && text.trim().is_empty()
-            {
-                return Err(HarnessError::Validation(
-                    "text input must not be empty".into(),
-                ));
+            match part {
+                InputPart::Text(text) if text.trim().is_empty() => {
+                    return Err(HarnessError::Validation(
+                        "text input must not be empty".into(),
+                    ));
+                }
+                InputPart::ImageBytes { mime, data } => {
+                    if !mime.starts_with("image/") {
+                        return Err(HarnessError::Validation(format!(
+                            "image input must have an image/* mime type, got {mime:?}"
+                        )));
+                    }
+                    if data.is_empty() {
+                        return Err(HarnessError::Validation(
+                            "image input must not be empty".into(),
+                        ));
+                    }
+                }
+                InputPart::ToolCall { call_id, name, .. } => {
+                    if call_id.trim().is_empty() || name.trim().is_empty() {
+                        return Err(HarnessError::Validation(
+                            "tool call call_id and name must not be empty".into(),
+                        ));
+                    }
+                    tool_calls.insert(call_id.as_str());
+                }
+                InputPart::ToolResult { call_id, .. } if call_id.trim().is_empty() => {
+                    return Err(HarnessError::Validation(
+                        "tool result call_id must not be empty".into(),
+                    ));
+                }
+                InputPart::ToolResult { call_id, .. } if !tool_calls.contains(call_id.as_str()) => {
+                    return Err(HarnessError::Validation(format!(
+                        "tool result {call_id:?} has no preceding tool call"
+                    )));
+                }
+                _ => {}
             }
         }
 
@@ -207,6 +566,8 @@ impl RunBuilder {
             input_parts: self.input_parts,
             options: self.options,
             vendor_options: self.vendor_options,
+            headers: HashMap::new(),
+            privacy_mode: self.harness.privacy_mode(),
         };
         Ok(ValidatedRun { request })
     }
@@ -216,6 +577,40 @@ struct ValidatedRun {
     request: ProviderRequest,
 }
 
+/// Decoding state behind `RunBuilder::stream_json`.
+struct JsonLines<T> {
+    builder: Option<RunBuilder>,
+    stream: Option<RunStream>,
+    buffer: String,
+    ready: std::collections::VecDeque<Result<T, HarnessError>>,
+}
+
+impl<T: serde::de::DeserializeOwned> JsonLines<T> {
+    /// Decodes every complete line in the buffer, plus the trailing partial
+    /// line when `at_end`.
+    fn decode_lines(&mut self, at_end: bool) {
+        while let Some(newline) = self.buffer.find('\n') {
+            let line: String = self.buffer.drain(..=newline).collect();
+            self.decode(&line);
+        }
+        if at_end {
+            let rest = std::mem::take(&mut self.buffer);
+            self.decode(&rest);
+        }
+    }
+
+    fn decode(&mut self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() {
+            return;
+        }
+        self.ready.push_back(
+            serde_json::from_str(line)
+                .map_err(|err| HarnessError::protocol_msg(format!("invalid NDJSON line: {err}"))),
+        );
+    }
+}
+
 /// Streaming handle returned by `RunBuilder::start_stream`.
 ///
 /// Use `next_event()` to consume events as they arrive and `finish()` to obtain
@@ -282,17 +677,49 @@ impl RunStream {
     }
 }
 
+/// Cost estimation and session totals applied to a completed run.
+struct RunAccounting {
+    price: Option<crate::cost::ModelPrice>,
+    session_usage: Arc<Mutex<SessionUsage>>,
+}
+
+impl RunAccounting {
+    fn apply(&self, output: &mut RunOutput) {
+        if let (Some(price), Some(usage)) = (&self.price, &output.usage) {
+            output.cost_usd = Some(price.cost_usd(usage));
+        }
+        self.session_usage
+            .lock()
+            .expect("session usage lock")
+            .record(output);
+    }
+}
+
 async fn run_task(
     provider: Arc<dyn ProviderAdapter>,
-    request: ProviderRequest,
+    harness: Arc<HarnessInner>,
+    accounting: RunAccounting,
+    mut request: ProviderRequest,
     tx: mpsc::Sender<StreamEvent>,
     final_tx: oneshot::Sender<Result<RunOutput, HarnessError>>,
-    mut abort_rx: watch::Receiver<bool>,
+    mut cancellation: RunCancellation,
 ) {
+    let clock = harness.clock();
+    let middleware = harness.middleware();
     let run_id = request.run_id;
     let session_id = request.session_id;
     let provider_id = request.model.provider.clone();
     let model_name = request.model.model.clone();
+    let timeout = request.options.timeout;
+    let started_at = clock.now();
+    let mut ttft: Option<std::time::Duration> = None;
+    let mut deadline = match timeout {
+        Some(timeout) => clock.sleep(timeout),
+        None => Box::pin(std::future::pending()),
+    };
+    let timed_out = || RunFailure::Timeout {
+        timeout_ms: timeout.unwrap_or_default().as_millis() as u64,
+    };
 
     if !send_event(
         &tx,
@@ -311,7 +738,25 @@ async fn run_task(
         return;
     }
 
-    let started = provider.start_stream(request).await;
+    for layer in middleware {
+        layer.before_request(&mut request);
+    }
+    request.privacy_mode = harness.privacy_mode();
+    let started = tokio::select! {
+        started = provider.start_stream(request) => started,
+        _ = &mut deadline => {
+            let failure = timed_out();
+            let _ = send_event(&tx, StreamEvent::Error { run_id, error: failure.clone() }).await;
+            let _ = final_tx.send(Err(HarnessError::run_failed(failure)));
+            return;
+        }
+        _ = cancellation.cancelled() => {
+            let failure = RunFailure::Cancelled;
+            let _ = send_event(&tx, StreamEvent::Error { run_id, error: failure.clone() }).await;
+            let _ = final_tx.send(Err(HarnessError::run_failed(failure)));
+            return;
+        }
+    };
     let mut handle = match started {
         Ok(handle) => handle,
         Err(err) => {
@@ -333,24 +778,32 @@ async fn run_task(
     let mut aggregated_parts: Vec<OutputPart> = Vec::new();
     loop {
         tokio::select! {
-            changed = abort_rx.changed() => {
-                match changed {
-                    Ok(_) if *abort_rx.borrow() => {
-                        let failure = RunFailure::Cancelled;
-                        let _ = send_event(&tx, StreamEvent::Error { run_id, error: failure.clone() }).await;
-                        let _ = final_tx.send(Err(HarnessError::run_failed(failure)));
-                        return;
-                    }
-                    Ok(_) => {}
-                    Err(_) => {}
-                }
+            _ = &mut deadline => {
+                let failure = timed_out();
+                let _ = send_event(&tx, StreamEvent::Error { run_id, error: failure.clone() }).await;
+                let _ = final_tx.send(Err(HarnessError::run_failed(failure)));
+                return;
+            }
+            _ = cancellation.cancelled() => {
+                let failure = RunFailure::Cancelled;
+                let _ = send_event(&tx, StreamEvent::Error { run_id, error: failure.clone() }).await;
+                let _ = final_tx.send(Err(HarnessError::run_failed(failure)));
+                return;
             }
             next = handle.stream.next() => {
+                if let Some(Ok(event)) = &next {
+                    for layer in middleware {
+                        layer.after_event(event);
+                    }
+                }
                 match next {
                     Some(Ok(ProviderEvent::TextDelta { text })) => {
                         if text.is_empty() {
                             continue;
                         }
+                        if ttft.is_none() {
+                            ttft = Some(clock.now().duration_since(started_at));
+                        }
                         debug!(run_id = %run_id, provider = %provider_id, model = %model_name, seq, "provider text delta");
                         aggregated_parts.push(OutputPart::Text(text.clone()));
                         let sent = send_event(&tx, StreamEvent::OutputDelta { run_id, seq, text }).await;
@@ -360,8 +813,25 @@ async fn run_task(
                             return;
                         }
                     }
+                    Some(Ok(ProviderEvent::ToolCallDelta { call_id, arguments_fragment })) => {
+                        if !send_event(&tx, StreamEvent::ToolCallDelta { run_id, call_id, arguments_fragment }).await {
+                            let _ = final_tx.send(Err(HarnessError::protocol_msg("run stream receiver dropped during output")));
+                            return;
+                        }
+                    }
+                    Some(Ok(ProviderEvent::ToolCall { call_id, name, arguments })) => {
+                        debug!(run_id = %run_id, provider = %provider_id, model = %model_name, call_id = %call_id, tool = %name, "provider tool call");
+                        aggregated_parts.push(OutputPart::ToolCall { call_id: call_id.clone(), name: name.clone(), arguments: arguments.clone() });
+                        if !send_event(&tx, StreamEvent::ToolCall { run_id, call_id, name, arguments }).await {
+                            let _ = final_tx.send(Err(HarnessError::protocol_msg("run stream receiver dropped during output")));
+                            return;
+                        }
+                    }
                     Some(Ok(ProviderEvent::Completed { output, finish_reason })) => {
-                        let output = finalize_output(aggregated_parts, output, finish_reason);
+                        let mut output = finalize_output(aggregated_parts, output, finish_reason);
+                        output.metrics = RunMetrics { ttft, total: clock.now().duration_since(started_at) };
+                        output.provider_meta.fill_missing_from(&handle.metadata);
+                        accounting.apply(&mut output);
                         let sent = send_event(&tx, StreamEvent::Completed { run_id, output: output.clone() }).await;
                         let _ = final_tx.send(if sent { Ok(output) } else { Err(HarnessError::protocol_msg("run stream receiver dropped before completion")) });
                         return;
@@ -412,11 +882,16 @@ fn finalize_output(
             RunOutput {
                 parts,
                 finish_reason: finish_reason.or(provider_output.finish_reason.take()),
+                provider_meta: provider_output.provider_meta,
+                usage: provider_output.usage,
+                raw_response: provider_output.raw_response,
+                ..RunOutput::default()
             }
         }
         (false, None) => RunOutput {
             parts: aggregated_parts,
             finish_reason,
+            ..RunOutput::default()
         },
         (true, Some(mut provider_output)) => {
             if provider_output.finish_reason.is_none() {
@@ -427,10 +902,19 @@ fn finalize_output(
         (true, None) => RunOutput {
             parts: Vec::new(),
             finish_reason,
+            ..RunOutput::default()
         },
     }
 }
 
+fn match_label<'a>(allowed: &'a [String], answer: &str) -> Option<&'a str> {
+    let answer = answer.trim();
+    allowed
+        .iter()
+        .find(|label| label.eq_ignore_ascii_case(answer))
+        .map(String::as_str)
+}
+
 async fn send_event(tx: &mpsc::Sender<StreamEvent>, event: StreamEvent) -> bool {
     tx.send(event).await.is_ok()
 }
@@ -438,6 +922,7 @@ async fn send_event(tx: &mpsc::Sender<StreamEvent>, event: StreamEvent) -> bool
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::Clock;
     use crate::errors::ProviderError;
     use crate::provider::{ProviderResponseMeta, ProviderStreamHandle};
     use futures::stream;
@@ -452,6 +937,13 @@ mod tests {
     enum FakeProviderBehavior {
         ImmediateError(ProviderError),
         Events(Vec<Result<ProviderEvent, ProviderError>>),
+        /// Event list per call; the last entry repeats once exhausted.
+        Sequence(Vec<Vec<Result<ProviderEvent, ProviderError>>>),
+        /// Waits before yielding the first event.
+        Delayed(
+            std::time::Duration,
+            Vec<Result<ProviderEvent, ProviderError>>,
+        ),
         Pending,
     }
 
@@ -465,13 +957,28 @@ mod tests {
             &self,
             _req: ProviderRequest,
         ) -> Result<crate::ProviderStreamHandle, ProviderError> {
-            self.calls.fetch_add(1, Ordering::SeqCst);
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
             match &self.start_result {
                 FakeProviderBehavior::ImmediateError(err) => Err(err.clone()),
                 FakeProviderBehavior::Events(events) => Ok(ProviderStreamHandle {
                     stream: Box::pin(stream::iter(events.clone())),
                     metadata: ProviderResponseMeta::default(),
                 }),
+                FakeProviderBehavior::Sequence(calls) => {
+                    let events = calls[call.min(calls.len() - 1)].clone();
+                    Ok(ProviderStreamHandle {
+                        stream: Box::pin(stream::iter(events)),
+                        metadata: ProviderResponseMeta::default(),
+                    })
+                }
+                FakeProviderBehavior::Delayed(delay, events) => {
+                    let delay = tokio::time::sleep(*delay);
+                    let delayed = stream::once(delay).filter_map(|_| async { None });
+                    Ok(ProviderStreamHandle {
+                        stream: Box::pin(delayed.chain(stream::iter(events.clone()))),
+                        metadata: ProviderResponseMeta::default(),
+                    })
+                }
                 FakeProviderBehavior::Pending => Ok(ProviderStreamHandle {
                     stream: Box::pin(stream::pending()),
                     metadata: ProviderResponseMeta::default(),
@@ -532,12 +1039,61 @@ mod tests {
         assert!(matches!(err, HarnessError::Validation(msg) if msg.contains("text input")));
     }
 
+    #[test]
+    fn tool_result_requires_a_preceding_tool_call() {
+        let err = builder_with_fake_events(vec![])
+            .tool_result("call_1", "21C")
+            .validate_and_build_request()
+            .err()
+            .expect("unmatched tool result should fail");
+        assert!(
+            matches!(err, HarnessError::Validation(msg) if msg.contains("no preceding tool call"))
+        );
+
+        let request = builder_with_fake_events(vec![])
+            .tool_call("call_1", "weather", "{}")
+            .tool_result("call_1", "21C")
+            .validate_and_build_request()
+            .expect("valid request")
+            .request;
+        assert_eq!(request.input_parts.len(), 3);
+    }
+
+    #[test]
+    fn user_parts_keep_insertion_order() {
+        let request = builder_with_fake_events(vec![])
+            .user_image("image/png", b"png".to_vec())
+            .user_text("after")
+            .validate_and_build_request()
+            .expect("valid request")
+            .request;
+        assert_eq!(
+            request.input_parts,
+            vec![
+                InputPart::Text("hello".into()),
+                InputPart::ImageBytes {
+                    mime: "image/png".into(),
+                    data: bytes::Bytes::from_static(b"png"),
+                },
+                InputPart::Text("after".into()),
+            ]
+        );
+
+        let err = builder_with_fake_events(vec![])
+            .user_image("text/plain", b"png".to_vec())
+            .validate_and_build_request()
+            .err()
+            .expect("non-image mime should fail");
+        assert!(matches!(err, HarnessError::Validation(msg) if msg.contains("image/*")));
+    }
+
     #[tokio::test]
     async fn emits_started_then_completed_zero_delta() {
         let mut stream = builder_with_fake_events(vec![Ok(ProviderEvent::Completed {
             output: Some(RunOutput {
                 parts: vec![OutputPart::Text("final".into())],
                 finish_reason: Some("stop".into()),
+                ..RunOutput::default()
             }),
             finish_reason: Some("stop".into()),
         })])
@@ -583,6 +1139,91 @@ mod tests {
         assert_eq!(stream.finish().await.expect("finish").text(), "ab");
     }
 
+    #[test]
+    fn seed_is_forwarded_in_provider_request() {
+        let validated = builder_with_fake_events(vec![])
+            .seed(7)
+            .validate_and_build_request()
+            .expect("valid");
+        assert_eq!(validated.request.options.seed, Some(7));
+    }
+
+    #[tokio::test]
+    async fn system_fingerprint_survives_delta_aggregation() {
+        let output = builder_with_fake_events(vec![
+            Ok(ProviderEvent::TextDelta { text: "a".into() }),
+            Ok(ProviderEvent::Completed {
+                output: Some(RunOutput {
+                    provider_meta: ProviderResponseMeta {
+                        system_fingerprint: Some("fp_1".into()),
+                        ..ProviderResponseMeta::default()
+                    },
+                    ..RunOutput::default()
+                }),
+                finish_reason: Some("stop".into()),
+            }),
+        ])
+        .collect_output()
+        .await
+        .expect("output");
+        assert_eq!(output.text(), "a");
+        assert_eq!(
+            output.provider_meta.system_fingerprint.as_deref(),
+            Some("fp_1")
+        );
+    }
+
+    #[tokio::test]
+    async fn cost_model_prices_runs_and_session_accumulates() {
+        let harness = crate::Harness::builder()
+            .register_provider(Arc::new(FakeProvider {
+                id: ProviderId::new("fake"),
+                calls: Arc::new(AtomicUsize::new(0)),
+                start_result: FakeProviderBehavior::Events(vec![
+                    Ok(ProviderEvent::TextDelta { text: "a".into() }),
+                    Ok(ProviderEvent::Completed {
+                        output: Some(RunOutput {
+                            usage: Some(crate::TokenUsage {
+                                input_tokens: 1200,
+                                output_tokens: 300,
+                            }),
+                            ..RunOutput::default()
+                        }),
+                        finish_reason: Some("stop".into()),
+                    }),
+                ]),
+            }))
+            .cost_model(crate::CostModel::new().with_price(
+                crate::ModelRef::new("fake", "priced"),
+                crate::ModelPrice::per_1k(0.5, 2.0),
+            ))
+            .build()
+            .expect("build harness");
+        let session = harness.session(crate::SessionConfig::named("budget"));
+
+        let priced = session
+            .run(crate::ModelRef::new("fake", "priced"))
+            .user_text("hello")
+            .collect_output()
+            .await
+            .expect("priced run");
+        let cost = priced.cost_usd.expect("cost for priced model");
+        assert!((cost - 1.2).abs() < 1e-9, "got {cost}");
+
+        let unpriced = session
+            .run(crate::ModelRef::new("fake", "unpriced"))
+            .user_text("hello")
+            .collect_output()
+            .await
+            .expect("unpriced run");
+        assert_eq!(unpriced.cost_usd, None);
+
+        let totals = session.usage();
+        assert_eq!(totals.runs, 2);
+        assert_eq!(totals.usage.total_tokens(), 3000);
+        assert!((totals.cost_usd - 1.2).abs() < 1e-9);
+    }
+
     #[tokio::test]
     async fn provider_runtime_error_becomes_terminal_error_and_finish_error() {
         let mut stream = builder_with_fake_events(vec![Err(ProviderError::provider(
@@ -645,6 +1286,204 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn print_stream_writes_each_delta() {
+        let mut out = Vec::new();
+        builder_with_fake_events(vec![
+            Ok(ProviderEvent::TextDelta {
+                text: "Hello".into(),
+            }),
+            Ok(ProviderEvent::TextDelta {
+                text: ", world".into(),
+            }),
+            Ok(ProviderEvent::Completed {
+                finish_reason: Some("stop".into()),
+                output: None,
+            }),
+        ])
+        .print_stream_to(&mut out, std::future::pending::<()>())
+        .await
+        .expect("print");
+        assert_eq!(String::from_utf8(out).unwrap(), "Hello, world\n");
+    }
+
+    #[tokio::test]
+    async fn print_stream_stops_when_cancel_completes() {
+        let harness = harness_with_provider(FakeProvider {
+            id: ProviderId::new("fake"),
+            calls: Arc::new(AtomicUsize::new(0)),
+            start_result: FakeProviderBehavior::Pending,
+        });
+        let mut out = Vec::new();
+        let err = harness
+            .session(crate::SessionConfig::named("test"))
+            .run(crate::ModelRef::new("fake", "model-a"))
+            .user_text("hello")
+            .print_stream_to(&mut out, async {})
+            .await
+            .expect_err("cancelled run");
+        assert_eq!(err, HarnessError::RunFailed(RunFailure::Cancelled));
+    }
+
+    #[tokio::test]
+    async fn print_stream_reports_write_failures_as_io_errors() {
+        struct Broken;
+        impl std::io::Write for Broken {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("closed"))
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+        let err = builder_with_fake_events(vec![Ok(ProviderEvent::TextDelta {
+            text: "Hello".into(),
+        })])
+        .print_stream_to(Broken, std::future::pending::<()>())
+        .await
+        .expect_err("write failure");
+        assert!(matches!(err, HarnessError::Io(msg) if msg.contains("closed")));
+    }
+
+    #[tokio::test]
+    async fn provider_selected_by_name_runs_through_dyn_factory() {
+        let reply = |id: &'static str, text: &'static str| {
+            move || -> Result<Arc<dyn ProviderAdapter>, HarnessError> {
+                Ok(Arc::new(FakeProvider {
+                    id: ProviderId::new(id),
+                    calls: Arc::new(AtomicUsize::new(0)),
+                    start_result: FakeProviderBehavior::Events(vec![
+                        Ok(ProviderEvent::TextDelta { text: text.into() }),
+                        Ok(ProviderEvent::Completed {
+                            finish_reason: Some("stop".into()),
+                            output: None,
+                        }),
+                    ]),
+                }))
+            }
+        };
+        let factories = crate::ProviderFactories::new()
+            .register("local", reply("local", "from local"))
+            .register("hosted", reply("hosted", "from hosted"));
+        let configured = "hosted";
+
+        let harness = crate::Harness::builder()
+            .register_provider_by_name(&factories, configured)
+            .expect("known provider")
+            .build()
+            .expect("build harness");
+        let text = harness
+            .session(crate::SessionConfig::named("test"))
+            .run(crate::ModelRef::new(configured, "model-a"))
+            .user_text("hello")
+            .collect_text()
+            .await
+            .expect("run");
+        assert_eq!(text, "from hosted");
+
+        let boxed: Box<dyn ProviderAdapter> = Box::new(FakeProvider {
+            id: ProviderId::new("boxed"),
+            calls: Arc::new(AtomicUsize::new(0)),
+            start_result: FakeProviderBehavior::Events(vec![]),
+        });
+        crate::Harness::builder()
+            .register_provider_boxed(boxed)
+            .build()
+            .expect("boxed provider");
+
+        let err = crate::Harness::builder()
+            .register_provider_by_name(&factories, "missing")
+            .err()
+            .expect("unknown name");
+        assert!(
+            matches!(&err, HarnessError::Config(message) if message.contains("known: hosted, local")),
+            "got: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_json_decodes_lines_split_across_deltas() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct Item {
+            id: u32,
+            name: String,
+        }
+        let deltas = [
+            "{\"id\":1,\"na",
+            "me\":\"a\"}\n{\"id\"",
+            ":2,\"name\":\"b\"}",
+            "\n",
+        ];
+        let mut events: Vec<_> = deltas
+            .iter()
+            .map(|text| {
+                Ok(ProviderEvent::TextDelta {
+                    text: (*text).into(),
+                })
+            })
+            .collect();
+        events.push(Ok(ProviderEvent::Completed {
+            finish_reason: Some("stop".into()),
+            output: None,
+        }));
+        let items: Vec<Item> = builder_with_fake_events(events)
+            .stream_json::<Item>()
+            .map(|item| item.expect("decode"))
+            .collect()
+            .await;
+        assert_eq!(
+            items,
+            vec![
+                Item {
+                    id: 1,
+                    name: "a".into()
+                },
+                Item {
+                    id: 2,
+                    name: "b".into()
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn cancellation_token_cancels_the_run() {
+        let harness = harness_with_provider(FakeProvider {
+            id: ProviderId::new("fake"),
+            calls: Arc::new(AtomicUsize::new(0)),
+            start_result: FakeProviderBehavior::Pending,
+        });
+        let parent = CancellationToken::new();
+        let mut stream = harness
+            .session(crate::SessionConfig::named("test"))
+            .run(crate::ModelRef::new("fake", "model-a"))
+            .user_text("hello")
+            .with_cancellation_token(parent.child_token())
+            .start_stream()
+            .await
+            .expect("start");
+
+        let _ = stream.next_event().await;
+        parent.cancel();
+
+        let mut saw_cancel = false;
+        while let Some(event) = stream.next_event().await {
+            if let StreamEvent::Error {
+                error: RunFailure::Cancelled,
+                ..
+            } = event
+            {
+                saw_cancel = true;
+                break;
+            }
+        }
+        assert!(saw_cancel);
+        assert!(matches!(
+            stream.finish().await,
+            Err(HarnessError::RunFailed(RunFailure::Cancelled))
+        ));
+    }
+
     #[tokio::test]
     async fn user_json_and_vendor_option_storage_are_preserved() {
         let harness = harness_with_provider(FakeProvider {
@@ -683,4 +1522,289 @@ mod tests {
         };
         assert!(matches!(err, HarnessError::ProviderNotFound { .. }));
     }
+
+    fn text_completion(text: &str) -> Vec<Result<ProviderEvent, ProviderError>> {
+        vec![
+            Ok(ProviderEvent::TextDelta { text: text.into() }),
+            Ok(ProviderEvent::Completed {
+                output: None,
+                finish_reason: Some("stop".into()),
+            }),
+        ]
+    }
+
+    fn builder_with_sequence(
+        calls: Arc<AtomicUsize>,
+        sequence: Vec<Vec<Result<ProviderEvent, ProviderError>>>,
+    ) -> RunBuilder {
+        let harness = harness_with_provider(FakeProvider {
+            id: ProviderId::new("fake"),
+            calls,
+            start_result: FakeProviderBehavior::Sequence(sequence),
+        });
+        harness
+            .session(crate::SessionConfig::named("test"))
+            .run(crate::ModelRef::new("fake", "model-a"))
+            .user_text("classify this")
+    }
+
+    #[tokio::test]
+    async fn collect_label_retries_once_after_out_of_set_answer() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let label = builder_with_sequence(
+            calls.clone(),
+            vec![text_completion("maybe"), text_completion("  Positive\n")],
+        )
+        .collect_label(&["positive", "negative"])
+        .await
+        .expect("label");
+        assert_eq!(label, "positive");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn collect_label_fails_with_invalid_label_after_retry() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let err = builder_with_sequence(calls.clone(), vec![text_completion("maybe")])
+            .collect_label(&["positive", "negative"])
+            .await
+            .expect_err("out-of-set label");
+        assert_eq!(
+            err,
+            HarnessError::InvalidLabel {
+                got: "maybe".into(),
+                allowed: vec!["positive".into(), "negative".into()],
+            }
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn best_of_returns_highest_scoring_candidate() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let best = builder_with_sequence(
+            calls.clone(),
+            vec![
+                text_completion("short"),
+                text_completion("the longest candidate"),
+                text_completion("mid length"),
+            ],
+        )
+        .best_of(3, |text| text.len() as f64)
+        .await
+        .expect("best candidate");
+        assert_eq!(best, "the longest candidate");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let err = builder_with_sequence(calls, vec![text_completion("x")])
+            .best_of(0, |_| 0.0)
+            .await
+            .expect_err("zero candidates");
+        assert!(matches!(err, HarnessError::Validation(_)));
+    }
+
+    #[derive(Default)]
+    struct MemoryRunLogger {
+        records: Mutex<Vec<crate::RunRecord>>,
+    }
+
+    impl crate::RunLogger for MemoryRunLogger {
+        fn log(&self, record: crate::RunRecord) {
+            self.records.lock().unwrap().push(record);
+        }
+    }
+
+    #[tokio::test]
+    async fn run_logger_records_one_entry_per_run() {
+        for privacy in [false, true] {
+            let logger = Arc::new(MemoryRunLogger::default());
+            let harness = crate::Harness::builder()
+                .register_provider(Arc::new(FakeProvider {
+                    id: ProviderId::new("fake"),
+                    calls: Arc::new(AtomicUsize::new(0)),
+                    start_result: FakeProviderBehavior::Sequence(vec![
+                        text_completion("first answer"),
+                        text_completion("second answer"),
+                    ]),
+                }))
+                .with_run_logger(logger.clone())
+                .privacy_mode(privacy)
+                .build()
+                .expect("build harness");
+            let session = harness.session(crate::SessionConfig::named("audit"));
+            for prompt in ["first question", "second question"] {
+                session
+                    .run(crate::ModelRef::new("fake", "model-a"))
+                    .system_prompt("Be brief.")
+                    .user_text(prompt)
+                    .collect_text()
+                    .await
+                    .expect("run");
+            }
+
+            let records = logger.records.lock().unwrap();
+            assert_eq!(records.len(), 2);
+            assert_eq!(records[0].model, "model-a");
+            assert_ne!(records[0].run_id, records[1].run_id);
+            assert_eq!(records[1].redacted, privacy);
+            if privacy {
+                assert_eq!(records[1].prompt, None);
+                assert_eq!(records[1].response, None);
+                assert_eq!(records[1].system_prompt, None);
+            } else {
+                assert_eq!(records[1].prompt.as_deref(), Some("second question"));
+                assert_eq!(records[1].response.as_deref(), Some("second answer"));
+                assert_eq!(records[1].system_prompt.as_deref(), Some("Be brief."));
+            }
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn metrics_record_time_to_first_token_and_total() {
+        let delay = std::time::Duration::from_millis(50);
+        let harness = harness_with_provider(FakeProvider {
+            id: ProviderId::new("fake"),
+            calls: Arc::new(AtomicUsize::new(0)),
+            start_result: FakeProviderBehavior::Delayed(delay, text_completion("hi")),
+        });
+        let output = harness
+            .session(crate::SessionConfig::named("test"))
+            .run(crate::ModelRef::new("fake", "model-a"))
+            .user_text("hello")
+            .collect_output()
+            .await
+            .expect("output");
+
+        let ttft = output.metrics.ttft.expect("ttft recorded");
+        assert!(ttft >= delay, "ttft {ttft:?} should include the delay");
+        assert!(output.metrics.total >= ttft);
+    }
+
+    fn pending_builder(harness: &crate::Harness) -> RunBuilder {
+        harness
+            .session(crate::SessionConfig::named("test"))
+            .run(crate::ModelRef::new("fake", "model-a"))
+            .user_text("hello")
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn timeout_fires_on_paused_clock_without_real_wait() {
+        let harness = harness_with_provider(FakeProvider {
+            id: ProviderId::new("fake"),
+            calls: Arc::new(AtomicUsize::new(0)),
+            start_result: FakeProviderBehavior::Pending,
+        });
+        let wall = std::time::Instant::now();
+        let err = pending_builder(&harness)
+            .timeout(std::time::Duration::from_secs(600))
+            .collect_output()
+            .await
+            .expect_err("pending provider should time out");
+
+        assert_eq!(
+            err,
+            HarnessError::RunFailed(RunFailure::Timeout {
+                timeout_ms: 600_000
+            })
+        );
+        assert!(wall.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn injected_clock_drives_timeout() {
+        struct ExpiredClock;
+        impl Clock for ExpiredClock {
+            fn now(&self) -> tokio::time::Instant {
+                tokio::time::Instant::now()
+            }
+
+            fn sleep(
+                &self,
+                _duration: std::time::Duration,
+            ) -> futures::future::BoxFuture<'static, ()> {
+                Box::pin(std::future::ready(()))
+            }
+        }
+
+        let harness = crate::Harness::builder()
+            .register_provider(Arc::new(FakeProvider {
+                id: ProviderId::new("fake"),
+                calls: Arc::new(AtomicUsize::new(0)),
+                start_result: FakeProviderBehavior::Pending,
+            }))
+            .with_clock(Arc::new(ExpiredClock))
+            .build()
+            .expect("build harness");
+        let err = pending_builder(&harness)
+            .timeout(std::time::Duration::from_secs(3600))
+            .collect_output()
+            .await
+            .expect_err("expired clock should time out");
+
+        assert!(matches!(
+            err,
+            HarnessError::RunFailed(RunFailure::Timeout { .. })
+        ));
+    }
+
+    struct HeaderEchoProvider;
+
+    #[async_trait::async_trait]
+    impl ProviderAdapter for HeaderEchoProvider {
+        fn id(&self) -> ProviderId {
+            ProviderId::new("echo")
+        }
+
+        async fn start_stream(
+            &self,
+            req: ProviderRequest,
+        ) -> Result<ProviderStreamHandle, ProviderError> {
+            let trace = req.headers.get("x-trace-id").cloned().unwrap_or_default();
+            Ok(ProviderStreamHandle {
+                stream: Box::pin(stream::iter(vec![
+                    Ok(ProviderEvent::TextDelta { text: trace }),
+                    Ok(ProviderEvent::Completed {
+                        output: None,
+                        finish_reason: Some("stop".into()),
+                    }),
+                ])),
+                metadata: ProviderResponseMeta::default(),
+            })
+        }
+    }
+
+    struct TraceMiddleware {
+        events: Arc<AtomicUsize>,
+    }
+
+    impl crate::Middleware for TraceMiddleware {
+        fn before_request(&self, req: &mut ProviderRequest) {
+            req.headers.insert("x-trace-id".into(), "trace-42".into());
+        }
+
+        fn after_event(&self, _event: &ProviderEvent) {
+            self.events.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn middleware_injects_headers_and_observes_events() {
+        let events = Arc::new(AtomicUsize::new(0));
+        let harness = crate::Harness::builder()
+            .register_provider(Arc::new(HeaderEchoProvider))
+            .with_middleware(Arc::new(TraceMiddleware {
+                events: events.clone(),
+            }))
+            .build()
+            .expect("build harness");
+        let text = harness
+            .session(crate::SessionConfig::named("mw"))
+            .run(crate::ModelRef::new("echo", "m"))
+            .user_text("hello")
+            .collect_text()
+            .await
+            .expect("run");
+        assert_eq!(text, "trace-42");
+        assert_eq!(events.load(Ordering::SeqCst), 2);
+    }
 }
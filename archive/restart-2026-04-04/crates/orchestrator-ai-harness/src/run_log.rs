@@ -0,0 +1,125 @@
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use tracing::warn;
+
+use crate::content::{InputPart, RunOutput, TokenUsage};
+use crate::errors::HarnessError;
+use crate::provider::ProviderRequest;
+
+/// One finished run, as handed to a [`RunLogger`].
+///
+/// In privacy mode `system_prompt`, `prompt` and `response` are `None` and
+/// `redacted` is set; ids, model and usage are still recorded.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RunRecord {
+    pub run_id: uuid::Uuid,
+    pub session_id: uuid::Uuid,
+    pub provider: String,
+    pub model: String,
+    pub system_prompt: Option<String>,
+    /// User input parts rendered as text, one part per line.
+    pub prompt: Option<String>,
+    /// Text output of a successful run.
+    pub response: Option<String>,
+    /// Error message of a failed run.
+    pub error: Option<String>,
+    pub usage: Option<TokenUsage>,
+    pub redacted: bool,
+}
+
+impl RunRecord {
+    pub(crate) fn new(request: &ProviderRequest, redact: bool) -> Self {
+        Self {
+            run_id: request.run_id,
+            session_id: request.session_id,
+            provider: request.model.provider.to_string(),
+            model: request.model.model.clone(),
+            system_prompt: request.system_prompt.clone().filter(|_| !redact),
+            prompt: (!redact).then(|| prompt_text(&request.input_parts)),
+            response: None,
+            error: None,
+            usage: None,
+            redacted: redact,
+        }
+    }
+
+    pub(crate) fn finish(mut self, result: &Result<RunOutput, HarnessError>) -> Self {
+        match result {
+            Ok(output) => {
+                self.usage = output.usage;
+                if !self.redacted {
+                    self.response = Some(output.text());
+                }
+            }
+            Err(err) => self.error = Some(err.to_string()),
+        }
+        self
+    }
+}
+
+fn prompt_text(parts: &[InputPart]) -> String {
+    parts
+        .iter()
+        .map(|part| match part {
+            InputPart::Text(text) => text.clone(),
+            InputPart::Json(value) => value.to_string(),
+            InputPart::ImageBytes { mime, data } => format!("[{mime}, {} bytes]", data.len()),
+            InputPart::ToolCall {
+                call_id,
+                name,
+                arguments,
+            } => format!("[call {call_id}] {name}({arguments})"),
+            InputPart::ToolResult { call_id, content } => format!("[tool {call_id}] {content}"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Receives a [`RunRecord`] for every run the harness finishes, successful or not.
+///
+/// Register with `HarnessBuilder::with_run_logger`. Called from the run task,
+/// so keep it quick; failures should be reported, not propagated.
+pub trait RunLogger: Send + Sync {
+    fn log(&self, record: RunRecord);
+}
+
+/// Appends each record as one JSON line to a file (for example `runs.jsonl`).
+pub struct JsonlRunLogger {
+    path: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl JsonlRunLogger {
+    /// Creates a logger appending to `path`; the file and its parent directory
+    /// are created on first write.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    fn append(&self, record: &RunRecord) -> std::io::Result<()> {
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+        let _guard = self.write_lock.lock().expect("run log lock");
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(line.as_bytes())
+    }
+}
+
+impl RunLogger for JsonlRunLogger {
+    fn log(&self, record: RunRecord) {
+        if let Err(err) = self.append(&record) {
+            warn!(path = %self.path.display(), error = %err, "failed to write run log");
+        }
+    }
+}
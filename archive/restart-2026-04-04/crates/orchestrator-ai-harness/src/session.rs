@@ -1,5 +1,6 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+use crate::content::{RunOutput, TokenUsage};
 use crate::harness::HarnessInner;
 use crate::model::ModelRef;
 use crate::run::RunBuilder;
@@ -18,6 +19,27 @@ impl SessionConfig {
     }
 }
 
+/// Token and cost totals accumulated over a session's completed runs.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SessionUsage {
+    /// Completed runs counted in the totals.
+    pub runs: u64,
+    /// Summed token usage of runs that reported it.
+    pub usage: TokenUsage,
+    /// Summed estimated cost of runs with a known price.
+    pub cost_usd: f64,
+}
+
+impl SessionUsage {
+    pub(crate) fn record(&mut self, output: &RunOutput) {
+        self.runs += 1;
+        if let Some(usage) = &output.usage {
+            self.usage.add(usage);
+        }
+        self.cost_usd += output.cost_usd.unwrap_or_default();
+    }
+}
+
 /// Logical grouping for runs.
 ///
 /// `v1` sessions are lightweight and in-memory only; they do not persist
@@ -27,6 +49,7 @@ pub struct Session {
     pub(crate) harness: Arc<HarnessInner>,
     pub(crate) session_id: uuid::Uuid,
     pub(crate) config: SessionConfig,
+    pub(crate) usage: Arc<Mutex<SessionUsage>>,
 }
 
 impl Session {
@@ -35,15 +58,24 @@ impl Session {
             harness,
             session_id: uuid::Uuid::new_v4(),
             config,
+            usage: Arc::default(),
         }
     }
 
+    /// Returns token and cost totals for runs completed so far in this session.
+    ///
+    /// Clones of a `Session` share the same totals.
+    pub fn usage(&self) -> SessionUsage {
+        self.usage.lock().expect("session usage lock").clone()
+    }
+
     /// Starts building a run for the given model.
     pub fn run(&self, model: ModelRef) -> RunBuilder {
         RunBuilder::new(
             self.harness.clone(),
             self.session_id,
             self.config.name.clone(),
+            self.usage.clone(),
             model,
         )
     }
@@ -16,6 +16,19 @@ pub enum StreamEvent {
         seq: u64,
         text: String,
     },
+    /// Fragment of the JSON arguments of a tool call the model is still writing.
+    ToolCallDelta {
+        run_id: uuid::Uuid,
+        call_id: String,
+        arguments_fragment: String,
+    },
+    /// Tool call the model finished, with the concatenated arguments.
+    ToolCall {
+        run_id: uuid::Uuid,
+        call_id: String,
+        name: String,
+        arguments: String,
+    },
     /// Terminal success event with aggregated output.
     Completed {
         run_id: uuid::Uuid,
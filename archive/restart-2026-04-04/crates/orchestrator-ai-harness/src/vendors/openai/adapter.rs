@@ -1,27 +1,31 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::pin::Pin;
+use std::time::Duration;
 
 use futures::StreamExt as _;
 use futures::stream;
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::ProviderId;
-use crate::content::InputPart;
+use crate::content::{InputPart, RunOutput};
 use crate::errors::{HarnessError, ProviderError};
+use crate::model::redact_headers;
 use crate::provider::{
     ProviderAdapter, ProviderEvent, ProviderRequest, ProviderResponseMeta, ProviderStreamHandle,
 };
 
-use super::config::OpenAiClientConfig;
+use super::config::{OpenAiApiSurface, OpenAiClientConfig};
 use super::options::OpenAiRequestOptions;
-use super::transport::{SseDecoder, map_openai_frame_to_events};
+use super::transport::{
+    SseDecoder, ToolCallAssembler, map_non_stream_response, map_openai_frame_to_events,
+};
 
 const OPENAI_PROVIDER: &str = "openai";
 
 type ByteStream =
     Pin<Box<dyn futures::Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Send + 'static>>;
 
-/// Provider adapter for OpenAI's Responses API (streaming).
+/// Provider adapter for OpenAI's Responses or Chat Completions API (streaming).
 pub struct OpenAiProvider {
     client: reqwest::Client,
     config: OpenAiClientConfig,
@@ -60,15 +64,162 @@ impl ProviderAdapter for OpenAiProvider {
     ) -> Result<ProviderStreamHandle, ProviderError> {
         let provider_id = ProviderId::new(OPENAI_PROVIDER);
         let request_options = read_openai_options(&req, &provider_id)?;
-        let body = build_request_body(&req, &request_options)?;
-        debug!(run_id = %req.run_id, session_id = %req.session_id, model = %req.model.model, "starting OpenAI responses stream");
+        let surface = self.config.api_surface;
+        let mut body = match surface {
+            OpenAiApiSurface::Responses => build_request_body(&req, &request_options)?,
+            OpenAiApiSurface::ChatCompletions => {
+                build_chat_completions_body(&req, &request_options)?
+            }
+        };
+        if !self.config.streaming {
+            body["stream"] = serde_json::json!(false);
+        }
+        // Only background responses can be resumed; they must be stored.
+        let resumable = surface == OpenAiApiSurface::Responses
+            && self.config.streaming
+            && self.config.max_reconnects > 0
+            && !req.privacy_mode
+            && request_options.store != Some(false);
+        if resumable {
+            body["background"] = serde_json::json!(true);
+            body["store"] = serde_json::json!(true);
+        }
+        let mut headers: HashMap<String, String> =
+            req.options.headers.clone().into_iter().collect();
+        headers.extend(req.headers);
+        debug!(run_id = %req.run_id, session_id = %req.session_id, model = %req.model.model, surface = ?surface, headers = ?redact_headers(&headers), "starting OpenAI stream");
+
+        let request = StreamRequest {
+            client: self.client.clone(),
+            config: self.config.clone(),
+            body,
+            headers,
+            timeout: req.options.timeout,
+            raw_response: req.options.raw_response,
+            resumable,
+        };
+        let response = request.send(&provider_id).await?;
 
+        if !self.config.streaming {
+            let value: serde_json::Value = response.json().await.map_err(|e| {
+                ProviderError::transport(
+                    provider_id.clone(),
+                    format!("OpenAI response read failed: {e}"),
+                )
+            })?;
+            let mut events = map_non_stream_response(&provider_id, surface, &value)?;
+            if req.options.raw_response {
+                events
+                    .iter_mut()
+                    .for_each(|event| attach_raw(event, &value));
+            }
+            return Ok(ProviderStreamHandle {
+                stream: Box::pin(stream::iter(events.into_iter().map(Ok))),
+                metadata: ProviderResponseMeta::default(),
+            });
+        }
+
+        let bytes_stream: ByteStream = Box::pin(response.bytes_stream());
+        let stream = openai_event_stream(provider_id.clone(), surface, bytes_stream, request);
+
+        Ok(ProviderStreamHandle {
+            stream: Box::pin(stream),
+            metadata: ProviderResponseMeta::default(),
+        })
+    }
+
+    async fn list_models(&self) -> Result<Vec<String>, ProviderError> {
+        let provider_id = ProviderId::new(OPENAI_PROVIDER);
         let mut http_req = self
             .client
-            .post(self.config.responses_url())
-            .bearer_auth(&self.config.api_key)
-            .json(&body);
-        if let Some(timeout) = req.options.timeout {
+            .get(self.config.models_url())
+            .bearer_auth(&self.config.api_key);
+        for (name, value) in self.config.account_headers() {
+            http_req = http_req.header(name, value);
+        }
+        let response = http_req.send().await.map_err(|e| {
+            ProviderError::transport(
+                provider_id.clone(),
+                format!("OpenAI models request failed: {e}"),
+            )
+        })?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "<unreadable body>".to_string());
+            return Err(ProviderError::provider(
+                provider_id,
+                format!("OpenAI models request failed with status {status}: {body}"),
+                Some(status.as_u16()),
+            ));
+        }
+        let value: serde_json::Value = response.json().await.map_err(|e| {
+            ProviderError::transport(
+                provider_id.clone(),
+                format!("OpenAI models response read failed: {e}"),
+            )
+        })?;
+        let data = value["data"].as_array().ok_or_else(|| {
+            ProviderError::protocol(provider_id.clone(), "OpenAI models response missing data")
+        })?;
+        Ok(data
+            .iter()
+            .filter_map(|model| model["id"].as_str().map(String::from))
+            .collect())
+    }
+}
+
+/// Everything needed to send the streaming request and resume it after a disconnect.
+struct StreamRequest {
+    client: reqwest::Client,
+    config: OpenAiClientConfig,
+    body: serde_json::Value,
+    headers: HashMap<String, String>,
+    timeout: Option<Duration>,
+    /// Keep the frame carrying the completion as `RunOutput::raw_response`.
+    raw_response: bool,
+    /// Sent in background mode, so a dropped stream can be resumed by response id.
+    resumable: bool,
+}
+
+impl StreamRequest {
+    async fn send(&self, provider_id: &ProviderId) -> Result<reqwest::Response, ProviderError> {
+        let http_req = self.client.post(self.config.request_url()).json(&self.body);
+        self.dispatch(provider_id, http_req).await
+    }
+
+    /// Streams the events of background response `response_id` after `starting_after`.
+    async fn resume(
+        &self,
+        provider_id: &ProviderId,
+        response_id: &str,
+        starting_after: u64,
+    ) -> Result<reqwest::Response, ProviderError> {
+        let http_req = self
+            .client
+            .get(format!("{}/{response_id}", self.config.request_url()))
+            .query(&[
+                ("stream", "true".to_string()),
+                ("starting_after", starting_after.to_string()),
+            ]);
+        self.dispatch(provider_id, http_req).await
+    }
+
+    async fn dispatch(
+        &self,
+        provider_id: &ProviderId,
+        http_req: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, ProviderError> {
+        let mut http_req = http_req.bearer_auth(&self.config.api_key);
+        for (name, value) in self.config.account_headers() {
+            http_req = http_req.header(name, value);
+        }
+        for (name, value) in &self.headers {
+            http_req = http_req.header(name, value);
+        }
+        if let Some(timeout) = self.timeout {
             http_req = http_req.timeout(timeout);
         }
 
@@ -82,19 +233,19 @@ impl ProviderAdapter for OpenAiProvider {
                 .await
                 .unwrap_or_else(|_| "<unreadable body>".to_string());
             return Err(ProviderError::provider(
-                provider_id,
-                format!("OpenAI responses request failed with status {status}: {body}"),
+                provider_id.clone(),
+                format!("OpenAI request failed with status {status}: {body}"),
                 Some(status.as_u16()),
             ));
         }
+        Ok(response)
+    }
+}
 
-        let bytes_stream: ByteStream = Box::pin(response.bytes_stream());
-        let stream = openai_event_stream(provider_id.clone(), bytes_stream);
-
-        Ok(ProviderStreamHandle {
-            stream: Box::pin(stream),
-            metadata: ProviderResponseMeta::default(),
-        })
+/// Sets `raw` as the raw response of a `Completed` event; other events are left alone.
+fn attach_raw(event: &mut ProviderEvent, raw: &serde_json::Value) {
+    if let ProviderEvent::Completed { output, .. } = event {
+        output.get_or_insert_with(RunOutput::default).raw_response = Some(raw.clone());
     }
 }
 
@@ -110,12 +261,19 @@ fn read_openai_options(
     }
 }
 
-pub(crate) fn build_request_body(
+/// Validates options and builds the system/user message list with the given user content.
+/// Tool calls and their results become their own items, ahead of the user message, in part order.
+fn build_messages(
     req: &ProviderRequest,
     options: &OpenAiRequestOptions,
-) -> Result<serde_json::Value, ProviderError> {
+    render: fn(&[InputPart]) -> Result<Vec<serde_json::Value>, serde_json::Error>,
+    render_tools: fn(&[InputPart]) -> Vec<serde_json::Value>,
+) -> Result<Vec<serde_json::Value>, ProviderError> {
     let provider_id = ProviderId::new(OPENAI_PROVIDER);
-    let user_payload = render_user_input(&req.input_parts).map_err(|e| {
+    options.validate().map_err(|e| {
+        ProviderError::protocol(provider_id.clone(), format!("invalid OpenAI options: {e}"))
+    })?;
+    let user_payload = render(&req.input_parts).map_err(|e| {
         ProviderError::protocol(
             provider_id.clone(),
             format!("failed to serialize input parts: {e}"),
@@ -134,10 +292,55 @@ pub(crate) fn build_request_body(
             "content": system_prompt,
         }));
     }
-    input.push(serde_json::json!({
-        "role": "user",
-        "content": user_payload,
-    }));
+    input.extend(render_tools(&req.input_parts));
+    if !user_payload.is_empty() {
+        input.push(serde_json::json!({
+            "role": "user",
+            "content": user_payload,
+        }));
+    }
+    Ok(input)
+}
+
+/// Applies request fields shared by both API surfaces.
+fn apply_common_options(
+    body: &mut serde_json::Value,
+    req: &ProviderRequest,
+    options: &OpenAiRequestOptions,
+) {
+    if req.privacy_mode {
+        body["store"] = serde_json::json!(false);
+        return;
+    }
+
+    if let Some(metadata) = options.metadata.as_ref() {
+        body["metadata"] = serde_json::json!(metadata);
+    }
+
+    if let Some(user) = options.user.as_ref() {
+        body["user"] = serde_json::json!(user);
+    }
+}
+
+/// Builds a Responses API request body.
+pub(crate) fn build_request_body(
+    req: &ProviderRequest,
+    options: &OpenAiRequestOptions,
+) -> Result<serde_json::Value, ProviderError> {
+    let provider_id = ProviderId::new(OPENAI_PROVIDER);
+    for (name, set) in [
+        ("logit_bias", options.logit_bias.is_some()),
+        ("stop", options.stop.is_some()),
+        ("seed", req.options.seed.is_some()),
+    ] {
+        if set {
+            return Err(ProviderError::protocol(
+                provider_id,
+                format!("OpenAI option {name} is only supported by the Chat Completions API"),
+            ));
+        }
+    }
+    let input = build_messages(req, options, render_user_input, render_tool_items)?;
 
     let mut body = serde_json::json!({
         "model": req.model.model,
@@ -146,6 +349,8 @@ pub(crate) fn build_request_body(
         "store": options.store.unwrap_or(false),
     });
 
+    apply_common_options(&mut body, req, options);
+
     if let Some(effort) = options.reasoning_effort.as_ref() {
         body["reasoning"] = serde_json::json!({ "effort": effort });
     }
@@ -153,36 +358,276 @@ pub(crate) fn build_request_body(
     Ok(body)
 }
 
-fn render_user_input(parts: &[InputPart]) -> Result<String, serde_json::Error> {
-    let mut segments = Vec::with_capacity(parts.len());
+/// Builds a Chat Completions request body.
+pub(crate) fn build_chat_completions_body(
+    req: &ProviderRequest,
+    options: &OpenAiRequestOptions,
+) -> Result<serde_json::Value, ProviderError> {
+    let messages = build_messages(
+        req,
+        options,
+        render_chat_user_content,
+        render_chat_tool_messages,
+    )?;
+
+    let mut body = serde_json::json!({
+        "model": req.model.model,
+        "messages": messages,
+        "stream": true,
+        "store": options.store.unwrap_or(false),
+    });
+
+    apply_common_options(&mut body, req, options);
+
+    if let Some(seed) = req.options.seed {
+        body["seed"] = serde_json::json!(seed);
+    }
+
+    if let Some(logit_bias) = options.logit_bias.as_ref() {
+        body["logit_bias"] = serde_json::json!(logit_bias);
+    }
+
+    if let Some(stop) = options.stop.as_ref() {
+        body["stop"] = serde_json::json!(stop);
+    }
+
+    if let Some(effort) = options.reasoning_effort.as_ref() {
+        body["reasoning_effort"] = serde_json::json!(effort);
+    }
+
+    Ok(body)
+}
+
+/// Renders input parts as Responses API content items, preserving part order.
+fn render_user_input(parts: &[InputPart]) -> Result<Vec<serde_json::Value>, serde_json::Error> {
+    use base64::Engine as _;
+    let mut content = Vec::with_capacity(parts.len());
+    for part in parts {
+        let item = match part {
+            InputPart::Text(text) => serde_json::json!({ "type": "input_text", "text": text }),
+            InputPart::Json(value) => serde_json::json!({
+                "type": "input_text",
+                "text": serde_json::to_string(value)?,
+            }),
+            InputPart::ImageBytes { mime, data } => serde_json::json!({
+                "type": "input_image",
+                "image_url": format!(
+                    "data:{mime};base64,{}",
+                    base64::engine::general_purpose::STANDARD.encode(data)
+                ),
+            }),
+            InputPart::ToolCall { .. } | InputPart::ToolResult { .. } => continue,
+        };
+        content.push(item);
+    }
+    Ok(content)
+}
+
+/// Renders tool calls and results as Responses `function_call`/`function_call_output` items.
+fn render_tool_items(parts: &[InputPart]) -> Vec<serde_json::Value> {
+    parts
+        .iter()
+        .filter_map(|part| match part {
+            InputPart::ToolCall {
+                call_id,
+                name,
+                arguments,
+            } => Some(serde_json::json!({
+                "type": "function_call",
+                "call_id": call_id,
+                "name": name,
+                "arguments": arguments,
+            })),
+            InputPart::ToolResult { call_id, content } => Some(serde_json::json!({
+                "type": "function_call_output",
+                "call_id": call_id,
+                "output": content,
+            })),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Renders tool calls as assistant `tool_calls` messages (consecutive calls share one
+/// message) and tool results as `tool` messages, in part order.
+fn render_chat_tool_messages(parts: &[InputPart]) -> Vec<serde_json::Value> {
+    let mut messages: Vec<serde_json::Value> = Vec::new();
+    let mut in_call_run = false;
     for part in parts {
         match part {
-            InputPart::Text(text) => segments.push(text.clone()),
-            InputPart::Json(value) => segments.push(serde_json::to_string(value)?),
+            InputPart::ToolCall {
+                call_id,
+                name,
+                arguments,
+            } => {
+                let call = serde_json::json!({
+                    "id": call_id,
+                    "type": "function",
+                    "function": { "name": name, "arguments": arguments },
+                });
+                match messages.last_mut() {
+                    Some(last) if in_call_run => {
+                        if let Some(calls) = last["tool_calls"].as_array_mut() {
+                            calls.push(call);
+                        }
+                    }
+                    _ => messages.push(serde_json::json!({
+                        "role": "assistant",
+                        "content": null,
+                        "tool_calls": [call],
+                    })),
+                }
+                in_call_run = true;
+            }
+            InputPart::ToolResult { call_id, content } => {
+                messages.push(serde_json::json!({
+                    "role": "tool",
+                    "tool_call_id": call_id,
+                    "content": content,
+                }));
+                in_call_run = false;
+            }
+            _ => {}
         }
     }
-    Ok(segments.join("\n"))
+    messages
 }
 
-fn openai_event_stream(
+/// Renders input parts as Chat Completions content parts, preserving part order.
+fn render_chat_user_content(
+    parts: &[InputPart],
+) -> Result<Vec<serde_json::Value>, serde_json::Error> {
+    use base64::Engine as _;
+    let mut content = Vec::with_capacity(parts.len());
+    for part in parts {
+        let item = match part {
+            InputPart::Text(text) => serde_json::json!({ "type": "text", "text": text }),
+            InputPart::Json(value) => serde_json::json!({
+                "type": "text",
+                "text": serde_json::to_string(value)?,
+            }),
+            InputPart::ImageBytes { mime, data } => serde_json::json!({
+                "type": "image_url",
+                "image_url": {
+                    "url": format!(
+                        "data:{mime};base64,{}",
+                        base64::engine::general_purpose::STANDARD.encode(data)
+                    ),
+                },
+            }),
+            InputPart::ToolCall { .. } | InputPart::ToolResult { .. } => continue,
+        };
+        content.push(item);
+    }
+    Ok(content)
+}
+
+/// Stream state, including what is needed to resume a dropped stream.
+struct EventStreamState {
     provider_id: ProviderId,
+    surface: OpenAiApiSurface,
     bytes_stream: ByteStream,
-) -> impl futures::Stream<Item = Result<ProviderEvent, ProviderError>> + Send {
-    struct State {
-        provider_id: ProviderId,
-        bytes_stream: ByteStream,
-        decoder: SseDecoder,
-        pending: VecDeque<ProviderEvent>,
-        done: bool,
+    decoder: SseDecoder,
+    pending: VecDeque<ProviderEvent>,
+    done: bool,
+    request: StreamRequest,
+    reconnects: u32,
+    /// Id of the background response being streamed, once an event has named it.
+    response_id: Option<String>,
+    /// Highest `sequence_number` handled; resumed streams start after it.
+    last_sequence: Option<u64>,
+    tool_calls: ToolCallAssembler,
+}
+
+impl EventStreamState {
+    fn push_frame(&mut self, frame: &super::transport::SseFrame) -> Result<(), ProviderError> {
+        if self.request.resumable
+            && let Some((seq, response_id)) = frame.sequence()
+        {
+            if self.last_sequence.is_some_and(|last| seq <= last) {
+                return Ok(());
+            }
+            self.last_sequence = Some(seq);
+            if response_id.is_some() {
+                self.response_id = response_id;
+            }
+        }
+        for mut event in map_openai_frame_to_events(
+            &self.provider_id,
+            self.surface,
+            frame,
+            &mut self.tool_calls,
+        )? {
+            if self.request.raw_response
+                && let Ok(raw) = serde_json::from_str(&frame.data)
+            {
+                attach_raw(&mut event, &raw);
+            }
+            self.pending.push_back(event);
+        }
+        Ok(())
     }
 
+    /// Resumes a dropped background stream after the last handled event, with bounded,
+    /// doubling backoff.
+    ///
+    /// A stream that cannot be resumed fails with the transport error: sending the request
+    /// again would start a new generation rather than continue this one.
+    async fn reconnect(&mut self, cause: ProviderError) -> Result<(), ProviderError> {
+        let max_reconnects = self.request.config.max_reconnects;
+        let backoff = self.request.config.reconnect_backoff;
+        let (Some(response_id), Some(starting_after)) =
+            (self.response_id.clone(), self.last_sequence)
+        else {
+            return Err(cause);
+        };
+        let mut last_error = cause;
+        while self.reconnects < max_reconnects {
+            self.reconnects += 1;
+            warn!(
+                attempt = self.reconnects,
+                max_reconnects,
+                error = %last_error,
+                "OpenAI stream dropped; reconnecting"
+            );
+            tokio::time::sleep(backoff.saturating_mul(1 << (self.reconnects - 1).min(16))).await;
+            match self
+                .request
+                .resume(&self.provider_id, &response_id, starting_after)
+                .await
+            {
+                Ok(response) => {
+                    self.bytes_stream = Box::pin(response.bytes_stream());
+                    self.decoder = SseDecoder::default();
+                    return Ok(());
+                }
+                Err(e @ ProviderError::Transport { .. }) => last_error = e,
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error)
+    }
+}
+
+fn openai_event_stream(
+    provider_id: ProviderId,
+    surface: OpenAiApiSurface,
+    bytes_stream: ByteStream,
+    request: StreamRequest,
+) -> impl futures::Stream<Item = Result<ProviderEvent, ProviderError>> + Send {
     stream::try_unfold(
-        State {
+        EventStreamState {
             provider_id,
+            surface,
             bytes_stream,
             decoder: SseDecoder::default(),
             pending: VecDeque::new(),
             done: false,
+            request,
+            reconnects: 0,
+            response_id: None,
+            last_sequence: None,
+            tool_calls: ToolCallAssembler::default(),
         },
         |mut state| async move {
             loop {
@@ -195,20 +640,17 @@ fn openai_event_stream(
 
                 match state.bytes_stream.next().await {
                     Some(Ok(chunk)) => {
-                        let frames = state.decoder.push_chunk(&chunk);
-                        for frame in frames {
-                            let events = map_openai_frame_to_events(&state.provider_id, &frame)?;
-                            for event in events {
-                                state.pending.push_back(event);
-                            }
+                        for frame in state.decoder.push_chunk(&chunk) {
+                            state.push_frame(&frame)?;
                         }
                         continue;
                     }
                     Some(Err(e)) => {
-                        return Err(ProviderError::transport(
-                            state.provider_id,
+                        let err = ProviderError::transport(
+                            state.provider_id.clone(),
                             format!("OpenAI streaming read failed: {e}"),
-                        ));
+                        );
+                        state.reconnect(err).await?;
                     }
                     None => {
                         state.done = true;
@@ -237,6 +679,8 @@ mod tests {
             input_parts: parts,
             options: RunOptions::default(),
             vendor_options: HashMap::new(),
+            headers: HashMap::new(),
+            privacy_mode: false,
         }
     }
 
@@ -271,6 +715,675 @@ mod tests {
         );
     }
 
+    #[test]
+    fn seed_is_sent_when_set() {
+        let mut req = request_with_parts(vec![InputPart::Text("hello".into())]);
+        let options = OpenAiRequestOptions::default();
+        let body = build_chat_completions_body(&req, &options).expect("body");
+        assert!(body.get("seed").is_none());
+
+        req.options.seed = Some(42);
+        let body = build_chat_completions_body(&req, &options).expect("body");
+        assert_eq!(body.get("seed").and_then(|v| v.as_u64()), Some(42));
+    }
+
+    #[test]
+    fn seed_is_rejected_for_responses() {
+        let mut req = request_with_parts(vec![InputPart::Text("hello".into())]);
+        req.options.seed = Some(42);
+        let err = build_request_body(&req, &OpenAiRequestOptions::default()).unwrap_err();
+        assert!(err.message().contains("seed"), "got: {err}");
+        assert!(err.message().contains("Chat Completions"), "got: {err}");
+    }
+
+    #[test]
+    fn logit_bias_and_stop_are_sent_when_set() {
+        let req = request_with_parts(vec![InputPart::Text("yes or no?".into())]);
+        let body =
+            build_chat_completions_body(&req, &OpenAiRequestOptions::default()).expect("body");
+        assert!(body.get("logit_bias").is_none());
+        assert!(body.get("stop").is_none());
+
+        let options = OpenAiRequestOptions::default()
+            .logit_bias(9891, 100)
+            .logit_bias(2201, -100)
+            .stop("\n");
+        let body = build_chat_completions_body(&req, &options).expect("body");
+        assert_eq!(
+            body["logit_bias"],
+            serde_json::json!({ "2201": -100, "9891": 100 })
+        );
+        assert_eq!(body["stop"], serde_json::json!(["\n"]));
+    }
+
+    #[test]
+    fn logit_bias_and_stop_are_rejected_for_responses() {
+        let req = request_with_parts(vec![InputPart::Text("yes or no?".into())]);
+        for options in [
+            OpenAiRequestOptions::default().logit_bias(9891, 100),
+            OpenAiRequestOptions::default().stop("\n"),
+        ] {
+            let err = build_request_body(&req, &options).unwrap_err();
+            assert!(err.message().contains("Chat Completions"), "got: {err}");
+        }
+    }
+
+    #[test]
+    fn out_of_range_logit_bias_is_rejected() {
+        let req = request_with_parts(vec![InputPart::Text("hello".into())]);
+        let err =
+            build_chat_completions_body(&req, &OpenAiRequestOptions::default().logit_bias(42, 101))
+                .unwrap_err();
+        assert!(err.message().contains("logit_bias"), "got: {err}");
+    }
+
+    #[test]
+    fn each_api_surface_builds_its_path_and_body() {
+        let config = OpenAiClientConfig::new("sk-test").base_url("http://localhost:8080/");
+        assert_eq!(config.request_url(), "http://localhost:8080/v1/responses");
+        let chat = config.api_surface(OpenAiApiSurface::ChatCompletions);
+        assert_eq!(
+            chat.request_url(),
+            "http://localhost:8080/v1/chat/completions"
+        );
+
+        let mut req = request_with_parts(vec![InputPart::Text("hello".into())]);
+        let options = OpenAiRequestOptions::default().reasoning_effort(OpenAiReasoningEffort::High);
+
+        let responses = build_request_body(&req, &options).expect("responses body");
+        assert_eq!(
+            responses,
+            serde_json::json!({
+                "model": "gpt-5-nano",
+                "input": [
+                    { "role": "system", "content": "sys" },
+                    { "role": "user", "content": [{ "type": "input_text", "text": "hello" }] },
+                ],
+                "stream": true,
+                "store": false,
+                "reasoning": { "effort": "high" },
+            })
+        );
+
+        // Sampling controls below are Chat Completions parameters.
+        req.options.seed = Some(7);
+        let chat = build_chat_completions_body(&req, &options.stop("END")).expect("chat body");
+        assert_eq!(
+            chat,
+            serde_json::json!({
+                "model": "gpt-5-nano",
+                "messages": [
+                    { "role": "system", "content": "sys" },
+                    { "role": "user", "content": [{ "type": "text", "text": "hello" }] },
+                ],
+                "stream": true,
+                "store": false,
+                "seed": 7,
+                "stop": ["END"],
+                "reasoning_effort": "high",
+            })
+        );
+    }
+
+    #[test]
+    fn interleaved_parts_keep_order_in_single_user_message() {
+        let req = request_with_parts(vec![
+            InputPart::Text("before".into()),
+            InputPart::ImageBytes {
+                mime: "image/png".into(),
+                data: bytes::Bytes::from_static(b"png"),
+            },
+            InputPart::Text("after".into()),
+        ]);
+        let body = build_request_body(&req, &OpenAiRequestOptions::default()).expect("body");
+        let input = body["input"].as_array().expect("input array");
+        let user_messages: Vec<_> = input.iter().filter(|m| m["role"] == "user").collect();
+        assert_eq!(user_messages.len(), 1);
+        assert_eq!(
+            user_messages[0]["content"],
+            serde_json::json!([
+                { "type": "input_text", "text": "before" },
+                { "type": "input_image", "image_url": "data:image/png;base64,cG5n" },
+                { "type": "input_text", "text": "after" },
+            ])
+        );
+    }
+
+    #[test]
+    fn tool_results_follow_the_calls_they_answer() {
+        let call = |id: &str| InputPart::ToolCall {
+            call_id: id.into(),
+            name: "weather".into(),
+            arguments: r#"{"city":"Paris"}"#.into(),
+        };
+        let result = |id: &str, content: &str| InputPart::ToolResult {
+            call_id: id.into(),
+            content: content.into(),
+        };
+        let req = request_with_parts(vec![
+            call("call_1"),
+            call("call_2"),
+            result("call_1", "21C"),
+            result("call_2", "19C"),
+            InputPart::Text("and tomorrow?".into()),
+        ]);
+
+        let body = build_request_body(&req, &OpenAiRequestOptions::default()).expect("body");
+        assert_eq!(
+            body["input"],
+            serde_json::json!([
+                { "role": "system", "content": "sys" },
+                { "type": "function_call", "call_id": "call_1", "name": "weather", "arguments": "{\"city\":\"Paris\"}" },
+                { "type": "function_call", "call_id": "call_2", "name": "weather", "arguments": "{\"city\":\"Paris\"}" },
+                { "type": "function_call_output", "call_id": "call_1", "output": "21C" },
+                { "type": "function_call_output", "call_id": "call_2", "output": "19C" },
+                { "role": "user", "content": [{ "type": "input_text", "text": "and tomorrow?" }] },
+            ])
+        );
+
+        let chat =
+            build_chat_completions_body(&req, &OpenAiRequestOptions::default()).expect("chat");
+        let function =
+            serde_json::json!({ "name": "weather", "arguments": "{\"city\":\"Paris\"}" });
+        assert_eq!(
+            chat["messages"],
+            serde_json::json!([
+                { "role": "system", "content": "sys" },
+                {
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [
+                        { "id": "call_1", "type": "function", "function": function },
+                        { "id": "call_2", "type": "function", "function": function },
+                    ],
+                },
+                { "role": "tool", "tool_call_id": "call_1", "content": "21C" },
+                { "role": "tool", "tool_call_id": "call_2", "content": "19C" },
+                { "role": "user", "content": [{ "type": "text", "text": "and tomorrow?" }] },
+            ])
+        );
+    }
+
+    /// Serves one HTTP request with `response_body` and returns the JSON request body it received.
+    fn serve_once(response_body: serde_json::Value) -> (String, std::thread::JoinHandle<String>) {
+        let (base_url, server) = serve_once_with_headers(response_body);
+        let handle = std::thread::spawn(move || server.join().expect("server").1);
+        (base_url, handle)
+    }
+
+    /// Headers (lowercase names) and body received by a test server.
+    type ReceivedRequest = (HashMap<String, String>, String);
+
+    /// Like `serve_once`, but also returns the received headers.
+    fn serve_once_with_headers(
+        response_body: serde_json::Value,
+    ) -> (String, std::thread::JoinHandle<ReceivedRequest>) {
+        let payload = response_body.to_string();
+        let len = payload.len();
+        let (base_url, server) = serve("application/json", vec![(payload, len)]);
+        let handle = std::thread::spawn(move || {
+            let (_, received) = server.join().expect("server").remove(0);
+            received
+        });
+        (base_url, handle)
+    }
+
+    /// Serves one SSE connection per `(body, declared_len)`; see `serve`.
+    fn serve_streams(
+        responses: Vec<(String, usize)>,
+    ) -> (
+        String,
+        std::thread::JoinHandle<Vec<(String, ReceivedRequest)>>,
+    ) {
+        serve("text/event-stream", responses)
+    }
+
+    /// Serves one connection per `(body, declared_len)`, closing early when `declared_len`
+    /// exceeds the body. Returns each request line with the headers and body received.
+    fn serve(
+        content_type: &'static str,
+        responses: Vec<(String, usize)>,
+    ) -> (
+        String,
+        std::thread::JoinHandle<Vec<(String, ReceivedRequest)>>,
+    ) {
+        use std::io::{BufRead as _, BufReader, Read as _, Write as _};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let base_url = format!("http://{}", listener.local_addr().expect("addr"));
+        let server = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (body, declared_len) in responses {
+                let (stream, _) = listener.accept().expect("accept");
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader
+                    .read_line(&mut request_line)
+                    .expect("read request line");
+                let mut headers = HashMap::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).expect("read header");
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+                    }
+                }
+                let content_length = headers
+                    .get("content-length")
+                    .map_or(0, |value| value.parse().expect("content length"));
+                let mut request_body = vec![0u8; content_length];
+                reader.read_exact(&mut request_body).expect("read body");
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\ncontent-type: {content_type}\r\ncontent-length: {declared_len}\r\nconnection: close\r\n\r\n{body}"
+                )
+                .expect("write response");
+                requests.push((
+                    request_line.trim().to_string(),
+                    (headers, String::from_utf8(request_body).expect("utf8 body")),
+                ));
+            }
+            requests
+        });
+        (base_url, server)
+    }
+
+    fn reconnecting_harness(base_url: String, surface: OpenAiApiSurface) -> crate::Harness {
+        let provider = OpenAiProvider::new(
+            OpenAiClientConfig::new("sk-test")
+                .base_url(base_url)
+                .api_surface(surface)
+                .reconnect(2, std::time::Duration::from_millis(1)),
+        )
+        .expect("provider");
+        crate::Harness::builder()
+            .register_provider(std::sync::Arc::new(provider))
+            .build()
+            .expect("harness")
+    }
+
+    #[tokio::test]
+    async fn dropped_stream_resumes_the_background_response_after_the_last_event() {
+        let frame = |seq: u64, mut data: serde_json::Value| {
+            data["sequence_number"] = serde_json::json!(seq);
+            format!("data: {data}\n\n")
+        };
+        let delta = |seq: u64, text: &str| {
+            frame(
+                seq,
+                serde_json::json!({ "type": "response.output_text.delta", "delta": text }),
+            )
+        };
+        let first = [
+            frame(
+                0,
+                serde_json::json!({ "type": "response.created", "response": { "id": "resp_1", "status": "in_progress" } }),
+            ),
+            delta(1, "Hello"),
+            delta(2, ", wor"),
+        ]
+        .concat();
+        let resumed = [
+            delta(3, "ld!"),
+            frame(
+                4,
+                serde_json::json!({ "type": "response.completed", "response": { "id": "resp_1", "status": "completed" } }),
+            ),
+        ]
+        .concat();
+        // The first response promises more bytes than it sends, then closes mid-stream.
+        let resumed_len = resumed.len();
+        let (base_url, server) = serve_streams(vec![(first, 10_000), (resumed, resumed_len)]);
+
+        let text = reconnecting_harness(base_url, OpenAiApiSurface::Responses)
+            .session(crate::SessionConfig::named("reconnect"))
+            .run(crate::ModelRef::new("openai", "gpt-5-nano"))
+            .user_text("hi")
+            .collect_text()
+            .await
+            .expect("resumed run");
+        assert_eq!(text, "Hello, world!");
+
+        let requests = server.join().expect("server");
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].0.starts_with("POST /v1/responses "));
+        let body: serde_json::Value = serde_json::from_str(&requests[0].1.1).expect("json body");
+        assert_eq!(body["background"], serde_json::json!(true));
+        assert_eq!(body["store"], serde_json::json!(true));
+        assert_eq!(
+            requests[1].0,
+            "GET /v1/responses/resp_1?stream=true&starting_after=2 HTTP/1.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn dropped_chat_stream_fails_instead_of_generating_again() {
+        let partial = format!(
+            "data: {}\n\n",
+            serde_json::json!({ "choices": [{ "index": 0, "delta": { "content": "Hello" } }] })
+        );
+        let (base_url, server) = serve_streams(vec![(partial, 10_000)]);
+
+        let result = reconnecting_harness(base_url, OpenAiApiSurface::ChatCompletions)
+            .session(crate::SessionConfig::named("reconnect"))
+            .run(crate::ModelRef::new("openai", "gpt-4o-mini"))
+            .user_text("hi")
+            .collect_text()
+            .await;
+        let err = result.expect_err("a dropped chat stream cannot be resumed");
+        assert!(err.to_string().contains("streaming read failed"), "{err}");
+
+        let requests = server.join().expect("server");
+        assert_eq!(requests.len(), 1);
+        let body: serde_json::Value = serde_json::from_str(&requests[0].1.1).expect("json body");
+        assert!(body.get("background").is_none());
+    }
+
+    #[tokio::test]
+    async fn privacy_mode_forces_store_off_and_strips_identifiers() {
+        use crate::vendors::openai::OpenAiRunBuilderExt as _;
+
+        /// Tries to switch privacy off again; the harness must win.
+        struct Leaky;
+        impl crate::Middleware for Leaky {
+            fn before_request(&self, req: &mut ProviderRequest) {
+                req.privacy_mode = false;
+            }
+        }
+
+        let (base_url, server) = serve_once(serde_json::json!({
+            "status": "completed",
+            "output": [{ "type": "message", "content": [{ "type": "output_text", "text": "ok" }] }]
+        }));
+        let provider = OpenAiProvider::new(
+            OpenAiClientConfig::new("sk-test")
+                .base_url(base_url)
+                .streaming(false),
+        )
+        .expect("provider");
+        let harness = crate::Harness::builder()
+            .register_provider(std::sync::Arc::new(provider))
+            .with_middleware(std::sync::Arc::new(Leaky))
+            .privacy_mode(true)
+            .build()
+            .expect("harness");
+
+        harness
+            .session(crate::SessionConfig::named("private"))
+            .run(crate::ModelRef::new("openai", "gpt-5-nano"))
+            .user_text("hi")
+            .openai_options(
+                OpenAiRequestOptions::default()
+                    .store(true)
+                    .metadata("team", "payments")
+                    .user("user-42"),
+            )
+            .collect_text()
+            .await
+            .expect("private run");
+        let body: serde_json::Value =
+            serde_json::from_str(&server.join().expect("server")).expect("json body");
+        assert_eq!(body["store"], serde_json::json!(false));
+        assert!(body.get("metadata").is_none());
+        assert!(body.get("user").is_none());
+
+        let req = request_with_parts(vec![InputPart::Text("hello".into())]);
+        let options = OpenAiRequestOptions::default().store(true).user("user-42");
+        let body = build_chat_completions_body(&req, &options).expect("chat");
+        assert_eq!(body["store"], serde_json::json!(true));
+        assert_eq!(body["user"], serde_json::json!("user-42"));
+    }
+
+    #[tokio::test]
+    async fn streamed_tool_call_arguments_are_reassembled() {
+        let frames = [
+            serde_json::json!({ "type": "response.output_item.added", "output_index": 0,
+                "item": { "type": "function_call", "id": "fc_1", "call_id": "call_1", "name": "get_weather", "arguments": "" } }),
+            serde_json::json!({ "type": "response.function_call_arguments.delta", "item_id": "fc_1", "delta": "{\"city\":" }),
+            serde_json::json!({ "type": "response.function_call_arguments.delta", "item_id": "fc_1", "delta": "\"Par" }),
+            serde_json::json!({ "type": "response.function_call_arguments.delta", "item_id": "fc_1", "delta": "is\"}" }),
+            serde_json::json!({ "type": "response.output_item.done", "output_index": 0,
+                "item": { "type": "function_call", "id": "fc_1", "call_id": "call_1", "name": "get_weather" } }),
+            serde_json::json!({ "type": "response.completed", "response": { "status": "completed" } }),
+        ];
+        let body: String = frames.iter().map(|f| format!("data: {f}\n\n")).collect();
+
+        let body_len = body.len();
+        let (base_url, server) = serve_streams(vec![(body, body_len)]);
+
+        let provider = OpenAiProvider::new(OpenAiClientConfig::new("sk-test").base_url(base_url))
+            .expect("provider");
+        let harness = crate::Harness::builder()
+            .register_provider(std::sync::Arc::new(provider))
+            .build()
+            .expect("harness");
+        let mut run = harness
+            .session(crate::SessionConfig::named("tools"))
+            .run(crate::ModelRef::new("openai", "gpt-5-nano"))
+            .user_text("weather in Paris?")
+            .start_stream()
+            .await
+            .expect("start stream");
+
+        let mut fragments = Vec::new();
+        let mut calls = Vec::new();
+        while let Some(event) = run.next_event().await {
+            match event {
+                crate::StreamEvent::ToolCallDelta {
+                    call_id,
+                    arguments_fragment,
+                    ..
+                } => {
+                    assert_eq!(call_id, "call_1");
+                    fragments.push(arguments_fragment);
+                }
+                crate::StreamEvent::ToolCall {
+                    call_id,
+                    name,
+                    arguments,
+                    ..
+                } => calls.push((call_id, name, arguments)),
+                crate::StreamEvent::Completed { .. } | crate::StreamEvent::Error { .. } => break,
+                _ => {}
+            }
+        }
+        let output = run.finish().await.expect("run output");
+        server.join().expect("server");
+
+        assert_eq!(fragments, vec!["{\"city\":", "\"Par", "is\"}"]);
+        let arguments = "{\"city\":\"Paris\"}".to_string();
+        assert_eq!(
+            calls,
+            vec![("call_1".into(), "get_weather".into(), arguments.clone())]
+        );
+        assert_eq!(
+            output.parts,
+            vec![crate::OutputPart::ToolCall {
+                call_id: "call_1".into(),
+                name: "get_weather".into(),
+                arguments,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn non_streaming_config_still_collects_text() {
+        let (base_url, server) = serve_once(serde_json::json!({
+            "id": "resp_1",
+            "model": "gpt-5-nano",
+            "status": "completed",
+            "output": [{ "type": "message", "content": [{ "type": "output_text", "text": "hello there" }] }]
+        }));
+        let provider = OpenAiProvider::new(
+            OpenAiClientConfig::new("sk-test")
+                .base_url(base_url)
+                .streaming(false),
+        )
+        .expect("provider");
+        let harness = crate::Harness::builder()
+            .register_provider(std::sync::Arc::new(provider))
+            .build()
+            .expect("harness");
+
+        let output = harness
+            .session(crate::SessionConfig::named("non-stream"))
+            .run(crate::ModelRef::new("openai", "gpt-5-nano"))
+            .user_text("hi")
+            .collect_output()
+            .await
+            .expect("non-streaming run");
+        assert_eq!(output.text(), "hello there");
+        assert_eq!(output.provider_meta.request_id.as_deref(), Some("resp_1"));
+
+        let request: serde_json::Value =
+            serde_json::from_str(&server.join().expect("server")).expect("request json");
+        assert_eq!(request["stream"], serde_json::json!(false));
+    }
+
+    #[tokio::test]
+    async fn complete_raw_returns_the_provider_response_json() {
+        let (base_url, server) = serve_once(serde_json::json!({
+            "id": "resp_raw",
+            "object": "response",
+            "model": "gpt-5-nano",
+            "status": "completed",
+            "output": [{ "type": "message", "content": [{ "type": "output_text", "text": "raw hello" }] }],
+            "usage": { "input_tokens": 3, "output_tokens": 2 }
+        }));
+        let provider = OpenAiProvider::new(
+            OpenAiClientConfig::new("sk-test")
+                .base_url(base_url)
+                .streaming(false),
+        )
+        .expect("provider");
+        let harness = crate::Harness::builder()
+            .register_provider(std::sync::Arc::new(provider))
+            .build()
+            .expect("harness");
+
+        let (output, raw) = harness
+            .session(crate::SessionConfig::named("raw"))
+            .run(crate::ModelRef::new("openai", "gpt-5-nano"))
+            .user_text("hi")
+            .complete_raw()
+            .await
+            .expect("raw run");
+        server.join().expect("server");
+        assert_eq!(output.text(), "raw hello");
+        assert_eq!(output.raw_response, None);
+        assert_eq!(raw["id"], "resp_raw");
+        assert_eq!(raw["object"], "response");
+        assert_eq!(raw["usage"]["output_tokens"], 2);
+        assert_eq!(raw["output"][0]["content"][0]["text"], "raw hello");
+    }
+
+    #[tokio::test]
+    async fn list_models_reads_model_ids() {
+        let (base_url, server) = serve_once(serde_json::json!({
+            "object": "list",
+            "data": [
+                { "id": "gpt-5-nano", "object": "model" },
+                { "id": "gpt-5-mini", "object": "model" }
+            ]
+        }));
+        let provider = OpenAiProvider::new(OpenAiClientConfig::new("sk-test").base_url(base_url))
+            .expect("provider");
+        let models = provider.list_models().await.expect("models");
+        assert_eq!(
+            models,
+            vec!["gpt-5-nano".to_string(), "gpt-5-mini".to_string()]
+        );
+        assert_eq!(server.join().expect("server"), "");
+    }
+
+    #[tokio::test]
+    async fn account_headers_are_sent_only_when_configured() {
+        let models = serde_json::json!({ "object": "list", "data": [] });
+
+        let (base_url, server) = serve_once_with_headers(models.clone());
+        let provider = OpenAiProvider::new(
+            OpenAiClientConfig::new("sk-test")
+                .base_url(base_url)
+                .organization("org-123")
+                .project("proj_abc"),
+        )
+        .expect("provider");
+        provider.list_models().await.expect("models");
+        let (headers, _) = server.join().expect("server");
+        assert_eq!(
+            headers.get("openai-organization").map(String::as_str),
+            Some("org-123")
+        );
+        assert_eq!(
+            headers.get("openai-project").map(String::as_str),
+            Some("proj_abc")
+        );
+
+        let (base_url, server) = serve_once_with_headers(models);
+        let provider = OpenAiProvider::new(OpenAiClientConfig::new("sk-test").base_url(base_url))
+            .expect("provider");
+        provider.list_models().await.expect("models");
+        let (headers, _) = server.join().expect("server");
+        assert!(!headers.contains_key("openai-organization"));
+        assert!(!headers.contains_key("openai-project"));
+    }
+
+    #[tokio::test]
+    async fn run_headers_reach_the_outgoing_request() {
+        let (base_url, server) = serve_once_with_headers(serde_json::json!({
+            "status": "completed",
+            "output": [{ "type": "message", "content": [{ "type": "output_text", "text": "ok" }] }]
+        }));
+        let provider = OpenAiProvider::new(
+            OpenAiClientConfig::new("sk-test")
+                .base_url(base_url)
+                .streaming(false),
+        )
+        .expect("provider");
+        let harness = crate::Harness::builder()
+            .register_provider(std::sync::Arc::new(provider))
+            .build()
+            .expect("harness");
+
+        harness
+            .session(crate::SessionConfig::named("routed"))
+            .run(crate::ModelRef::new("openai", "gpt-5-nano"))
+            .user_text("hi")
+            .header("X-Route", "beta")
+            .header("X-Gateway-Token", "gw-123")
+            .collect_text()
+            .await
+            .expect("routed run");
+        let (headers, _) = server.join().expect("server");
+        assert_eq!(headers.get("x-route").map(String::as_str), Some("beta"));
+        assert_eq!(
+            headers.get("x-gateway-token").map(String::as_str),
+            Some("gw-123")
+        );
+    }
+
+    #[test]
+    fn non_stream_chat_completion_maps_to_delta_and_completion() {
+        let provider = ProviderId::new(OPENAI_PROVIDER);
+        let events = map_non_stream_response(
+            &provider,
+            OpenAiApiSurface::ChatCompletions,
+            &serde_json::json!({
+                "id": "chatcmpl-1",
+                "choices": [{ "message": { "role": "assistant", "content": "hi" }, "finish_reason": "stop" }]
+            }),
+        )
+        .expect("events");
+        assert!(matches!(&events[0], ProviderEvent::TextDelta { text } if text == "hi"));
+        assert!(matches!(
+            &events[1],
+            ProviderEvent::Completed { finish_reason: Some(reason), .. } if reason == "stop"
+        ));
+    }
+
     #[tokio::test]
     async fn env_gated_smoke_collect_text_if_key_present() {
         if std::env::var("OPENAI_API_KEY")
@@ -337,7 +1450,9 @@ mod tests {
                     saw_terminal = true;
                     break;
                 }
-                crate::StreamEvent::OutputDelta { .. } => {}
+                crate::StreamEvent::OutputDelta { .. }
+                | crate::StreamEvent::ToolCallDelta { .. }
+                | crate::StreamEvent::ToolCall { .. } => {}
             }
         }
 
@@ -2,6 +2,16 @@ use std::time::Duration;
 
 use crate::errors::HarnessError;
 
+/// OpenAI API surface the provider talks to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OpenAiApiSurface {
+    /// `POST /v1/responses` with Responses API streaming events.
+    #[default]
+    Responses,
+    /// `POST /v1/chat/completions` with `chat.completion.chunk` streaming.
+    ChatCompletions,
+}
+
 /// Configuration for the OpenAI provider client.
 #[derive(Clone, Debug)]
 pub struct OpenAiClientConfig {
@@ -13,6 +23,19 @@ pub struct OpenAiClientConfig {
     pub base_url: String,
     /// Default HTTP timeout for requests.
     pub timeout: Duration,
+    /// Endpoint family used for requests and stream parsing.
+    pub api_surface: OpenAiApiSurface,
+    /// Request a streaming response. When `false`, one non-streaming request is sent and its
+    /// result is replayed as a single text delta plus completion.
+    pub streaming: bool,
+    /// Sent as `OpenAI-Organization` when set.
+    pub organization: Option<String>,
+    /// Sent as `OpenAI-Project` when set.
+    pub project: Option<String>,
+    /// How many times a stream that drops mid-way is resumed. `0` disables reconnection.
+    pub max_reconnects: u32,
+    /// Wait before the first reconnect; doubles on each further attempt.
+    pub reconnect_backoff: Duration,
 }
 
 impl OpenAiClientConfig {
@@ -22,10 +45,16 @@ impl OpenAiClientConfig {
             api_key: api_key.into(),
             base_url: "https://api.openai.com".to_string(),
             timeout: Duration::from_secs(120),
+            api_surface: OpenAiApiSurface::default(),
+            streaming: true,
+            organization: None,
+            project: None,
+            max_reconnects: 0,
+            reconnect_backoff: Duration::from_millis(500),
         }
     }
 
-    /// Builds a config from `OPENAI_API_KEY`.
+    /// Builds a config from `OPENAI_API_KEY`, plus `OPENAI_ORG_ID` and `OPENAI_PROJECT` when set.
     pub fn from_env() -> Result<Self, HarnessError> {
         let api_key = std::env::var("OPENAI_API_KEY").unwrap_or_default();
         if api_key.trim().is_empty() {
@@ -33,7 +62,15 @@ impl OpenAiClientConfig {
                 "missing OPENAI_API_KEY for OpenAI provider".into(),
             ));
         }
-        Ok(Self::new(api_key))
+        let non_empty_env = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+        };
+        let mut config = Self::new(api_key);
+        config.organization = non_empty_env("OPENAI_ORG_ID");
+        config.project = non_empty_env("OPENAI_PROJECT");
+        Ok(config)
     }
 
     /// Overrides the API base URL (for proxies or test servers).
@@ -48,7 +85,63 @@ impl OpenAiClientConfig {
         self
     }
 
-    pub(crate) fn responses_url(&self) -> String {
-        format!("{}/v1/responses", self.base_url.trim_end_matches('/'))
+    /// Selects the API surface (Responses by default).
+    pub fn api_surface(mut self, api_surface: OpenAiApiSurface) -> Self {
+        self.api_surface = api_surface;
+        self
+    }
+
+    /// Enables or disables streaming, for endpoints or models that do not support it.
+    pub fn streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        self
+    }
+
+    /// Sets the organization sent with every request.
+    pub fn organization(mut self, organization: impl Into<String>) -> Self {
+        self.organization = Some(organization.into());
+        self
+    }
+
+    /// Sets the project sent with every request.
+    pub fn project(mut self, project: impl Into<String>) -> Self {
+        self.project = Some(project.into());
+        self
+    }
+
+    /// Resumes a stream that fails mid-way up to `max_reconnects` times, waiting `backoff`
+    /// (doubling) between attempts.
+    ///
+    /// Responses API streams are then sent in background mode, which stores the response, and a
+    /// drop is resumed with `GET /v1/responses/{id}?stream=true&starting_after=<seq>`, so the
+    /// same generation continues and delivered events are not repeated. Chat Completions,
+    /// privacy-mode and `store(false)` streams cannot be resumed and fail with a transport error.
+    pub fn reconnect(mut self, max_reconnects: u32, backoff: Duration) -> Self {
+        self.max_reconnects = max_reconnects;
+        self.reconnect_backoff = backoff;
+        self
+    }
+
+    /// Organization/project headers for the configured account.
+    pub(crate) fn account_headers(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("OpenAI-Organization", self.organization.as_deref()),
+            ("OpenAI-Project", self.project.as_deref()),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|value| (name, value)))
+        .collect()
+    }
+
+    pub(crate) fn request_url(&self) -> String {
+        let path = match self.api_surface {
+            OpenAiApiSurface::Responses => "v1/responses",
+            OpenAiApiSurface::ChatCompletions => "v1/chat/completions",
+        };
+        format!("{}/{}", self.base_url.trim_end_matches('/'), path)
+    }
+
+    pub(crate) fn models_url(&self) -> String {
+        format!("{}/v1/models", self.base_url.trim_end_matches('/'))
     }
 }
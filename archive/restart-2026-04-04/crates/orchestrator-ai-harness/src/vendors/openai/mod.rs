@@ -8,7 +8,7 @@ mod options;
 pub(crate) mod transport;
 
 pub use adapter::OpenAiProvider;
-pub use config::OpenAiClientConfig;
+pub use config::{OpenAiApiSurface, OpenAiClientConfig};
 pub use options::{OpenAiReasoningEffort, OpenAiRequestOptions};
 
 use crate::ProviderId;
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 /// OpenAI reasoning effort hint (when supported by the selected model/API).
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -10,6 +12,9 @@ pub enum OpenAiReasoningEffort {
     High,
 }
 
+/// Inclusive range OpenAI accepts for each `logit_bias` value.
+const LOGIT_BIAS_RANGE: std::ops::RangeInclusive<i32> = -100..=100;
+
 /// Per-run OpenAI request options.
 #[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct OpenAiRequestOptions {
@@ -19,6 +24,20 @@ pub struct OpenAiRequestOptions {
     /// Optional reasoning effort hint.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reasoning_effort: Option<OpenAiReasoningEffort>,
+    /// Per-token bias keyed by token id; values must lie within -100..=100.
+    /// Chat Completions only; the Responses API rejects the request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logit_bias: Option<BTreeMap<u32, i32>>,
+    /// Sequences that stop generation when produced.
+    /// Chat Completions only; the Responses API rejects the request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop: Option<Vec<String>>,
+    /// Key/value tags attached to the request. Dropped under privacy mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<BTreeMap<String, String>>,
+    /// End-user identifier for abuse monitoring. Dropped under privacy mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
 }
 
 impl OpenAiRequestOptions {
@@ -33,4 +52,44 @@ impl OpenAiRequestOptions {
         self.reasoning_effort = Some(effort);
         self
     }
+
+    /// Biases a single token id; repeated calls accumulate into the bias map.
+    pub fn logit_bias(mut self, token_id: u32, bias: i32) -> Self {
+        self.logit_bias
+            .get_or_insert_with(BTreeMap::new)
+            .insert(token_id, bias);
+        self
+    }
+
+    /// Adds a stop sequence.
+    pub fn stop(mut self, sequence: impl Into<String>) -> Self {
+        self.stop.get_or_insert_with(Vec::new).push(sequence.into());
+        self
+    }
+
+    /// Adds a metadata tag; repeated calls accumulate into the metadata map.
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata
+            .get_or_insert_with(BTreeMap::new)
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets the end-user identifier.
+    pub fn user(mut self, user: impl Into<String>) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+
+    /// Checks option values that OpenAI would otherwise reject server-side.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        for (token_id, bias) in self.logit_bias.iter().flatten() {
+            if !LOGIT_BIAS_RANGE.contains(bias) {
+                return Err(format!(
+                    "logit_bias for token {token_id} must be within -100..=100, got {bias}"
+                ));
+            }
+        }
+        Ok(())
+    }
 }
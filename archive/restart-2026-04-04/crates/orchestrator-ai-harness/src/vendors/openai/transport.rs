@@ -1,6 +1,8 @@
-use crate::content::{OutputPart, RunOutput};
+use crate::content::{OutputPart, RunOutput, TokenUsage};
 use crate::errors::ProviderError;
-use crate::provider::ProviderEvent;
+use crate::provider::{ProviderEvent, ProviderResponseMeta};
+
+use super::config::OpenAiApiSurface;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct SseFrame {
@@ -8,6 +10,19 @@ pub(crate) struct SseFrame {
     pub data: String,
 }
 
+impl SseFrame {
+    /// Responses API `sequence_number` and, when the event carries it, the response id.
+    pub fn sequence(&self) -> Option<(u64, Option<String>)> {
+        let value: serde_json::Value = serde_json::from_str(&self.data).ok()?;
+        let seq = value.get("sequence_number")?.as_u64()?;
+        let response_id = value
+            .pointer("/response/id")
+            .and_then(|id| id.as_str())
+            .map(String::from);
+        Some((seq, response_id))
+    }
+}
+
 #[derive(Default)]
 pub(crate) struct SseDecoder {
     buf: Vec<u8>,
@@ -78,7 +93,9 @@ fn parse_sse_frame(bytes: &[u8]) -> Option<SseFrame> {
 
 pub(crate) fn map_openai_frame_to_events(
     provider: &crate::ProviderId,
+    surface: OpenAiApiSurface,
     frame: &SseFrame,
+    tool_calls: &mut ToolCallAssembler,
 ) -> Result<Vec<ProviderEvent>, ProviderError> {
     if frame.data.trim().is_empty() || frame.data.trim() == "[DONE]" {
         return Ok(Vec::new());
@@ -86,7 +103,269 @@ pub(crate) fn map_openai_frame_to_events(
     let value: serde_json::Value = serde_json::from_str(&frame.data).map_err(|e| {
         ProviderError::transport(provider.clone(), format!("invalid SSE JSON frame: {e}"))
     })?;
-    map_openai_json_to_events(provider, &value)
+    let mut events = tool_calls.observe(surface, &value);
+    events.extend(match surface {
+        OpenAiApiSurface::Responses => map_openai_json_to_events(provider, &value)?,
+        OpenAiApiSurface::ChatCompletions => map_chat_completion_chunk_to_events(provider, &value)?,
+    });
+    Ok(events)
+}
+
+/// A tool call whose arguments are still streaming.
+#[derive(Debug, Default)]
+struct PendingToolCall {
+    call_id: String,
+    name: String,
+    arguments: String,
+}
+
+/// Reassembles streamed tool-call arguments into complete calls.
+///
+/// Responses API fragments are keyed by output item id, Chat Completions fragments by
+/// `tool_calls[].index`. Every fragment becomes a `ToolCallDelta`; the finished call becomes a
+/// `ToolCall` (on `response.output_item.done`, or on the chunk carrying `finish_reason`).
+#[derive(Debug, Default)]
+pub(crate) struct ToolCallAssembler {
+    /// Calls in the order they were started.
+    pending: Vec<(String, PendingToolCall)>,
+}
+
+impl ToolCallAssembler {
+    fn entry(&mut self, key: &str) -> &mut PendingToolCall {
+        let index = match self.pending.iter().position(|(k, _)| k == key) {
+            Some(index) => index,
+            None => {
+                self.pending
+                    .push((key.to_string(), PendingToolCall::default()));
+                self.pending.len() - 1
+            }
+        };
+        &mut self.pending[index].1
+    }
+
+    fn finish(&mut self, key: &str) -> Option<PendingToolCall> {
+        let index = self.pending.iter().position(|(k, _)| k == key)?;
+        Some(self.pending.remove(index).1)
+    }
+
+    fn delta(call: &mut PendingToolCall, fragment: &str) -> Option<ProviderEvent> {
+        if fragment.is_empty() {
+            return None;
+        }
+        call.arguments.push_str(fragment);
+        Some(ProviderEvent::ToolCallDelta {
+            call_id: call.call_id.clone(),
+            arguments_fragment: fragment.to_string(),
+        })
+    }
+
+    fn completed(call: PendingToolCall) -> ProviderEvent {
+        ProviderEvent::ToolCall {
+            call_id: call.call_id,
+            name: call.name,
+            arguments: call.arguments,
+        }
+    }
+
+    /// Tool-call events carried by one stream payload.
+    pub(crate) fn observe(
+        &mut self,
+        surface: OpenAiApiSurface,
+        value: &serde_json::Value,
+    ) -> Vec<ProviderEvent> {
+        match surface {
+            OpenAiApiSurface::Responses => self.observe_response_event(value),
+            OpenAiApiSurface::ChatCompletions => self.observe_chat_chunk(value),
+        }
+    }
+
+    fn observe_response_event(&mut self, value: &serde_json::Value) -> Vec<ProviderEvent> {
+        let str_field = |v: &serde_json::Value, name: &str| {
+            v.get(name)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let item = value
+            .get("item")
+            .filter(|item| item["type"] == "function_call");
+        match value.get("type").and_then(|v| v.as_str()) {
+            Some("response.output_item.added") => {
+                if let Some(item) = item {
+                    let call = self.entry(&str_field(item, "id"));
+                    call.call_id = str_field(item, "call_id");
+                    call.name = str_field(item, "name");
+                    return Self::delta(call, &str_field(item, "arguments"))
+                        .into_iter()
+                        .collect();
+                }
+                Vec::new()
+            }
+            Some("response.function_call_arguments.delta") => {
+                let item_id = str_field(value, "item_id");
+                let call = self.entry(&item_id);
+                if call.call_id.is_empty() {
+                    call.call_id = item_id;
+                }
+                Self::delta(call, &str_field(value, "delta"))
+                    .into_iter()
+                    .collect()
+            }
+            Some("response.output_item.done") => {
+                let Some(item) = item else {
+                    return Vec::new();
+                };
+                let mut call = self.finish(&str_field(item, "id")).unwrap_or_default();
+                for (field, target) in [
+                    ("call_id", &mut call.call_id),
+                    ("name", &mut call.name),
+                    ("arguments", &mut call.arguments),
+                ] {
+                    if let Some(v) = item.get(field).and_then(|v| v.as_str()) {
+                        *target = v.to_string();
+                    }
+                }
+                vec![Self::completed(call)]
+            }
+            Some("response.completed") => self.flush(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn observe_chat_chunk(&mut self, value: &serde_json::Value) -> Vec<ProviderEvent> {
+        let Some(choice) = value
+            .get("choices")
+            .and_then(|v| v.as_array())
+            .and_then(|choices| choices.first())
+        else {
+            return Vec::new();
+        };
+        let mut events = Vec::new();
+        let deltas = choice
+            .get("delta")
+            .and_then(|d| d.get("tool_calls"))
+            .and_then(|v| v.as_array());
+        for delta in deltas.into_iter().flatten() {
+            let key = delta
+                .get("index")
+                .map(|i| i.to_string())
+                .unwrap_or_default();
+            let call = self.entry(&key);
+            if let Some(id) = delta.get("id").and_then(|v| v.as_str()) {
+                call.call_id = id.to_string();
+            }
+            let function = delta.get("function");
+            if let Some(name) = function
+                .and_then(|f| f.get("name"))
+                .and_then(|v| v.as_str())
+            {
+                call.name.push_str(name);
+            }
+            let fragment = function
+                .and_then(|f| f.get("arguments"))
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            events.extend(Self::delta(call, fragment));
+        }
+        if choice.get("finish_reason").is_some_and(|v| !v.is_null()) {
+            events.extend(self.flush());
+        }
+        events
+    }
+
+    /// Completes every call still pending, in start order.
+    fn flush(&mut self) -> Vec<ProviderEvent> {
+        self.pending
+            .drain(..)
+            .map(|(_, call)| Self::completed(call))
+            .collect()
+    }
+}
+
+/// Tool calls in a complete (non-streaming) response.
+fn non_stream_tool_calls(
+    surface: OpenAiApiSurface,
+    value: &serde_json::Value,
+) -> Vec<ProviderEvent> {
+    let str_field = |v: &serde_json::Value, name: &str| {
+        v.get(name)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let calls = match surface {
+        OpenAiApiSurface::Responses => value.get("output"),
+        OpenAiApiSurface::ChatCompletions => value.pointer("/choices/0/message/tool_calls"),
+    };
+    calls
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|call| match surface {
+            OpenAiApiSurface::Responses => {
+                (call["type"] == "function_call").then(|| ProviderEvent::ToolCall {
+                    call_id: str_field(call, "call_id"),
+                    name: str_field(call, "name"),
+                    arguments: str_field(call, "arguments"),
+                })
+            }
+            OpenAiApiSurface::ChatCompletions => {
+                let function = call.get("function")?;
+                Some(ProviderEvent::ToolCall {
+                    call_id: str_field(call, "id"),
+                    name: str_field(function, "name"),
+                    arguments: str_field(function, "arguments"),
+                })
+            }
+        })
+        .collect()
+}
+
+/// Maps a `chat.completion.chunk` frame. Chat completions only stream deltas, so the chunk
+/// carrying `finish_reason` becomes a delta-only `Completed` event.
+pub(crate) fn map_chat_completion_chunk_to_events(
+    provider: &crate::ProviderId,
+    value: &serde_json::Value,
+) -> Result<Vec<ProviderEvent>, ProviderError> {
+    if let Some(error) = value.get("error") {
+        let message = error
+            .get("message")
+            .and_then(|v| v.as_str())
+            .unwrap_or("OpenAI stream error");
+        return Err(ProviderError::provider(provider.clone(), message, None));
+    }
+    let Some(choice) = value
+        .get("choices")
+        .and_then(|v| v.as_array())
+        .and_then(|choices| choices.first())
+    else {
+        return Ok(Vec::new());
+    };
+
+    let mut events = Vec::new();
+    if let Some(text) = choice
+        .get("delta")
+        .and_then(|d| d.get("content"))
+        .and_then(|v| v.as_str())
+        .filter(|text| !text.is_empty())
+    {
+        events.push(ProviderEvent::TextDelta {
+            text: text.to_string(),
+        });
+    }
+    if let Some(finish_reason) = choice.get("finish_reason").and_then(|v| v.as_str()) {
+        let finish_reason = Some(finish_reason.to_string());
+        events.push(ProviderEvent::Completed {
+            output: Some(RunOutput {
+                finish_reason: finish_reason.clone(),
+                provider_meta: extract_response_meta(value),
+                usage: extract_usage(value),
+                ..RunOutput::default()
+            }),
+            finish_reason,
+        });
+    }
+    Ok(events)
 }
 
 pub(crate) fn map_openai_json_to_events(
@@ -118,10 +397,27 @@ pub(crate) fn map_openai_json_to_events(
                         .and_then(|v| v.as_str())
                         .map(ToOwned::to_owned)
                 });
-            let output = extract_output_text(response).map(|text| RunOutput {
-                parts: vec![OutputPart::Text(text)],
-                finish_reason: finish_reason.clone(),
-            });
+            let provider_meta = extract_response_meta(response);
+            let usage = extract_usage(response);
+            let output = match extract_output_text(response) {
+                Some(text) => Some(RunOutput {
+                    parts: vec![OutputPart::Text(text)],
+                    finish_reason: finish_reason.clone(),
+                    provider_meta,
+                    usage,
+                    ..RunOutput::default()
+                }),
+                // Delta-only stream: keep metadata and usage without inventing output parts.
+                None if provider_meta != ProviderResponseMeta::default() || usage.is_some() => {
+                    Some(RunOutput {
+                        finish_reason: finish_reason.clone(),
+                        provider_meta,
+                        usage,
+                        ..RunOutput::default()
+                    })
+                }
+                None => None,
+            };
             Ok(vec![ProviderEvent::Completed {
                 output,
                 finish_reason,
@@ -140,6 +436,107 @@ pub(crate) fn map_openai_json_to_events(
     }
 }
 
+/// Maps a complete (non-streaming) response body to the events a stream would have produced:
+/// one text delta with the whole output, any tool calls, then the completion.
+pub(crate) fn map_non_stream_response(
+    provider: &crate::ProviderId,
+    surface: OpenAiApiSurface,
+    value: &serde_json::Value,
+) -> Result<Vec<ProviderEvent>, ProviderError> {
+    if let Some(error) = value.get("error").filter(|e| !e.is_null()) {
+        let message = error
+            .get("message")
+            .and_then(|v| v.as_str())
+            .unwrap_or("OpenAI request error");
+        return Err(ProviderError::provider(provider.clone(), message, None));
+    }
+    let (text, completed) = match surface {
+        OpenAiApiSurface::Responses => {
+            let completed = serde_json::json!({ "type": "response.completed", "response": value });
+            (
+                extract_output_text(value),
+                map_openai_json_to_events(provider, &completed)?,
+            )
+        }
+        OpenAiApiSurface::ChatCompletions => {
+            let choice = value
+                .get("choices")
+                .and_then(|v| v.as_array())
+                .and_then(|choices| choices.first())
+                .ok_or_else(|| {
+                    ProviderError::protocol(provider.clone(), "chat completion has no choices")
+                })?;
+            let text = choice
+                .get("message")
+                .and_then(|m| m.get("content"))
+                .and_then(|v| v.as_str())
+                .map(ToOwned::to_owned);
+            let finish_reason = choice
+                .get("finish_reason")
+                .and_then(|v| v.as_str())
+                .map(ToOwned::to_owned);
+            let completed = ProviderEvent::Completed {
+                output: Some(RunOutput {
+                    parts: text.iter().cloned().map(OutputPart::Text).collect(),
+                    finish_reason: finish_reason.clone(),
+                    provider_meta: extract_response_meta(value),
+                    usage: extract_usage(value),
+                    ..RunOutput::default()
+                }),
+                finish_reason,
+            };
+            (text, vec![completed])
+        }
+    };
+    let mut events: Vec<ProviderEvent> = text
+        .filter(|text| !text.is_empty())
+        .map(|text| ProviderEvent::TextDelta { text })
+        .into_iter()
+        .collect();
+    events.extend(non_stream_tool_calls(surface, value));
+    events.extend(completed);
+    Ok(events)
+}
+
+fn extract_response_meta(response: &serde_json::Value) -> ProviderResponseMeta {
+    let field = |name: &str| {
+        response
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(ToOwned::to_owned)
+    };
+    ProviderResponseMeta {
+        request_id: field("id"),
+        model: field("model"),
+        system_fingerprint: field("system_fingerprint"),
+        cache_hit: extract_cached_tokens(response).map(|cached| cached > 0),
+    }
+}
+
+/// Reads `usage.{input,prompt}_tokens_details.cached_tokens` when the provider reports it.
+fn extract_cached_tokens(response: &serde_json::Value) -> Option<u64> {
+    let usage = response.get("usage")?;
+    ["input_tokens_details", "prompt_tokens_details"]
+        .iter()
+        .find_map(|name| usage.get(*name)?.get("cached_tokens")?.as_u64())
+}
+
+/// Reads `usage` from a Responses (`input_tokens`/`output_tokens`) or Chat Completions
+/// (`prompt_tokens`/`completion_tokens`) payload.
+fn extract_usage(response: &serde_json::Value) -> Option<TokenUsage> {
+    let usage = response.get("usage").filter(|u| u.is_object())?;
+    let count = |names: [&str; 2]| {
+        names
+            .iter()
+            .find_map(|name| usage.get(*name).and_then(|v| v.as_u64()))
+            .unwrap_or(0)
+    };
+    Some(TokenUsage {
+        input_tokens: count(["input_tokens", "prompt_tokens"]),
+        output_tokens: count(["output_tokens", "completion_tokens"]),
+    })
+}
+
 pub(crate) fn extract_output_text(response: &serde_json::Value) -> Option<String> {
     let mut parts = Vec::new();
     if let Some(items) = response.get("output").and_then(|v| v.as_array()) {
@@ -220,6 +617,158 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn completed_captures_system_fingerprint() {
+        let provider = crate::ProviderId::new("openai");
+        let completed = serde_json::json!({
+            "type":"response.completed",
+            "response": {
+                "id":"resp_123",
+                "model":"gpt-5-nano",
+                "system_fingerprint":"fp_abc",
+                "status":"completed",
+                "output":[]
+            }
+        });
+        let events = map_openai_json_to_events(&provider, &completed).expect("should map");
+        let ProviderEvent::Completed {
+            output: Some(output),
+            ..
+        } = &events[0]
+        else {
+            panic!("expected completed event with metadata");
+        };
+        assert!(output.parts.is_empty());
+        assert_eq!(
+            output.provider_meta.system_fingerprint.as_deref(),
+            Some("fp_abc")
+        );
+        assert_eq!(output.provider_meta.request_id.as_deref(), Some("resp_123"));
+    }
+
+    #[test]
+    fn completed_reports_cache_hit_from_cached_tokens() {
+        let provider = crate::ProviderId::new("openai");
+        let completed = |cached: u64| {
+            serde_json::json!({
+                "type":"response.completed",
+                "response": {
+                    "id":"resp_1",
+                    "status":"completed",
+                    "output":[],
+                    "usage": {
+                        "input_tokens": 1200,
+                        "input_tokens_details": { "cached_tokens": cached },
+                        "output_tokens": 40
+                    }
+                }
+            })
+        };
+        let output_for = |cached| {
+            let events = map_openai_json_to_events(&provider, &completed(cached)).expect("maps");
+            match &events[0] {
+                ProviderEvent::Completed {
+                    output: Some(output),
+                    ..
+                } => output.clone(),
+                other => panic!("expected completed output, got {other:?}"),
+            }
+        };
+
+        let hit = output_for(1024);
+        assert_eq!(hit.cache_hit(), Some(true));
+        assert_eq!(output_for(0).cache_hit(), Some(false));
+    }
+
+    #[test]
+    fn maps_chat_completion_chunks() {
+        let provider = crate::ProviderId::new("openai");
+        let frame = |data: &str| SseFrame {
+            event: None,
+            data: data.to_string(),
+        };
+        let surface = OpenAiApiSurface::ChatCompletions;
+        let mut tools = ToolCallAssembler::default();
+
+        let role = frame(r#"{"choices":[{"index":0,"delta":{"role":"assistant","content":""}}]}"#);
+        assert!(
+            map_openai_frame_to_events(&provider, surface, &role, &mut tools)
+                .expect("role chunk")
+                .is_empty()
+        );
+
+        let delta = frame(r#"{"choices":[{"index":0,"delta":{"content":"Hi"}}]}"#);
+        let events =
+            map_openai_frame_to_events(&provider, surface, &delta, &mut tools).expect("delta");
+        assert!(matches!(&events[..], [ProviderEvent::TextDelta { text }] if text == "Hi"));
+
+        let last = frame(
+            r#"{"id":"chatcmpl-1","model":"gpt-4o-mini","system_fingerprint":"fp_x","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        );
+        let events =
+            map_openai_frame_to_events(&provider, surface, &last, &mut tools).expect("finish");
+        let [
+            ProviderEvent::Completed {
+                output: Some(output),
+                finish_reason,
+            },
+        ] = &events[..]
+        else {
+            panic!("expected a single completed event");
+        };
+        assert_eq!(finish_reason.as_deref(), Some("stop"));
+        assert_eq!(
+            output.provider_meta.request_id.as_deref(),
+            Some("chatcmpl-1")
+        );
+
+        assert!(
+            map_openai_frame_to_events(&provider, surface, &frame("[DONE]"), &mut tools)
+                .expect("done")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn chat_tool_call_fragments_complete_on_finish() {
+        let provider = crate::ProviderId::new("openai");
+        let surface = OpenAiApiSurface::ChatCompletions;
+        let mut tools = ToolCallAssembler::default();
+        let mut events = Vec::new();
+        for data in [
+            r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"id":"call_a","function":{"name":"lookup","arguments":""}}]}}]}"#,
+            r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"q\":"}}]}}]}"#,
+            r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"\"rust\"}"}}]}}]}"#,
+            r#"{"choices":[{"index":0,"delta":{},"finish_reason":"tool_calls"}]}"#,
+        ] {
+            let frame = SseFrame {
+                event: None,
+                data: data.to_string(),
+            };
+            events.extend(
+                map_openai_frame_to_events(&provider, surface, &frame, &mut tools).expect("chunk"),
+            );
+        }
+        let fragments: Vec<&str> = events
+            .iter()
+            .filter_map(|e| match e {
+                ProviderEvent::ToolCallDelta {
+                    call_id,
+                    arguments_fragment,
+                } if call_id == "call_a" => Some(arguments_fragment.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(fragments, vec!["{\"q\":", "\"rust\"}"]);
+        assert!(matches!(
+            &events[2..],
+            [
+                ProviderEvent::ToolCall { call_id, name, arguments },
+                ProviderEvent::Completed { .. },
+            ] if call_id == "call_a" && name == "lookup" && arguments == "{\"q\":\"rust\"}"
+        ));
+    }
+
     #[test]
     fn maps_response_failed_to_provider_error() {
         let provider = crate::ProviderId::new("openai");
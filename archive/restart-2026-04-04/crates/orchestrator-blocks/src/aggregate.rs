@@ -0,0 +1,294 @@
+//! Aggregate block: Transform that computes sum/avg/min/max/count over a numeric field of a JSON array.
+//! Items whose field is missing or non-numeric are skipped and reported in `skipped`.
+//! Pass your aggregator when registering: `register_aggregate(registry, Arc::new(your_aggregator))`.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::input_binding::{
+    resolve_effective_input, validate_expected_input, validate_single_input_mode,
+};
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
+};
+
+/// Error from aggregate operations.
+#[derive(Debug, Clone)]
+pub struct AggregateError(pub String);
+
+impl std::fmt::Display for AggregateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for AggregateError {}
+
+/// Aggregation applied to the collected numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AggregateOp {
+    Sum,
+    Avg,
+    Min,
+    Max,
+    Count,
+}
+
+impl AggregateOp {
+    fn key(self) -> &'static str {
+        match self {
+            AggregateOp::Sum => "sum",
+            AggregateOp::Avg => "avg",
+            AggregateOp::Min => "min",
+            AggregateOp::Max => "max",
+            AggregateOp::Count => "count",
+        }
+    }
+}
+
+/// Aggregator abstraction. Implement and pass when registering.
+pub trait Aggregator: Send + Sync {
+    /// Aggregate `field` over `items`, returning one key per op plus `skipped`.
+    fn aggregate(
+        &self,
+        items: &[serde_json::Value],
+        field: &str,
+        ops: &[AggregateOp],
+    ) -> Result<serde_json::Value, AggregateError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AggregateConfig {
+    /// Field to read from each item; dots address nested objects (e.g. `stats.views`).
+    pub field: String,
+    pub ops: Vec<AggregateOp>,
+}
+
+impl AggregateConfig {
+    pub fn new(field: impl Into<String>, ops: impl Into<Vec<AggregateOp>>) -> Self {
+        Self {
+            field: field.into(),
+            ops: ops.into(),
+        }
+    }
+}
+
+pub struct AggregateBlock {
+    config: AggregateConfig,
+    aggregator: Arc<dyn Aggregator>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl AggregateBlock {
+    pub fn new(config: AggregateConfig, aggregator: Arc<dyn Aggregator>) -> Self {
+        Self {
+            config,
+            aggregator,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+impl BlockExecutor for AggregateBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        if self.config.field.trim().is_empty() {
+            return Err(BlockError::Other("aggregate requires a field".into()));
+        }
+        if self.config.ops.is_empty() {
+            return Err(BlockError::Other(
+                "aggregate requires at least one op".into(),
+            ));
+        }
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        let value = match input {
+            BlockInput::Json(v) => v,
+            BlockInput::String(s) | BlockInput::Text(s) => {
+                serde_json::from_str(&s).map_err(|e| BlockError::Other(e.to_string()))?
+            }
+            BlockInput::Error { message } => return Err(BlockError::Other(message)),
+            BlockInput::Empty
+            | BlockInput::List { .. }
+            | BlockInput::Multi { .. }
+            | BlockInput::MultiNamed { .. } => {
+                return Err(BlockError::Other("aggregate expects a JSON array".into()));
+            }
+        };
+        let items = value
+            .as_array()
+            .ok_or_else(|| BlockError::Other("aggregate expects a JSON array".into()))?;
+        let result = self
+            .aggregator
+            .aggregate(items, &self.config.field, &self.config.ops)
+            .map_err(|e| BlockError::Other(e.0))?;
+        let skipped = result.get("skipped").and_then(|v| v.as_u64()).unwrap_or(0);
+        if skipped > 0 {
+            warn!(
+                event = "aggregate.values_skipped",
+                block_id = %ctx.block_id,
+                field = self.config.field.as_str(),
+                skipped = skipped,
+                "skipped missing or non-numeric values"
+            );
+        }
+        Ok(BlockExecutionResult::Once(BlockOutput::Json {
+            value: result,
+        }))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::Json, OutputMode::Once)
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        validate_single_input_mode(ctx)?;
+        validate_expected_input(
+            ctx,
+            ValueKindSet::singleton(ValueKind::String)
+                | ValueKindSet::singleton(ValueKind::Text)
+                | ValueKindSet::singleton(ValueKind::Json),
+        )
+    }
+}
+
+/// Default implementation over `f64`.
+pub struct StdAggregator;
+
+fn lookup<'a>(item: &'a serde_json::Value, field: &str) -> Option<&'a serde_json::Value> {
+    field
+        .split('.')
+        .try_fold(item, |current, segment| current.get(segment))
+}
+
+impl Aggregator for StdAggregator {
+    fn aggregate(
+        &self,
+        items: &[serde_json::Value],
+        field: &str,
+        ops: &[AggregateOp],
+    ) -> Result<serde_json::Value, AggregateError> {
+        let numbers: Vec<f64> = items
+            .iter()
+            .filter_map(|item| lookup(item, field).and_then(|v| v.as_f64()))
+            .collect();
+        let skipped = items.len() - numbers.len();
+        let sum: f64 = numbers.iter().sum();
+
+        let mut out = serde_json::Map::new();
+        for op in ops {
+            let value = match op {
+                AggregateOp::Sum => serde_json::json!(sum),
+                AggregateOp::Count => serde_json::json!(numbers.len()),
+                AggregateOp::Avg if numbers.is_empty() => serde_json::Value::Null,
+                AggregateOp::Avg => serde_json::json!(sum / numbers.len() as f64),
+                AggregateOp::Min => numbers
+                    .iter()
+                    .copied()
+                    .reduce(f64::min)
+                    .map_or(serde_json::Value::Null, |v| serde_json::json!(v)),
+                AggregateOp::Max => numbers
+                    .iter()
+                    .copied()
+                    .reduce(f64::max)
+                    .map_or(serde_json::Value::Null, |v| serde_json::json!(v)),
+            };
+            out.insert(op.key().to_string(), value);
+        }
+        out.insert("skipped".to_string(), serde_json::json!(skipped));
+        Ok(serde_json::Value::Object(out))
+    }
+}
+
+/// Register the aggregate block with an aggregator.
+pub fn register_aggregate(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    aggregator: Arc<dyn Aggregator>,
+) {
+    let aggregator = Arc::clone(&aggregator);
+    registry.register_custom("aggregate", move |payload, input_from| {
+        let config: AggregateConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            AggregateBlock::new(config, Arc::clone(&aggregator)).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use serde_json::json;
+
+    fn aggregate(config: AggregateConfig, items: serde_json::Value) -> serde_json::Value {
+        let block = AggregateBlock::new(config, Arc::new(StdAggregator));
+        match block.execute(test_ctx(BlockInput::Json(items))).unwrap() {
+            BlockExecutionResult::Once(BlockOutput::Json { value }) => value,
+            _ => panic!("expected Once(Json)"),
+        }
+    }
+
+    #[test]
+    fn aggregate_sum_and_avg_over_list() {
+        let value = aggregate(
+            AggregateConfig::new("price", vec![AggregateOp::Sum, AggregateOp::Avg]),
+            json!([{ "price": 10 }, { "price": 20.5 }, { "price": 30 }]),
+        );
+        assert_eq!(
+            value,
+            json!({ "sum": 60.5, "avg": 60.5 / 3.0, "skipped": 0 })
+        );
+    }
+
+    #[test]
+    fn aggregate_skips_missing_and_non_numeric_fields() {
+        let value = aggregate(
+            AggregateConfig::new(
+                "stats.views",
+                vec![
+                    AggregateOp::Count,
+                    AggregateOp::Min,
+                    AggregateOp::Max,
+                    AggregateOp::Sum,
+                ],
+            ),
+            json!([
+                { "stats": { "views": 4 } },
+                { "stats": { "views": "many" } },
+                { "stats": {} },
+                { "title": "no stats" },
+                { "stats": { "views": -2 } }
+            ]),
+        );
+        assert_eq!(
+            value,
+            json!({ "count": 2, "min": -2.0, "max": 4.0, "sum": 2.0, "skipped": 3 })
+        );
+
+        let empty = aggregate(
+            AggregateConfig::new("price", vec![AggregateOp::Avg, AggregateOp::Min]),
+            json!([{ "price": null }]),
+        );
+        assert_eq!(empty, json!({ "avg": null, "min": null, "skipped": 1 }));
+    }
+
+    #[test]
+    fn aggregate_rejects_non_array_input() {
+        let block = AggregateBlock::new(
+            AggregateConfig::new("price", vec![AggregateOp::Sum]),
+            Arc::new(StdAggregator),
+        );
+        let err = block
+            .execute(test_ctx(BlockInput::Json(json!({ "price": 1 }))))
+            .unwrap_err();
+        assert!(err.to_string().contains("JSON array"));
+    }
+}
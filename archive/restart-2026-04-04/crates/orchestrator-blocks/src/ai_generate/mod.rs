@@ -3,6 +3,7 @@
 //! Pass your generator when registering: `register_ai_generate(registry, Arc::new(your_generator))`.
 
 mod openai;
+mod shortcuts;
 
 use std::sync::Arc;
 
@@ -16,6 +17,11 @@ use orchestrator_core::block::{
     BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
 };
 
+pub use shortcuts::{
+    SummarizeBlock, SummarizeConfig, TranslateBlock, TranslateConfig, register_summarize,
+    register_translate,
+};
+
 /// Error from AI generation.
 #[derive(Debug, Clone)]
 pub struct AiGenerateError(pub String);
@@ -101,6 +107,7 @@ fn block_input_kind(input: &BlockInput) -> &'static str {
         BlockInput::Json(_) => "json",
         BlockInput::List { .. } => "list",
         BlockInput::Multi { .. } => "multi",
+        BlockInput::MultiNamed { .. } => "multi_named",
         BlockInput::Error { .. } => "error",
     }
 }
@@ -165,6 +172,12 @@ fn payload_from_input(input: &BlockInput, prompt_from_input_mode: bool) -> serde
         BlockInput::Multi { outputs } => serde_json::json!({
             "outputs": outputs.iter().map(output_to_value).collect::<Vec<_>>()
         }),
+        BlockInput::MultiNamed { outputs } => serde_json::json!({
+            "outputs": outputs
+                .iter()
+                .map(|(label, o)| (label.clone(), output_to_value(o)))
+                .collect::<serde_json::Map<_, _>>()
+        }),
         BlockInput::Empty => serde_json::json!({}),
         BlockInput::Error { .. } => serde_json::json!({}),
     }
@@ -252,8 +265,12 @@ impl BlockExecutor for AiGenerateBlock {
                 }
                 Err(err) => {
                     let (code, retryable, provider_status) = classify_ai_error(&err.0);
-                    let can_retry =
-                        retryable && request_config.retry_policy.can_retry(retries_done);
+                    let can_retry = retryable
+                        && request_config.retry_policy.can_retry_failure(
+                            retries_done,
+                            code == "ai.timeout",
+                            self.idempotent(),
+                        );
                     debug!(
                         event = "ai.generate_failed",
                         domain = "ai",
@@ -304,6 +321,11 @@ impl BlockExecutor for AiGenerateBlock {
         OutputContract::from_kind(ValueKind::Text, OutputMode::Once)
     }
 
+    /// Generation has no side effects, so a timed-out request can be repeated.
+    fn idempotent(&self) -> bool {
+        true
+    }
+
     fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
         if !self.input_from.is_empty() || self.config.prompt.is_none() {
             return validate_expected_input(
@@ -412,21 +434,10 @@ pub fn register_ai_generate(
     });
 }
 
-#[cfg(test)]
-fn test_ctx(input: BlockInput) -> BlockExecutionContext {
-    BlockExecutionContext {
-        workflow_id: uuid::Uuid::new_v4(),
-        run_id: uuid::Uuid::new_v4(),
-        block_id: uuid::Uuid::new_v4(),
-        attempt: 1,
-        prev: input,
-        store: Default::default(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_ctx;
 
     struct FakeGenerator;
 
@@ -0,0 +1,306 @@
+//! Summarize and Translate blocks: ai_generate with a built-in prompt.
+//! Both run through [`AiGenerateBlock`], so they share its generator, retries and error payloads.
+//! Pass your generator when registering: `register_summarize(registry, Arc::new(your_generator))`.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::{AiGenerateBlock, AiGenerateConfig, AiGenerator};
+use crate::input_binding::{resolve_effective_input, validate_expected_input};
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SummarizeConfig {
+    /// Upper bound on the summary length, in words.
+    pub max_words: u32,
+    /// Provider, model, timeout and retries; `prompt` is replaced by the summarize prompt.
+    #[serde(flatten)]
+    pub ai: AiGenerateConfig,
+}
+
+impl SummarizeConfig {
+    pub fn new(max_words: u32) -> Self {
+        Self {
+            max_words,
+            ai: shortcut_ai_config(),
+        }
+    }
+
+    pub fn prompt(&self) -> String {
+        format!(
+            "Summarize the input in at most {} words. Keep key facts, names and numbers, \
+             and do not add information. Respond with the summary only.",
+            self.max_words
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranslateConfig {
+    /// Language to translate into, e.g. `French` or `pt-BR`.
+    pub target_language: String,
+    /// Provider, model, timeout and retries; `prompt` is replaced by the translate prompt.
+    #[serde(flatten)]
+    pub ai: AiGenerateConfig,
+}
+
+impl TranslateConfig {
+    pub fn new(target_language: impl Into<String>) -> Self {
+        Self {
+            target_language: target_language.into(),
+            ai: shortcut_ai_config(),
+        }
+    }
+
+    pub fn prompt(&self) -> String {
+        format!(
+            "Translate the input into {}. Preserve meaning, tone and markdown formatting. \
+             Respond with the translation only.",
+            self.target_language.trim()
+        )
+    }
+}
+
+fn shortcut_ai_config() -> AiGenerateConfig {
+    AiGenerateConfig {
+        prompt: None,
+        ..AiGenerateConfig::new("")
+    }
+}
+
+/// Resolve the input, then run ai_generate with `prompt` on it.
+fn generate_with_prompt(
+    block_type: &str,
+    ai: &AiGenerateConfig,
+    prompt: String,
+    generator: &Arc<dyn AiGenerator>,
+    input_from: &[uuid::Uuid],
+    mut ctx: BlockExecutionContext,
+) -> Result<BlockExecutionResult, BlockError> {
+    let input = resolve_effective_input(&ctx, input_from, None)?;
+    match &input {
+        BlockInput::Error { message } => return Err(BlockError::Other(message.clone())),
+        BlockInput::Empty => {
+            return Err(BlockError::Other(format!(
+                "{} requires input text",
+                block_type
+            )));
+        }
+        _ => {}
+    }
+    let config = AiGenerateConfig {
+        prompt: Some(prompt),
+        ..ai.clone()
+    };
+    ctx.prev = input;
+    AiGenerateBlock::new(config, Arc::clone(generator)).execute(ctx)
+}
+
+fn validate_text_input(ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+    validate_expected_input(
+        ctx,
+        ValueKindSet::singleton(ValueKind::String)
+            | ValueKindSet::singleton(ValueKind::Text)
+            | ValueKindSet::singleton(ValueKind::Json),
+    )
+}
+
+pub struct SummarizeBlock {
+    config: SummarizeConfig,
+    generator: Arc<dyn AiGenerator>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl SummarizeBlock {
+    pub fn new(config: SummarizeConfig, generator: Arc<dyn AiGenerator>) -> Self {
+        Self {
+            config,
+            generator,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+impl BlockExecutor for SummarizeBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        if self.config.max_words == 0 {
+            return Err(BlockError::Other(
+                "summarize max_words must be greater than 0".into(),
+            ));
+        }
+        generate_with_prompt(
+            "summarize",
+            &self.config.ai,
+            self.config.prompt(),
+            &self.generator,
+            &self.input_from,
+            ctx,
+        )
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::Text, OutputMode::Once)
+    }
+
+    fn idempotent(&self) -> bool {
+        true
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        validate_text_input(ctx)
+    }
+}
+
+pub struct TranslateBlock {
+    config: TranslateConfig,
+    generator: Arc<dyn AiGenerator>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl TranslateBlock {
+    pub fn new(config: TranslateConfig, generator: Arc<dyn AiGenerator>) -> Self {
+        Self {
+            config,
+            generator,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+impl BlockExecutor for TranslateBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        if self.config.target_language.trim().is_empty() {
+            return Err(BlockError::Other(
+                "translate requires a target_language".into(),
+            ));
+        }
+        generate_with_prompt(
+            "translate",
+            &self.config.ai,
+            self.config.prompt(),
+            &self.generator,
+            &self.input_from,
+            ctx,
+        )
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::Text, OutputMode::Once)
+    }
+
+    fn idempotent(&self) -> bool {
+        true
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        validate_text_input(ctx)
+    }
+}
+
+/// Register the summarize block with a generator.
+pub fn register_summarize(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    generator: Arc<dyn AiGenerator>,
+) {
+    let generator = Arc::clone(&generator);
+    registry.register_custom("summarize", move |payload, input_from| {
+        let config: SummarizeConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            SummarizeBlock::new(config, Arc::clone(&generator)).with_input_from(input_from),
+        ))
+    });
+}
+
+/// Register the translate block with a generator.
+pub fn register_translate(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    generator: Arc<dyn AiGenerator>,
+) {
+    let generator = Arc::clone(&generator);
+    registry.register_custom("translate", move |payload, input_from| {
+        let config: TranslateConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            TranslateBlock::new(config, Arc::clone(&generator)).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::AiGenerateError;
+    use super::*;
+    use crate::test_ctx;
+    use orchestrator_core::block::BlockOutput;
+
+    /// Echoes the prompt and the payload back so tests can inspect both.
+    struct EchoGenerator;
+
+    impl AiGenerator for EchoGenerator {
+        fn generate_markdown(
+            &self,
+            config: &AiGenerateConfig,
+            input: &serde_json::Value,
+        ) -> Result<String, AiGenerateError> {
+            Ok(format!(
+                "{}\n{}",
+                config.prompt.clone().unwrap_or_default(),
+                input["input"].as_str().unwrap_or_default()
+            ))
+        }
+    }
+
+    fn text(result: BlockExecutionResult) -> String {
+        match result.into_once() {
+            BlockOutput::Text { value } => value,
+            other => panic!("expected text output, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn summarize_prompt_includes_target_length() {
+        let block = SummarizeBlock::new(SummarizeConfig::new(120), Arc::new(EchoGenerator));
+        let out = text(
+            block
+                .execute(test_ctx(BlockInput::Text("long article".into())))
+                .unwrap(),
+        );
+        assert!(out.contains("at most 120 words"), "got: {out}");
+        assert!(out.ends_with("long article"));
+
+        let err = block.execute(test_ctx(BlockInput::Empty)).unwrap_err();
+        assert!(err.to_string().contains("summarize requires input"));
+    }
+
+    #[test]
+    fn translate_prompt_includes_target_language() {
+        let mut config = TranslateConfig::new("French");
+        config.ai.model = "gpt-5-mini".into();
+        let block = TranslateBlock::new(config, Arc::new(EchoGenerator));
+        let out = text(
+            block
+                .execute(test_ctx(BlockInput::String("good morning".into())))
+                .unwrap(),
+        );
+        assert!(
+            out.contains("Translate the input into French."),
+            "got: {out}"
+        );
+        assert!(out.ends_with("good morning"));
+    }
+}
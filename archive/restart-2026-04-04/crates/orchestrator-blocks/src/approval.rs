@@ -0,0 +1,187 @@
+//! Approval block: pause the run until a human decides.
+//! The run stops with `RunError::PendingApproval { token }`; blocks on the same level still finish.
+//! `Workflow::resume_with_input(token, decision)` continues it, with the decision (e.g. `"approve"`)
+//! as this block's string output, so a `switch` or custom block downstream can act on it.
+//! With a run store set, the pause is saved in the run checkpoint and the token survives a restart.
+//! Register with: `register_approval(registry)`.
+
+use serde::{Deserialize, Serialize};
+
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, OutputContract,
+    OutputMode, ValidateContext, ValueKind,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalConfig {
+    /// What the approver is asked; logged when the run pauses.
+    #[serde(default = "default_prompt")]
+    pub prompt: String,
+}
+
+fn default_prompt() -> String {
+    "approve to continue".to_string()
+}
+
+impl ApprovalConfig {
+    pub fn new(prompt: impl Into<String>) -> Self {
+        Self {
+            prompt: prompt.into(),
+        }
+    }
+}
+
+impl Default for ApprovalConfig {
+    fn default() -> Self {
+        Self::new(default_prompt())
+    }
+}
+
+pub struct ApprovalBlock {
+    config: ApprovalConfig,
+}
+
+impl ApprovalBlock {
+    pub fn new(config: ApprovalConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl BlockExecutor for ApprovalBlock {
+    fn execute(&self, _ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        Err(BlockError::PendingApproval {
+            prompt: self.config.prompt.clone(),
+        })
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::String, OutputMode::Once)
+    }
+}
+
+/// Register the approval block.
+pub fn register_approval(registry: &mut orchestrator_core::block::BlockRegistry) {
+    registry.register_custom("approval", |payload, _input_from| {
+        let config: ApprovalConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(ApprovalBlock::new(config)))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Block;
+    use orchestrator_core::block::{BlockInput, BlockOutput};
+    use orchestrator_core::{BlockConfig, BlockRegistry, RunError, Workflow};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Counted(Arc<AtomicUsize>);
+    impl BlockExecutor for Counted {
+        fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            let seen = match ctx.prev {
+                BlockInput::String(s) => s,
+                _ => "start".to_string(),
+            };
+            Ok(BlockExecutionResult::Once(BlockOutput::String {
+                value: format!("after {seen}"),
+            }))
+        }
+    }
+
+    #[test]
+    fn approval_pauses_run_and_resume_completes_it() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let mut registry = BlockRegistry::new();
+        register_approval(&mut registry);
+        let counted_runs = Arc::clone(&runs);
+        registry.register_custom("counted", move |_, _| {
+            Ok(Box::new(Counted(Arc::clone(&counted_runs))))
+        });
+        let counted = || BlockConfig::Custom {
+            type_id: "counted".to_string(),
+            payload: serde_json::Value::Null,
+            input_from: Box::new([]),
+        };
+
+        let mut w = Workflow::with_registry(registry);
+        let build = w.add(counted());
+        let approval = w.add(Block::approval("deploy to production?"));
+        let deploy = w.add(counted());
+        w.link(build, approval);
+        w.link(approval, deploy);
+
+        let token = match w.run() {
+            Err(RunError::PendingApproval { token }) => token,
+            other => panic!("expected pending approval, got {:?}", other),
+        };
+        assert_eq!(runs.load(Ordering::SeqCst), 1, "deploy must wait");
+
+        let out = w.resume_with_input(&token, "approve").unwrap();
+        assert_eq!(
+            out,
+            BlockOutput::String {
+                value: "after approve".into()
+            }
+        );
+        assert_eq!(runs.load(Ordering::SeqCst), 2, "build is not re-run");
+
+        assert!(matches!(
+            w.resume_with_input(&token, "approve"),
+            Err(RunError::UnknownApprovalToken(_))
+        ));
+    }
+
+    #[test]
+    fn approval_as_entry_block_pauses_and_resumes() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let registry = || {
+            let mut registry = BlockRegistry::new();
+            register_approval(&mut registry);
+            let counted_runs = Arc::clone(&runs);
+            registry.register_custom("counted", move |_, _| {
+                Ok(Box::new(Counted(Arc::clone(&counted_runs))))
+            });
+            registry
+        };
+
+        let mut w = Workflow::with_registry(registry());
+        let approval = w.add(Block::approval("start the release?"));
+        let release = w.add(BlockConfig::Custom {
+            type_id: "counted".to_string(),
+            payload: serde_json::Value::Null,
+            input_from: Box::new([]),
+        });
+        w.link(approval, release);
+        let token = match w.run_detailed() {
+            Err(RunError::PendingApproval { token }) => token,
+            other => panic!(
+                "expected pending approval, got {:?}",
+                other.map(|r| r.output)
+            ),
+        };
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+        let out = w.resume_with_input(&token, "approve").unwrap();
+        assert_eq!(
+            out,
+            BlockOutput::String {
+                value: "after approve".into()
+            }
+        );
+
+        let mut alone = Workflow::with_registry(registry());
+        alone.add(Block::approval("go?"));
+        let token = match alone.run() {
+            Err(RunError::PendingApproval { token }) => token,
+            other => panic!("expected pending approval, got {:?}", other),
+        };
+        assert_eq!(
+            alone.resume_with_input(&token, "approve").unwrap(),
+            BlockOutput::String {
+                value: "approve".into()
+            }
+        );
+    }
+}
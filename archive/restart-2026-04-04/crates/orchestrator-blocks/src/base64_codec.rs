@@ -0,0 +1,311 @@
+//! Base64 block: Transform that encodes text to base64 or decodes base64 back to text.
+//! Supports the standard and URL-safe alphabets, with or without padding.
+//! Pass your codec when registering: `register_base64(registry, Arc::new(your_codec))`.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::input_binding::{
+    resolve_effective_input, validate_expected_input, validate_single_input_mode,
+};
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
+};
+
+/// Error from base64 operations.
+#[derive(Debug, Clone)]
+pub struct Base64Error(pub String);
+
+impl std::fmt::Display for Base64Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for Base64Error {}
+
+/// Base64 codec abstraction. Implement and pass when registering.
+pub trait Base64Codec: Send + Sync {
+    fn encode(&self, bytes: &[u8], url_safe: bool, padding: bool) -> String;
+    /// Decode `encoded`; padding is accepted whether or not it is present.
+    fn decode(&self, encoded: &str, url_safe: bool) -> Result<Vec<u8>, Base64Error>;
+}
+
+/// Direction of the conversion.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Base64Mode {
+    /// Text in, base64 string out.
+    #[default]
+    Encode,
+    /// Base64 string in, text out.
+    Decode,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Base64Config {
+    #[serde(default)]
+    pub mode: Base64Mode,
+    /// Use the URL-safe alphabet (`-` and `_` instead of `+` and `/`).
+    #[serde(default)]
+    pub url_safe: bool,
+    /// Emit `=` padding when encoding.
+    #[serde(default = "default_true")]
+    pub padding: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for Base64Config {
+    fn default() -> Self {
+        Self {
+            mode: Base64Mode::default(),
+            url_safe: false,
+            padding: true,
+        }
+    }
+}
+
+impl Base64Config {
+    pub fn new(mode: Base64Mode) -> Self {
+        Self {
+            mode,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_url_safe(mut self, url_safe: bool) -> Self {
+        self.url_safe = url_safe;
+        self
+    }
+
+    pub fn with_padding(mut self, padding: bool) -> Self {
+        self.padding = padding;
+        self
+    }
+}
+
+pub struct Base64Block {
+    config: Base64Config,
+    codec: Arc<dyn Base64Codec>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl Base64Block {
+    pub fn new(config: Base64Config, codec: Arc<dyn Base64Codec>) -> Self {
+        Self {
+            config,
+            codec,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+impl BlockExecutor for Base64Block {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        let text = match input {
+            BlockInput::String(s) | BlockInput::Text(s) => s,
+            BlockInput::Json(v) => v
+                .as_str()
+                .map(String::from)
+                .unwrap_or_else(|| v.to_string()),
+            BlockInput::Empty => String::new(),
+            BlockInput::Error { message } => return Err(BlockError::Other(message)),
+            BlockInput::List { .. } | BlockInput::Multi { .. } | BlockInput::MultiNamed { .. } => {
+                return Err(BlockError::Other(
+                    "base64 expects string/text/json input".into(),
+                ));
+            }
+        };
+
+        let output = match self.config.mode {
+            Base64Mode::Encode => BlockOutput::String {
+                value: self.codec.encode(
+                    text.as_bytes(),
+                    self.config.url_safe,
+                    self.config.padding,
+                ),
+            },
+            Base64Mode::Decode => {
+                let bytes = self
+                    .codec
+                    .decode(text.trim(), self.config.url_safe)
+                    .map_err(|e| BlockError::Other(e.0))?;
+                let value = String::from_utf8(bytes).map_err(|_| {
+                    BlockError::Other("base64 decoded bytes are not valid UTF-8 text".into())
+                })?;
+                BlockOutput::Text { value }
+            }
+        };
+        Ok(BlockExecutionResult::Once(output))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        let kind = match self.config.mode {
+            Base64Mode::Encode => ValueKind::String,
+            Base64Mode::Decode => ValueKind::Text,
+        };
+        OutputContract::from_kind(kind, OutputMode::Once)
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        validate_single_input_mode(ctx)?;
+        validate_expected_input(
+            ctx,
+            ValueKindSet::singleton(ValueKind::Empty)
+                | ValueKindSet::singleton(ValueKind::String)
+                | ValueKindSet::singleton(ValueKind::Text)
+                | ValueKindSet::singleton(ValueKind::Json),
+        )
+    }
+}
+
+/// Default implementation using the `base64` crate.
+pub struct StdBase64Codec;
+
+impl StdBase64Codec {
+    fn engine(url_safe: bool, padding: bool) -> ::base64::engine::GeneralPurpose {
+        use ::base64::alphabet;
+        use ::base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+        let alphabet = if url_safe {
+            &alphabet::URL_SAFE
+        } else {
+            &alphabet::STANDARD
+        };
+        GeneralPurpose::new(
+            alphabet,
+            GeneralPurposeConfig::new()
+                .with_encode_padding(padding)
+                .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+        )
+    }
+}
+
+impl Base64Codec for StdBase64Codec {
+    fn encode(&self, bytes: &[u8], url_safe: bool, padding: bool) -> String {
+        use ::base64::Engine as _;
+        Self::engine(url_safe, padding).encode(bytes)
+    }
+
+    fn decode(&self, encoded: &str, url_safe: bool) -> Result<Vec<u8>, Base64Error> {
+        use ::base64::Engine as _;
+        Self::engine(url_safe, true)
+            .decode(encoded)
+            .map_err(|e| Base64Error(format!("invalid base64: {}", e)))
+    }
+}
+
+/// Register the base64 block with a codec.
+pub fn register_base64(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    codec: Arc<dyn Base64Codec>,
+) {
+    let codec = Arc::clone(&codec);
+    registry.register_custom("base64", move |payload, input_from| {
+        let config: Base64Config =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            Base64Block::new(config, Arc::clone(&codec)).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+
+    fn run(config: Base64Config, input: BlockInput) -> Result<BlockOutput, BlockError> {
+        let block = Base64Block::new(config, Arc::new(StdBase64Codec));
+        match block.execute(test_ctx(input))? {
+            BlockExecutionResult::Once(output) => Ok(output),
+            _ => panic!("expected Once"),
+        }
+    }
+
+    fn value_of(output: BlockOutput) -> String {
+        match output {
+            BlockOutput::String { value } | BlockOutput::Text { value } => value,
+            other => panic!("expected string/text output, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn base64_round_trip_standard() {
+        let encoded = run(
+            Base64Config::new(Base64Mode::Encode),
+            BlockInput::Text("hello, world?>".into()),
+        )
+        .unwrap();
+        assert_eq!(
+            encoded,
+            BlockOutput::String {
+                value: "aGVsbG8sIHdvcmxkPz4=".into()
+            }
+        );
+        let decoded = run(
+            Base64Config::new(Base64Mode::Decode),
+            BlockInput::String(value_of(encoded)),
+        )
+        .unwrap();
+        assert_eq!(
+            decoded,
+            BlockOutput::Text {
+                value: "hello, world?>".into()
+            }
+        );
+    }
+
+    #[test]
+    fn base64_round_trip_url_safe_without_padding() {
+        let config = Base64Config::new(Base64Mode::Encode)
+            .with_url_safe(true)
+            .with_padding(false);
+        let encoded = value_of(run(config, BlockInput::Text("hello, world?>".into())).unwrap());
+        assert_eq!(encoded, "aGVsbG8sIHdvcmxkPz4");
+        assert!(!encoded.contains('+') && !encoded.contains('/'));
+
+        let decoded = run(
+            Base64Config::new(Base64Mode::Decode).with_url_safe(true),
+            BlockInput::String(encoded),
+        )
+        .unwrap();
+        assert_eq!(value_of(decoded), "hello, world?>");
+
+        let url_safe = value_of(
+            run(
+                Base64Config::new(Base64Mode::Encode).with_url_safe(true),
+                BlockInput::Text("???>>>".into()),
+            )
+            .unwrap(),
+        );
+        assert_eq!(url_safe, "Pz8_Pj4-");
+    }
+
+    #[test]
+    fn base64_decode_rejects_invalid_input() {
+        let err = run(
+            Base64Config::new(Base64Mode::Decode),
+            BlockInput::String("not base64!".into()),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("invalid base64"), "got: {err}");
+
+        let err = run(
+            Base64Config::new(Base64Mode::Decode),
+            BlockInput::String("//79".into()),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("not valid UTF-8"), "got: {err}");
+    }
+}
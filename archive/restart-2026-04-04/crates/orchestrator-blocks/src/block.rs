@@ -5,9 +5,17 @@
 use smallvec::SmallVec;
 
 use crate::{
-    AiGenerateConfig, CombineConfig, CronConfig, CustomTransformConfig, FileReadConfig,
-    FileWriteConfig, HttpRequestConfig, ListDirectoryConfig, RssParseConfig, SelectFirstConfig,
-    SendEmailConfig, SplitByKeysConfig, SplitLinesConfig, TemplateHandlebarsConfig,
+    AggregateConfig, AggregateOp, AiGenerateConfig, ApprovalConfig, Base64Config, Base64Mode,
+    ChatWebhookConfig, ChunkBy, CombineConfig, ConditionalConfig, CronConfig, CsvReadConfig,
+    CustomTransformConfig, DedupeConfig, DelayConfig, EnvFileConfig, FileChunkConfig,
+    FileReadConfig, FileWatchConfig, FileWriteConfig, GzipConfig, GzipMode, HttpRequestConfig,
+    IdConfig, IdFormat, JoinConfig, JoinMode, JsonExtractConfig, JsonMergeConfig,
+    JsonSchemaValidateConfig, ListDirectoryConfig, MarkdownToHtmlConfig, MergeStrategy, MetricKind,
+    MetricsConfig, MultiSource, PaginatedHttpConfig, Pagination, RssParseConfig, RuleKind,
+    SelectFirstConfig, SendEmailConfig, SetOpConfig, SetOpMode, SplitByKeysConfig,
+    SplitLinesConfig, SqliteConfig, StdinConfig, StdinMode, SummarizeConfig, SwitchConfig,
+    TemplateHandlebarsConfig, TextfileMetricsConfig, ThrottlePolicy, TranslateConfig,
+    XmlParseConfig,
 };
 use orchestrator_core::block::{BlockConfig, ChildWorkflowConfig};
 use orchestrator_core::{BlockId, RetryPolicy, Workflow, WorkflowDefinition, WorkflowEndpoint};
@@ -22,22 +30,29 @@ enum BlockKind {
         timeout_ms: Option<u64>,
         retry_policy: RetryPolicy,
     },
-    Cron {
-        cron: String,
-    },
+    Summarize(SummarizeConfig),
+    Approval(ApprovalConfig),
+    Translate(TranslateConfig),
+    Cron(CronConfig),
+    FileWatch(FileWatchConfig),
     HttpRequest {
         url: Option<String>,
         timeout_ms: Option<u64>,
         user_agent: Option<String>,
         retry_policy: RetryPolicy,
+        method: Option<String>,
+        body: Option<serde_json::Value>,
+        headers: std::collections::BTreeMap<String, String>,
+        include_metadata: bool,
     },
+    PaginatedHttp(PaginatedHttpConfig),
     ListDirectory {
         path: Option<String>,
         force_config_path: bool,
+        pattern: Option<String>,
+        recursive: bool,
     },
-    Combine {
-        keys: Vec<String>,
-    },
+    Combine(CombineConfig),
     CustomTransform {
         template: Option<String>,
     },
@@ -47,13 +62,42 @@ enum BlockKind {
     FileWrite {
         path: Option<String>,
         append: bool,
+        decode_base64: bool,
     },
-    MarkdownToHtml,
+    MarkdownToHtml(MarkdownToHtmlConfig),
     FileRead {
         path: Option<String>,
         force_config_path: bool,
     },
+    CsvRead(CsvReadConfig),
     RssParse,
+    XmlParse,
+    Aggregate {
+        field: String,
+        ops: Vec<AggregateOp>,
+    },
+    Dedupe(DedupeConfig),
+    EnvFile(EnvFileConfig),
+    Base64 {
+        mode: Base64Mode,
+        url_safe: bool,
+        padding: bool,
+    },
+    Gzip(GzipConfig),
+    Switch(SwitchConfig),
+    Conditional(ConditionalConfig),
+    Metrics(MetricsConfig),
+    TextfileMetrics(TextfileMetricsConfig),
+    Sqlite(SqliteConfig),
+    SetOp(SetOpConfig),
+    Join(JoinConfig),
+    FileChunk(FileChunkConfig),
+    Stdin(StdinConfig),
+    Id(IdConfig),
+    Delay(DelayConfig),
+    JsonExtract(JsonExtractConfig),
+    JsonMerge(JsonMergeConfig),
+    JsonSchemaValidate(JsonSchemaValidateConfig),
     SelectFirst {
         strategy: Option<String>,
     },
@@ -61,21 +105,29 @@ enum BlockKind {
         delimiter: String,
         trim_each: bool,
         skip_empty: bool,
+        fan_out: bool,
     },
-    TemplateHandlebars {
-        template: Option<String>,
-        partials: Option<serde_json::Value>,
-    },
+    TemplateHandlebars(TemplateHandlebarsConfig),
     SendEmail {
         to: Option<String>,
         subject: Option<String>,
         timeout_ms: Option<u64>,
         retry_policy: RetryPolicy,
+        skip_if_empty: bool,
+        empty_sentinel: Option<String>,
+    },
+    ChatWebhook {
+        url: Option<String>,
+        message_field: String,
+        timeout_ms: Option<u64>,
+        min_interval_ms: Option<u64>,
+        on_throttle: ThrottlePolicy,
     },
     ChildWorkflow {
         definition: WorkflowDefinition,
         timeout_ms: Option<u64>,
         retry_policy: RetryPolicy,
+        map_concurrency: Option<usize>,
     },
     Custom {
         type_id: String,
@@ -154,8 +206,23 @@ impl Block {
         })
     }
 
+    /// Summarize the input in at most `max_words` words (default openai model; see `ai_generate`).
+    pub fn summarize(max_words: u32) -> Self {
+        Self::new(BlockKind::Summarize(SummarizeConfig::new(max_words)))
+    }
+
+    /// Translate the input into `target_language` (default openai model; see `ai_generate`).
+    pub fn translate(target_language: impl Into<String>) -> Self {
+        Self::new(BlockKind::Translate(TranslateConfig::new(target_language)))
+    }
+
     pub fn cron(cron: impl Into<String>) -> Self {
-        Self::new(BlockKind::Cron { cron: cron.into() })
+        Self::new(BlockKind::Cron(CronConfig::new(cron)))
+    }
+
+    /// Entry trigger emitting the path of each file created or modified in `directory`.
+    pub fn file_watch(directory: impl Into<String>) -> Self {
+        Self::new(BlockKind::FileWatch(FileWatchConfig::new(directory)))
     }
 
     pub fn http_request(url: Option<impl Into<String>>) -> Self {
@@ -164,13 +231,26 @@ impl Block {
             timeout_ms: Some(30_000),
             user_agent: None,
             retry_policy: Self::default_http_retry_policy(),
+            method: None,
+            body: None,
+            headers: Default::default(),
+            include_metadata: false,
         })
     }
 
+    /// GET every page of a JSON list API (`url` from config or input) into one JSON array.
+    pub fn paginated_http(url: Option<impl Into<String>>, pagination: Pagination) -> Self {
+        Self::new(BlockKind::PaginatedHttp(PaginatedHttpConfig::new(
+            url, pagination,
+        )))
+    }
+
     pub fn list_directory(path: Option<impl Into<String>>) -> Self {
         Self::new(BlockKind::ListDirectory {
             path: path.map(Into::into),
             force_config_path: false,
+            pattern: None,
+            recursive: false,
         })
     }
 
@@ -179,11 +259,13 @@ impl Block {
         Self::new(BlockKind::ListDirectory {
             path: path.map(Into::into),
             force_config_path: true,
+            pattern: None,
+            recursive: false,
         })
     }
 
     pub fn combine(keys: impl Into<Vec<String>>) -> Self {
-        Self::new(BlockKind::Combine { keys: keys.into() })
+        Self::new(BlockKind::Combine(CombineConfig::new(keys)))
     }
 
     pub fn custom_transform(template: Option<impl Into<String>>) -> Self {
@@ -200,6 +282,7 @@ impl Block {
         Self::new(BlockKind::FileWrite {
             path: path.map(Into::into),
             append: false,
+            decode_base64: false,
         })
     }
 
@@ -207,11 +290,12 @@ impl Block {
         Self::new(BlockKind::FileWrite {
             path: path.map(Into::into),
             append: true,
+            decode_base64: false,
         })
     }
 
     pub fn markdown_to_html() -> Self {
-        Self::new(BlockKind::MarkdownToHtml)
+        Self::new(BlockKind::MarkdownToHtml(MarkdownToHtmlConfig::default()))
     }
 
     pub fn file_read(path: Option<impl Into<String>>) -> Self {
@@ -228,10 +312,205 @@ impl Block {
         })
     }
 
+    /// Read a file as a list of chunks of `size` lines or bytes.
+    pub fn file_chunk(path: Option<impl Into<String>>, by: ChunkBy, size: usize) -> Self {
+        Self::new(BlockKind::FileChunk(FileChunkConfig::new(path, by, size)))
+    }
+
+    /// Entry block reading all of stdin as text, or as a list of lines.
+    pub fn stdin(mode: StdinMode) -> Self {
+        Self::new(BlockKind::Stdin(StdinConfig::new(mode)))
+    }
+
+    /// Read a CSV file into a JSON array of objects keyed by header.
+    pub fn csv_read(path: Option<impl Into<String>>) -> Self {
+        Self::new(BlockKind::CsvRead(CsvReadConfig::new(path)))
+    }
+
     pub fn rss_parse() -> Self {
         Self::new(BlockKind::RssParse)
     }
 
+    pub fn xml_parse() -> Self {
+        Self::new(BlockKind::XmlParse)
+    }
+
+    pub fn aggregate(field: impl Into<String>, ops: impl Into<Vec<AggregateOp>>) -> Self {
+        Self::new(BlockKind::Aggregate {
+            field: field.into(),
+            ops: ops.into(),
+        })
+    }
+
+    /// Drop items whose `key_field` was already recorded in the seen-set file at `state_path`.
+    pub fn dedupe(key_field: impl Into<String>, state_path: impl Into<String>) -> Self {
+        Self::new(BlockKind::Dedupe(DedupeConfig::new(key_field, state_path)))
+    }
+
+    /// Read a dotenv-style `KEY=value` file into a JSON object.
+    pub fn env_file(path: impl Into<String>) -> Self {
+        Self::new(BlockKind::EnvFile(EnvFileConfig::new(path)))
+    }
+
+    pub fn base64_encode() -> Self {
+        Self::base64(Base64Mode::Encode)
+    }
+
+    pub fn base64_decode() -> Self {
+        Self::base64(Base64Mode::Decode)
+    }
+
+    fn base64(mode: Base64Mode) -> Self {
+        let config = Base64Config::new(mode);
+        Self::new(BlockKind::Base64 {
+            mode: config.mode,
+            url_safe: config.url_safe,
+            padding: config.padding,
+        })
+    }
+
+    /// Gzip text into a base64 string.
+    pub fn gzip_compress() -> Self {
+        Self::new(BlockKind::Gzip(GzipConfig::new(GzipMode::Compress)))
+    }
+
+    /// Decode a base64 gzip string back to text.
+    pub fn gzip_decompress() -> Self {
+        Self::new(BlockKind::Gzip(GzipConfig::new(GzipMode::Decompress)))
+    }
+
+    /// Increment StatsD counter `name` by the input value (array length, number, or 1).
+    pub fn metrics_counter(name: impl Into<String>) -> Self {
+        Self::new(BlockKind::Metrics(MetricsConfig::new(
+            name,
+            MetricKind::Counter,
+        )))
+    }
+
+    /// Set StatsD gauge `name` to the input value.
+    pub fn metrics_gauge(name: impl Into<String>) -> Self {
+        Self::new(BlockKind::Metrics(MetricsConfig::new(
+            name,
+            MetricKind::Gauge,
+        )))
+    }
+
+    /// Write a JSON object of name → number to `path` as a Prometheus textfile (gauges by default).
+    pub fn textfile_metrics(path: impl Into<String>) -> Self {
+        Self::new(BlockKind::TextfileMetrics(TextfileMetricsConfig::new(path)))
+    }
+
+    /// Pause the run until someone decides; see `Workflow::resume_with_input`.
+    pub fn approval(prompt: impl Into<String>) -> Self {
+        Self::new(BlockKind::Approval(ApprovalConfig::new(prompt)))
+    }
+
+    /// Sorted, deduplicated union of all list inputs (in edge order).
+    pub fn set_union() -> Self {
+        Self::new(BlockKind::SetOp(SetOpConfig::new(SetOpMode::Union)))
+    }
+
+    /// Items present in every list input.
+    pub fn set_intersection() -> Self {
+        Self::new(BlockKind::SetOp(SetOpConfig::new(SetOpMode::Intersection)))
+    }
+
+    /// Items of the first list input that appear in none of the others.
+    pub fn set_difference() -> Self {
+        Self::new(BlockKind::SetOp(SetOpConfig::new(SetOpMode::Difference)))
+    }
+
+    /// Inner join of two JSON arrays (in edge order) on the `on` field.
+    pub fn join(on: impl Into<String>) -> Self {
+        Self::new(BlockKind::Join(JoinConfig::new(on)))
+    }
+
+    /// Left join of two JSON arrays (in edge order); unmatched right fields are null.
+    pub fn left_join(on: impl Into<String>) -> Self {
+        Self::new(BlockKind::Join(
+            JoinConfig::new(on).with_mode(JoinMode::Left),
+        ))
+    }
+
+    /// Wait `delay_ms`, then forward the input unchanged (see `set_forward_input`).
+    pub fn delay(delay_ms: u64) -> Self {
+        Self::new(BlockKind::Delay(DelayConfig::new(delay_ms)))
+    }
+
+    /// Emit a fresh UUID v4 or ULID string, e.g. to tag records before writing or emailing.
+    pub fn id(format: IdFormat) -> Self {
+        Self::new(BlockKind::Id(IdConfig::new(format)))
+    }
+
+    /// Output the value at a JSON Pointer (e.g. `/data/items/0`) in the JSON input.
+    pub fn json_extract(pointer: impl Into<String>) -> Self {
+        Self::new(BlockKind::JsonExtract(JsonExtractConfig::new(pointer)))
+    }
+
+    /// Merge the JSON objects from all inputs in edge order; later keys win.
+    pub fn json_merge(strategy: MergeStrategy) -> Self {
+        Self::new(BlockKind::JsonMerge(JsonMergeConfig::new(strategy)))
+    }
+
+    /// Fail unless the JSON input matches `schema`; passes the input through otherwise.
+    pub fn json_schema_validate(schema: serde_json::Value) -> Self {
+        Self::new(BlockKind::JsonSchemaValidate(
+            JsonSchemaValidateConfig::new(schema),
+        ))
+    }
+
+    /// Read `key` from the key-value table in SQLite database `path`; outputs `Empty` when unset.
+    pub fn sqlite_get(path: impl Into<String>, key: impl Into<String>) -> Self {
+        Self::new(BlockKind::Sqlite(SqliteConfig::get(path, key)))
+    }
+
+    /// Store the input under `key` in SQLite database `path`.
+    pub fn sqlite_set(path: impl Into<String>, key: impl Into<String>) -> Self {
+        Self::new(BlockKind::Sqlite(SqliteConfig::set(path, key)))
+    }
+
+    /// Run `sql` against SQLite database `path`; rows come back as JSON objects.
+    pub fn sqlite_query(path: impl Into<String>, sql: impl Into<String>) -> Self {
+        Self::new(BlockKind::Sqlite(SqliteConfig::query(path, sql)))
+    }
+
+    /// Route input by `field` value: each `(value, slot)` case goes to the branch linked with
+    /// `link_slot`; unmatched values go to the `default` slot.
+    pub fn switch<V, S>(field: impl Into<String>, cases: impl IntoIterator<Item = (V, S)>) -> Self
+    where
+        V: Into<String>,
+        S: Into<String>,
+    {
+        let config = cases
+            .into_iter()
+            .fold(SwitchConfig::new(field), |config, (value, slot)| {
+                config.with_case(value, slot)
+            });
+        Self::new(BlockKind::Switch(config))
+    }
+
+    /// Route input by its own value (e.g. a conditional's `"then"` / `"else"`): each
+    /// `(value, slot)` case goes to the branch linked with `link_slot`; unmatched values go to the
+    /// `default` slot.
+    pub fn switch_value<V, S>(cases: impl IntoIterator<Item = (V, S)>) -> Self
+    where
+        V: Into<String>,
+        S: Into<String>,
+    {
+        let config = cases
+            .into_iter()
+            .fold(SwitchConfig::value(), |config, (value, slot)| {
+                config.with_case(value, slot)
+            });
+        Self::new(BlockKind::Switch(config))
+    }
+
+    /// Compare the input with `value` by `rule`; outputs `"then"` when the rule holds, `"else"`
+    /// otherwise.
+    pub fn conditional(rule: RuleKind, value: impl Into<String>) -> Self {
+        Self::new(BlockKind::Conditional(ConditionalConfig::new(rule, value)))
+    }
+
     pub fn select_first(strategy: Option<impl Into<String>>) -> Self {
         Self::new(BlockKind::SelectFirst {
             strategy: strategy.map(|s| s.into()),
@@ -244,6 +523,7 @@ impl Block {
             delimiter: cfg.delimiter,
             trim_each: cfg.trim_each,
             skip_empty: cfg.skip_empty,
+            fan_out: cfg.fan_out,
         })
     }
 
@@ -251,10 +531,10 @@ impl Block {
         template: Option<impl Into<String>>,
         partials: Option<serde_json::Value>,
     ) -> Self {
-        Self::new(BlockKind::TemplateHandlebars {
+        Self::new(BlockKind::TemplateHandlebars(TemplateHandlebarsConfig {
             template: template.map(|t| t.into()),
-            partials,
-        })
+            ..TemplateHandlebarsConfig::new(partials)
+        }))
     }
 
     pub fn send_email(to: impl Into<String>, subject: Option<impl Into<String>>) -> Self {
@@ -263,6 +543,19 @@ impl Block {
             subject: subject.map(|s| s.into()),
             timeout_ms: Some(30_000),
             retry_policy: Self::default_email_retry_policy(),
+            skip_if_empty: false,
+            empty_sentinel: None,
+        })
+    }
+
+    pub fn chat_webhook(url: Option<impl Into<String>>) -> Self {
+        let config = ChatWebhookConfig::new(url);
+        Self::new(BlockKind::ChatWebhook {
+            url: config.url,
+            message_field: config.message_field,
+            timeout_ms: config.timeout_ms,
+            min_interval_ms: None,
+            on_throttle: ThrottlePolicy::default(),
         })
     }
 
@@ -271,6 +564,18 @@ impl Block {
             definition,
             timeout_ms: None,
             retry_policy: RetryPolicy::none(),
+            map_concurrency: None,
+        })
+    }
+
+    /// Run the child once per item of a list/JSON array input, at most `max_concurrency` at a
+    /// time, and collect the outputs into a JSON array.
+    pub fn map_child_workflow(definition: WorkflowDefinition, max_concurrency: usize) -> Self {
+        Self::new(BlockKind::ChildWorkflow {
+            definition,
+            timeout_ms: None,
+            retry_policy: RetryPolicy::none(),
+            map_concurrency: Some(max_concurrency),
         })
     }
 
@@ -287,9 +592,17 @@ impl Block {
             BlockKind::AiGenerate { timeout_ms, .. }
             | BlockKind::HttpRequest { timeout_ms, .. }
             | BlockKind::SendEmail { timeout_ms, .. }
+            | BlockKind::ChatWebhook { timeout_ms, .. }
             | BlockKind::ChildWorkflow { timeout_ms, .. } => {
                 *timeout_ms = timeout;
             }
+            BlockKind::Summarize(SummarizeConfig { ai, .. })
+            | BlockKind::Translate(TranslateConfig { ai, .. }) => {
+                ai.timeout_ms = timeout;
+            }
+            BlockKind::PaginatedHttp(config) => {
+                config.timeout_ms = timeout;
+            }
             _ => {}
         }
         self
@@ -300,9 +613,17 @@ impl Block {
             BlockKind::AiGenerate { timeout_ms, .. }
             | BlockKind::HttpRequest { timeout_ms, .. }
             | BlockKind::SendEmail { timeout_ms, .. }
+            | BlockKind::ChatWebhook { timeout_ms, .. }
             | BlockKind::ChildWorkflow { timeout_ms, .. } => {
                 *timeout_ms = None;
             }
+            BlockKind::Summarize(SummarizeConfig { ai, .. })
+            | BlockKind::Translate(TranslateConfig { ai, .. }) => {
+                ai.timeout_ms = None;
+            }
+            BlockKind::PaginatedHttp(config) => {
+                config.timeout_ms = None;
+            }
             _ => {}
         }
         self
@@ -331,6 +652,13 @@ impl Block {
             } => {
                 *r = retry_policy;
             }
+            BlockKind::Summarize(SummarizeConfig { ai, .. })
+            | BlockKind::Translate(TranslateConfig { ai, .. }) => {
+                ai.retry_policy = retry_policy;
+            }
+            BlockKind::PaginatedHttp(config) => {
+                config.retry_policy = retry_policy;
+            }
             _ => {}
         }
         self
@@ -344,6 +672,13 @@ impl Block {
             | BlockKind::ChildWorkflow { retry_policy, .. } => {
                 *retry_policy = RetryPolicy::none();
             }
+            BlockKind::Summarize(SummarizeConfig { ai, .. })
+            | BlockKind::Translate(TranslateConfig { ai, .. }) => {
+                ai.retry_policy = RetryPolicy::none();
+            }
+            BlockKind::PaginatedHttp(config) => {
+                config.retry_policy = RetryPolicy::none();
+            }
             _ => {}
         }
         self
@@ -357,11 +692,384 @@ impl Block {
             | BlockKind::ChildWorkflow { retry_policy, .. } => {
                 *retry_policy = retry_policy.clone().with_max_backoff_ms(max_backoff_ms);
             }
+            BlockKind::Summarize(SummarizeConfig { ai, .. })
+            | BlockKind::Translate(TranslateConfig { ai, .. }) => {
+                ai.retry_policy = ai.retry_policy.clone().with_max_backoff_ms(max_backoff_ms);
+            }
+            BlockKind::PaginatedHttp(config) => {
+                config.retry_policy = config
+                    .retry_policy
+                    .clone()
+                    .with_max_backoff_ms(max_backoff_ms);
+            }
             _ => {}
         }
         self
     }
 
+    /// Rate-limit sends per destination (chat webhook only).
+    pub fn set_rate_limit(mut self, min_interval_ms: u64, policy: ThrottlePolicy) -> Self {
+        if let BlockKind::ChatWebhook {
+            min_interval_ms: interval,
+            on_throttle,
+            ..
+        } = &mut self.kind
+        {
+            *interval = Some(min_interval_ms);
+            *on_throttle = policy;
+        }
+        self
+    }
+
+    /// Skip sending blank bodies, or bodies equal to `sentinel` (send_email only).
+    pub fn set_skip_if_empty(mut self, sentinel: Option<impl Into<String>>) -> Self {
+        if let BlockKind::SendEmail {
+            skip_if_empty,
+            empty_sentinel,
+            ..
+        } = &mut self.kind
+        {
+            *skip_if_empty = true;
+            *empty_sentinel = sentinel.map(Into::into);
+        }
+        self
+    }
+
+    pub fn set_base64_alphabet(mut self, url_safe: bool, padding: bool) -> Self {
+        if let BlockKind::Base64 {
+            url_safe: current_url_safe,
+            padding: current_padding,
+            ..
+        } = &mut self.kind
+        {
+            *current_url_safe = url_safe;
+            *current_padding = padding;
+        }
+        self
+    }
+
+    /// Compression level 0-9 (gzip only).
+    pub fn set_gzip_level(mut self, level: u32) -> Self {
+        if let BlockKind::Gzip(config) = &mut self.kind {
+            config.level = level;
+        }
+        self
+    }
+
+    /// StatsD `host:port` (metrics only).
+    pub fn set_metrics_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        if let BlockKind::Metrics(config) = &mut self.kind {
+            config.endpoint = endpoint.into();
+        }
+        self
+    }
+
+    /// JSON field holding the metric value (metrics only).
+    pub fn set_metrics_field(mut self, field: impl Into<String>) -> Self {
+        if let BlockKind::Metrics(config) = &mut self.kind {
+            config.field = Some(field.into());
+        }
+        self
+    }
+
+    /// Fail the block when the metric cannot be sent (metrics only).
+    pub fn set_metrics_fail_on_error(mut self, fail_on_error: bool) -> Self {
+        if let BlockKind::Metrics(config) = &mut self.kind {
+            config.fail_on_error = fail_on_error;
+        }
+        self
+    }
+
+    /// Largest file to read, 64 MiB by default; larger files fail the block (file_chunk only).
+    pub fn set_file_chunk_max_bytes(mut self, max_bytes: u64) -> Self {
+        if let BlockKind::FileChunk(config) = &mut self.kind {
+            config.max_bytes = max_bytes;
+        }
+        self
+    }
+
+    /// Decode base64 content and write the raw bytes, e.g. gzip output (file_write only).
+    pub fn set_file_write_decode_base64(mut self, decode: bool) -> Self {
+        if let BlockKind::FileWrite { decode_base64, .. } = &mut self.kind {
+            *decode_base64 = decode;
+        }
+        self
+    }
+
+    /// Field separator, `,` by default (csv_read only).
+    pub fn set_csv_delimiter(mut self, delimiter: char) -> Self {
+        if let BlockKind::CsvRead(config) = &mut self.kind {
+            config.delimiter = Some(delimiter);
+        }
+        self
+    }
+
+    /// Whether the first row names the columns; rows are keyed by index when false (csv_read only).
+    pub fn set_csv_has_headers(mut self, has_headers: bool) -> Self {
+        if let BlockKind::CsvRead(config) = &mut self.kind {
+            config.has_headers = has_headers;
+        }
+        self
+    }
+
+    /// Generate a reproducible id sequence from `seed`, for tests (id only).
+    pub fn set_id_seed(mut self, seed: u64) -> Self {
+        if let BlockKind::Id(config) = &mut self.kind {
+            config.seed = Some(seed);
+        }
+        self
+    }
+
+    /// Enable markdown extensions such as tables and strikethrough (markdown_to_html only).
+    pub fn set_markdown_options(mut self, options: MarkdownToHtmlConfig) -> Self {
+        if let BlockKind::MarkdownToHtml(config) = &mut self.kind {
+            *config = options;
+        }
+        self
+    }
+
+    /// Register a partial usable as `{{> name}}` (template_handlebars only).
+    pub fn set_template_partial(
+        mut self,
+        name: impl Into<String>,
+        template: impl Into<String>,
+    ) -> Self {
+        if let BlockKind::TemplateHandlebars(config) = &mut self.kind {
+            config.partials.insert(name.into(), template.into());
+        }
+        self
+    }
+
+    /// Fail rendering on references to missing fields (template_handlebars only).
+    pub fn set_template_strict_mode(mut self, strict_mode: bool) -> Self {
+        if let BlockKind::TemplateHandlebars(config) = &mut self.kind {
+            config.strict_mode = strict_mode;
+        }
+        self
+    }
+
+    /// Fail instead of outputting `Empty` when the pointer does not resolve (json_extract only).
+    pub fn set_missing_is_error(mut self, missing_is_error: bool) -> Self {
+        if let BlockKind::JsonExtract(config) = &mut self.kind {
+            config.missing_is_error = missing_is_error;
+        }
+        self
+    }
+
+    /// `# TYPE` written for every metric (textfile_metrics only).
+    pub fn set_textfile_metric_kind(mut self, kind: MetricKind) -> Self {
+        if let BlockKind::TextfileMetrics(config) = &mut self.kind {
+            config.kind = kind;
+        }
+        self
+    }
+
+    /// Field holding each page's items, dot-separated (paginated_http only).
+    pub fn set_items_field(mut self, items_field: impl Into<String>) -> Self {
+        if let BlockKind::PaginatedHttp(config) = &mut self.kind {
+            config.items_field = Some(items_field.into());
+        }
+        self
+    }
+
+    /// Upper bound on pages fetched (paginated_http only).
+    pub fn set_max_pages(mut self, max_pages: u32) -> Self {
+        if let BlockKind::PaginatedHttp(config) = &mut self.kind {
+            config.max_pages = max_pages;
+        }
+        self
+    }
+
+    /// HTTP method, e.g. `POST` (http_request only).
+    pub fn set_http_method(mut self, method: impl Into<String>) -> Self {
+        if let BlockKind::HttpRequest { method: m, .. } = &mut self.kind {
+            *m = Some(method.into());
+        }
+        self
+    }
+
+    /// JSON request body (http_request only).
+    pub fn set_http_body(mut self, body: serde_json::Value) -> Self {
+        if let BlockKind::HttpRequest { body: b, .. } = &mut self.kind {
+            *b = Some(body);
+        }
+        self
+    }
+
+    /// Request header sent on every call; repeat for several (http_request only).
+    pub fn set_http_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        if let BlockKind::HttpRequest { headers, .. } = &mut self.kind {
+            headers.insert(name.into(), value.into());
+        }
+        self
+    }
+
+    /// Emit `{ status, headers, body }` JSON instead of the body text (http_request only).
+    pub fn set_http_include_metadata(mut self, include_metadata: bool) -> Self {
+        if let BlockKind::HttpRequest {
+            include_metadata: m,
+            ..
+        } = &mut self.kind
+        {
+            *m = include_metadata;
+        }
+        self
+    }
+
+    /// Glob matched against entry file names, e.g. `*.md` (list_directory only).
+    pub fn set_list_pattern(mut self, pattern: impl Into<String>) -> Self {
+        if let BlockKind::ListDirectory { pattern: p, .. } = &mut self.kind {
+            *p = Some(pattern.into());
+        }
+        self
+    }
+
+    /// Also list subdirectories (list_directory only).
+    pub fn set_list_recursive(mut self, recursive: bool) -> Self {
+        if let BlockKind::ListDirectory { recursive: r, .. } = &mut self.kind {
+            *r = recursive;
+        }
+        self
+    }
+
+    /// Trim whitespace around each line (split_lines only).
+    pub fn set_trim_each(mut self, trim: bool) -> Self {
+        if let BlockKind::SplitLines { trim_each, .. } = &mut self.kind {
+            *trim_each = trim;
+        }
+        self
+    }
+
+    /// Drop lines that are empty after optional trimming (split_lines only).
+    pub fn set_skip_empty(mut self, skip: bool) -> Self {
+        if let BlockKind::SplitLines { skip_empty, .. } = &mut self.kind {
+            *skip_empty = skip;
+        }
+        self
+    }
+
+    /// Emit one output per line to successors in edge order, or one `List` when false (split_lines only).
+    pub fn set_fan_out(mut self, enabled: bool) -> Self {
+        if let BlockKind::SplitLines { fan_out, .. } = &mut self.kind {
+            *fan_out = enabled;
+        }
+        self
+    }
+
+    /// Field identifying JSON object items (set operations only).
+    pub fn set_setop_key(mut self, key: impl Into<String>) -> Self {
+        if let BlockKind::SetOp(config) = &mut self.kind {
+            config.key = Some(key.into());
+        }
+        self
+    }
+
+    /// What a forced source that produced multiple outputs contributes (combine only).
+    pub fn set_multi_source(mut self, multi_source: MultiSource) -> Self {
+        if let BlockKind::Combine(config) = &mut self.kind {
+            config.multi_source = multi_source;
+        }
+        self
+    }
+
+    /// Emit `Empty` after waiting instead of forwarding the input (delay only).
+    pub fn set_forward_input(mut self, forward_input: bool) -> Self {
+        if let BlockKind::Delay(config) = &mut self.kind {
+            config.forward_input = forward_input;
+        }
+        self
+    }
+
+    /// Stop the schedule after `max_ticks` ticks (cron only). Zero fails the block.
+    pub fn set_cron_max_ticks(mut self, max_ticks: u64) -> Self {
+        if let BlockKind::Cron(config) = &mut self.kind {
+            config.max_ticks = Some(max_ticks);
+        }
+        self
+    }
+
+    /// Render tick times in `timezone`: `UTC`, `Local`, an IANA zone like `Europe/Paris` or an
+    /// offset like `+05:30` (cron only).
+    pub fn set_cron_timezone(mut self, timezone: impl Into<String>) -> Self {
+        if let BlockKind::Cron(config) = &mut self.kind {
+            config.timezone = Some(timezone.into());
+        }
+        self
+    }
+
+    /// Stop the schedule at `until` (RFC 3339); an invalid time fails the block (cron only).
+    pub fn set_cron_until(mut self, until: impl Into<String>) -> Self {
+        if let BlockKind::Cron(config) = &mut self.kind {
+            config.until_rfc3339 = Some(until.into());
+        }
+        self
+    }
+
+    /// Give a forced source that produced no output a `null` value instead of failing, so every
+    /// key stays present (combine only).
+    pub fn set_fill_missing(mut self, fill_missing: bool) -> Self {
+        if let BlockKind::Combine(config) = &mut self.kind {
+            config.fill_missing = fill_missing;
+        }
+        self
+    }
+
+    /// Key-value table name (sqlite only).
+    pub fn set_sqlite_table(mut self, table: impl Into<String>) -> Self {
+        if let BlockKind::Sqlite(config) = &mut self.kind {
+            config.table = table.into();
+        }
+        self
+    }
+
+    /// Slot for values without a case (switch only).
+    pub fn set_switch_default(mut self, slot: impl Into<String>) -> Self {
+        if let BlockKind::Switch(config) = &mut self.kind {
+            config.default_slot = slot.into();
+        }
+        self
+    }
+
+    /// Return one output per distinct slot in edge order instead of routing (switch only).
+    pub fn set_switch_fan_out(mut self, fan_out: bool) -> Self {
+        if let BlockKind::Switch(config) = &mut self.kind {
+            config.fan_out = fan_out;
+        }
+        self
+    }
+
+    /// File name pattern to watch for, e.g. `*.csv` (file_watch only).
+    pub fn set_watch_glob(mut self, glob: impl Into<String>) -> Self {
+        if let BlockKind::FileWatch(config) = &mut self.kind {
+            config.glob = glob.into();
+        }
+        self
+    }
+
+    /// Quiet period before a changed file is emitted (file_watch only).
+    pub fn set_debounce_ms(mut self, debounce_ms: u64) -> Self {
+        if let BlockKind::FileWatch(config) = &mut self.kind {
+            config.debounce_ms = debounce_ms;
+        }
+        self
+    }
+
+    /// Emit an empty object instead of failing when the file is missing (env_file only).
+    pub fn set_env_file_optional(mut self, optional: bool) -> Self {
+        if let BlockKind::EnvFile(config) = &mut self.kind {
+            config.optional = optional;
+        }
+        self
+    }
+
+    /// Merge the file values into the run vars before the run starts (env_file only).
+    pub fn set_env_file_merge_vars(mut self, merge_vars: bool) -> Self {
+        if let BlockKind::EnvFile(config) = &mut self.kind {
+            config.merge_vars = merge_vars;
+        }
+        self
+    }
+
     /// Convert this block to a BlockConfig for adding to a workflow.
     pub fn into_config(self) -> BlockConfig {
         self.into()
@@ -391,9 +1099,24 @@ impl From<Block> for BlockConfig {
                 .unwrap(),
                 input_from: Box::new([]),
             },
-            BlockKind::Cron { cron } => BlockConfig::Custom {
+            BlockKind::Summarize(config) => BlockConfig::Custom {
+                type_id: "summarize".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::Translate(config) => BlockConfig::Custom {
+                type_id: "translate".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::Cron(config) => BlockConfig::Custom {
                 type_id: "cron".to_string(),
-                payload: serde_json::to_value(CronConfig::new(cron)).unwrap(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::FileWatch(config) => BlockConfig::Custom {
+                type_id: "file_watch".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
                 input_from: Box::new([]),
             },
             BlockKind::HttpRequest {
@@ -401,6 +1124,10 @@ impl From<Block> for BlockConfig {
                 timeout_ms,
                 user_agent,
                 retry_policy,
+                method,
+                body,
+                headers,
+                include_metadata,
             } => BlockConfig::Custom {
                 type_id: "http_request".to_string(),
                 payload: serde_json::to_value(HttpRequestConfig {
@@ -408,24 +1135,38 @@ impl From<Block> for BlockConfig {
                     timeout_ms,
                     user_agent,
                     retry_policy,
+                    method,
+                    body,
+                    headers,
+                    include_metadata,
                 })
                 .unwrap(),
                 input_from: Box::new([]),
             },
+            BlockKind::PaginatedHttp(config) => BlockConfig::Custom {
+                type_id: "paginated_http".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
             BlockKind::ListDirectory {
                 path,
                 force_config_path,
+                pattern,
+                recursive,
             } => BlockConfig::Custom {
                 type_id: "list_directory".to_string(),
-                payload: serde_json::to_value(
-                    ListDirectoryConfig::new(path).with_force_config_path(force_config_path),
-                )
+                payload: serde_json::to_value(ListDirectoryConfig {
+                    pattern,
+                    ..ListDirectoryConfig::new(path)
+                        .with_force_config_path(force_config_path)
+                        .with_recursive(recursive)
+                })
                 .unwrap(),
                 input_from: Box::new([]),
             },
-            BlockKind::Combine { keys } => BlockConfig::Custom {
+            BlockKind::Combine(config) => BlockConfig::Custom {
                 type_id: "combine".to_string(),
-                payload: serde_json::to_value(CombineConfig::new(keys)).unwrap(),
+                payload: serde_json::to_value(config).unwrap(),
                 input_from: Box::new([]),
             },
             BlockKind::CustomTransform { template } => BlockConfig::Custom {
@@ -438,15 +1179,23 @@ impl From<Block> for BlockConfig {
                 payload: serde_json::to_value(SplitByKeysConfig::new(keys)).unwrap(),
                 input_from: Box::new([]),
             },
-            BlockKind::FileWrite { path, append } => BlockConfig::Custom {
+            BlockKind::FileWrite {
+                path,
+                append,
+                decode_base64,
+            } => BlockConfig::Custom {
                 type_id: "file_write".to_string(),
-                payload: serde_json::to_value(FileWriteConfig::new(path).with_append(append))
-                    .unwrap(),
+                payload: serde_json::to_value(
+                    FileWriteConfig::new(path)
+                        .with_append(append)
+                        .with_decode_base64(decode_base64),
+                )
+                .unwrap(),
                 input_from: Box::new([]),
             },
-            BlockKind::MarkdownToHtml => BlockConfig::Custom {
+            BlockKind::MarkdownToHtml(config) => BlockConfig::Custom {
                 type_id: "markdown_to_html".to_string(),
-                payload: serde_json::json!({}),
+                payload: serde_json::to_value(config).unwrap(),
                 input_from: Box::new([]),
             },
             BlockKind::FileRead {
@@ -465,6 +1214,125 @@ impl From<Block> for BlockConfig {
                 payload: serde_json::to_value(RssParseConfig::default()).unwrap(),
                 input_from: Box::new([]),
             },
+            BlockKind::XmlParse => BlockConfig::Custom {
+                type_id: "xml_parse".to_string(),
+                payload: serde_json::to_value(XmlParseConfig::default()).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::Aggregate { field, ops } => BlockConfig::Custom {
+                type_id: "aggregate".to_string(),
+                payload: serde_json::to_value(AggregateConfig::new(field, ops)).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::Dedupe(config) => BlockConfig::Custom {
+                type_id: "dedupe".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::EnvFile(config) => BlockConfig::Custom {
+                type_id: "env_file".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::Base64 {
+                mode,
+                url_safe,
+                padding,
+            } => BlockConfig::Custom {
+                type_id: "base64".to_string(),
+                payload: serde_json::to_value(
+                    Base64Config::new(mode)
+                        .with_url_safe(url_safe)
+                        .with_padding(padding),
+                )
+                .unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::Gzip(config) => BlockConfig::Custom {
+                type_id: "gzip".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::Switch(config) => BlockConfig::Custom {
+                type_id: "switch".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::Conditional(config) => BlockConfig::Custom {
+                type_id: "conditional".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::Approval(config) => BlockConfig::Custom {
+                type_id: "approval".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::FileChunk(config) => BlockConfig::Custom {
+                type_id: "file_chunk".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::Stdin(config) => BlockConfig::Custom {
+                type_id: "stdin".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::Delay(config) => BlockConfig::Custom {
+                type_id: "delay".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::Id(config) => BlockConfig::Custom {
+                type_id: "id".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::JsonExtract(config) => BlockConfig::Custom {
+                type_id: "json_extract".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::JsonMerge(config) => BlockConfig::Custom {
+                type_id: "json_merge".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::JsonSchemaValidate(config) => BlockConfig::Custom {
+                type_id: "json_schema_validate".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::SetOp(config) => BlockConfig::Custom {
+                type_id: "setop".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::Join(config) => BlockConfig::Custom {
+                type_id: "join".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::Sqlite(config) => BlockConfig::Custom {
+                type_id: "sqlite".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::Metrics(config) => BlockConfig::Custom {
+                type_id: "metrics".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::CsvRead(config) => BlockConfig::Custom {
+                type_id: "csv_read".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::TextfileMetrics(config) => BlockConfig::Custom {
+                type_id: "textfile_metrics".to_string(),
+                payload: serde_json::to_value(config).unwrap(),
+                input_from: Box::new([]),
+            },
             BlockKind::SelectFirst { strategy } => BlockConfig::Custom {
                 type_id: "select_first".to_string(),
                 payload: serde_json::to_value(SelectFirstConfig::new(strategy)).unwrap(),
@@ -474,20 +1342,21 @@ impl From<Block> for BlockConfig {
                 delimiter,
                 trim_each,
                 skip_empty,
+                fan_out,
             } => BlockConfig::Custom {
                 type_id: "split_lines".to_string(),
                 payload: serde_json::to_value(SplitLinesConfig {
                     delimiter,
                     trim_each,
                     skip_empty,
+                    fan_out,
                 })
                 .unwrap(),
                 input_from: Box::new([]),
             },
-            BlockKind::TemplateHandlebars { template, partials } => BlockConfig::Custom {
+            BlockKind::TemplateHandlebars(config) => BlockConfig::Custom {
                 type_id: "template_handlebars".to_string(),
-                payload: serde_json::to_value(TemplateHandlebarsConfig { template, partials })
-                    .unwrap(),
+                payload: serde_json::to_value(config).unwrap(),
                 input_from: Box::new([]),
             },
             BlockKind::SendEmail {
@@ -495,6 +1364,8 @@ impl From<Block> for BlockConfig {
                 subject,
                 timeout_ms,
                 retry_policy,
+                skip_if_empty,
+                empty_sentinel,
             } => BlockConfig::Custom {
                 type_id: "send_email".to_string(),
                 payload: serde_json::to_value(SendEmailConfig {
@@ -504,6 +1375,26 @@ impl From<Block> for BlockConfig {
                     smtp_port: None,
                     timeout_ms,
                     retry_policy,
+                    skip_if_empty,
+                    empty_sentinel,
+                })
+                .unwrap(),
+                input_from: Box::new([]),
+            },
+            BlockKind::ChatWebhook {
+                url,
+                message_field,
+                timeout_ms,
+                min_interval_ms,
+                on_throttle,
+            } => BlockConfig::Custom {
+                type_id: "chat_webhook".to_string(),
+                payload: serde_json::to_value(ChatWebhookConfig {
+                    url,
+                    message_field,
+                    timeout_ms,
+                    min_interval_ms,
+                    on_throttle,
                 })
                 .unwrap(),
                 input_from: Box::new([]),
@@ -512,10 +1403,12 @@ impl From<Block> for BlockConfig {
                 definition,
                 timeout_ms,
                 retry_policy,
+                map_concurrency,
             } => BlockConfig::ChildWorkflow(
                 ChildWorkflowConfig::new(definition)
                     .with_timeout_ms(timeout_ms)
-                    .with_retry_policy(retry_policy),
+                    .with_retry_policy(retry_policy)
+                    .with_map_concurrency(map_concurrency),
             ),
             BlockKind::Custom { type_id, payload } => BlockConfig::Custom {
                 type_id,
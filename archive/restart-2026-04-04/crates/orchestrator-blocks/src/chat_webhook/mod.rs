@@ -0,0 +1,486 @@
+//! ChatWebhook block: post a message to a generic chat webhook (Slack, Discord, ...),
+//! with optional per-destination rate limiting.
+//! Pass your poster when registering: `register_chat_webhook(registry, Arc::new(your_poster))`.
+
+mod reqwest_poster;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+use crate::input_binding::{
+    resolve_effective_input, validate_expected_input, validate_single_input_mode,
+};
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
+};
+
+pub use reqwest_poster::ReqwestWebhookPoster;
+
+/// Error from chat webhook operations.
+#[derive(Debug, Clone)]
+pub struct ChatWebhookError(pub String);
+
+impl std::fmt::Display for ChatWebhookError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ChatWebhookError {}
+
+/// Webhook poster abstraction. Implement and pass when registering.
+pub trait WebhookPoster: Send + Sync {
+    fn post_json(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+        timeout: Duration,
+    ) -> Result<(), ChatWebhookError>;
+}
+
+/// What to do with a send that falls inside `min_interval_ms` of the previous one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThrottlePolicy {
+    /// Drop the message; the block outputs `{"sent": false, "throttled": true}`.
+    #[default]
+    Skip,
+    /// Block until the interval has elapsed, then send.
+    Wait,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatWebhookConfig {
+    #[serde(default)]
+    pub url: Option<String>,
+    /// JSON field the message is posted under: `text` for Slack, `content` for Discord.
+    #[serde(default = "default_message_field")]
+    pub message_field: String,
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: Option<u64>,
+    /// Minimum time between two posts to the same URL. `None` disables rate limiting.
+    #[serde(default)]
+    pub min_interval_ms: Option<u64>,
+    #[serde(default)]
+    pub on_throttle: ThrottlePolicy,
+}
+
+fn default_message_field() -> String {
+    "text".to_string()
+}
+
+fn default_timeout_ms() -> Option<u64> {
+    Some(10_000)
+}
+
+impl ChatWebhookConfig {
+    pub fn new(url: Option<impl Into<String>>) -> Self {
+        Self {
+            url: url.map(Into::into),
+            message_field: default_message_field(),
+            timeout_ms: default_timeout_ms(),
+            min_interval_ms: None,
+            on_throttle: ThrottlePolicy::default(),
+        }
+    }
+
+    pub fn with_message_field(mut self, field: impl Into<String>) -> Self {
+        self.message_field = field.into();
+        self
+    }
+
+    pub fn with_rate_limit(mut self, min_interval_ms: u64, on_throttle: ThrottlePolicy) -> Self {
+        self.min_interval_ms = Some(min_interval_ms);
+        self.on_throttle = on_throttle;
+        self
+    }
+}
+
+/// Last send time per destination URL. Shared by every block built from one registration,
+/// so the limit holds across executions (e.g. recurring ticks).
+#[derive(Debug, Default)]
+pub struct WebhookRateLimiter {
+    last_sent: Mutex<HashMap<String, Instant>>,
+}
+
+impl WebhookRateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserve a send slot for `url`, or `None` when the send should be skipped.
+    fn reserve(
+        &self,
+        url: &str,
+        min_interval: Duration,
+        policy: ThrottlePolicy,
+    ) -> Option<Reservation> {
+        let mut last_sent = self.last_sent.lock().expect("rate limiter lock poisoned");
+        let now = Instant::now();
+        let previous = last_sent.get(url).copied();
+        let next_allowed = previous
+            .map(|last| last + min_interval)
+            .filter(|next| *next > now);
+        let slot = match (next_allowed, policy) {
+            (None, _) => now,
+            (Some(_), ThrottlePolicy::Skip) => return None,
+            (Some(next), ThrottlePolicy::Wait) => next,
+        };
+        last_sent.insert(url.to_string(), slot);
+        Some(Reservation {
+            wait: slot - now,
+            slot,
+            previous,
+        })
+    }
+
+    /// Give back a slot whose send failed, so it does not count against the limit. A slot
+    /// another send has already reserved after is kept.
+    fn release(&self, url: &str, reservation: Reservation) {
+        let mut last_sent = self.last_sent.lock().expect("rate limiter lock poisoned");
+        if last_sent.get(url) != Some(&reservation.slot) {
+            return;
+        }
+        match reservation.previous {
+            Some(previous) => last_sent.insert(url.to_string(), previous),
+            None => last_sent.remove(url),
+        };
+    }
+}
+
+/// A send slot taken from a [`WebhookRateLimiter`].
+struct Reservation {
+    /// How long to wait before sending.
+    wait: Duration,
+    slot: Instant,
+    /// The destination's last send time before this reservation.
+    previous: Option<Instant>,
+}
+
+pub struct ChatWebhookBlock {
+    config: ChatWebhookConfig,
+    poster: Arc<dyn WebhookPoster>,
+    limiter: Arc<WebhookRateLimiter>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl ChatWebhookBlock {
+    pub fn new(config: ChatWebhookConfig, poster: Arc<dyn WebhookPoster>) -> Self {
+        Self {
+            config,
+            poster,
+            limiter: Arc::new(WebhookRateLimiter::new()),
+            input_from: Box::new([]),
+        }
+    }
+
+    /// Share rate limiting state with other blocks posting to the same destinations.
+    pub fn with_rate_limiter(mut self, limiter: Arc<WebhookRateLimiter>) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+fn message_from_input(input: &BlockInput) -> Result<(String, Option<String>), BlockError> {
+    match input {
+        BlockInput::String(s) | BlockInput::Text(s) => Ok((s.clone(), None)),
+        BlockInput::Json(v) => {
+            if let Some(obj) = v.as_object() {
+                let url = obj.get("url").and_then(|u| u.as_str()).map(String::from);
+                let message = obj
+                    .get("text")
+                    .or_else(|| obj.get("message"))
+                    .map(|m| {
+                        m.as_str()
+                            .map(String::from)
+                            .unwrap_or_else(|| m.to_string())
+                    })
+                    .unwrap_or_else(|| v.to_string());
+                Ok((message, url))
+            } else {
+                Ok((
+                    v.as_str()
+                        .map(String::from)
+                        .unwrap_or_else(|| v.to_string()),
+                    None,
+                ))
+            }
+        }
+        BlockInput::List { .. } => Err(BlockError::Other(
+            "chat_webhook expects a single message".into(),
+        )),
+        BlockInput::Empty | BlockInput::Multi { .. } | BlockInput::MultiNamed { .. } => {
+            Err(BlockError::Other("message required from upstream".into()))
+        }
+        BlockInput::Error { message } => Err(BlockError::Other(message.clone())),
+    }
+}
+
+impl BlockExecutor for ChatWebhookBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        let (message, input_url) = message_from_input(&input)?;
+        let url = if !self.input_from.is_empty() {
+            input_url.ok_or_else(|| {
+                BlockError::Other("chat_webhook url required from forced input sources".into())
+            })?
+        } else if let Some(url) = self.config.url.clone() {
+            url
+        } else {
+            input_url.ok_or_else(|| {
+                BlockError::Other("chat_webhook url required from input or config".into())
+            })?
+        };
+
+        let reservation = match self.config.min_interval_ms {
+            Some(min_interval_ms) => {
+                let min_interval = Duration::from_millis(min_interval_ms);
+                let Some(reservation) =
+                    self.limiter
+                        .reserve(&url, min_interval, self.config.on_throttle)
+                else {
+                    info!(
+                        event = "chat_webhook.throttled",
+                        domain = "chat_webhook",
+                        block_type = "chat_webhook",
+                        policy = "skip",
+                        min_interval_ms = min_interval_ms
+                    );
+                    return Ok(BlockExecutionResult::Once(BlockOutput::Json {
+                        value: serde_json::json!({ "sent": false, "throttled": true }),
+                    }));
+                };
+                if !reservation.wait.is_zero() {
+                    info!(
+                        event = "chat_webhook.throttled",
+                        domain = "chat_webhook",
+                        block_type = "chat_webhook",
+                        policy = "wait",
+                        wait_ms = reservation.wait.as_millis() as u64
+                    );
+                    std::thread::sleep(reservation.wait);
+                }
+                Some(reservation)
+            }
+            None => None,
+        };
+
+        let mut body = serde_json::Map::new();
+        body.insert(
+            self.config.message_field.clone(),
+            serde_json::Value::String(message),
+        );
+        let timeout = Duration::from_millis(self.config.timeout_ms.unwrap_or(10_000));
+        debug!(
+            event = "chat_webhook.post",
+            domain = "chat_webhook",
+            block_type = "chat_webhook",
+            message_field = %self.config.message_field,
+            timeout_ms = timeout.as_millis() as u64
+        );
+        if let Err(e) = self
+            .poster
+            .post_json(&url, &serde_json::Value::Object(body), timeout)
+        {
+            if let Some(reservation) = reservation {
+                self.limiter.release(&url, reservation);
+            }
+            return Err(BlockError::Other(e.0));
+        }
+
+        Ok(BlockExecutionResult::Once(BlockOutput::Json {
+            value: serde_json::json!({ "sent": true, "throttled": false }),
+        }))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::Json, OutputMode::Once)
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        validate_single_input_mode(ctx)?;
+        validate_expected_input(
+            ctx,
+            ValueKindSet::singleton(ValueKind::String)
+                | ValueKindSet::singleton(ValueKind::Text)
+                | ValueKindSet::singleton(ValueKind::Json),
+        )
+    }
+}
+
+/// Register the chat_webhook block with a poster. All blocks created from this registration
+/// share one rate limiter.
+pub fn register_chat_webhook(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    poster: Arc<dyn WebhookPoster>,
+) {
+    let poster = Arc::clone(&poster);
+    let limiter = Arc::new(WebhookRateLimiter::new());
+    registry.register_custom("chat_webhook", move |payload, input_from| {
+        let config: ChatWebhookConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            ChatWebhookBlock::new(config, Arc::clone(&poster))
+                .with_rate_limiter(Arc::clone(&limiter))
+                .with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+
+    #[derive(Default)]
+    struct RecordingPoster {
+        posts: Mutex<Vec<(String, serde_json::Value)>>,
+    }
+
+    impl WebhookPoster for RecordingPoster {
+        fn post_json(
+            &self,
+            url: &str,
+            body: &serde_json::Value,
+            _timeout: Duration,
+        ) -> Result<(), ChatWebhookError> {
+            self.posts
+                .lock()
+                .unwrap()
+                .push((url.to_string(), body.clone()));
+            Ok(())
+        }
+    }
+
+    fn sent_flag(result: BlockExecutionResult) -> bool {
+        match result {
+            BlockExecutionResult::Once(BlockOutput::Json { value }) => {
+                value["sent"].as_bool().unwrap()
+            }
+            _ => panic!("expected Once(Json)"),
+        }
+    }
+
+    #[test]
+    fn chat_webhook_posts_message_under_configured_field() {
+        let poster = Arc::new(RecordingPoster::default());
+        let block = ChatWebhookBlock::new(
+            ChatWebhookConfig::new(Some("https://hooks.test/a")).with_message_field("content"),
+            poster.clone(),
+        );
+        let out = block
+            .execute(test_ctx(BlockInput::String("deploy done".into())))
+            .unwrap();
+        assert!(sent_flag(out));
+        let posts = poster.posts.lock().unwrap();
+        assert_eq!(posts[0].0, "https://hooks.test/a");
+        assert_eq!(posts[0].1, serde_json::json!({ "content": "deploy done" }));
+    }
+
+    #[test]
+    fn chat_webhook_skip_policy_drops_second_rapid_send() {
+        let poster = Arc::new(RecordingPoster::default());
+        let limiter = Arc::new(WebhookRateLimiter::new());
+        let config = ChatWebhookConfig::new(Some("https://hooks.test/a"))
+            .with_rate_limit(60_000, ThrottlePolicy::Skip);
+        let first = ChatWebhookBlock::new(config.clone(), poster.clone())
+            .with_rate_limiter(Arc::clone(&limiter));
+        let second = ChatWebhookBlock::new(config, poster.clone()).with_rate_limiter(limiter);
+
+        assert!(sent_flag(
+            first
+                .execute(test_ctx(BlockInput::String("one".into())))
+                .unwrap()
+        ));
+        assert!(!sent_flag(
+            second
+                .execute(test_ctx(BlockInput::String("two".into())))
+                .unwrap()
+        ));
+        assert_eq!(poster.posts.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn chat_webhook_wait_policy_delays_second_rapid_send() {
+        let poster = Arc::new(RecordingPoster::default());
+        let block = ChatWebhookBlock::new(
+            ChatWebhookConfig::new(Some("https://hooks.test/a"))
+                .with_rate_limit(100, ThrottlePolicy::Wait),
+            poster.clone(),
+        );
+        let started = Instant::now();
+        block
+            .execute(test_ctx(BlockInput::String("one".into())))
+            .unwrap();
+        assert!(sent_flag(
+            block
+                .execute(test_ctx(BlockInput::String("two".into())))
+                .unwrap()
+        ));
+        assert!(started.elapsed() >= Duration::from_millis(100));
+        assert_eq!(poster.posts.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn chat_webhook_rate_limit_is_per_destination() {
+        let poster = Arc::new(RecordingPoster::default());
+        let block = ChatWebhookBlock::new(
+            ChatWebhookConfig::new(None::<String>).with_rate_limit(60_000, ThrottlePolicy::Skip),
+            poster.clone(),
+        );
+        for url in ["https://hooks.test/a", "https://hooks.test/b"] {
+            let out = block
+                .execute(test_ctx(BlockInput::Json(serde_json::json!({
+                    "url": url,
+                    "text": "hello"
+                }))))
+                .unwrap();
+            assert!(sent_flag(out));
+        }
+        assert_eq!(poster.posts.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn chat_webhook_failed_send_does_not_use_up_the_rate_limit() {
+        struct FailingPoster;
+        impl WebhookPoster for FailingPoster {
+            fn post_json(
+                &self,
+                _url: &str,
+                _body: &serde_json::Value,
+                _timeout: Duration,
+            ) -> Result<(), ChatWebhookError> {
+                Err(ChatWebhookError("503 service unavailable".into()))
+            }
+        }
+
+        let limiter = Arc::new(WebhookRateLimiter::new());
+        let config = ChatWebhookConfig::new(Some("https://hooks.test/a"))
+            .with_rate_limit(60_000, ThrottlePolicy::Skip);
+        let failing = ChatWebhookBlock::new(config.clone(), Arc::new(FailingPoster))
+            .with_rate_limiter(Arc::clone(&limiter));
+        let err = failing
+            .execute(test_ctx(BlockInput::String("one".into())))
+            .unwrap_err();
+        assert!(err.to_string().contains("503"), "got: {err}");
+
+        let poster = Arc::new(RecordingPoster::default());
+        let retry = ChatWebhookBlock::new(config, poster.clone()).with_rate_limiter(limiter);
+        assert!(sent_flag(
+            retry
+                .execute(test_ctx(BlockInput::String("one".into())))
+                .unwrap()
+        ));
+        assert_eq!(poster.posts.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+use super::{ChatWebhookError, WebhookPoster};
+
+/// Default webhook poster using reqwest blocking client.
+pub struct ReqwestWebhookPoster;
+
+impl WebhookPoster for ReqwestWebhookPoster {
+    fn post_json(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+        timeout: Duration,
+    ) -> Result<(), ChatWebhookError> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(timeout)
+            .user_agent("local-orchestration/0.1")
+            .build()
+            .map_err(|e| ChatWebhookError(e.to_string()))?;
+        let resp = client
+            .post(url)
+            .json(body)
+            .send()
+            .map_err(|e| ChatWebhookError(e.to_string()))?;
+        let status = resp.status();
+        if !status.is_success() {
+            let text = resp.text().unwrap_or_default();
+            return Err(ChatWebhookError(format!(
+                "chat_webhook post failed: status={} body={}",
+                status, text
+            )));
+        }
+        Ok(())
+    }
+}
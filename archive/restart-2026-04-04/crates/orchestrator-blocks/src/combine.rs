@@ -1,4 +1,9 @@
 //! Combine block: Transform that merges incoming values and outputs one Json object using an injected strategy.
+//! Each key takes one value. Linked predecessors give exactly one each (a `Multiple` producer gives the
+//! output routed to this block, a skipped one gives `null`), by link order or, when linked with
+//! `Workflow::link_as`, by the edge label matching the key; sources declared with `with_input_from` that produced `Multiple` are
+//! mapped to their key per `multi_source`. With `fill_missing`, a source that produced no output
+//! (e.g. skipped by a switch) maps to `null` instead of failing, so every key is always present.
 //! Pass your strategy when registering: `register_combine(registry, Arc::new(your_strategy))`.
 
 use std::sync::Arc;
@@ -8,7 +13,7 @@ use serde::{Deserialize, Serialize};
 use crate::input_binding::resolve_effective_input;
 use orchestrator_core::block::{
     BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
-    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind,
+    BlockOutput, OutputContract, OutputMode, StoredOutput, ValidateContext, ValueKind,
 };
 
 /// Error from combine operations.
@@ -32,14 +37,46 @@ pub trait CombineStrategy: Send + Sync {
     ) -> Result<serde_json::Value, CombineError>;
 }
 
+/// What a forced input source that produced `Multiple` contributes to its key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MultiSource {
+    /// The source's first output.
+    #[default]
+    First,
+    /// All of the source's outputs, as a JSON array.
+    List,
+    /// Fail the block.
+    Error,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CombineConfig {
     pub keys: Vec<String>,
+    #[serde(default)]
+    pub multi_source: MultiSource,
+    /// Forced sources without an output become `null` instead of failing the block.
+    #[serde(default)]
+    pub fill_missing: bool,
 }
 
 impl CombineConfig {
     pub fn new(keys: impl Into<Vec<String>>) -> Self {
-        Self { keys: keys.into() }
+        Self {
+            keys: keys.into(),
+            multi_source: MultiSource::default(),
+            fill_missing: false,
+        }
+    }
+
+    pub fn with_multi_source(mut self, multi_source: MultiSource) -> Self {
+        self.multi_source = multi_source;
+        self
+    }
+
+    pub fn with_fill_missing(mut self, fill_missing: bool) -> Self {
+        self.fill_missing = fill_missing;
+        self
     }
 }
 
@@ -74,11 +111,64 @@ impl CombineBlock {
         self.input_from = input_from;
         self
     }
+
+    /// One output per forced source, in declaration order.
+    fn forced_source_outputs(
+        &self,
+        ctx: &BlockExecutionContext,
+    ) -> Result<Vec<BlockOutput>, BlockError> {
+        let mut outputs = Vec::with_capacity(self.input_from.len());
+        for source_id in self.input_from.iter() {
+            let Some(stored) = ctx.store.get(source_id) else {
+                if self.config.fill_missing {
+                    outputs.push(BlockOutput::Empty);
+                    continue;
+                }
+                return Err(BlockError::InputMissing {
+                    source_id: *source_id,
+                    message: "source output not found in run store".into(),
+                });
+            };
+            let output = match stored.value() {
+                StoredOutput::Once(output) => output.as_ref().clone(),
+                StoredOutput::Multiple(all) => match self.config.multi_source {
+                    MultiSource::First => all.first().cloned().unwrap_or(BlockOutput::Empty),
+                    MultiSource::List => BlockOutput::Json {
+                        value: serde_json::Value::Array(all.iter().map(output_to_value).collect()),
+                    },
+                    MultiSource::Error => {
+                        return Err(BlockError::Other(format!(
+                            "combine source {} produced {} outputs; multi_source is error",
+                            source_id,
+                            all.len()
+                        )));
+                    }
+                },
+            };
+            outputs.push(output);
+        }
+        Ok(outputs)
+    }
 }
 
-fn input_to_outputs(input: BlockInput) -> Result<Vec<BlockOutput>, BlockError> {
+fn input_to_outputs(
+    input: BlockInput,
+    config: &CombineConfig,
+) -> Result<Vec<BlockOutput>, BlockError> {
     match input {
         BlockInput::Multi { outputs } => Ok(outputs),
+        BlockInput::MultiNamed { mut outputs } => config
+            .keys
+            .iter()
+            .map(|key| match outputs.remove(key) {
+                Some(output) => Ok(output),
+                None if config.fill_missing => Ok(BlockOutput::Empty),
+                None => Err(BlockError::Other(format!(
+                    "combine has no input labeled {:?}",
+                    key
+                ))),
+            })
+            .collect(),
         BlockInput::Empty => Ok(vec![]),
         BlockInput::String(value) => Ok(vec![BlockOutput::String { value }]),
         BlockInput::Text(value) => Ok(vec![BlockOutput::Text { value }]),
@@ -90,8 +180,14 @@ fn input_to_outputs(input: BlockInput) -> Result<Vec<BlockOutput>, BlockError> {
 
 impl BlockExecutor for CombineBlock {
     fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
-        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
-        let outputs = input_to_outputs(input)?;
+        let outputs = if self.input_from.is_empty() {
+            input_to_outputs(
+                resolve_effective_input(&ctx, &self.input_from, None)?,
+                &self.config,
+            )?
+        } else {
+            self.forced_source_outputs(&ctx)?
+        };
         let value = self
             .strategy
             .combine(&self.config.keys, &outputs)
@@ -140,21 +236,10 @@ pub fn register_combine(
     });
 }
 
-#[cfg(test)]
-fn test_ctx(input: BlockInput) -> BlockExecutionContext {
-    BlockExecutionContext {
-        workflow_id: uuid::Uuid::new_v4(),
-        run_id: uuid::Uuid::new_v4(),
-        block_id: uuid::Uuid::new_v4(),
-        attempt: 1,
-        prev: input,
-        store: Default::default(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_ctx;
 
     #[test]
     fn combine_executes_with_multi_input() {
@@ -196,6 +281,51 @@ mod tests {
         }
     }
 
+    /// Source `a` produced two outputs (`Multiple`), source `b` one.
+    fn run_multi_source(multi_source: MultiSource) -> Result<serde_json::Value, BlockError> {
+        let (a, b) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        let ctx = test_ctx(BlockInput::empty());
+        let string = |value: &str| BlockOutput::String {
+            value: value.into(),
+        };
+        ctx.store.insert(
+            a,
+            StoredOutput::Multiple(Arc::from(vec![string("first"), string("second")])),
+        );
+        ctx.store
+            .insert(b, StoredOutput::Once(Arc::new(string("only"))));
+        let config =
+            CombineConfig::new(vec!["a".into(), "b".into()]).with_multi_source(multi_source);
+        let block = CombineBlock::new(config, Arc::new(KeyedCombineStrategy))
+            .with_input_from(vec![a, b].into_boxed_slice());
+        block.execute(ctx).map(|result| match result.into_once() {
+            BlockOutput::Json { value } => value,
+            other => panic!("expected Json, got {:?}", other),
+        })
+    }
+
+    #[test]
+    fn combine_multi_source_first_takes_first_output() {
+        assert_eq!(
+            run_multi_source(MultiSource::First).unwrap(),
+            serde_json::json!({ "a": "first", "b": "only" })
+        );
+    }
+
+    #[test]
+    fn combine_multi_source_list_collects_all_outputs() {
+        assert_eq!(
+            run_multi_source(MultiSource::List).unwrap(),
+            serde_json::json!({ "a": ["first", "second"], "b": "only" })
+        );
+    }
+
+    #[test]
+    fn combine_multi_source_error_rejects_multiple() {
+        let err = run_multi_source(MultiSource::Error).unwrap_err();
+        assert!(err.to_string().contains("produced 2 outputs"), "got: {err}");
+    }
+
     #[test]
     fn combine_error_input_returns_error() {
         let config = CombineConfig::new(vec!["a".into()]);
@@ -207,4 +337,160 @@ mod tests {
         assert!(err.is_err());
         assert!(err.unwrap_err().to_string().contains("upstream error"));
     }
+
+    /// Three forced sources: `a` produced text, `b` produced `Empty`, `c` never ran.
+    fn run_with_gaps(fill_missing: bool) -> Result<serde_json::Value, BlockError> {
+        let (a, b, c) = (
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+        );
+        let ctx = test_ctx(BlockInput::empty());
+        ctx.store.insert(
+            a,
+            StoredOutput::Once(Arc::new(BlockOutput::Text {
+                value: "news".into(),
+            })),
+        );
+        ctx.store
+            .insert(b, StoredOutput::Once(Arc::new(BlockOutput::Empty)));
+        let config = CombineConfig::new(vec!["a".into(), "b".into(), "c".into()])
+            .with_fill_missing(fill_missing);
+        CombineBlock::new(config, Arc::new(KeyedCombineStrategy))
+            .with_input_from(vec![a, b, c].into_boxed_slice())
+            .execute(ctx)
+            .map(|result| match result.into_once() {
+                BlockOutput::Json { value } => value,
+                other => panic!("expected Json, got {:?}", other),
+            })
+    }
+
+    /// Sources finish in the reverse of their link order; keys still follow link order.
+    #[test]
+    fn combine_keys_follow_link_order_not_completion_order() {
+        use orchestrator_core::Workflow;
+        use orchestrator_core::block::{BlockConfig, BlockRegistry};
+        use serde_json::json;
+
+        struct SlowSource {
+            name: String,
+            delay_ms: u64,
+        }
+        impl BlockExecutor for SlowSource {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<BlockExecutionResult, BlockError> {
+                std::thread::sleep(std::time::Duration::from_millis(self.delay_ms));
+                Ok(BlockExecutionResult::Once(BlockOutput::Text {
+                    value: self.name.clone(),
+                }))
+            }
+        }
+
+        let mut registry = BlockRegistry::new();
+        register_combine(&mut registry, Arc::new(KeyedCombineStrategy));
+        registry.register_custom("slow", |payload, _| {
+            Ok(Box::new(SlowSource {
+                name: payload["name"].as_str().unwrap_or_default().to_string(),
+                delay_ms: payload["delay_ms"].as_u64().unwrap_or_default(),
+            }))
+        });
+        let slow = |name: &str, delay_ms: u64| BlockConfig::Custom {
+            type_id: "slow".to_string(),
+            payload: json!({"name": name, "delay_ms": delay_ms}),
+            input_from: Box::new([]),
+        };
+
+        let mut w = Workflow::with_registry(registry);
+        let start = w.add(slow("start", 0));
+        let sources = [
+            w.add(slow("prices", 40)),
+            w.add(slow("stock", 20)),
+            w.add(slow("reviews", 0)),
+        ];
+        let combine = w.add(BlockConfig::Custom {
+            type_id: "combine".to_string(),
+            payload: json!({"keys": ["prices", "stock", "reviews"]}),
+            input_from: Box::new([]),
+        });
+        for source in sources {
+            w.link(start, source);
+            w.link(source, combine);
+        }
+        match w.run().unwrap() {
+            BlockOutput::Json { value } => assert_eq!(
+                value,
+                json!({"prices": "prices", "stock": "stock", "reviews": "reviews"})
+            ),
+            other => panic!("expected Json, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn combine_maps_named_inputs_by_key() {
+        let named = |pairs: &[(&str, &str)]| BlockInput::MultiNamed {
+            outputs: pairs
+                .iter()
+                .map(|(label, value)| {
+                    (
+                        label.to_string(),
+                        BlockOutput::String {
+                            value: value.to_string(),
+                        },
+                    )
+                })
+                .collect(),
+        };
+        let keys = vec!["title".to_string(), "body".to_string()];
+        let block = CombineBlock::new(
+            CombineConfig::new(keys.clone()),
+            Arc::new(KeyedCombineStrategy),
+        );
+        let out = block
+            .execute(test_ctx(named(&[("body", "text"), ("title", "Weekly")])))
+            .unwrap()
+            .into_once();
+        assert_eq!(
+            out,
+            BlockOutput::Json {
+                value: serde_json::json!({"title": "Weekly", "body": "text"})
+            }
+        );
+
+        assert!(
+            block
+                .execute(test_ctx(named(&[("title", "Weekly")])))
+                .is_err()
+        );
+        let filled = CombineBlock::new(
+            CombineConfig::new(keys).with_fill_missing(true),
+            Arc::new(KeyedCombineStrategy),
+        );
+        assert_eq!(
+            filled
+                .execute(test_ctx(named(&[("title", "Weekly")])))
+                .unwrap()
+                .into_once(),
+            BlockOutput::Json {
+                value: serde_json::json!({"title": "Weekly", "body": null})
+            }
+        );
+    }
+
+    #[test]
+    fn combine_fill_missing_keeps_every_key_with_null() {
+        assert_eq!(
+            run_with_gaps(true).unwrap(),
+            serde_json::json!({ "a": "news", "b": null, "c": null })
+        );
+    }
+
+    #[test]
+    fn combine_without_fill_missing_rejects_missing_source() {
+        assert!(matches!(
+            run_with_gaps(false),
+            Err(BlockError::InputMissing { .. })
+        ));
+    }
 }
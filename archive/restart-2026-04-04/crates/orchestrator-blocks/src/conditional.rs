@@ -0,0 +1,245 @@
+//! Conditional block: Control block that compares its input against a rule value and outputs the
+//! string `"then"` when the rule holds, `"else"` otherwise. `equals` and `contains` compare text
+//! (non-string JSON input as its serialized JSON), `greater_than` / `less_than` compare numbers
+//! (e.g. a price), `regex` matches a pattern.
+//! Register with: `register_conditional(registry)`.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::input_binding::{
+    resolve_effective_input, validate_expected_input, validate_single_input_mode,
+};
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
+};
+
+/// How the input is compared with [`ConditionalConfig::value`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleKind {
+    /// Input text equals the value, both trimmed.
+    #[default]
+    Equals,
+    /// Input number is greater than the value.
+    GreaterThan,
+    /// Input number is less than the value.
+    LessThan,
+    /// Input text contains the value. Non-string JSON input is matched against its serialized
+    /// JSON text, e.g. `{"status":"ok"}` contains `"status":"ok"`.
+    Contains,
+    /// Input text matches the value as a regular expression.
+    Regex,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConditionalConfig {
+    #[serde(default)]
+    pub rule: RuleKind,
+    pub value: String,
+}
+
+impl ConditionalConfig {
+    pub fn new(rule: RuleKind, value: impl Into<String>) -> Self {
+        Self {
+            rule,
+            value: value.into(),
+        }
+    }
+}
+
+fn parse_number(text: &str, what: &str) -> Result<f64, BlockError> {
+    text.trim()
+        .parse()
+        .map_err(|_| BlockError::Other(format!("conditional {} is not a number: {}", what, text)))
+}
+
+fn input_to_text(input: BlockInput) -> Result<String, BlockError> {
+    match input {
+        BlockInput::Empty => Ok(String::new()),
+        BlockInput::String(s) | BlockInput::Text(s) => Ok(s),
+        BlockInput::Json(serde_json::Value::String(s)) => Ok(s),
+        BlockInput::Json(v) => Ok(v.to_string()),
+        BlockInput::Error { message } => Err(BlockError::Other(message)),
+        BlockInput::List { .. } | BlockInput::Multi { .. } | BlockInput::MultiNamed { .. } => Err(
+            BlockError::Other("conditional expects a single value, not a list".into()),
+        ),
+    }
+}
+
+pub struct ConditionalBlock {
+    config: ConditionalConfig,
+    /// Compiled once for `regex` rules; an invalid pattern is reported by `validate_linkage`.
+    pattern: Result<Option<Regex>, String>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl ConditionalBlock {
+    pub fn new(config: ConditionalConfig) -> Self {
+        let pattern = match config.rule {
+            RuleKind::Regex => Regex::new(&config.value)
+                .map(Some)
+                .map_err(|e| format!("conditional regex {:?} is invalid: {}", config.value, e)),
+            _ => Ok(None),
+        };
+        Self {
+            config,
+            pattern,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+
+    fn holds(&self, text: &str) -> Result<bool, BlockError> {
+        let value = &self.config.value;
+        Ok(match self.config.rule {
+            RuleKind::Equals => text.trim() == value.trim(),
+            RuleKind::GreaterThan => parse_number(text, "input")? > parse_number(value, "value")?,
+            RuleKind::LessThan => parse_number(text, "input")? < parse_number(value, "value")?,
+            RuleKind::Contains => text.contains(value.as_str()),
+            RuleKind::Regex => match &self.pattern {
+                Ok(Some(re)) => re.is_match(text),
+                Ok(None) => false,
+                Err(msg) => return Err(BlockError::Other(msg.clone())),
+            },
+        })
+    }
+}
+
+impl BlockExecutor for ConditionalBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        let text = input_to_text(input)?;
+        let branch = if self.holds(&text)? { "then" } else { "else" };
+        Ok(BlockExecutionResult::Once(BlockOutput::String {
+            value: branch.to_string(),
+        }))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::String, OutputMode::Once)
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        if let Err(msg) = &self.pattern {
+            return Err(BlockError::Other(msg.clone()));
+        }
+        if matches!(self.config.rule, RuleKind::GreaterThan | RuleKind::LessThan) {
+            parse_number(&self.config.value, "value")?;
+        }
+        validate_single_input_mode(ctx)?;
+        validate_expected_input(
+            ctx,
+            ValueKindSet::singleton(ValueKind::Empty)
+                | ValueKindSet::singleton(ValueKind::String)
+                | ValueKindSet::singleton(ValueKind::Text)
+                | ValueKindSet::singleton(ValueKind::Json),
+        )
+    }
+}
+
+/// Register the conditional block.
+pub fn register_conditional(registry: &mut orchestrator_core::block::BlockRegistry) {
+    registry.register_custom("conditional", |payload, input_from| {
+        let config: ConditionalConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            ConditionalBlock::new(config).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use orchestrator_core::block::InputContract;
+    use serde_json::json;
+
+    fn branch(rule: RuleKind, value: &str, input: BlockInput) -> String {
+        match ConditionalBlock::new(ConditionalConfig::new(rule, value))
+            .execute(test_ctx(input))
+            .unwrap()
+            .into_once()
+        {
+            BlockOutput::String { value } => value,
+            other => panic!("unexpected output: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn conditional_equals_keeps_then_else_outputs() {
+        let status = || BlockInput::String("200\n".into());
+        assert_eq!(branch(RuleKind::Equals, "200", status()), "then");
+        assert_eq!(branch(RuleKind::Equals, "404", status()), "else");
+        assert_eq!(branch(RuleKind::Equals, " 200\n", status()), "then");
+    }
+
+    #[test]
+    fn conditional_greater_than_compares_numbers() {
+        assert_eq!(
+            branch(RuleKind::GreaterThan, "99.5", BlockInput::Json(json!(120))),
+            "then"
+        );
+        assert_eq!(
+            branch(RuleKind::GreaterThan, "99.5", BlockInput::Text("80".into())),
+            "else"
+        );
+        let err = ConditionalBlock::new(ConditionalConfig::new(RuleKind::GreaterThan, "10"))
+            .execute(test_ctx(BlockInput::String("cheap".into())));
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn conditional_less_than_compares_numbers() {
+        assert_eq!(
+            branch(
+                RuleKind::LessThan,
+                "100",
+                BlockInput::String("19.99".into())
+            ),
+            "then"
+        );
+        assert_eq!(
+            branch(RuleKind::LessThan, "100", BlockInput::String("100".into())),
+            "else"
+        );
+    }
+
+    #[test]
+    fn conditional_contains_matches_substring() {
+        let body = || BlockInput::Text("status: in stock".into());
+        assert_eq!(branch(RuleKind::Contains, "in stock", body()), "then");
+        assert_eq!(branch(RuleKind::Contains, "sold out", body()), "else");
+
+        let json = || BlockInput::Json(json!({"status": "ok", "count": 2}));
+        assert_eq!(
+            branch(RuleKind::Contains, r#""status":"ok""#, json()),
+            "then"
+        );
+        assert_eq!(branch(RuleKind::Contains, "status: ok", json()), "else");
+    }
+
+    #[test]
+    fn conditional_regex_matches_pattern() {
+        let sku = || BlockInput::String("SKU-12345".into());
+        assert_eq!(branch(RuleKind::Regex, r"^SKU-\d{5}$", sku()), "then");
+        assert_eq!(branch(RuleKind::Regex, r"^ISBN-", sku()), "else");
+    }
+
+    #[test]
+    fn conditional_invalid_regex_fails_validation() {
+        let block = ConditionalBlock::new(ConditionalConfig::new(RuleKind::Regex, "(unclosed"));
+        let ctx = ValidateContext::new(InputContract::One(ValueKindSet::singleton(
+            ValueKind::String,
+        )));
+        assert!(block.validate_linkage(&ctx).is_err());
+        let valid = ConditionalBlock::new(ConditionalConfig::new(RuleKind::Regex, "ok"));
+        assert!(valid.validate_linkage(&ctx).is_ok());
+    }
+}
@@ -1,4 +1,9 @@
 //! Cron block: fires on a schedule and produces a stream of outputs (Recurring).
+//! Each tick is `{"scheduled_at": "<rfc3339>", "tick": n}` with the fire time rendered in
+//! `timezone` (`UTC` by default, `Local`, an IANA zone like `Europe/Paris`, or a fixed offset
+//! like `+05:30`) and `tick` counting
+//! from 1. `max_ticks` (at least 1) and `until_rfc3339` bound the stream: once either is reached
+//! [`CronRunner::run_bounded`] closes the channel and the recurring run completes.
 //! Pass your runner when registering: `register_cron(registry, Arc::new(your_runner))`.
 
 use std::sync::Arc;
@@ -23,25 +28,167 @@ impl std::fmt::Display for CronError {
 
 impl std::error::Error for CronError {}
 
-/// Cron runner abstraction: start a schedule and return a receiver of outputs.
-/// Implement and pass when registering.
+/// Cron runner abstraction: start a schedule and return a receiver of tick outputs (see
+/// [`tick_output`]). Implement and pass when registering.
 pub trait CronRunner: Send + Sync {
+    /// Start `cron_expr`; ticks flow until the receiver is dropped.
     fn run(&self, cron_expr: &str) -> Result<mpsc::Receiver<BlockOutput>, CronError>;
+
+    /// Start `config`'s schedule and close the channel once its `max_ticks` or `until_rfc3339`
+    /// is reached. The default forwards [`run`](CronRunner::run)'s ticks and checks `until`
+    /// against the clock when each arrives; override it to stop exactly on the schedule.
+    fn run_bounded(&self, config: &CronConfig) -> Result<mpsc::Receiver<BlockOutput>, CronError> {
+        let mut ticks = self.run(&config.cron)?;
+        let (max_ticks, until) = (config.max_ticks, config.until()?);
+        if max_ticks.is_none() && until.is_none() {
+            return Ok(ticks);
+        }
+        let rt = tokio::runtime::Handle::try_current()
+            .map_err(|e| CronError(format!("cron needs a tokio runtime: {}", e)))?;
+        let (tx, rx) = mpsc::channel(64);
+        rt.spawn(async move {
+            let mut sent = 0u64;
+            while max_ticks.is_none_or(|max| sent < max) {
+                let Some(out) = ticks.recv().await else {
+                    break;
+                };
+                if until.is_some_and(|until| chrono::Utc::now() > until)
+                    || tx.send(out).await.is_err()
+                {
+                    break;
+                }
+                sent += 1;
+            }
+        });
+        Ok(rx)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CronConfig {
     pub cron: String,
+    /// Stop after this many ticks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_ticks: Option<u64>,
+    /// Stop at this wall-clock time (RFC 3339, e.g. `2026-01-31T18:00:00Z`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until_rfc3339: Option<String>,
+    /// Zone `scheduled_at` is rendered in: `UTC` (default), `Local`, an IANA zone like
+    /// `Europe/Paris`, or an offset like `+05:30`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
 }
 
 impl CronConfig {
     pub fn new(cron: impl Into<String>) -> Self {
         Self {
             cron: cron.into().trim().to_string(),
+            max_ticks: None,
+            until_rfc3339: None,
+            timezone: None,
+        }
+    }
+
+    /// Stop after `max_ticks` ticks. Fails when `max_ticks` is zero.
+    pub fn with_max_ticks(mut self, max_ticks: u64) -> Result<Self, CronError> {
+        self.max_ticks = Some(max_ticks);
+        self.check_max_ticks()?;
+        Ok(self)
+    }
+
+    fn check_max_ticks(&self) -> Result<(), CronError> {
+        if self.max_ticks == Some(0) {
+            return Err(CronError("max_ticks must be at least 1".into()));
+        }
+        Ok(())
+    }
+
+    /// Stop at `until` (RFC 3339). Fails when `until` does not parse.
+    pub fn with_until(mut self, until: impl Into<String>) -> Result<Self, CronError> {
+        self.until_rfc3339 = Some(until.into());
+        self.until()?;
+        Ok(self)
+    }
+
+    /// Render `scheduled_at` in `timezone`. Fails when `timezone` is not supported.
+    pub fn with_timezone(mut self, timezone: impl Into<String>) -> Result<Self, CronError> {
+        self.timezone = Some(timezone.into());
+        self.zone()?;
+        Ok(self)
+    }
+
+    /// The zone ticks are rendered in. Fails when `timezone` is not supported.
+    fn zone(&self) -> Result<TickZone, CronError> {
+        let Some(tz) = self.timezone.as_deref().map(str::trim) else {
+            return Ok(TickZone::Utc);
+        };
+        if tz.eq_ignore_ascii_case("utc") || tz == "Z" {
+            return Ok(TickZone::Utc);
         }
+        if tz.eq_ignore_ascii_case("local") {
+            return Ok(TickZone::Local);
+        }
+        if let Ok(named) = tz.parse::<chrono_tz::Tz>() {
+            return Ok(TickZone::Named(named));
+        }
+        chrono::DateTime::parse_from_rfc3339(&format!("2000-01-01T00:00:00{}", tz))
+            .map(|t| TickZone::Fixed(*t.offset()))
+            .map_err(|_| {
+                CronError(format!(
+                    "unsupported timezone {:?}: use UTC, Local, an IANA zone like Europe/Paris \
+                     or an offset like +05:30",
+                    tz
+                ))
+            })
+    }
+
+    /// The parsed `until_rfc3339` deadline, if any.
+    pub fn until(&self) -> Result<Option<chrono::DateTime<chrono::Utc>>, CronError> {
+        self.until_rfc3339
+            .as_deref()
+            .map(|until| {
+                chrono::DateTime::parse_from_rfc3339(until.trim())
+                    .map(|t| t.with_timezone(&chrono::Utc))
+                    .map_err(|e| CronError(format!("invalid until_rfc3339 {:?}: {}", until, e)))
+            })
+            .transpose()
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+enum TickZone {
+    Utc,
+    Local,
+    Named(chrono_tz::Tz),
+    Fixed(chrono::FixedOffset),
+}
+
+impl TickZone {
+    fn render(self, at: chrono::DateTime<chrono::Utc>) -> String {
+        match self {
+            TickZone::Utc => at.to_rfc3339(),
+            TickZone::Local => at.with_timezone(&chrono::Local).to_rfc3339(),
+            TickZone::Named(tz) => at.with_timezone(&tz).to_rfc3339(),
+            TickZone::Fixed(offset) => at.with_timezone(&offset).to_rfc3339(),
+        }
+    }
+}
+
+/// Output for tick number `tick` (from 1) scheduled at `scheduled_at`, rendered in the config's
+/// timezone.
+pub fn tick_output(
+    config: &CronConfig,
+    scheduled_at: chrono::DateTime<chrono::Utc>,
+    tick: u64,
+) -> Result<BlockOutput, CronError> {
+    Ok(BlockOutput::Json {
+        value: serde_json::json!({
+            "scheduled_at": config.zone()?.render(scheduled_at),
+            "tick": tick,
+        }),
+    })
+}
+
 pub struct CronBlock {
     config: CronConfig,
     runner: Arc<dyn CronRunner>,
@@ -57,13 +204,13 @@ impl BlockExecutor for CronBlock {
     fn execute(&self, _ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
         let rx = self
             .runner
-            .run(&self.config.cron)
+            .run_bounded(&self.config)
             .map_err(|e| BlockError::Other(e.0))?;
         Ok(BlockExecutionResult::Recurring(rx))
     }
 
     fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
-        OutputContract::from_kind(ValueKind::Text, OutputMode::Recurring)
+        OutputContract::from_kind(ValueKind::Json, OutputMode::Recurring)
     }
 }
 
@@ -72,13 +219,22 @@ pub struct StdCronRunner;
 
 impl CronRunner for StdCronRunner {
     fn run(&self, cron_expr: &str) -> Result<mpsc::Receiver<BlockOutput>, CronError> {
+        self.run_bounded(&CronConfig::new(cron_expr))
+    }
+
+    fn run_bounded(&self, config: &CronConfig) -> Result<mpsc::Receiver<BlockOutput>, CronError> {
         use std::str::FromStr;
         use std::time::Duration;
 
         use chrono::Utc;
         use cron::Schedule;
 
-        let cron_expr = cron_expr.trim();
+        config.check_max_ticks()?;
+        let until = config.until()?;
+        config.zone()?;
+        let max_ticks = config.max_ticks;
+        let config = config.clone();
+        let cron_expr = config.cron.trim();
         // Cron 0.15 expects 7 fields: sec min hour day month day_of_week year. Normalize 5-field to 7.
         let cron_expr = if cron_expr.split_whitespace().count() == 5 {
             format!("0 {} *", cron_expr)
@@ -95,25 +251,30 @@ impl CronRunner for StdCronRunner {
                 Ok(s) => s,
                 Err(_) => return,
             };
+            let mut ticks = 0u64;
             loop {
+                if max_ticks.is_some_and(|max| ticks >= max) {
+                    break;
+                }
                 let now = Utc::now();
-                println!("now: {}", now);
                 let next_run = match sched.upcoming(Utc).next() {
                     Some(t) => t,
                     None => break,
                 };
-                println!("next_run: {}", next_run);
+                if until.is_some_and(|until| next_run > until) {
+                    break;
+                }
                 let duration = match (next_run - now).to_std() {
                     Ok(d) => d,
                     Err(_) => break,
                 };
-                println!("duration: {}ms", &duration.as_millis());
                 if duration > Duration::ZERO {
                     std::thread::sleep(duration);
                 }
-                let value = Utc::now().to_rfc3339();
-                println!("value: {}", value);
-                let out = BlockOutput::Text { value };
+                ticks += 1;
+                let Ok(out) = tick_output(&config, next_run, ticks) else {
+                    break;
+                };
                 if rt.block_on(tx.send(out)).is_err() {
                     break;
                 }
@@ -133,25 +294,19 @@ pub fn register_cron(
         let mut config: CronConfig =
             serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
         config.cron = config.cron.trim().to_string();
+        config
+            .check_max_ticks()
+            .map_err(|e| BlockError::Other(e.0))?;
+        config.until().map_err(|e| BlockError::Other(e.0))?;
+        config.zone().map_err(|e| BlockError::Other(e.0))?;
         Ok(Box::new(CronBlock::new(config, Arc::clone(&runner))))
     });
 }
 
-#[cfg(test)]
-fn test_ctx(input: orchestrator_core::block::BlockInput) -> BlockExecutionContext {
-    BlockExecutionContext {
-        workflow_id: uuid::Uuid::new_v4(),
-        run_id: uuid::Uuid::new_v4(),
-        block_id: uuid::Uuid::new_v4(),
-        attempt: 1,
-        prev: input,
-        store: Default::default(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_ctx;
 
     #[test]
     fn cron_config_invalid_fails_at_execute() {
@@ -162,22 +317,141 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn cron_block_returns_recurring_receiver() {
-        let config = CronConfig::new("* * * * * * *");
+    async fn cron_block_emits_scheduled_at_and_tick() {
+        let config = CronConfig::new("* * * * * * *")
+            .with_timezone("+05:30")
+            .unwrap();
         let block = CronBlock::new(config, Arc::new(StdCronRunner));
         let result = block
             .execute(test_ctx(orchestrator_core::block::BlockInput::empty()))
             .unwrap();
         match result {
             BlockExecutionResult::Recurring(mut rx) => {
-                let first = rx.recv().await.unwrap();
-                match &first {
-                    BlockOutput::Text { value } => assert!(value.contains('T')),
-                    _ => panic!("expected Text output"),
+                let mut previous: Option<chrono::DateTime<chrono::FixedOffset>> = None;
+                for expected_tick in 1..=2 {
+                    let BlockOutput::Json { value } = rx.recv().await.unwrap() else {
+                        panic!("expected Json output");
+                    };
+                    assert_eq!(value["tick"], expected_tick);
+                    let at = value["scheduled_at"].as_str().unwrap();
+                    assert!(at.ends_with("+05:30"), "got {at}");
+                    let at = chrono::DateTime::parse_from_rfc3339(at).unwrap();
+                    assert!(previous.is_none_or(|p| p < at));
+                    previous = Some(at);
                 }
             }
             BlockExecutionResult::Once(_) => panic!("expected Recurring"),
             BlockExecutionResult::Multiple(_) => panic!("expected Recurring"),
+            BlockExecutionResult::Labeled(_) => panic!("expected Recurring"),
+            BlockExecutionResult::Routed { .. } => panic!("expected Recurring"),
+        }
+    }
+
+    #[test]
+    fn cron_config_accepts_named_timezone() {
+        let config = CronConfig::new("* * * * * * *")
+            .with_timezone("Europe/Paris")
+            .unwrap();
+        let winter = chrono::DateTime::parse_from_rfc3339("2026-01-15T12:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let summer = chrono::DateTime::parse_from_rfc3339("2026-07-15T12:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let rendered = |at| match tick_output(&config, at, 1).unwrap() {
+            BlockOutput::Json { value } => value["scheduled_at"].as_str().unwrap().to_string(),
+            other => panic!("expected Json output, got {:?}", other),
+        };
+        assert_eq!(rendered(winter), "2026-01-15T13:00:00+01:00");
+        assert_eq!(rendered(summer), "2026-07-15T14:00:00+02:00");
+        assert!(
+            CronConfig::new("* * * * * * *")
+                .with_timezone("Local")
+                .is_ok()
+        );
+        assert!(
+            CronConfig::new("* * * * * * *")
+                .with_timezone("Mars/Olympus")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn cron_config_rejects_bad_until() {
+        assert!(
+            CronConfig::new("* * * * * * *")
+                .with_until("tomorrow")
+                .is_err()
+        );
+        let config = CronConfig::new("* * * * * * *")
+            .with_until("2026-01-31T18:00:00+01:00")
+            .unwrap();
+        assert_eq!(
+            config.until().unwrap().unwrap().to_rfc3339(),
+            "2026-01-31T17:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn cron_config_rejects_zero_max_ticks() {
+        assert!(CronConfig::new("* * * * * * *").with_max_ticks(0).is_err());
+        assert_eq!(
+            CronConfig::new("* * * * * * *")
+                .with_max_ticks(3)
+                .unwrap()
+                .max_ticks,
+            Some(3)
+        );
+        let mut w = crate::new_workflow();
+        w.add(crate::Block::cron("* * * * * * *").set_cron_max_ticks(0));
+        assert!(w.run().is_err());
+    }
+
+    /// Emits a tick every millisecond forever, like a runner written before bounds existed.
+    struct EndlessRunner;
+
+    impl CronRunner for EndlessRunner {
+        fn run(&self, _cron_expr: &str) -> Result<mpsc::Receiver<BlockOutput>, CronError> {
+            let (tx, rx) = mpsc::channel(1);
+            tokio::runtime::Handle::current().spawn(async move {
+                let mut tick = 0u64;
+                loop {
+                    tick += 1;
+                    let out = BlockOutput::Json {
+                        value: serde_json::json!({ "tick": tick }),
+                    };
+                    if tx.send(out).await.is_err() {
+                        break;
+                    }
+                    tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+                }
+            });
+            Ok(rx)
+        }
+    }
+
+    #[tokio::test]
+    async fn default_run_bounded_stops_a_plain_runner_after_max_ticks() {
+        let config = CronConfig::new("* * * * * * *").with_max_ticks(3).unwrap();
+        let mut rx = EndlessRunner.run_bounded(&config).unwrap();
+        let mut ticks = Vec::new();
+        while let Some(BlockOutput::Json { value }) = rx.recv().await {
+            ticks.push(value["tick"].as_u64().unwrap());
+        }
+        assert_eq!(ticks, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn cron_max_ticks_ends_the_run_after_that_many_outputs() {
+        let mut w = crate::new_workflow();
+        let cron = w.add(crate::Block::cron("* * * * * * *").set_cron_max_ticks(2));
+        let sink = w.add(crate::Block::custom_transform(None::<String>));
+        w.link(cron, sink);
+        match w.run_collect_ticks().unwrap() {
+            BlockOutput::Json {
+                value: serde_json::Value::Array(ticks),
+            } => assert_eq!(ticks.len(), 2),
+            other => panic!("expected collected ticks, got {:?}", other),
         }
     }
 }
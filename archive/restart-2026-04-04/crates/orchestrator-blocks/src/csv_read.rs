@@ -0,0 +1,276 @@
+//! CsvRead block: read a CSV file into a JSON array of objects using an injected reader.
+//! Rows are keyed by header, or by column index (`"0"`, `"1"`, ...) when `has_headers` is false;
+//! values stay strings. The path follows `file_read` precedence: forced input sources, then the
+//! config path, then a path string (or `{"path": ...}`) from the previous block.
+//! Pass your reader when registering: `register_csv_read(registry, Arc::new(your_reader))`.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::input_binding::{
+    path_from_input, resolve_effective_input, validate_expected_input, validate_single_input_mode,
+};
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
+};
+
+/// Error from CSV read operations.
+#[derive(Debug, Clone)]
+pub struct CsvReadError(pub String);
+
+impl std::fmt::Display for CsvReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for CsvReadError {}
+
+/// CSV reader abstraction. Implement and pass when registering.
+pub trait CsvReader: Send + Sync {
+    /// One JSON object per data row.
+    fn read(
+        &self,
+        path: &Path,
+        delimiter: u8,
+        has_headers: bool,
+    ) -> Result<Vec<serde_json::Value>, CsvReadError>;
+}
+
+fn default_has_headers() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CsvReadConfig {
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Field separator; `,` when unset. Must be a single ASCII character.
+    #[serde(default)]
+    pub delimiter: Option<char>,
+    /// First row names the columns.
+    #[serde(default = "default_has_headers")]
+    pub has_headers: bool,
+}
+
+impl CsvReadConfig {
+    pub fn new(path: Option<impl Into<String>>) -> Self {
+        Self {
+            path: path.map(Into::into),
+            delimiter: None,
+            has_headers: default_has_headers(),
+        }
+    }
+
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = Some(delimiter);
+        self
+    }
+
+    pub fn with_has_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    fn delimiter_byte(&self) -> Result<u8, BlockError> {
+        match self.delimiter {
+            None => Ok(b','),
+            Some(c) if c.is_ascii() => Ok(c as u8),
+            Some(c) => Err(BlockError::Other(format!(
+                "csv_read delimiter must be ASCII, got {:?}",
+                c
+            ))),
+        }
+    }
+}
+
+pub struct CsvReadBlock {
+    config: CsvReadConfig,
+    reader: Arc<dyn CsvReader>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl CsvReadBlock {
+    pub fn new(config: CsvReadConfig, reader: Arc<dyn CsvReader>) -> Self {
+        Self {
+            config,
+            reader,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+impl BlockExecutor for CsvReadBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        if let BlockInput::Error { message } = &input {
+            return Err(BlockError::Other(message.clone()));
+        }
+        let path = if !self.input_from.is_empty() {
+            path_from_input(&input).ok_or_else(|| {
+                BlockError::Other("path required from forced input sources".into())
+            })?
+        } else if let Some(path) = &self.config.path {
+            PathBuf::from(path)
+        } else {
+            path_from_input(&input).ok_or_else(|| {
+                BlockError::Other("path required from previous input or block config".into())
+            })?
+        };
+        let rows = self
+            .reader
+            .read(
+                &path,
+                self.config.delimiter_byte()?,
+                self.config.has_headers,
+            )
+            .map_err(|e| BlockError::Other(e.0))?;
+        Ok(BlockExecutionResult::Once(BlockOutput::Json {
+            value: serde_json::Value::Array(rows),
+        }))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::Json, OutputMode::Once)
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        self.config.delimiter_byte()?;
+        if self.input_from.is_empty() && self.config.path.is_some() {
+            return Ok(());
+        }
+        validate_single_input_mode(ctx)?;
+        validate_expected_input(
+            ctx,
+            ValueKindSet::singleton(ValueKind::String)
+                | ValueKindSet::singleton(ValueKind::Text)
+                | ValueKindSet::singleton(ValueKind::Json),
+        )
+    }
+}
+
+/// Default implementation using the `csv` crate.
+pub struct StdCsvReader;
+
+impl CsvReader for StdCsvReader {
+    fn read(
+        &self,
+        path: &Path,
+        delimiter: u8,
+        has_headers: bool,
+    ) -> Result<Vec<serde_json::Value>, CsvReadError> {
+        let err = |e: csv::Error| CsvReadError(format!("{}: {}", path.display(), e));
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .has_headers(has_headers)
+            .flexible(true)
+            .from_path(path)
+            .map_err(err)?;
+        let headers: Vec<String> = if has_headers {
+            reader
+                .headers()
+                .map_err(err)?
+                .iter()
+                .map(|h| h.trim().to_string())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            let record = record.map_err(err)?;
+            let row: serde_json::Map<String, serde_json::Value> = record
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    let key = headers.get(i).cloned().unwrap_or_else(|| i.to_string());
+                    (key, serde_json::Value::String(field.to_string()))
+                })
+                .collect();
+            rows.push(serde_json::Value::Object(row));
+        }
+        Ok(rows)
+    }
+}
+
+/// Register the csv_read block with a reader.
+pub fn register_csv_read(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    reader: Arc<dyn CsvReader>,
+) {
+    let reader = Arc::clone(&reader);
+    registry.register_custom("csv_read", move |payload, input_from| {
+        let config: CsvReadConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            CsvReadBlock::new(config, Arc::clone(&reader)).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use serde_json::json;
+
+    #[test]
+    fn csv_read_keys_rows_by_header() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("expenses.csv");
+        std::fs::write(
+            &path,
+            "date,category,amount\n2026-01-03,food,12.50\n2026-01-04,\"rent, flat\",900\n",
+        )
+        .unwrap();
+        let block = CsvReadBlock::new(CsvReadConfig::new(None::<String>), Arc::new(StdCsvReader));
+        let out = block
+            .execute(test_ctx(BlockInput::String(
+                path.to_string_lossy().into_owned(),
+            )))
+            .unwrap()
+            .into_once();
+        assert_eq!(
+            out,
+            BlockOutput::Json {
+                value: json!([
+                    { "date": "2026-01-03", "category": "food", "amount": "12.50" },
+                    { "date": "2026-01-04", "category": "rent, flat", "amount": "900" },
+                ])
+            }
+        );
+    }
+
+    #[test]
+    fn csv_read_without_headers_keys_by_column_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("prices.csv");
+        std::fs::write(&path, "AAPL;187.2\nMSFT;402.1\n").unwrap();
+        let config = CsvReadConfig::new(Some(path.to_string_lossy()))
+            .with_delimiter(';')
+            .with_has_headers(false);
+        let out = CsvReadBlock::new(config, Arc::new(StdCsvReader))
+            .execute(test_ctx(BlockInput::String(
+                "/ignored/by/config.csv".into(),
+            )))
+            .unwrap()
+            .into_once();
+        assert_eq!(
+            out,
+            BlockOutput::Json {
+                value: json!([
+                    { "0": "AAPL", "1": "187.2" },
+                    { "0": "MSFT", "1": "402.1" },
+                ])
+            }
+        );
+    }
+}
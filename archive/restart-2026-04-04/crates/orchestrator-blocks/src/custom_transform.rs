@@ -94,6 +94,9 @@ impl Transform for IdentityTransform {
             BlockInput::Multi { outputs } => BlockOutput::Json {
                 value: serde_json::to_value(&outputs).unwrap_or(serde_json::Value::Null),
             },
+            BlockInput::MultiNamed { outputs } => BlockOutput::Json {
+                value: serde_json::to_value(&outputs).unwrap_or(serde_json::Value::Null),
+            },
             BlockInput::Error { message } => return Err(CustomTransformError(message)),
         };
         Ok(output)
@@ -115,21 +118,10 @@ pub fn register_custom_transform(
     });
 }
 
-#[cfg(test)]
-fn test_ctx(input: BlockInput) -> BlockExecutionContext {
-    BlockExecutionContext {
-        workflow_id: uuid::Uuid::new_v4(),
-        run_id: uuid::Uuid::new_v4(),
-        block_id: uuid::Uuid::new_v4(),
-        attempt: 1,
-        prev: input,
-        store: Default::default(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_ctx;
 
     #[test]
     fn custom_transform_passthrough_string() {
@@ -0,0 +1,391 @@
+//! Dedupe block: Transform that drops JSON array items whose key was seen in an earlier run.
+//! Seen keys live in a JSONL state file (`{"key": ...}` per line); new keys are appended after filtering.
+//! Without a state file only repeats within the input are dropped. When nothing is left the block
+//! fails with the `{"kind":"no_new_items",...}` envelope, so a Recurring run skips the tick.
+//! Pass your seen-set store when registering: `register_dedupe(registry, Arc::new(your_store))`.
+
+use std::collections::HashSet;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::input_binding::{
+    resolve_effective_input, validate_expected_input, validate_single_input_mode,
+};
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
+};
+
+/// Error from dedupe operations.
+#[derive(Debug, Clone)]
+pub struct DedupeError(pub String);
+
+impl std::fmt::Display for DedupeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for DedupeError {}
+
+/// Seen-set store abstraction. Implement and pass when registering.
+pub trait SeenStore: Send + Sync {
+    /// Record `keys` in the set at `path` and return, per key, whether it was new.
+    /// Must be atomic with respect to other callers using the same `path`.
+    fn record_new(&self, path: &str, keys: &[String]) -> Result<Vec<bool>, DedupeError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DedupeConfig {
+    /// Field identifying an item; dots address nested objects (e.g. `meta.url`).
+    #[serde(alias = "key")]
+    pub key_field: String,
+    /// Seen-set state file; `None` only drops repeats within one input.
+    #[serde(default)]
+    pub state_path: Option<String>,
+}
+
+impl DedupeConfig {
+    pub fn new(key_field: impl Into<String>, state_path: impl Into<String>) -> Self {
+        Self {
+            key_field: key_field.into(),
+            state_path: Some(state_path.into()),
+        }
+    }
+
+    /// Drop repeats within each input without persisting seen keys.
+    pub fn within_input(key_field: impl Into<String>) -> Self {
+        Self {
+            key_field: key_field.into(),
+            state_path: None,
+        }
+    }
+}
+
+pub struct DedupeBlock {
+    config: DedupeConfig,
+    store: Arc<dyn SeenStore>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl DedupeBlock {
+    pub fn new(config: DedupeConfig, store: Arc<dyn SeenStore>) -> Self {
+        Self {
+            config,
+            store,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+/// Key for an item: the string form of `key_field`, or the item's JSON text when it is missing.
+fn item_key(item: &serde_json::Value, key_field: &str) -> String {
+    let field = key_field
+        .split('.')
+        .try_fold(item, |current, segment| current.get(segment));
+    match field {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(serde_json::Value::Null) | None => item.to_string(),
+        Some(other) => other.to_string(),
+    }
+}
+
+impl BlockExecutor for DedupeBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        if self.config.key_field.trim().is_empty() {
+            return Err(BlockError::Other("dedupe requires a key_field".into()));
+        }
+        if self
+            .config
+            .state_path
+            .as_ref()
+            .is_some_and(|path| path.trim().is_empty())
+        {
+            return Err(BlockError::Other(
+                "dedupe state_path must not be empty".into(),
+            ));
+        }
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        let value = match input {
+            BlockInput::Json(v) => v,
+            BlockInput::String(s) | BlockInput::Text(s) => {
+                serde_json::from_str(&s).map_err(|e| BlockError::Other(e.to_string()))?
+            }
+            BlockInput::Error { message } => return Err(BlockError::Other(message)),
+            BlockInput::Empty
+            | BlockInput::List { .. }
+            | BlockInput::Multi { .. }
+            | BlockInput::MultiNamed { .. } => {
+                return Err(BlockError::Other("dedupe expects a JSON array".into()));
+            }
+        };
+        let serde_json::Value::Array(items) = value else {
+            return Err(BlockError::Other("dedupe expects a JSON array".into()));
+        };
+
+        let keys: Vec<String> = items
+            .iter()
+            .map(|item| item_key(item, &self.config.key_field))
+            .collect();
+        let is_new = match &self.config.state_path {
+            Some(path) => self
+                .store
+                .record_new(path, &keys)
+                .map_err(|e| BlockError::Other(e.0))?,
+            None => {
+                let mut seen = HashSet::new();
+                keys.iter().map(|key| seen.insert(key.as_str())).collect()
+            }
+        };
+        let total_count = items.len();
+        let filtered: Vec<serde_json::Value> = items
+            .into_iter()
+            .zip(is_new)
+            .filter_map(|(item, new)| new.then_some(item))
+            .collect();
+        if filtered.is_empty() {
+            return Err(BlockError::Other(
+                serde_json::json!({
+                    "kind": "no_new_items",
+                    "total_count": total_count,
+                    "skipped_count": total_count
+                })
+                .to_string(),
+            ));
+        }
+        Ok(BlockExecutionResult::Once(BlockOutput::Json {
+            value: serde_json::Value::Array(filtered),
+        }))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::Json, OutputMode::Once)
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        validate_single_input_mode(ctx)?;
+        validate_expected_input(
+            ctx,
+            ValueKindSet::singleton(ValueKind::String)
+                | ValueKindSet::singleton(ValueKind::Text)
+                | ValueKindSet::singleton(ValueKind::Json),
+        )
+    }
+}
+
+/// Default implementation: JSONL file guarded by an exclusive file lock.
+/// Also reads `{"id": ...}` lines and bare lines, so existing `sent_items.jsonl` files work.
+pub struct FileSeenStore;
+
+fn parse_seen_line(line: &str) -> Option<String> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+    if let Ok(v) = serde_json::from_str::<serde_json::Value>(line)
+        && let Some(key) = ["key", "id"]
+            .iter()
+            .find_map(|name| v.get(name).and_then(|k| k.as_str()))
+    {
+        return Some(key.to_string());
+    }
+    Some(line.to_string())
+}
+
+impl SeenStore for FileSeenStore {
+    fn record_new(&self, path: &str, keys: &[String]) -> Result<Vec<bool>, DedupeError> {
+        let path = Path::new(path);
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| DedupeError(e.to_string()))?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)
+            .map_err(|e| DedupeError(format!("open {}: {}", path.display(), e)))?;
+        file.lock()
+            .map_err(|e| DedupeError(format!("lock {}: {}", path.display(), e)))?;
+
+        let mut content = String::new();
+        file.seek(SeekFrom::Start(0))
+            .and_then(|_| file.read_to_string(&mut content))
+            .map_err(|e| DedupeError(e.to_string()))?;
+        let mut seen: HashSet<String> = content.lines().filter_map(parse_seen_line).collect();
+
+        let mut appended = String::new();
+        if !content.is_empty() && !content.ends_with('\n') {
+            appended.push('\n');
+        }
+        let is_new: Vec<bool> = keys
+            .iter()
+            .map(|key| {
+                let new = seen.insert(key.clone());
+                if new {
+                    appended.push_str(&serde_json::json!({ "key": key }).to_string());
+                    appended.push('\n');
+                }
+                new
+            })
+            .collect();
+        if is_new.iter().any(|new| *new) {
+            file.write_all(appended.as_bytes())
+                .and_then(|_| file.flush())
+                .map_err(|e| DedupeError(e.to_string()))?;
+        }
+        Ok(is_new)
+    }
+}
+
+/// Register the dedupe block with a seen-set store.
+pub fn register_dedupe(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    store: Arc<dyn SeenStore>,
+) {
+    let store = Arc::clone(&store);
+    registry.register_custom("dedupe", move |payload, input_from| {
+        let config: DedupeConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            DedupeBlock::new(config, Arc::clone(&store)).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use serde_json::json;
+
+    fn dedupe(state_path: &Path, items: serde_json::Value) -> serde_json::Value {
+        let block = DedupeBlock::new(
+            DedupeConfig::new("link.url", state_path.to_string_lossy()),
+            Arc::new(FileSeenStore),
+        );
+        match block.execute(test_ctx(BlockInput::Json(items))).unwrap() {
+            BlockExecutionResult::Once(BlockOutput::Json { value }) => value,
+            _ => panic!("expected Once(Json)"),
+        }
+    }
+
+    #[test]
+    fn dedupe_second_run_filters_previously_seen_items() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = dir.path().join("state").join("seen.jsonl");
+        let a = json!({ "title": "a", "link": { "url": "https://example.com/a" } });
+        let b = json!({ "title": "b", "link": { "url": "https://example.com/b" } });
+        let c = json!({ "title": "c", "link": { "url": "https://example.com/c" } });
+
+        let first = dedupe(&state, json!([a, b, a]));
+        assert_eq!(first, json!([a, b]));
+
+        let second = dedupe(&state, json!([b, c, a]));
+        assert_eq!(second, json!([c]));
+
+        let lines: Vec<String> = std::fs::read_to_string(&state)
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[2], r#"{"key":"https://example.com/c"}"#);
+    }
+
+    #[derive(Default)]
+    struct MemorySeenStore(std::sync::Mutex<HashSet<(String, String)>>);
+
+    impl SeenStore for MemorySeenStore {
+        fn record_new(&self, path: &str, keys: &[String]) -> Result<Vec<bool>, DedupeError> {
+            let mut seen = self.0.lock().unwrap();
+            Ok(keys
+                .iter()
+                .map(|key| seen.insert((path.to_string(), key.clone())))
+                .collect())
+        }
+    }
+
+    #[test]
+    fn dedupe_all_seen_returns_no_new_items_envelope() {
+        let store = Arc::new(MemorySeenStore::default());
+        let config: DedupeConfig =
+            serde_json::from_value(json!({ "key": "id", "state_path": "seen" })).unwrap();
+        let block = DedupeBlock::new(config, store);
+        let items = json!([{ "id": 1 }, { "id": 2 }]);
+
+        let first = block
+            .execute(test_ctx(BlockInput::Json(items.clone())))
+            .unwrap()
+            .into_once();
+        assert_eq!(
+            first,
+            BlockOutput::Json {
+                value: items.clone()
+            }
+        );
+
+        let Err(BlockError::Other(message)) = block.execute(test_ctx(BlockInput::Json(items)))
+        else {
+            panic!("expected no_new_items error");
+        };
+        let envelope: serde_json::Value = serde_json::from_str(&message).unwrap();
+        assert_eq!(
+            envelope,
+            json!({ "kind": "no_new_items", "total_count": 2, "skipped_count": 2 })
+        );
+    }
+
+    #[test]
+    fn dedupe_without_state_path_drops_repeats_within_input() {
+        let block = DedupeBlock::new(
+            DedupeConfig::within_input("id"),
+            Arc::new(MemorySeenStore::default()),
+        );
+        let input = || BlockInput::Json(json!([{ "id": 1 }, { "id": 1 }, { "id": 2 }]));
+        for _ in 0..2 {
+            let out = block.execute(test_ctx(input())).unwrap().into_once();
+            assert_eq!(
+                out,
+                BlockOutput::Json {
+                    value: json!([{ "id": 1 }, { "id": 2 }])
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn dedupe_reads_existing_id_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = dir.path().join("sent_items.jsonl");
+        std::fs::write(&state, "{\"id\":\"7\"}\nlegacy").unwrap();
+        let block = DedupeBlock::new(
+            DedupeConfig::new("id", state.to_string_lossy()),
+            Arc::new(FileSeenStore),
+        );
+        let out = block
+            .execute(test_ctx(BlockInput::Json(
+                json!([{ "id": 7 }, { "id": "legacy" }, { "id": 8 }]),
+            )))
+            .unwrap()
+            .into_once();
+        assert_eq!(
+            out,
+            BlockOutput::Json {
+                value: json!([{ "id": 8 }])
+            }
+        );
+        assert!(
+            std::fs::read_to_string(&state)
+                .unwrap()
+                .ends_with("legacy\n{\"key\":\"8\"}\n")
+        );
+    }
+}
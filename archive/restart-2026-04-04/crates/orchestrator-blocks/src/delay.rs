@@ -0,0 +1,161 @@
+//! Delay block: Control block that waits `delay_ms`, then forwards its input unchanged
+//! (`forward_input`, the default) or emits `BlockOutput::Empty`. Useful to space out side effects
+//! or give an external system time to settle before the next step.
+//! Register with: `register_delay(registry)`.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::input_binding::{resolve_effective_input, validate_single_input_mode};
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, InputContract, OutputContract, OutputMode, ValidateContext, ValueKind,
+    ValueKindSet,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DelayConfig {
+    pub delay_ms: u64,
+    #[serde(default = "default_forward_input")]
+    pub forward_input: bool,
+}
+
+fn default_forward_input() -> bool {
+    true
+}
+
+impl DelayConfig {
+    pub fn new(delay_ms: u64) -> Self {
+        Self {
+            delay_ms,
+            forward_input: default_forward_input(),
+        }
+    }
+
+    pub fn with_forward_input(mut self, forward_input: bool) -> Self {
+        self.forward_input = forward_input;
+        self
+    }
+}
+
+fn input_to_output(input: BlockInput) -> Result<BlockOutput, BlockError> {
+    match input {
+        BlockInput::Empty => Ok(BlockOutput::Empty),
+        BlockInput::String(value) => Ok(BlockOutput::String { value }),
+        BlockInput::Text(value) => Ok(BlockOutput::Text { value }),
+        BlockInput::Json(value) => Ok(BlockOutput::Json { value }),
+        BlockInput::List { items } => Ok(BlockOutput::List { items }),
+        BlockInput::Error { message } => Err(BlockError::Other(message)),
+        BlockInput::Multi { .. } | BlockInput::MultiNamed { .. } => Err(BlockError::Other(
+            "delay forwards a single input, not Multi".into(),
+        )),
+    }
+}
+
+pub struct DelayBlock {
+    config: DelayConfig,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl DelayBlock {
+    pub fn new(config: DelayConfig) -> Self {
+        Self {
+            config,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+impl BlockExecutor for DelayBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        if let BlockInput::Error { message } = &input {
+            return Err(BlockError::Other(message.clone()));
+        }
+        std::thread::sleep(Duration::from_millis(self.config.delay_ms));
+        let output = if self.config.forward_input {
+            input_to_output(input)?
+        } else {
+            BlockOutput::Empty
+        };
+        Ok(BlockExecutionResult::Once(output))
+    }
+
+    fn infer_output_contract(&self, ctx: &ValidateContext<'_>) -> OutputContract {
+        let kinds = match &ctx.prev {
+            InputContract::One(kinds) if self.config.forward_input => *kinds,
+            _ => ValueKindSet::singleton(ValueKind::Empty),
+        };
+        OutputContract {
+            kinds,
+            mode: OutputMode::Once,
+        }
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        validate_single_input_mode(ctx)
+    }
+}
+
+/// Register the delay block.
+pub fn register_delay(registry: &mut orchestrator_core::block::BlockRegistry) {
+    registry.register_custom("delay", |payload, input_from| {
+        let config: DelayConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            DelayBlock::new(config).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use serde_json::json;
+    use std::time::Instant;
+
+    #[test]
+    fn delay_waits_then_forwards_or_drops_input() {
+        let started = Instant::now();
+        let out = DelayBlock::new(DelayConfig::new(20))
+            .execute(test_ctx(BlockInput::Json(json!({"id": 7}))))
+            .unwrap()
+            .into_once();
+        assert!(started.elapsed() >= Duration::from_millis(20));
+        assert_eq!(
+            out,
+            BlockOutput::Json {
+                value: json!({"id": 7})
+            }
+        );
+
+        let out = DelayBlock::new(DelayConfig::new(0).with_forward_input(false))
+            .execute(test_ctx(BlockInput::Text("report".into())))
+            .unwrap()
+            .into_once();
+        assert_eq!(out, BlockOutput::Empty);
+    }
+
+    #[test]
+    fn delay_output_contract_reports_forwarded_kind() {
+        let ctx =
+            ValidateContext::new(InputContract::One(ValueKindSet::singleton(ValueKind::Text)));
+        let forwarding = DelayBlock::new(DelayConfig::new(10));
+        assert_eq!(
+            forwarding.infer_output_contract(&ctx).kinds,
+            ValueKindSet::singleton(ValueKind::Text)
+        );
+        let dropping = DelayBlock::new(DelayConfig::new(10).with_forward_input(false));
+        assert_eq!(
+            dropping.infer_output_contract(&ctx).kinds,
+            ValueKindSet::singleton(ValueKind::Empty)
+        );
+    }
+}
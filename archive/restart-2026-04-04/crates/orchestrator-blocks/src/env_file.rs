@@ -0,0 +1,367 @@
+//! EnvFile block: Source that reads a dotenv-style `KEY=value` file and emits its values as a JSON object.
+//! With `merge_vars`, the file values are also merged into the run vars when the run starts, so
+//! every block sees them in `ctx.vars`; the emitted object is then the merged vars.
+//! Pass your loader when registering: `register_env_file(registry, Arc::new(your_loader))`.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::input_binding::resolve_effective_input;
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind,
+};
+
+/// Error from env file operations.
+#[derive(Debug, Clone)]
+pub struct EnvFileError(pub String);
+
+impl std::fmt::Display for EnvFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for EnvFileError {}
+
+/// Env file loader abstraction. Implement and pass when registering.
+pub trait EnvFileLoader: Send + Sync {
+    /// Load the `KEY=value` pairs at `path`, or `None` when the file does not exist.
+    fn load(
+        &self,
+        path: &Path,
+    ) -> Result<Option<serde_json::Map<String, serde_json::Value>>, EnvFileError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvFileConfig {
+    pub path: String,
+    /// Emit an empty object instead of failing when the file is missing.
+    #[serde(default)]
+    pub optional: bool,
+    /// Merge the file values into the run vars (file values win) before the run starts.
+    #[serde(default)]
+    pub merge_vars: bool,
+}
+
+impl EnvFileConfig {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            optional: false,
+            merge_vars: false,
+        }
+    }
+
+    pub fn with_optional(mut self, optional: bool) -> Self {
+        self.optional = optional;
+        self
+    }
+
+    pub fn with_merge_vars(mut self, merge_vars: bool) -> Self {
+        self.merge_vars = merge_vars;
+        self
+    }
+}
+
+pub struct EnvFileBlock {
+    config: EnvFileConfig,
+    loader: Arc<dyn EnvFileLoader>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl EnvFileBlock {
+    pub fn new(config: EnvFileConfig, loader: Arc<dyn EnvFileLoader>) -> Self {
+        Self {
+            config,
+            loader,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+impl EnvFileBlock {
+    fn load_values(&self) -> Result<serde_json::Map<String, serde_json::Value>, BlockError> {
+        if self.config.path.trim().is_empty() {
+            return Err(BlockError::Other("env_file requires a path".into()));
+        }
+        let path = Path::new(&self.config.path);
+        match self.loader.load(path).map_err(|e| BlockError::Other(e.0))? {
+            Some(values) => Ok(values),
+            None if self.config.optional => Ok(serde_json::Map::new()),
+            None => Err(BlockError::Other(format!("{}: not found", path.display()))),
+        }
+    }
+}
+
+impl BlockExecutor for EnvFileBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        // Upstream output only triggers the read (e.g. a cron tick); errors still propagate.
+        if let BlockInput::Error { message } =
+            resolve_effective_input(&ctx, &self.input_from, None)?
+        {
+            return Err(BlockError::Other(message));
+        }
+        let values = self.load_values()?;
+        let mut object = if self.config.merge_vars {
+            ctx.vars.as_ref().clone()
+        } else {
+            serde_json::Map::new()
+        };
+        object.extend(values);
+        Ok(BlockExecutionResult::Once(BlockOutput::Json {
+            value: serde_json::Value::Object(object),
+        }))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::Json, OutputMode::Once)
+    }
+
+    fn run_vars(&self) -> Result<Option<serde_json::Map<String, serde_json::Value>>, BlockError> {
+        if !self.config.merge_vars {
+            return Ok(None);
+        }
+        self.load_values().map(Some)
+    }
+}
+
+/// Default implementation: reads the file with std::fs and parses dotenv syntax.
+/// Supports `#` comments, an optional `export ` prefix, and single or double quoted values
+/// (double quotes expand `\n`, `\t`, `\"` and `\\`).
+pub struct DotenvFileLoader;
+
+fn parse_value(raw: &str) -> String {
+    let raw = raw.trim();
+    if raw.len() >= 2 && raw.starts_with('\'') && raw.ends_with('\'') {
+        return raw[1..raw.len() - 1].to_string();
+    }
+    if raw.len() >= 2 && raw.starts_with('"') && raw.ends_with('"') {
+        let mut out = String::new();
+        let mut chars = raw[1..raw.len() - 1].chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            }
+        }
+        return out;
+    }
+    match raw.find(" #") {
+        Some(idx) => raw[..idx].trim_end().to_string(),
+        None => raw.to_string(),
+    }
+}
+
+fn parse_env(
+    content: &str,
+    path: &Path,
+) -> Result<serde_json::Map<String, serde_json::Value>, EnvFileError> {
+    let mut values = serde_json::Map::new();
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line.split_once('=').ok_or_else(|| {
+            EnvFileError(format!(
+                "{}:{}: expected KEY=value",
+                path.display(),
+                idx + 1
+            ))
+        })?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(EnvFileError(format!(
+                "{}:{}: empty key",
+                path.display(),
+                idx + 1
+            )));
+        }
+        values.insert(
+            key.to_string(),
+            serde_json::Value::String(parse_value(value)),
+        );
+    }
+    Ok(values)
+}
+
+impl EnvFileLoader for DotenvFileLoader {
+    fn load(
+        &self,
+        path: &Path,
+    ) -> Result<Option<serde_json::Map<String, serde_json::Value>>, EnvFileError> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => parse_env(&content, path).map(Some),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(EnvFileError(format!("{}: {}", path.display(), e))),
+        }
+    }
+}
+
+/// Register the env_file block with a loader.
+pub fn register_env_file(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    loader: Arc<dyn EnvFileLoader>,
+) {
+    let loader = Arc::clone(&loader);
+    registry.register_custom("env_file", move |payload, input_from| {
+        let config: EnvFileConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            EnvFileBlock::new(config, Arc::clone(&loader)).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use serde_json::json;
+
+    fn load(config: EnvFileConfig, ctx: BlockExecutionContext) -> Result<BlockOutput, BlockError> {
+        EnvFileBlock::new(config, Arc::new(DotenvFileLoader))
+            .execute(ctx)
+            .map(BlockExecutionResult::into_once)
+    }
+
+    #[test]
+    fn env_file_parses_small_file_into_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        std::fs::write(
+            &path,
+            "# feeds\nFEED_URL=https://example.com/rss # primary\nexport TO=\"a@example.com\"\n\nGREETING='hi # there'\nMULTI=\"one\\ntwo\"\n",
+        )
+        .unwrap();
+
+        let out = load(
+            EnvFileConfig::new(path.to_string_lossy()),
+            test_ctx(BlockInput::Empty),
+        )
+        .unwrap();
+        assert_eq!(
+            out,
+            BlockOutput::Json {
+                value: json!({
+                    "FEED_URL": "https://example.com/rss",
+                    "TO": "a@example.com",
+                    "GREETING": "hi # there",
+                    "MULTI": "one\ntwo"
+                })
+            }
+        );
+
+        let mut ctx = test_ctx(BlockInput::Empty);
+        ctx.vars = Arc::new(
+            json!({ "TO": "override-me", "env": "prod" })
+                .as_object()
+                .cloned()
+                .unwrap(),
+        );
+        let merged = load(
+            EnvFileConfig::new(path.to_string_lossy()).with_merge_vars(true),
+            ctx,
+        )
+        .unwrap();
+        let BlockOutput::Json { value } = merged else {
+            panic!("expected json output");
+        };
+        assert_eq!(value["TO"], json!("a@example.com"));
+        assert_eq!(value["env"], json!("prod"));
+    }
+
+    #[test]
+    fn env_file_missing_errors_unless_optional() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing.env");
+        let err = load(
+            EnvFileConfig::new(path.to_string_lossy()),
+            test_ctx(BlockInput::Empty),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("not found"), "got: {err}");
+
+        let out = load(
+            EnvFileConfig::new(path.to_string_lossy()).with_optional(true),
+            test_ctx(BlockInput::Empty),
+        )
+        .unwrap();
+        assert_eq!(out, BlockOutput::Json { value: json!({}) });
+    }
+
+    #[test]
+    fn env_file_merge_vars_reach_every_block_in_the_run() {
+        struct VarsProbe;
+
+        impl BlockExecutor for VarsProbe {
+            fn execute(
+                &self,
+                ctx: BlockExecutionContext,
+            ) -> Result<BlockExecutionResult, BlockError> {
+                Ok(BlockExecutionResult::Once(BlockOutput::Json {
+                    value: serde_json::Value::Object(ctx.vars.as_ref().clone()),
+                }))
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        std::fs::write(
+            &path,
+            "TO=a@example.com
+FEED_URL=https://example.com/rss
+",
+        )
+        .unwrap();
+
+        let mut registry = orchestrator_core::BlockRegistry::new();
+        registry.register_custom("vars_probe", |_, _| Ok(Box::new(VarsProbe)));
+        register_env_file(&mut registry, Arc::new(DotenvFileLoader));
+        let mut workflow = orchestrator_core::Workflow::with_registry(registry);
+        let env = workflow.add(orchestrator_core::BlockConfig::Custom {
+            type_id: "env_file".to_string(),
+            payload: serde_json::to_value(
+                EnvFileConfig::new(path.to_string_lossy()).with_merge_vars(true),
+            )
+            .unwrap(),
+            input_from: Box::new([]),
+        });
+        let probe = workflow.add(orchestrator_core::BlockConfig::Custom {
+            type_id: "vars_probe".to_string(),
+            payload: json!({}),
+            input_from: Box::new([]),
+        });
+        workflow.link(env, probe);
+
+        let vars = json!({ "TO": "override-me", "env": "prod" });
+        let out = workflow
+            .run_with_vars(vars.as_object().cloned().unwrap())
+            .unwrap();
+        assert_eq!(
+            out,
+            BlockOutput::Json {
+                value: json!({
+                    "TO": "a@example.com",
+                    "FEED_URL": "https://example.com/rss",
+                    "env": "prod"
+                })
+            }
+        );
+    }
+}
@@ -0,0 +1,316 @@
+//! FileChunk block: read a file and output its content as a `List` of chunks of `size` lines or
+//! `size` bytes, for per-chunk processing downstream. Line chunks keep their line endings; byte
+//! chunks never split a UTF-8 character, so a chunk may run a few bytes short. All chunks are held
+//! in memory at once, so files larger than `max_bytes` (64 MiB by default) fail the block.
+//! The path follows `file_read` precedence: forced input sources, then the config path, then a
+//! path string (or `{"path": ...}`) from the previous block.
+//! Pass your chunker when registering: `register_file_chunk(registry, Arc::new(your_chunker))`.
+
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::input_binding::{
+    path_from_input, resolve_effective_input, validate_expected_input, validate_single_input_mode,
+};
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
+};
+
+/// Error from file chunk operations.
+#[derive(Debug, Clone)]
+pub struct FileChunkError(pub String);
+
+impl std::fmt::Display for FileChunkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for FileChunkError {}
+
+/// Unit of `size`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkBy {
+    #[default]
+    Lines,
+    Bytes,
+}
+
+/// File chunker abstraction. Implement and pass when registering.
+pub trait FileChunker: Send + Sync {
+    /// Split the file at `path` into chunks of `size` units; `size` is at least 1. Fails when the
+    /// file holds more than `max_bytes` bytes.
+    fn chunk(
+        &self,
+        path: &Path,
+        by: ChunkBy,
+        size: usize,
+        max_bytes: u64,
+    ) -> Result<Vec<String>, FileChunkError>;
+}
+
+fn default_max_bytes() -> u64 {
+    64 * 1024 * 1024
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileChunkConfig {
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub by: ChunkBy,
+    /// Lines or bytes per chunk; must be at least 1.
+    pub size: usize,
+    /// Largest file the block reads, in bytes.
+    #[serde(default = "default_max_bytes")]
+    pub max_bytes: u64,
+}
+
+impl FileChunkConfig {
+    pub fn new(path: Option<impl Into<String>>, by: ChunkBy, size: usize) -> Self {
+        Self {
+            path: path.map(Into::into),
+            by,
+            size,
+            max_bytes: default_max_bytes(),
+        }
+    }
+
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    fn validate_size(&self) -> Result<(), BlockError> {
+        if self.size == 0 {
+            return Err(BlockError::Other(
+                "file_chunk size must be at least 1".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub struct FileChunkBlock {
+    config: FileChunkConfig,
+    chunker: Arc<dyn FileChunker>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl FileChunkBlock {
+    pub fn new(config: FileChunkConfig, chunker: Arc<dyn FileChunker>) -> Self {
+        Self {
+            config,
+            chunker,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+impl BlockExecutor for FileChunkBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        self.config.validate_size()?;
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        if let BlockInput::Error { message } = &input {
+            return Err(BlockError::Other(message.clone()));
+        }
+        let path = if !self.input_from.is_empty() {
+            path_from_input(&input).ok_or_else(|| {
+                BlockError::Other("path required from forced input sources".into())
+            })?
+        } else if let Some(path) = &self.config.path {
+            PathBuf::from(path)
+        } else {
+            path_from_input(&input).ok_or_else(|| {
+                BlockError::Other("path required from previous input or block config".into())
+            })?
+        };
+        let items = self
+            .chunker
+            .chunk(
+                &path,
+                self.config.by,
+                self.config.size,
+                self.config.max_bytes,
+            )
+            .map_err(|e| BlockError::Other(e.0))?;
+        Ok(BlockExecutionResult::Once(BlockOutput::List { items }))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::List, OutputMode::Once)
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        self.config.validate_size()?;
+        if self.input_from.is_empty() && self.config.path.is_some() {
+            return Ok(());
+        }
+        validate_single_input_mode(ctx)?;
+        validate_expected_input(
+            ctx,
+            ValueKindSet::singleton(ValueKind::String)
+                | ValueKindSet::singleton(ValueKind::Text)
+                | ValueKindSet::singleton(ValueKind::Json),
+        )
+    }
+}
+
+/// Default implementation reading through a `BufReader`, one chunk at a time, and never more
+/// than `max_bytes + 1` bytes of the file.
+pub struct StdFileChunker;
+
+impl FileChunker for StdFileChunker {
+    fn chunk(
+        &self,
+        path: &Path,
+        by: ChunkBy,
+        size: usize,
+        max_bytes: u64,
+    ) -> Result<Vec<String>, FileChunkError> {
+        let err = |e: std::io::Error| FileChunkError(format!("{}: {}", path.display(), e));
+        let file = std::fs::File::open(path).map_err(err)?;
+        let mut reader = BufReader::new(file.take(max_bytes.saturating_add(1)));
+        let size = size.max(1);
+        let mut chunks = Vec::new();
+        match by {
+            ChunkBy::Lines => loop {
+                let mut chunk = String::new();
+                let mut lines = 0;
+                while lines < size && reader.read_line(&mut chunk).map_err(err)? > 0 {
+                    lines += 1;
+                }
+                if chunk.is_empty() {
+                    break;
+                }
+                chunks.push(chunk);
+            },
+            ChunkBy::Bytes => {
+                let invalid = || FileChunkError(format!("{}: invalid UTF-8", path.display()));
+                // Bytes of a character cut off at the end of the previous chunk.
+                let mut carry = Vec::new();
+                loop {
+                    let mut buf = std::mem::take(&mut carry);
+                    let want = size.saturating_sub(buf.len()) as u64;
+                    reader
+                        .by_ref()
+                        .take(want)
+                        .read_to_end(&mut buf)
+                        .map_err(err)?;
+                    if buf.is_empty() {
+                        break;
+                    }
+                    let valid = loop {
+                        match std::str::from_utf8(&buf) {
+                            Ok(_) => break buf.len(),
+                            Err(e) if e.error_len().is_some() => return Err(invalid()),
+                            Err(e) if e.valid_up_to() > 0 => break e.valid_up_to(),
+                            // `size` is smaller than this character: read the rest of it.
+                            Err(_) => {
+                                if reader.by_ref().take(1).read_to_end(&mut buf).map_err(err)? == 0
+                                {
+                                    return Err(invalid());
+                                }
+                            }
+                        }
+                    };
+                    carry = buf.split_off(valid);
+                    chunks.push(String::from_utf8(buf).expect("validated UTF-8 prefix"));
+                }
+            }
+        }
+        let total: usize = chunks.iter().map(String::len).sum();
+        if total as u64 > max_bytes {
+            return Err(FileChunkError(format!(
+                "{}: file exceeds max_bytes ({})",
+                path.display(),
+                max_bytes
+            )));
+        }
+        Ok(chunks)
+    }
+}
+
+/// Register the file_chunk block with a chunker.
+pub fn register_file_chunk(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    chunker: Arc<dyn FileChunker>,
+) {
+    let chunker = Arc::clone(&chunker);
+    registry.register_custom("file_chunk", move |payload, input_from| {
+        let config: FileChunkConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            FileChunkBlock::new(config, Arc::clone(&chunker)).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+
+    fn chunks(path: &Path, by: ChunkBy, size: usize) -> Vec<String> {
+        let block = FileChunkBlock::new(
+            FileChunkConfig::new(Some(path.to_string_lossy()), by, size),
+            Arc::new(StdFileChunker),
+        );
+        match block
+            .execute(test_ctx(BlockInput::Empty))
+            .unwrap()
+            .into_once()
+        {
+            BlockOutput::List { items } => items,
+            other => panic!("expected List, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn file_chunk_splits_by_line_count() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "one\ntwo\nthree\nfour\nfive").unwrap();
+        assert_eq!(
+            chunks(&path, ChunkBy::Lines, 2),
+            vec!["one\ntwo\n", "three\nfour\n", "five"]
+        );
+    }
+
+    #[test]
+    fn file_chunk_splits_by_bytes_on_char_boundaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "abcdéfgh").unwrap();
+        let got = chunks(&path, ChunkBy::Bytes, 4);
+        assert_eq!(got, vec!["abcd", "éfg", "h"]);
+        assert_eq!(got.concat(), "abcdéfgh");
+    }
+
+    #[test]
+    fn file_chunk_fails_past_max_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "one\ntwo\n").unwrap();
+        let block = |max_bytes| {
+            FileChunkBlock::new(
+                FileChunkConfig::new(Some(path.to_string_lossy()), ChunkBy::Lines, 1)
+                    .with_max_bytes(max_bytes),
+                Arc::new(StdFileChunker),
+            )
+        };
+        assert!(block(8).execute(test_ctx(BlockInput::Empty)).is_ok());
+        let err = block(7).execute(test_ctx(BlockInput::Empty)).err().unwrap();
+        assert!(err.to_string().contains("exceeds max_bytes"), "{}", err);
+    }
+}
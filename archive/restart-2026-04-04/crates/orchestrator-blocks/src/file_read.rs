@@ -7,7 +7,7 @@ use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 
 use crate::input_binding::{
-    resolve_effective_input, validate_expected_input, validate_single_input_mode,
+    path_from_input, resolve_effective_input, validate_expected_input, validate_single_input_mode,
 };
 use orchestrator_core::block::{
     BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
@@ -79,18 +79,6 @@ impl FileReadBlock {
     }
 }
 
-fn path_from_input(input: &BlockInput) -> Option<PathBuf> {
-    match input {
-        BlockInput::String(s) if !s.is_empty() => Some(PathBuf::from(s.as_str())),
-        BlockInput::Text(s) if !s.is_empty() => Some(PathBuf::from(s.as_str())),
-        BlockInput::Json(v) => v
-            .as_str()
-            .map(PathBuf::from)
-            .or_else(|| v.get("path").and_then(|p| p.as_str()).map(PathBuf::from)),
-        _ => None,
-    }
-}
-
 impl BlockExecutor for FileReadBlock {
     fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
         let input = resolve_effective_input(&ctx, &self.input_from, None)?;
@@ -180,21 +168,10 @@ pub fn register_file_read(
     });
 }
 
-#[cfg(test)]
-fn test_ctx(input: BlockInput) -> BlockExecutionContext {
-    BlockExecutionContext {
-        workflow_id: uuid::Uuid::new_v4(),
-        run_id: uuid::Uuid::new_v4(),
-        block_id: uuid::Uuid::new_v4(),
-        attempt: 1,
-        prev: input,
-        store: Default::default(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_ctx;
 
     #[test]
     fn file_read_returns_contents() {
@@ -0,0 +1,250 @@
+//! FileWatch block: entry trigger that emits a file path (Recurring) each time a file matching
+//! `glob` is created or modified in `directory`. Events for the same path within `debounce_ms`
+//! are merged, so a file written in several chunks is emitted once. Subdirectories are not watched.
+//! Pass your watcher when registering: `register_file_watch(registry, Arc::new(your_watcher))`.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockOutput,
+    OutputContract, OutputMode, ValidateContext, ValueKind,
+};
+
+/// Error from file watching.
+#[derive(Debug, Clone)]
+pub struct FileWatchError(pub String);
+
+impl std::fmt::Display for FileWatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for FileWatchError {}
+
+/// File watcher abstraction: start watching and return a receiver of path outputs.
+/// Implement and pass when registering.
+pub trait FileWatcher: Send + Sync {
+    fn watch(
+        &self,
+        config: &FileWatchConfig,
+    ) -> Result<mpsc::Receiver<BlockOutput>, FileWatchError>;
+}
+
+fn default_glob() -> String {
+    "*".to_string()
+}
+
+fn default_debounce_ms() -> u64 {
+    500
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileWatchConfig {
+    pub directory: String,
+    /// Pattern matched against file names (e.g. `*.csv`).
+    #[serde(default = "default_glob")]
+    pub glob: String,
+    #[serde(default = "default_debounce_ms")]
+    pub debounce_ms: u64,
+}
+
+impl FileWatchConfig {
+    pub fn new(directory: impl Into<String>) -> Self {
+        Self {
+            directory: directory.into(),
+            glob: default_glob(),
+            debounce_ms: default_debounce_ms(),
+        }
+    }
+
+    pub fn with_glob(mut self, glob: impl Into<String>) -> Self {
+        self.glob = glob.into();
+        self
+    }
+
+    pub fn with_debounce_ms(mut self, debounce_ms: u64) -> Self {
+        self.debounce_ms = debounce_ms;
+        self
+    }
+}
+
+pub struct FileWatchBlock {
+    config: FileWatchConfig,
+    watcher: Arc<dyn FileWatcher>,
+}
+
+impl FileWatchBlock {
+    pub fn new(config: FileWatchConfig, watcher: Arc<dyn FileWatcher>) -> Self {
+        Self { config, watcher }
+    }
+}
+
+impl BlockExecutor for FileWatchBlock {
+    fn execute(&self, _ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        let rx = self
+            .watcher
+            .watch(&self.config)
+            .map_err(|e| BlockError::Other(e.0))?;
+        Ok(BlockExecutionResult::Recurring(rx))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::String, OutputMode::Recurring)
+    }
+}
+
+/// Default implementation using the `notify` crate on a background thread.
+pub struct NotifyFileWatcher;
+
+fn is_wanted(event: &notify::Event) -> bool {
+    matches!(
+        event.kind,
+        notify::EventKind::Create(_) | notify::EventKind::Modify(_)
+    )
+}
+
+fn matches_glob(pattern: &glob::Pattern, path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| pattern.matches(name))
+}
+
+impl FileWatcher for NotifyFileWatcher {
+    fn watch(
+        &self,
+        config: &FileWatchConfig,
+    ) -> Result<mpsc::Receiver<BlockOutput>, FileWatchError> {
+        use notify::Watcher as _;
+
+        let directory = PathBuf::from(&config.directory);
+        if !directory.is_dir() {
+            return Err(FileWatchError(format!(
+                "file_watch directory not found: {}",
+                config.directory
+            )));
+        }
+        let pattern = glob::Pattern::new(&config.glob)
+            .map_err(|e| FileWatchError(format!("file_watch glob {:?}: {}", config.glob, e)))?;
+        let debounce = Duration::from_millis(config.debounce_ms);
+
+        let (event_tx, event_rx) = std::sync::mpsc::channel();
+        let mut watcher = notify::recommended_watcher(event_tx)
+            .map_err(|e| FileWatchError(format!("file_watch: {}", e)))?;
+        watcher
+            .watch(&directory, notify::RecursiveMode::NonRecursive)
+            .map_err(|e| FileWatchError(format!("file_watch {}: {}", config.directory, e)))?;
+
+        let (tx, rx) = mpsc::channel(64);
+        std::thread::spawn(move || {
+            // Keep the watcher alive for as long as the thread runs.
+            let _watcher = watcher;
+            let mut pending: BTreeMap<PathBuf, Instant> = BTreeMap::new();
+            let tick = debounce.max(Duration::from_millis(10));
+            loop {
+                match event_rx.recv_timeout(tick) {
+                    Ok(Ok(event)) if is_wanted(&event) => {
+                        for path in event.paths {
+                            if matches_glob(&pattern, &path) {
+                                pending.insert(path, Instant::now());
+                            }
+                        }
+                    }
+                    Ok(_) | Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+                    Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+                }
+                let settled: Vec<PathBuf> = pending
+                    .iter()
+                    .filter(|(_, seen)| seen.elapsed() >= debounce)
+                    .map(|(path, _)| path.clone())
+                    .collect();
+                for path in settled {
+                    pending.remove(&path);
+                    if !path.is_file() {
+                        continue;
+                    }
+                    let out = BlockOutput::String {
+                        value: path.to_string_lossy().into_owned(),
+                    };
+                    if tx.blocking_send(out).is_err() {
+                        return;
+                    }
+                }
+                if tx.is_closed() {
+                    break;
+                }
+            }
+        });
+        Ok(rx)
+    }
+}
+
+/// Register the file_watch block with a watcher.
+pub fn register_file_watch(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    watcher: Arc<dyn FileWatcher>,
+) {
+    let watcher = Arc::clone(&watcher);
+    registry.register_custom("file_watch", move |payload, _input_from| {
+        let config: FileWatchConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(FileWatchBlock::new(config, Arc::clone(&watcher))))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use orchestrator_core::block::BlockInput;
+
+    #[test]
+    fn file_watch_missing_directory_fails_at_execute() {
+        let config = FileWatchConfig::new("/nonexistent/file_watch_dir");
+        let block = FileWatchBlock::new(config, Arc::new(NotifyFileWatcher));
+        let err = block.execute(test_ctx(BlockInput::empty())).err().unwrap();
+        assert!(err.to_string().contains("not found"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn file_watch_emits_paths_of_matching_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = FileWatchConfig::new(dir.path().to_string_lossy())
+            .with_glob("*.csv")
+            .with_debounce_ms(50);
+        let block = FileWatchBlock::new(config, Arc::new(NotifyFileWatcher));
+        let BlockExecutionResult::Recurring(mut rx) =
+            block.execute(test_ctx(BlockInput::empty())).unwrap()
+        else {
+            panic!("expected Recurring");
+        };
+
+        std::fs::write(dir.path().join("a.csv"), "id\n1\n").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "skip").unwrap();
+        std::fs::write(dir.path().join("b.csv"), "id\n2\n").unwrap();
+
+        let mut seen = Vec::new();
+        while seen.len() < 2 {
+            let out = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+                .await
+                .expect("timed out waiting for file event")
+                .expect("watcher stopped");
+            match out {
+                BlockOutput::String { value } => seen.push(value),
+                other => panic!("expected String, got {:?}", other),
+            }
+        }
+        seen.sort();
+        let expected: Vec<String> = ["a.csv", "b.csv"]
+            .iter()
+            .map(|name| dir.path().join(name).to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(seen, expected);
+    }
+}
@@ -1,4 +1,8 @@
 //! FileWrite block: Writes content to a file using an injected writer.
+//! The destination comes from forced input sources when set, else the config `path`, else a
+//! JSON input `path`. With `append` the content is added to the end of the file, and a list
+//! input is written one item per line (e.g. JSONL run logs). With `decode_base64` the content is
+//! decoded from standard base64 and written as raw bytes, e.g. a `gzip` block's output to `.gz`.
 //! Pass your writer when registering: `register_file_write(registry, Arc::new(your_writer))`.
 
 use std::path::Path;
@@ -28,7 +32,20 @@ impl std::error::Error for FileWriteError {}
 
 /// File writer abstraction. Implement and pass when registering.
 pub trait FileWriter: Send + Sync {
+    /// Overwrite `path` with `content`, or with `append` add it to the end of the file, creating
+    /// it when missing.
     fn write(&self, path: &Path, content: &str, append: bool) -> Result<(), FileWriteError>;
+
+    /// Overwrite `path` with raw `bytes`. The default only accepts UTF-8 and forwards to `write`.
+    fn write_bytes(&self, path: &Path, bytes: &[u8]) -> Result<(), FileWriteError> {
+        let content = std::str::from_utf8(bytes).map_err(|_| {
+            FileWriteError(format!(
+                "{}: writer does not support binary content",
+                path.display()
+            ))
+        })?;
+        self.write(path, content, false)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -37,6 +54,10 @@ pub struct FileWriteConfig {
     pub path: Option<String>,
     #[serde(default)]
     pub append: bool,
+    /// Decode the content from standard base64 and write the raw bytes; cannot be combined
+    /// with `append`.
+    #[serde(default)]
+    pub decode_base64: bool,
 }
 
 impl FileWriteConfig {
@@ -44,6 +65,7 @@ impl FileWriteConfig {
         Self {
             path: path.map(Into::into),
             append: false,
+            decode_base64: false,
         }
     }
 
@@ -55,6 +77,20 @@ impl FileWriteConfig {
         self.append = append;
         self
     }
+
+    pub fn with_decode_base64(mut self, decode_base64: bool) -> Self {
+        self.decode_base64 = decode_base64;
+        self
+    }
+
+    fn check_decode_base64(&self) -> Result<(), BlockError> {
+        if self.decode_base64 && self.append {
+            return Err(BlockError::Other(
+                "file_write decode_base64 cannot be combined with append".into(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 pub struct FileWriteBlock {
@@ -87,8 +123,17 @@ fn json_to_content(value: &serde_json::Value) -> String {
 
 fn content_and_path_from_input(
     input: &BlockInput,
+    append: bool,
 ) -> Result<(String, Option<std::path::PathBuf>), BlockError> {
     match input {
+        BlockInput::List { items } if append => {
+            let mut content = String::new();
+            for item in items {
+                content.push_str(item);
+                content.push('\n');
+            }
+            Ok((content, None))
+        }
         BlockInput::String(s) => Ok((s.clone(), None)),
         BlockInput::Text(s) => Ok((s.clone(), None)),
         BlockInput::Json(v) => {
@@ -109,17 +154,18 @@ fn content_and_path_from_input(
         BlockInput::List { .. } => Err(BlockError::Other(
             "file_write expects single string content".into(),
         )),
-        BlockInput::Empty | BlockInput::Multi { .. } => Err(BlockError::Other(
-            "content required from upstream (e.g. file_read)".into(),
-        )),
+        BlockInput::Empty | BlockInput::Multi { .. } | BlockInput::MultiNamed { .. } => Err(
+            BlockError::Other("content required from upstream (e.g. file_read)".into()),
+        ),
         BlockInput::Error { message } => Err(BlockError::Other(message.clone())),
     }
 }
 
 impl BlockExecutor for FileWriteBlock {
     fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        self.config.check_decode_base64()?;
         let input = resolve_effective_input(&ctx, &self.input_from, None)?;
-        let (content, input_path) = content_and_path_from_input(&input)?;
+        let (content, input_path) = content_and_path_from_input(&input, self.config.append)?;
         let path = if !self.input_from.is_empty() {
             input_path.ok_or_else(|| {
                 BlockError::Other("destination path required from forced input sources".into())
@@ -132,9 +178,18 @@ impl BlockExecutor for FileWriteBlock {
             })?
         };
 
-        self.writer
-            .write(&path, &content, self.config.append)
-            .map_err(|e| BlockError::Other(e.0))?;
+        if self.config.decode_base64 {
+            use ::base64::Engine as _;
+            let bytes = ::base64::engine::general_purpose::STANDARD
+                .decode(content.trim())
+                .map_err(|e| {
+                    BlockError::Other(format!("file_write content is not valid base64: {}", e))
+                })?;
+            self.writer.write_bytes(&path, &bytes)
+        } else {
+            self.writer.write(&path, &content, self.config.append)
+        }
+        .map_err(|e| BlockError::Other(e.0))?;
 
         Ok(BlockExecutionResult::Once(BlockOutput::empty()))
     }
@@ -143,14 +198,21 @@ impl BlockExecutor for FileWriteBlock {
         OutputContract::from_kind(ValueKind::Empty, OutputMode::Once)
     }
 
+    /// Overwriting yields the same file on every attempt; appending does not.
+    fn idempotent(&self) -> bool {
+        !self.config.append
+    }
+
     fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        self.config.check_decode_base64()?;
         validate_single_input_mode(ctx)?;
-        validate_expected_input(
-            ctx,
-            ValueKindSet::singleton(ValueKind::String)
-                | ValueKindSet::singleton(ValueKind::Text)
-                | ValueKindSet::singleton(ValueKind::Json),
-        )
+        let mut accepted = ValueKindSet::singleton(ValueKind::String)
+            | ValueKindSet::singleton(ValueKind::Text)
+            | ValueKindSet::singleton(ValueKind::Json);
+        if self.config.append {
+            accepted |= ValueKindSet::singleton(ValueKind::List);
+        }
+        validate_expected_input(ctx, accepted)
     }
 }
 
@@ -163,19 +225,27 @@ impl FileWriter for StdFileWriter {
             std::fs::create_dir_all(parent)
                 .map_err(|e| FileWriteError(format!("create_dir_all {}: {}", path.display(), e)))?;
         }
-        if append {
-            use std::io::Write;
-            let mut file = std::fs::OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(path)
-                .map_err(|e| FileWriteError(format!("{}: {}", path.display(), e)))?;
-            file.write_all(content.as_bytes())
-                .map_err(|e| FileWriteError(format!("{}: {}", path.display(), e)))
-        } else {
-            std::fs::write(path, content)
-                .map_err(|e| FileWriteError(format!("{}: {}", path.display(), e)))
+        if !append {
+            return std::fs::write(path, content)
+                .map_err(|e| FileWriteError(format!("{}: {}", path.display(), e)));
+        }
+        use std::io::Write;
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| FileWriteError(format!("{}: {}", path.display(), e)))?;
+        file.write_all(content.as_bytes())
+            .map_err(|e| FileWriteError(format!("{}: {}", path.display(), e)))
+    }
+
+    fn write_bytes(&self, path: &Path, bytes: &[u8]) -> Result<(), FileWriteError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| FileWriteError(format!("create_dir_all {}: {}", path.display(), e)))?;
         }
+        std::fs::write(path, bytes)
+            .map_err(|e| FileWriteError(format!("{}: {}", path.display(), e)))
     }
 }
 
@@ -194,21 +264,10 @@ pub fn register_file_write(
     });
 }
 
-#[cfg(test)]
-fn test_ctx(input: BlockInput) -> BlockExecutionContext {
-    BlockExecutionContext {
-        workflow_id: uuid::Uuid::new_v4(),
-        run_id: uuid::Uuid::new_v4(),
-        block_id: uuid::Uuid::new_v4(),
-        attempt: 1,
-        prev: input,
-        store: Default::default(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_ctx;
 
     #[test]
     fn file_write_creates_file_with_content() {
@@ -298,6 +357,72 @@ mod tests {
         assert_eq!(std::fs::read_to_string(&path).unwrap(), "ab");
     }
 
+    #[test]
+    fn file_write_append_writes_list_items_as_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join("runs.jsonl");
+        let block = FileWriteBlock::new(
+            FileWriteConfig::new(Some(path.to_string_lossy())).with_append(true),
+            Arc::new(StdFileWriter),
+        );
+        for items in [vec![r#"{"run":1}"#, r#"{"run":2}"#], vec![r#"{"run":3}"#]] {
+            let items = items.into_iter().map(String::from).collect();
+            block.execute(test_ctx(BlockInput::List { items })).unwrap();
+        }
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\"run\":1}\n{\"run\":2}\n{\"run\":3}\n"
+        );
+    }
+
+    #[test]
+    fn file_write_append_works_with_a_writer_that_only_implements_write() {
+        struct RecordingWriter(std::sync::Mutex<Vec<(String, bool)>>);
+        impl FileWriter for RecordingWriter {
+            fn write(
+                &self,
+                _path: &Path,
+                content: &str,
+                append: bool,
+            ) -> Result<(), FileWriteError> {
+                self.0.lock().unwrap().push((content.to_string(), append));
+                Ok(())
+            }
+        }
+
+        let writer = Arc::new(RecordingWriter(std::sync::Mutex::new(Vec::new())));
+        let block = FileWriteBlock::new(
+            FileWriteConfig::new(Some("runs.jsonl")).with_append(true),
+            Arc::clone(&writer) as Arc<dyn FileWriter>,
+        );
+        block
+            .execute(test_ctx(BlockInput::String("line".into())))
+            .unwrap();
+        assert_eq!(*writer.0.lock().unwrap(), vec![("line".to_string(), true)]);
+    }
+
+    #[test]
+    fn file_write_decode_base64_writes_raw_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blob.bin");
+        let block = |config: FileWriteConfig| FileWriteBlock::new(config, Arc::new(StdFileWriter));
+        let config = FileWriteConfig::new(Some(path.to_string_lossy())).with_decode_base64(true);
+        block(config.clone())
+            .execute(test_ctx(BlockInput::String("H4v/AA==".into())))
+            .unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), vec![0x1f, 0x8b, 0xff, 0x00]);
+
+        let err = block(config.clone())
+            .execute(test_ctx(BlockInput::String("not base64!".into())))
+            .unwrap_err();
+        assert!(err.to_string().contains("not valid base64"), "got: {err}");
+
+        let err = block(config.with_append(true))
+            .execute(test_ctx(BlockInput::String("H4v/AA==".into())))
+            .unwrap_err();
+        assert!(err.to_string().contains("append"), "got: {err}");
+    }
+
     #[test]
     fn file_write_precedence_config_over_prev_path() {
         let dir = tempfile::tempdir().unwrap();
@@ -0,0 +1,312 @@
+//! Gzip block: Transform that gzip-compresses text or decompresses it back.
+//! There is no binary output kind, so compressed data travels as a standard base64 string; to
+//! store a real `.gz` file, follow the block with `file_write` using `decode_base64`.
+//! Pass your codec when registering: `register_gzip(registry, Arc::new(your_codec))`.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::input_binding::{
+    resolve_effective_input, validate_expected_input, validate_single_input_mode,
+};
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
+};
+
+/// Error from gzip operations.
+#[derive(Debug, Clone)]
+pub struct GzipError(pub String);
+
+impl std::fmt::Display for GzipError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for GzipError {}
+
+/// Gzip codec abstraction. Implement and pass when registering.
+pub trait GzipCodec: Send + Sync {
+    /// Compress `bytes` at `level` (0 = store only, 9 = smallest).
+    fn compress(&self, bytes: &[u8], level: u32) -> Result<Vec<u8>, GzipError>;
+    fn decompress(&self, bytes: &[u8]) -> Result<Vec<u8>, GzipError>;
+}
+
+/// Direction of the conversion.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GzipMode {
+    /// Text in, base64-encoded gzip out.
+    #[default]
+    Compress,
+    /// Base64-encoded gzip in, text out.
+    Decompress,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GzipConfig {
+    #[serde(default)]
+    pub mode: GzipMode,
+    /// Compression level 0-9; ignored when decompressing.
+    #[serde(default = "default_level")]
+    pub level: u32,
+}
+
+fn default_level() -> u32 {
+    6
+}
+
+impl Default for GzipConfig {
+    fn default() -> Self {
+        Self {
+            mode: GzipMode::default(),
+            level: default_level(),
+        }
+    }
+}
+
+impl GzipConfig {
+    pub fn new(mode: GzipMode) -> Self {
+        Self {
+            mode,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_level(mut self, level: u32) -> Self {
+        self.level = level;
+        self
+    }
+}
+
+pub struct GzipBlock {
+    config: GzipConfig,
+    codec: Arc<dyn GzipCodec>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl GzipBlock {
+    pub fn new(config: GzipConfig, codec: Arc<dyn GzipCodec>) -> Self {
+        Self {
+            config,
+            codec,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+fn base64_engine() -> ::base64::engine::GeneralPurpose {
+    ::base64::engine::general_purpose::STANDARD
+}
+
+impl BlockExecutor for GzipBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        use ::base64::Engine as _;
+        if self.config.level > 9 {
+            return Err(BlockError::Other(format!(
+                "gzip level must be 0-9, got {}",
+                self.config.level
+            )));
+        }
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        let text = match input {
+            BlockInput::String(s) | BlockInput::Text(s) => s,
+            BlockInput::Json(v) => v
+                .as_str()
+                .map(String::from)
+                .unwrap_or_else(|| v.to_string()),
+            BlockInput::Empty => String::new(),
+            BlockInput::Error { message } => return Err(BlockError::Other(message)),
+            BlockInput::List { .. } | BlockInput::Multi { .. } | BlockInput::MultiNamed { .. } => {
+                return Err(BlockError::Other(
+                    "gzip expects string/text/json input".into(),
+                ));
+            }
+        };
+
+        let output = match self.config.mode {
+            GzipMode::Compress => {
+                let compressed = self
+                    .codec
+                    .compress(text.as_bytes(), self.config.level)
+                    .map_err(|e| BlockError::Other(e.0))?;
+                BlockOutput::String {
+                    value: base64_engine().encode(compressed),
+                }
+            }
+            GzipMode::Decompress => {
+                let compressed = base64_engine().decode(text.trim()).map_err(|e| {
+                    BlockError::Other(format!("gzip input is not valid base64: {}", e))
+                })?;
+                let bytes = self
+                    .codec
+                    .decompress(&compressed)
+                    .map_err(|e| BlockError::Other(e.0))?;
+                let value = String::from_utf8(bytes).map_err(|_| {
+                    BlockError::Other("gzip decompressed bytes are not valid UTF-8 text".into())
+                })?;
+                BlockOutput::Text { value }
+            }
+        };
+        Ok(BlockExecutionResult::Once(output))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        let kind = match self.config.mode {
+            GzipMode::Compress => ValueKind::String,
+            GzipMode::Decompress => ValueKind::Text,
+        };
+        OutputContract::from_kind(kind, OutputMode::Once)
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        validate_single_input_mode(ctx)?;
+        validate_expected_input(
+            ctx,
+            ValueKindSet::singleton(ValueKind::Empty)
+                | ValueKindSet::singleton(ValueKind::String)
+                | ValueKindSet::singleton(ValueKind::Text)
+                | ValueKindSet::singleton(ValueKind::Json),
+        )
+    }
+}
+
+/// Default implementation using the `flate2` crate.
+pub struct Flate2GzipCodec;
+
+impl GzipCodec for Flate2GzipCodec {
+    fn compress(&self, bytes: &[u8], level: u32) -> Result<Vec<u8>, GzipError> {
+        use std::io::Write as _;
+        let mut encoder =
+            flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::new(level));
+        encoder
+            .write_all(bytes)
+            .and_then(|_| encoder.finish())
+            .map_err(|e| GzipError(format!("gzip compress failed: {}", e)))
+    }
+
+    fn decompress(&self, bytes: &[u8]) -> Result<Vec<u8>, GzipError> {
+        use std::io::Read as _;
+        let mut out = Vec::new();
+        flate2::read::GzDecoder::new(bytes)
+            .read_to_end(&mut out)
+            .map_err(|e| GzipError(format!("invalid gzip data: {}", e)))?;
+        Ok(out)
+    }
+}
+
+/// Register the gzip block with a codec.
+pub fn register_gzip(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    codec: Arc<dyn GzipCodec>,
+) {
+    let codec = Arc::clone(&codec);
+    registry.register_custom("gzip", move |payload, input_from| {
+        let config: GzipConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            GzipBlock::new(config, Arc::clone(&codec)).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use ::base64::Engine as _;
+
+    fn run(config: GzipConfig, input: BlockInput) -> Result<BlockOutput, BlockError> {
+        GzipBlock::new(config, Arc::new(Flate2GzipCodec))
+            .execute(test_ctx(input))
+            .map(BlockExecutionResult::into_once)
+    }
+
+    fn compressed_len(level: u32, text: &str) -> usize {
+        match run(
+            GzipConfig::new(GzipMode::Compress).with_level(level),
+            BlockInput::Text(text.into()),
+        )
+        .unwrap()
+        {
+            BlockOutput::String { value } => base64_engine().decode(value).unwrap().len(),
+            other => panic!("expected string output, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn gzip_round_trip_compress_decompress() {
+        let report = "weekly report: all green\n".repeat(50);
+        let compressed = run(
+            GzipConfig::new(GzipMode::Compress),
+            BlockInput::Text(report.clone()),
+        )
+        .unwrap();
+        let BlockOutput::String { value } = compressed else {
+            panic!("expected base64 string output");
+        };
+        assert!(value.len() < report.len());
+
+        let decompressed = run(
+            GzipConfig::new(GzipMode::Decompress),
+            BlockInput::String(value),
+        )
+        .unwrap();
+        assert_eq!(decompressed, BlockOutput::Text { value: report });
+
+        let err = run(
+            GzipConfig::new(GzipMode::Decompress),
+            BlockInput::String("aGVsbG8=".into()),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("invalid gzip data"), "got: {err}");
+    }
+
+    #[test]
+    fn gzip_level_selection_changes_output_size() {
+        let text = "abcdefghij".repeat(200);
+        let stored = compressed_len(0, &text);
+        let best = compressed_len(9, &text);
+        assert!(stored > text.len(), "level 0 stores without compressing");
+        assert!(best < stored / 10, "level 9 compresses repetitive text");
+
+        let err = run(
+            GzipConfig::new(GzipMode::Compress).with_level(10),
+            BlockInput::Text(text),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("0-9"), "got: {err}");
+    }
+
+    #[test]
+    fn gzip_output_written_with_decode_base64_is_a_gzip_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = dir.path().join("report.txt");
+        std::fs::write(&report, "all green\n".repeat(20)).unwrap();
+        let path = dir.path().join("report.txt.gz");
+        let mut w = crate::new_workflow();
+        let read = w.add(crate::Block::file_read(Some(report.to_string_lossy())));
+        let gzip = w.add(crate::Block::gzip_compress());
+        let write = w.add(
+            crate::Block::file_write(Some(path.to_string_lossy()))
+                .set_file_write_decode_base64(true),
+        );
+        w.link(read, gzip);
+        w.link(gzip, write);
+        w.run().unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(&bytes[..2], &[0x1f, 0x8b], "gzip magic header");
+        assert_eq!(
+            Flate2GzipCodec.decompress(&bytes).unwrap(),
+            "all green\n".repeat(20).into_bytes()
+        );
+    }
+}
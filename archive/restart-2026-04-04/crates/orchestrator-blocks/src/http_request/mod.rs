@@ -1,8 +1,16 @@
-//! HttpRequest block: fetch text body from a URL.
+//! HttpRequest block: fetch text body from a URL. GET by default; `method` and a JSON `body`
+//! call REST endpoints. A JSON input with both `url` and `body` keys overrides the config body.
+//! Config `headers` are sent on every request; a JSON input `headers` object merges over them.
+//! With `include_metadata` the block emits `{ status, headers, body }` JSON instead of the body
+//! text, for non-2xx responses too (e.g. a 404), so a workflow can branch on `status`. 429 and
+//! 5xx responses are still retried and fail the block once retries run out.
+//! PaginatedHttp block: fetch every page of a JSON list API into one JSON array.
 //! Pass your requester when registering: `register_http_request(registry, Arc::new(your_requester))`.
 
+mod paginated;
 mod reqwest_requester;
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -18,6 +26,7 @@ use orchestrator_core::block::{
     BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
 };
 
+pub use paginated::{PaginatedHttpBlock, PaginatedHttpConfig, Pagination, register_paginated_http};
 pub use reqwest_requester::ReqwestHttpRequester;
 
 /// Error from HTTP request operations.
@@ -32,14 +41,149 @@ impl std::fmt::Display for HttpRequestError {
 
 impl std::error::Error for HttpRequestError {}
 
+/// One outgoing HTTP request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpRequest {
+    /// Upper-case method name, e.g. `GET` or `POST`.
+    pub method: String,
+    pub url: String,
+    pub timeout: Duration,
+    pub headers: Vec<(String, String)>,
+    /// Sent as a JSON body when set.
+    pub body: Option<serde_json::Value>,
+}
+
+impl HttpRequest {
+    pub fn new(method: impl Into<String>, url: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            method: method.into().trim().to_ascii_uppercase(),
+            url: url.into(),
+            timeout,
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// A GET request, with `User-Agent` set when given.
+    pub fn get(url: impl Into<String>, timeout: Duration, user_agent: Option<&str>) -> Self {
+        let mut req = Self::new("GET", url, timeout);
+        if let Some(ua) = user_agent {
+            req = req.with_header("User-Agent", ua);
+        }
+        req
+    }
+
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn with_body(mut self, body: Option<serde_json::Value>) -> Self {
+        self.body = body;
+        self
+    }
+
+    /// Value of header `name` (case-insensitive).
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Whether repeating the request after an ambiguous failure is safe.
+    pub fn is_idempotent(&self) -> bool {
+        is_idempotent_method(&self.method)
+    }
+}
+
+/// Response to an `HttpRequest`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// A 200 response with no headers, for requesters that only return the body.
+    pub fn ok(body: impl Into<String>) -> Self {
+        Self {
+            status: 200,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// `{ "status", "headers", "body" }`; repeated headers are joined with `, `.
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut headers = serde_json::Map::new();
+        for (name, value) in &self.headers {
+            let name = name.to_ascii_lowercase();
+            match headers.get_mut(&name) {
+                Some(serde_json::Value::String(existing)) => {
+                    existing.push_str(", ");
+                    existing.push_str(value);
+                }
+                _ => {
+                    headers.insert(name, serde_json::Value::String(value.clone()));
+                }
+            }
+        }
+        serde_json::json!({
+            "status": self.status,
+            "headers": headers,
+            "body": self.body,
+        })
+    }
+}
+
+fn is_idempotent_method(method: &str) -> bool {
+    matches!(
+        method.trim().to_ascii_uppercase().as_str(),
+        "GET" | "HEAD" | "OPTIONS" | "PUT" | "DELETE"
+    )
+}
+
 /// HTTP requester abstraction. Implement and pass when registering.
+///
+/// Implement `request` to support every method. Requesters written against the older
+/// GET-only API may implement just `get`; they then reject other methods. Implement `send`
+/// as well to report the real status and response headers. A requester implementing none of
+/// them fails every request.
 pub trait HttpRequester: Send + Sync {
+    /// Full response for any status; the default wraps `request` as a 200.
+    fn send(&self, req: HttpRequest) -> Result<HttpResponse, HttpRequestError> {
+        self.request(req).map(HttpResponse::ok)
+    }
+
+    fn request(&self, req: HttpRequest) -> Result<String, HttpRequestError> {
+        if req.method != "GET" {
+            return Err(HttpRequestError(format!(
+                "http_request method {} is not supported by this requester",
+                req.method
+            )));
+        }
+        self.get(&req.url, req.timeout, req.header("User-Agent"))
+    }
+
+    /// GET-only requests; `request` falls back to it. The default fails: it must not call
+    /// `request`, which would call it back.
     fn get(
         &self,
         url: &str,
-        timeout: Duration,
-        user_agent: Option<&str>,
-    ) -> Result<String, HttpRequestError>;
+        _timeout: Duration,
+        _user_agent: Option<&str>,
+    ) -> Result<String, HttpRequestError> {
+        Err(HttpRequestError(format!(
+            "http_request to {} failed: the requester implements neither request nor get",
+            url
+        )))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -52,6 +196,19 @@ pub struct HttpRequestConfig {
     pub user_agent: Option<String>,
     #[serde(default = "default_retry_policy")]
     pub retry_policy: RetryPolicy,
+    /// HTTP method; GET when unset.
+    #[serde(default)]
+    pub method: Option<String>,
+    /// JSON request body.
+    #[serde(default)]
+    pub body: Option<serde_json::Value>,
+    /// Extra request headers, e.g. `Authorization` or `Accept`.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Emit `{ status, headers, body }` JSON instead of the body text. Non-2xx responses that
+    /// are not retried (e.g. 404) are emitted too rather than failing the block.
+    #[serde(default)]
+    pub include_metadata: bool,
 }
 
 fn default_timeout_ms() -> Option<u64> {
@@ -69,8 +226,36 @@ impl HttpRequestConfig {
             timeout_ms: default_timeout_ms(),
             user_agent: None,
             retry_policy: default_retry_policy(),
+            method: None,
+            body: None,
+            headers: BTreeMap::new(),
+            include_metadata: false,
         }
     }
+
+    pub fn with_include_metadata(mut self, include_metadata: bool) -> Self {
+        self.include_metadata = include_metadata;
+        self
+    }
+
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn with_method(mut self, method: impl Into<String>) -> Self {
+        self.method = Some(method.into());
+        self
+    }
+
+    pub fn with_body(mut self, body: serde_json::Value) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    fn method(&self) -> &str {
+        self.method.as_deref().unwrap_or("GET")
+    }
 }
 
 pub struct HttpRequestBlock {
@@ -102,6 +287,7 @@ fn block_input_kind(input: &BlockInput) -> &'static str {
         BlockInput::Json(_) => "json",
         BlockInput::List { .. } => "list",
         BlockInput::Multi { .. } => "multi",
+        BlockInput::MultiNamed { .. } => "multi_named",
         BlockInput::Error { .. } => "error",
     }
 }
@@ -115,6 +301,58 @@ fn url_host(url: &str) -> Option<&str> {
         .filter(|host| !host.is_empty())
 }
 
+/// Body from a JSON input carrying both `url` and `body`.
+fn body_from_input(input: &BlockInput) -> Option<serde_json::Value> {
+    match input {
+        BlockInput::Json(v) if v.get("url").is_some() => v.get("body").cloned(),
+        _ => None,
+    }
+}
+
+/// Config headers with a JSON input `headers` object merged over them (input wins,
+/// names compared case-insensitively).
+fn merged_headers(config: &BTreeMap<String, String>, input: &BlockInput) -> Vec<(String, String)> {
+    let mut headers: Vec<(String, String)> = config
+        .iter()
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    let from_input = match input {
+        BlockInput::Json(v) => v.get("headers").and_then(|h| h.as_object()),
+        _ => None,
+    };
+    for (name, value) in from_input.into_iter().flatten() {
+        let value = match value {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        headers.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+        headers.push((name.clone(), value));
+    }
+    headers
+}
+
+fn is_secret_header(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    ["authorization", "token", "key"]
+        .iter()
+        .any(|marker| lower.contains(marker))
+}
+
+/// `name: value` pairs for logging; secret-looking headers show only their name.
+fn redacted_headers(headers: &[(String, String)]) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            if is_secret_header(name) {
+                format!("{}: [redacted]", name)
+            } else {
+                format!("{}: {}", name, value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 fn url_from_input(input: &BlockInput) -> Option<String> {
     match input {
         BlockInput::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
@@ -139,124 +377,210 @@ impl BlockExecutor for HttpRequestBlock {
             return Err(BlockError::Other(message.clone()));
         }
 
-        let url = if !self.input_from.is_empty() {
-            url_from_input(&input).ok_or_else(|| {
-                BlockError::Other("http_request url required from forced input sources".into())
-            })?
-        } else if let Some(url) = self.config.url.clone() {
-            url
-        } else {
-            url_from_input(&input).ok_or_else(|| {
-                BlockError::Other("http_request url required from input or config".into())
-            })?
-        };
+        let url = resolve_url(
+            "http_request",
+            &self.input_from,
+            self.config.url.as_deref(),
+            &input,
+        )?;
         let timeout = Duration::from_millis(self.config.timeout_ms.unwrap_or(30_000));
+        let mut req = HttpRequest::new(self.config.method(), url, timeout)
+            .with_body(body_from_input(&input).or_else(|| self.config.body.clone()));
+        if let Some(ua) = &self.config.user_agent {
+            req = req.with_header("User-Agent", ua);
+        }
+        req.headers
+            .extend(merged_headers(&self.config.headers, &input));
         debug!(
             event = "http.request_configured",
             domain = "http",
             block_type = "http_request",
             input_kind = block_input_kind(&input),
-            url_host = url_host(&url).unwrap_or("unknown"),
+            method = req.method.as_str(),
+            url_host = url_host(&req.url).unwrap_or("unknown"),
             timeout_ms = timeout.as_millis() as u64,
             has_user_agent = self.config.user_agent.is_some(),
+            has_body = req.body.is_some(),
+            headers = %redacted_headers(&req.headers),
             max_retries = self.config.retry_policy.max_retries
         );
-        let mut retries_done = 0u32;
-        loop {
-            let attempt = retries_done + 1;
-            debug!(
-                event = "http.request_attempt",
-                domain = "http",
-                block_type = "http_request",
-                code = "request",
-                attempt = attempt,
-                url_host = url_host(&url).unwrap_or("unknown")
-            );
-            match self
-                .requester
-                .get(&url, timeout, self.config.user_agent.as_deref())
-            {
-                Ok(body) => {
-                    debug!(
-                        event = "http.request_succeeded",
-                        domain = "http",
-                        block_type = "http_request",
-                        attempt = attempt,
-                        response_bytes = body.len() as u64
-                    );
-                    return Ok(BlockExecutionResult::Once(BlockOutput::Text {
-                        value: body,
-                    }));
-                }
-                Err(err) => {
-                    let (code, retryable, provider_status) = classify_http_error(&err.0);
-                    let can_retry = retryable && self.config.retry_policy.can_retry(retries_done);
-                    debug!(
-                        event = "http.request_failed",
-                        domain = "http",
-                        block_type = "http_request",
-                        code = code,
-                        attempt = attempt,
-                        retryable = retryable,
-                        can_retry = can_retry,
-                        provider_status = ?provider_status,
-                        error = %err,
-                        error_len = err.0.len() as u64
-                    );
-                    if can_retry {
-                        let backoff = self.config.retry_policy.backoff_duration(retries_done);
-                        info!(
-                            event = "block.retry_scheduled",
-                            domain = "http",
-                            block_type = "http_request",
-                            code = code,
-                            attempt = retries_done + 1,
-                            next_attempt = retries_done + 2,
-                            backoff_ms = backoff.as_millis() as u64
-                        );
-                        std::thread::sleep(backoff);
-                        retries_done += 1;
-                        continue;
-                    }
-                    debug!(
-                        event = "http.request_retry_exhausted",
-                        domain = "http",
-                        block_type = "http_request",
-                        code = code,
-                        attempt = attempt
-                    );
-                    return Err(BlockError::Other(error_payload_json(
-                        "http",
-                        code,
-                        &err.0,
-                        provider_status.as_deref(),
-                        retries_done + 1,
-                    )));
-                }
+        let resp = send_with_retries(
+            self.requester.as_ref(),
+            "http_request",
+            &req,
+            &self.config.retry_policy,
+            self.config.include_metadata,
+        )?;
+        let out = if self.config.include_metadata {
+            BlockOutput::Json {
+                value: resp.to_json(),
             }
-        }
+        } else {
+            BlockOutput::Text { value: resp.body }
+        };
+        Ok(BlockExecutionResult::Once(out))
     }
 
     fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
-        OutputContract::from_kind(ValueKind::Text, OutputMode::Once)
+        let kind = if self.config.include_metadata {
+            ValueKind::Json
+        } else {
+            ValueKind::Text
+        };
+        OutputContract::from_kind(kind, OutputMode::Once)
+    }
+
+    /// GET, PUT and DELETE can be repeated after a timeout; POST and PATCH cannot.
+    fn idempotent(&self) -> bool {
+        is_idempotent_method(self.config.method())
     }
 
     fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
-        let accepted = ValueKindSet::singleton(ValueKind::String)
-            | ValueKindSet::singleton(ValueKind::Text)
-            | ValueKindSet::singleton(ValueKind::Json);
-        if !self.input_from.is_empty() {
-            validate_single_input_mode(ctx)?;
-            return validate_expected_input(ctx, accepted);
-        }
-        if self.config.url.is_some() {
-            return Ok(());
+        validate_url_linkage(ctx, &self.input_from, self.config.url.as_deref())
+    }
+}
+
+/// Forced input sources win over the configured url, which wins over the previous output.
+fn resolve_url(
+    block_type: &str,
+    input_from: &[uuid::Uuid],
+    config_url: Option<&str>,
+    input: &BlockInput,
+) -> Result<String, BlockError> {
+    if !input_from.is_empty() {
+        return url_from_input(input).ok_or_else(|| {
+            BlockError::Other(format!(
+                "{} url required from forced input sources",
+                block_type
+            ))
+        });
+    }
+    if let Some(url) = config_url {
+        return Ok(url.to_string());
+    }
+    url_from_input(input).ok_or_else(|| {
+        BlockError::Other(format!("{} url required from input or config", block_type))
+    })
+}
+
+fn validate_url_linkage(
+    ctx: &ValidateContext<'_>,
+    input_from: &[uuid::Uuid],
+    config_url: Option<&str>,
+) -> Result<(), BlockError> {
+    let accepted = ValueKindSet::singleton(ValueKind::String)
+        | ValueKindSet::singleton(ValueKind::Text)
+        | ValueKindSet::singleton(ValueKind::Json);
+    if input_from.is_empty() && config_url.is_some() {
+        return Ok(());
+    }
+    validate_single_input_mode(ctx)?;
+    validate_expected_input(ctx, accepted)
+}
+
+/// Send `req`, retrying 429s and 5xx responses per `retry_policy`. Timeouts are retried only
+/// for idempotent methods, since a timed-out POST may already have been applied. Other non-2xx
+/// responses fail, or are returned as-is when `accept_error_status` is set.
+fn send_with_retries(
+    requester: &dyn HttpRequester,
+    block_type: &'static str,
+    req: &HttpRequest,
+    retry_policy: &RetryPolicy,
+    accept_error_status: bool,
+) -> Result<HttpResponse, BlockError> {
+    let url = req.url.as_str();
+    let mut retries_done = 0u32;
+    loop {
+        let attempt = retries_done + 1;
+        debug!(
+            event = "http.request_attempt",
+            domain = "http",
+            block_type = block_type,
+            code = "request",
+            attempt = attempt,
+            url_host = url_host(url).unwrap_or("unknown")
+        );
+        let result = requester.send(req.clone()).and_then(|resp| {
+            if resp.is_success() || (accept_error_status && !is_retryable_status(resp.status)) {
+                Ok(resp)
+            } else {
+                Err(HttpRequestError(format!(
+                    "{} {} {} failed: status={} body={}",
+                    block_type, req.method, req.url, resp.status, resp.body
+                )))
+            }
+        });
+        match result {
+            Ok(resp) => {
+                debug!(
+                    event = "http.request_succeeded",
+                    domain = "http",
+                    block_type = block_type,
+                    attempt = attempt,
+                    status = resp.status,
+                    response_bytes = resp.body.len() as u64
+                );
+                return Ok(resp);
+            }
+            Err(err) => {
+                let (code, retryable, provider_status) = classify_http_error(&err.0);
+                let can_retry = retryable
+                    && retry_policy.can_retry_failure(
+                        retries_done,
+                        code == "http.timeout",
+                        req.is_idempotent(),
+                    );
+                debug!(
+                    event = "http.request_failed",
+                    domain = "http",
+                    block_type = block_type,
+                    code = code,
+                    attempt = attempt,
+                    retryable = retryable,
+                    can_retry = can_retry,
+                    provider_status = ?provider_status,
+                    error = %err,
+                    error_len = err.0.len() as u64
+                );
+                if can_retry {
+                    let backoff = retry_policy.backoff_duration(retries_done);
+                    info!(
+                        event = "block.retry_scheduled",
+                        domain = "http",
+                        block_type = block_type,
+                        code = code,
+                        attempt = retries_done + 1,
+                        next_attempt = retries_done + 2,
+                        backoff_ms = backoff.as_millis() as u64
+                    );
+                    std::thread::sleep(backoff);
+                    retries_done += 1;
+                    continue;
+                }
+                debug!(
+                    event = "http.request_retry_exhausted",
+                    domain = "http",
+                    block_type = block_type,
+                    code = code,
+                    attempt = attempt
+                );
+                return Err(BlockError::Other(error_payload_json(
+                    "http",
+                    code,
+                    &err.0,
+                    provider_status.as_deref(),
+                    retries_done + 1,
+                )));
+            }
         }
-        validate_single_input_mode(ctx)?;
-        validate_expected_input(ctx, accepted)
     }
 }
 
+/// Statuses [`classify_http_error`] treats as retryable: 429 and 5xx.
+fn is_retryable_status(status: u16) -> bool {
+    status == 429 || (500..600).contains(&status)
+}
+
 fn classify_http_error(message: &str) -> (&'static str, bool, Option<String>) {
     let lower = message.to_ascii_lowercase();
     let status = extract_status_code(message);
@@ -329,21 +653,10 @@ pub fn register_http_request(
     });
 }
 
-#[cfg(test)]
-fn test_ctx(input: BlockInput) -> BlockExecutionContext {
-    BlockExecutionContext {
-        workflow_id: uuid::Uuid::new_v4(),
-        run_id: uuid::Uuid::new_v4(),
-        block_id: uuid::Uuid::new_v4(),
-        attempt: 1,
-        prev: input,
-        store: Default::default(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_ctx;
 
     struct MockRequester;
 
@@ -424,4 +737,221 @@ mod tests {
             _ => panic!("expected Once(Text)"),
         }
     }
+
+    /// Records every request and answers with its method and body.
+    #[derive(Default)]
+    struct RecordingRequester {
+        seen: std::sync::Mutex<Vec<HttpRequest>>,
+    }
+
+    impl HttpRequester for RecordingRequester {
+        fn request(&self, req: HttpRequest) -> Result<String, HttpRequestError> {
+            self.seen.lock().unwrap().push(req.clone());
+            Ok(format!("{} {}", req.method, req.body.unwrap_or_default()))
+        }
+    }
+
+    #[test]
+    fn http_request_posts_config_body_and_input_body_overrides() {
+        let requester = Arc::new(RecordingRequester::default());
+        let config = HttpRequestConfig::new(Some("https://api.test/items"))
+            .with_method("post")
+            .with_body(serde_json::json!({ "name": "config" }));
+        let block = HttpRequestBlock::new(config, requester.clone());
+        assert!(!block.idempotent());
+
+        let out = block.execute(test_ctx(BlockInput::Empty)).unwrap();
+        match out {
+            BlockExecutionResult::Once(BlockOutput::Text { value }) => {
+                assert_eq!(value, r#"POST {"name":"config"}"#)
+            }
+            _ => panic!("expected Once(Text)"),
+        }
+
+        block
+            .execute(test_ctx(BlockInput::Json(serde_json::json!({
+                "url": "https://api.test/other",
+                "body": { "name": "input" }
+            }))))
+            .unwrap();
+        let seen = requester.seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[1].url, "https://api.test/items");
+        assert_eq!(seen[1].body, Some(serde_json::json!({ "name": "input" })));
+    }
+
+    #[test]
+    fn http_request_input_headers_merge_over_config() {
+        let requester = Arc::new(RecordingRequester::default());
+        let config = HttpRequestConfig::new(Some("https://api.test/items"))
+            .with_header("Accept", "application/json")
+            .with_header("Authorization", "Bearer config");
+        let block = HttpRequestBlock::new(config, requester.clone());
+        block
+            .execute(test_ctx(BlockInput::Json(serde_json::json!({
+                "headers": { "authorization": "Bearer input", "X-Trace": 7 }
+            }))))
+            .unwrap();
+        let seen = requester.seen.lock().unwrap();
+        assert_eq!(seen[0].header("Accept"), Some("application/json"));
+        assert_eq!(seen[0].header("Authorization"), Some("Bearer input"));
+        assert_eq!(seen[0].header("X-Trace"), Some("7"));
+        assert_eq!(seen[0].headers.len(), 3);
+
+        let logged = redacted_headers(&seen[0].headers);
+        assert!(!logged.contains("Bearer"), "got: {logged}");
+        assert!(logged.contains("Accept: application/json"), "got: {logged}");
+    }
+
+    struct MetadataRequester;
+
+    impl HttpRequester for MetadataRequester {
+        fn send(&self, _req: HttpRequest) -> Result<HttpResponse, HttpRequestError> {
+            Ok(HttpResponse {
+                status: 201,
+                headers: vec![
+                    ("Content-Type".into(), "application/json".into()),
+                    ("Set-Cookie".into(), "a=1".into()),
+                    ("Set-Cookie".into(), "b=2".into()),
+                ],
+                body: "{}".into(),
+            })
+        }
+    }
+
+    #[test]
+    fn http_request_include_metadata_emits_status_and_headers() {
+        let config =
+            HttpRequestConfig::new(Some("https://api.test/items")).with_include_metadata(true);
+        let block = HttpRequestBlock::new(config, Arc::new(MetadataRequester));
+        let out = block
+            .execute(test_ctx(BlockInput::Empty))
+            .unwrap()
+            .into_once();
+        assert_eq!(
+            out,
+            BlockOutput::Json {
+                value: serde_json::json!({
+                    "status": 201,
+                    "headers": {
+                        "content-type": "application/json",
+                        "set-cookie": "a=1, b=2"
+                    },
+                    "body": "{}"
+                })
+            }
+        );
+
+        // Body-only requesters report 200 with no headers.
+        let block = HttpRequestBlock::new(
+            HttpRequestConfig::new(Some("https://ok.test")).with_include_metadata(true),
+            Arc::new(MockRequester),
+        );
+        let out = block
+            .execute(test_ctx(BlockInput::Empty))
+            .unwrap()
+            .into_once();
+        assert_eq!(
+            out,
+            BlockOutput::Json {
+                value: serde_json::json!({ "status": 200, "headers": {}, "body": "ok" })
+            }
+        );
+    }
+
+    struct StatusRequester(u16);
+
+    impl HttpRequester for StatusRequester {
+        fn send(&self, _req: HttpRequest) -> Result<HttpResponse, HttpRequestError> {
+            Ok(HttpResponse {
+                status: self.0,
+                headers: Vec::new(),
+                body: "nope".into(),
+            })
+        }
+    }
+
+    #[test]
+    fn http_request_include_metadata_emits_non_retryable_error_status() {
+        let config =
+            HttpRequestConfig::new(Some("https://api.test/missing")).with_include_metadata(true);
+        let block = HttpRequestBlock::new(config, Arc::new(StatusRequester(404)));
+        let out = block
+            .execute(test_ctx(BlockInput::Empty))
+            .unwrap()
+            .into_once();
+        assert_eq!(
+            out,
+            BlockOutput::Json {
+                value: serde_json::json!({ "status": 404, "headers": {}, "body": "nope" })
+            }
+        );
+
+        // Without metadata the 404 fails the block; a 503 fails even with it.
+        let block = HttpRequestBlock::new(
+            HttpRequestConfig::new(Some("https://api.test/missing")),
+            Arc::new(StatusRequester(404)),
+        );
+        let err = block.execute(test_ctx(BlockInput::Empty)).unwrap_err();
+        assert!(err.to_string().contains("status=404"), "got: {err}");
+        let mut config =
+            HttpRequestConfig::new(Some("https://api.test/down")).with_include_metadata(true);
+        config.retry_policy = RetryPolicy::none();
+        let block = HttpRequestBlock::new(config, Arc::new(StatusRequester(503)));
+        let err = block.execute(test_ctx(BlockInput::Empty)).unwrap_err();
+        assert!(
+            err.to_string().contains("http.server_error.5xx"),
+            "got: {err}"
+        );
+    }
+
+    #[test]
+    fn get_only_requester_rejects_other_methods() {
+        let block = HttpRequestBlock::new(
+            HttpRequestConfig::new(Some("https://ok.test")).with_method("PUT"),
+            Arc::new(MockRequester),
+        );
+        let err = block.execute(test_ctx(BlockInput::Empty)).unwrap_err();
+        assert!(err.to_string().contains("not supported"), "got: {err}");
+    }
+
+    #[test]
+    fn requester_without_methods_fails_instead_of_recursing() {
+        struct BareRequester;
+        impl HttpRequester for BareRequester {}
+
+        let req = HttpRequest::get("https://bare.test", Duration::from_secs(1), None);
+        let err = BareRequester.send(req).unwrap_err();
+        assert!(err.0.contains("neither request nor get"), "got: {err:?}");
+    }
+
+    struct TimingOutRequester {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl HttpRequester for TimingOutRequester {
+        fn get(
+            &self,
+            _url: &str,
+            _timeout: Duration,
+            _user_agent: Option<&str>,
+        ) -> Result<String, HttpRequestError> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Err(HttpRequestError("operation timed out".into()))
+        }
+    }
+
+    #[test]
+    fn http_request_timeout_is_retried() {
+        let requester = Arc::new(TimingOutRequester {
+            calls: Default::default(),
+        });
+        let mut config = HttpRequestConfig::new(Some("https://slow.test"));
+        config.retry_policy = RetryPolicy::exponential(2, 1, 1.0);
+        let block = HttpRequestBlock::new(config, requester.clone());
+        assert!(block.idempotent());
+        let err = block.execute(test_ctx(BlockInput::Empty)).unwrap_err();
+        assert!(err.to_string().contains("http.timeout"), "got: {err}");
+        assert_eq!(requester.calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
 }
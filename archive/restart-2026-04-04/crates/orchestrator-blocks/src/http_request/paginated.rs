@@ -0,0 +1,384 @@
+//! PaginatedHttp block: GET a JSON list API page by page and concatenate the items.
+//! Pages are followed by a cursor field in each response, or by `?page=n` until a page is empty,
+//! and never more than `max_pages`. Items are read from `items_field` (dots address nested
+//! objects) or from the response itself when it is an array.
+//! Pass your requester when registering: `register_paginated_http(registry, Arc::new(your_requester))`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use super::{
+    HttpRequest, HttpRequester, block_input_kind, resolve_url, send_with_retries, url_host,
+    validate_url_linkage,
+};
+use crate::input_binding::resolve_effective_input;
+use orchestrator_core::RetryPolicy;
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind,
+};
+
+/// How the next page is requested.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Pagination {
+    /// Send the value of `cursor_field` from each response back as query parameter
+    /// `cursor_param`; stop when the field is missing, null or empty.
+    Cursor {
+        cursor_field: String,
+        cursor_param: String,
+    },
+    /// Request `?{param}=n` counting up from `start`; stop at the first page with no items.
+    Page {
+        #[serde(default = "default_page_param")]
+        param: String,
+        #[serde(default = "default_first_page")]
+        start: u64,
+    },
+}
+
+fn default_page_param() -> String {
+    "page".to_string()
+}
+
+fn default_first_page() -> u64 {
+    1
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Self::Page {
+            param: default_page_param(),
+            start: default_first_page(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaginatedHttpConfig {
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub pagination: Pagination,
+    /// Field holding each page's items; `None` when the response is the array itself.
+    #[serde(default)]
+    pub items_field: Option<String>,
+    /// Upper bound on requests; pagination stops quietly when it is reached.
+    #[serde(default = "default_max_pages")]
+    pub max_pages: u32,
+    #[serde(default = "super::default_timeout_ms")]
+    pub timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    #[serde(default = "super::default_retry_policy")]
+    pub retry_policy: RetryPolicy,
+}
+
+fn default_max_pages() -> u32 {
+    20
+}
+
+impl PaginatedHttpConfig {
+    pub fn new(url: Option<impl Into<String>>, pagination: Pagination) -> Self {
+        Self {
+            url: url.map(Into::into),
+            pagination,
+            items_field: None,
+            max_pages: default_max_pages(),
+            timeout_ms: super::default_timeout_ms(),
+            user_agent: None,
+            retry_policy: super::default_retry_policy(),
+        }
+    }
+
+    pub fn with_items_field(mut self, items_field: impl Into<String>) -> Self {
+        self.items_field = Some(items_field.into());
+        self
+    }
+
+    pub fn with_max_pages(mut self, max_pages: u32) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+}
+
+pub struct PaginatedHttpBlock {
+    config: PaginatedHttpConfig,
+    requester: Arc<dyn HttpRequester>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl PaginatedHttpBlock {
+    pub fn new(config: PaginatedHttpConfig, requester: Arc<dyn HttpRequester>) -> Self {
+        Self {
+            config,
+            requester,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+
+    /// Items of one response page.
+    fn page_items(&self, page: &serde_json::Value) -> Result<Vec<serde_json::Value>, BlockError> {
+        let items = match self.config.items_field.as_deref() {
+            Some(field) => json_path(page, field).ok_or_else(|| {
+                BlockError::Other(format!("paginated_http response has no {:?} field", field))
+            })?,
+            None => page,
+        };
+        match items {
+            serde_json::Value::Array(items) => Ok(items.clone()),
+            serde_json::Value::Null => Ok(Vec::new()),
+            other => Err(BlockError::Other(format!(
+                "paginated_http items must be a JSON array, got {}",
+                other
+            ))),
+        }
+    }
+}
+
+fn json_path<'a>(value: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    path.split('.')
+        .try_fold(value, |current, segment| current.get(segment))
+}
+
+/// Append `name=value` to the query string of `url`, percent-encoding the value.
+fn with_query_param(url: &str, name: &str, value: &str) -> String {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    format!("{url}{separator}{name}={encoded}")
+}
+
+/// Cursor value that requests the next page, if the response carries one.
+fn next_cursor(page: &serde_json::Value, cursor_field: &str) -> Option<String> {
+    match json_path(page, cursor_field)? {
+        serde_json::Value::String(s) if !s.is_empty() => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+impl BlockExecutor for PaginatedHttpBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        if let BlockInput::Error { message } = &input {
+            return Err(BlockError::Other(message.clone()));
+        }
+        let base_url = resolve_url(
+            "paginated_http",
+            &self.input_from,
+            self.config.url.as_deref(),
+            &input,
+        )?;
+        let timeout = Duration::from_millis(self.config.timeout_ms.unwrap_or(30_000));
+        debug!(
+            event = "http.pagination_configured",
+            domain = "http",
+            block_type = "paginated_http",
+            input_kind = block_input_kind(&input),
+            url_host = url_host(&base_url).unwrap_or("unknown"),
+            max_pages = self.config.max_pages,
+            timeout_ms = timeout.as_millis() as u64
+        );
+
+        let mut items = Vec::new();
+        let mut next_url = match &self.config.pagination {
+            Pagination::Cursor { .. } => Some(base_url.clone()),
+            Pagination::Page { param, start } => {
+                Some(with_query_param(&base_url, param, &start.to_string()))
+            }
+        };
+        let mut pages = 0u32;
+        while let Some(url) = next_url.take() {
+            if pages == self.config.max_pages {
+                debug!(
+                    event = "http.pagination_max_pages",
+                    domain = "http",
+                    block_type = "paginated_http",
+                    pages = pages
+                );
+                break;
+            }
+            let body = send_with_retries(
+                self.requester.as_ref(),
+                "paginated_http",
+                &HttpRequest::get(&url, timeout, self.config.user_agent.as_deref()),
+                &self.config.retry_policy,
+                false,
+            )?
+            .body;
+            pages += 1;
+            let page: serde_json::Value = serde_json::from_str(&body).map_err(|e| {
+                BlockError::Other(format!("paginated_http page {} is not JSON: {}", pages, e))
+            })?;
+            let page_items = self.page_items(&page)?;
+            let empty = page_items.is_empty();
+            items.extend(page_items);
+            next_url = match &self.config.pagination {
+                Pagination::Cursor {
+                    cursor_field,
+                    cursor_param,
+                } => next_cursor(&page, cursor_field)
+                    .filter(|_| !empty)
+                    .map(|cursor| with_query_param(&base_url, cursor_param, &cursor)),
+                Pagination::Page { param, start } => (!empty).then(|| {
+                    with_query_param(&base_url, param, &(start + u64::from(pages)).to_string())
+                }),
+            };
+        }
+        debug!(
+            event = "http.pagination_finished",
+            domain = "http",
+            block_type = "paginated_http",
+            pages = pages,
+            items = items.len() as u64
+        );
+        Ok(BlockExecutionResult::Once(BlockOutput::Json {
+            value: serde_json::Value::Array(items),
+        }))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::Json, OutputMode::Once)
+    }
+
+    /// Requests are GETs, so repeating one after a timeout is safe.
+    fn idempotent(&self) -> bool {
+        true
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        validate_url_linkage(ctx, &self.input_from, self.config.url.as_deref())
+    }
+}
+
+/// Register the paginated_http block with a requester.
+pub fn register_paginated_http(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    requester: Arc<dyn HttpRequester>,
+) {
+    let requester = Arc::clone(&requester);
+    registry.register_custom("paginated_http", move |payload, input_from| {
+        let config: PaginatedHttpConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            PaginatedHttpBlock::new(config, Arc::clone(&requester)).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::HttpRequestError;
+    use super::*;
+    use crate::test_ctx;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    /// Serves canned bodies by exact URL and records every request.
+    struct PagedRequester {
+        pages: Vec<(&'static str, serde_json::Value)>,
+        requested: Mutex<Vec<String>>,
+    }
+
+    impl HttpRequester for PagedRequester {
+        fn get(
+            &self,
+            url: &str,
+            _timeout: Duration,
+            _user_agent: Option<&str>,
+        ) -> Result<String, HttpRequestError> {
+            self.requested.lock().unwrap().push(url.to_string());
+            self.pages
+                .iter()
+                .find(|(page_url, _)| *page_url == url)
+                .map(|(_, body)| body.to_string())
+                .ok_or_else(|| HttpRequestError(format!("status=404 for {url}")))
+        }
+    }
+
+    fn run(
+        config: PaginatedHttpConfig,
+        pages: Vec<(&'static str, serde_json::Value)>,
+    ) -> (serde_json::Value, Vec<String>) {
+        let requester = Arc::new(PagedRequester {
+            pages,
+            requested: Mutex::default(),
+        });
+        let out = PaginatedHttpBlock::new(config, requester.clone())
+            .execute(test_ctx(BlockInput::Empty))
+            .unwrap()
+            .into_once();
+        let BlockOutput::Json { value } = out else {
+            panic!("expected JSON output, got {:?}", out);
+        };
+        let requested = requester.requested.lock().unwrap().clone();
+        (value, requested)
+    }
+
+    #[test]
+    fn page_numbers_are_followed_until_an_empty_page() {
+        let config =
+            PaginatedHttpConfig::new(Some("https://api.test/items"), Pagination::default())
+                .with_items_field("data");
+        let (items, requested) = run(
+            config.clone(),
+            vec![
+                ("https://api.test/items?page=1", json!({ "data": [1, 2] })),
+                ("https://api.test/items?page=2", json!({ "data": [3] })),
+                ("https://api.test/items?page=3", json!({ "data": [] })),
+            ],
+        );
+        assert_eq!(items, json!([1, 2, 3]));
+        assert_eq!(requested.len(), 3);
+
+        let (items, requested) = run(
+            config.with_max_pages(1),
+            vec![("https://api.test/items?page=1", json!({ "data": [1, 2] }))],
+        );
+        assert_eq!(items, json!([1, 2]));
+        assert_eq!(requested, vec!["https://api.test/items?page=1"]);
+    }
+
+    #[test]
+    fn cursor_field_is_sent_back_until_absent() {
+        let config = PaginatedHttpConfig::new(
+            Some("https://api.test/items?limit=2"),
+            Pagination::Cursor {
+                cursor_field: "meta.next".into(),
+                cursor_param: "after".into(),
+            },
+        )
+        .with_items_field("items");
+        let (items, requested) = run(
+            config,
+            vec![
+                (
+                    "https://api.test/items?limit=2",
+                    json!({ "items": [{ "id": "a" }, { "id": "b" }], "meta": { "next": "b/2" } }),
+                ),
+                (
+                    "https://api.test/items?limit=2&after=b%2F2",
+                    json!({ "items": [{ "id": "c" }], "meta": { "next": null } }),
+                ),
+            ],
+        );
+        assert_eq!(items, json!([{ "id": "a" }, { "id": "b" }, { "id": "c" }]));
+        assert_eq!(requested.len(), 2);
+    }
+}
@@ -1,36 +1,61 @@
-use std::time::Duration;
-
-use super::{HttpRequestError, HttpRequester};
+use super::{HttpRequest, HttpRequestError, HttpRequester, HttpResponse};
 
 /// Default HTTP requester using reqwest blocking client.
 pub struct ReqwestHttpRequester;
 
 impl HttpRequester for ReqwestHttpRequester {
-    fn get(
-        &self,
-        url: &str,
-        timeout: Duration,
-        user_agent: Option<&str>,
-    ) -> Result<String, HttpRequestError> {
-        let ua = user_agent.unwrap_or("local-orchestration/0.1");
+    fn send(&self, req: HttpRequest) -> Result<HttpResponse, HttpRequestError> {
+        let ua = req
+            .header("User-Agent")
+            .unwrap_or("local-orchestration/0.1");
         let builder = reqwest::blocking::Client::builder()
-            .timeout(timeout)
+            .timeout(req.timeout)
             .user_agent(ua);
         let client = builder
             .build()
             .map_err(|e| HttpRequestError(e.to_string()))?;
-        let resp = client
-            .get(url)
+        let method = reqwest::Method::from_bytes(req.method.as_bytes())
+            .map_err(|e| HttpRequestError(format!("http_request method {}: {}", req.method, e)))?;
+        let mut request = client.request(method, &req.url);
+        for (name, value) in &req.headers {
+            if !name.eq_ignore_ascii_case("User-Agent") {
+                request = request.header(name, value);
+            }
+        }
+        if let Some(body) = &req.body {
+            request = request.json(body);
+        }
+        let resp = request
             .send()
             .map_err(|e| HttpRequestError(e.to_string()))?;
-        let status = resp.status();
-        let text = resp.text().map_err(|e| HttpRequestError(e.to_string()))?;
-        if !status.is_success() {
+        let status = resp.status().as_u16();
+        let headers = resp
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.as_str().to_string(),
+                    String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                )
+            })
+            .collect();
+        let body = resp.text().map_err(|e| HttpRequestError(e.to_string()))?;
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    fn request(&self, req: HttpRequest) -> Result<String, HttpRequestError> {
+        let (method, url) = (req.method.clone(), req.url.clone());
+        let resp = self.send(req)?;
+        if !resp.is_success() {
             return Err(HttpRequestError(format!(
-                "http_request {} failed: status={} body={}",
-                url, status, text
+                "http_request {} {} failed: status={} body={}",
+                method, url, resp.status, resp.body
             )));
         }
-        Ok(text)
+        Ok(resp.body)
     }
 }
@@ -0,0 +1,164 @@
+//! Id block: emit a fresh correlation id as `BlockOutput::String`, ignoring its input.
+//! `uuid` yields a UUID v4 (`6f1c…-4…`); `ulid` yields a 26-character Crockford base32 ULID
+//! that sorts by creation time. With a `seed`, the block emits the same id sequence on every
+//! run (ULID timestamps are then zero), which keeps tests and snapshots stable.
+//! Register with: `register_id(registry)`.
+
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockOutput,
+    OutputContract, OutputMode, ValidateContext, ValueKind,
+};
+
+/// Kind of id to generate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdFormat {
+    #[default]
+    Uuid,
+    Ulid,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdConfig {
+    #[serde(default)]
+    pub format: IdFormat,
+    /// Makes the id sequence reproducible. Leave unset in production.
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+impl IdConfig {
+    pub fn new(format: IdFormat) -> Self {
+        Self { format, seed: None }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+pub struct IdBlock {
+    config: IdConfig,
+    /// SplitMix64 state; only used when seeded.
+    state: Mutex<u64>,
+}
+
+impl IdBlock {
+    pub fn new(config: IdConfig) -> Self {
+        let state = Mutex::new(config.seed.unwrap_or_default());
+        Self { config, state }
+    }
+
+    fn seeded_bytes(&self) -> [u8; 16] {
+        let mut state = self.state.lock().expect("id block lock poisoned");
+        let mut next = || {
+            *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = *state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        };
+        let high = next();
+        let low = next();
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&high.to_be_bytes());
+        bytes[8..].copy_from_slice(&low.to_be_bytes());
+        bytes
+    }
+
+    fn generate(&self) -> String {
+        match (self.config.format, self.config.seed) {
+            (IdFormat::Uuid, None) => uuid::Uuid::new_v4().to_string(),
+            (IdFormat::Uuid, Some(_)) => uuid::Builder::from_random_bytes(self.seeded_bytes())
+                .into_uuid()
+                .to_string(),
+            (IdFormat::Ulid, None) => ulid::Ulid::new().to_string(),
+            (IdFormat::Ulid, Some(_)) => {
+                ulid::Ulid::from_parts(0, u128::from_be_bytes(self.seeded_bytes())).to_string()
+            }
+        }
+    }
+}
+
+impl BlockExecutor for IdBlock {
+    fn execute(&self, _ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        Ok(BlockExecutionResult::Once(BlockOutput::String {
+            value: self.generate(),
+        }))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::String, OutputMode::Once)
+    }
+}
+
+/// Register the id block.
+pub fn register_id(registry: &mut orchestrator_core::block::BlockRegistry) {
+    registry.register_custom("id", |payload, _input_from| {
+        let config: IdConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(IdBlock::new(config)))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use orchestrator_core::block::BlockInput;
+
+    fn ids(block: &IdBlock, n: usize) -> Vec<String> {
+        (0..n)
+            .map(|_| {
+                match block
+                    .execute(test_ctx(BlockInput::Empty))
+                    .unwrap()
+                    .into_once()
+                {
+                    BlockOutput::String { value } => value,
+                    other => panic!("expected String, got {:?}", other),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn id_block_emits_valid_uuid_and_ulid() {
+        for format in [IdFormat::Uuid, IdFormat::Ulid] {
+            for config in [IdConfig::new(format), IdConfig::new(format).with_seed(7)] {
+                let generated = ids(&IdBlock::new(config), 2);
+                assert_ne!(generated[0], generated[1]);
+                for id in generated {
+                    match format {
+                        IdFormat::Uuid => {
+                            let parsed = uuid::Uuid::parse_str(&id).unwrap();
+                            assert_eq!(parsed.get_version(), Some(uuid::Version::Random));
+                        }
+                        IdFormat::Ulid => {
+                            assert_eq!(id.len(), 26);
+                            ulid::Ulid::from_string(&id).unwrap();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn id_block_seeded_sequence_is_reproducible() {
+        for format in [IdFormat::Uuid, IdFormat::Ulid] {
+            let config = IdConfig::new(format).with_seed(42);
+            let first = ids(&IdBlock::new(config.clone()), 3);
+            assert_eq!(first, ids(&IdBlock::new(config), 3));
+            assert_ne!(
+                first,
+                ids(&IdBlock::new(IdConfig::new(format).with_seed(43)), 3)
+            );
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use orchestrator_core::block::{
     BlockError, BlockExecutionContext, BlockInput, InputContract, OutputContract, OutputMode,
     ValidateContext, ValueKind, ValueKindSet, resolve_forced_input,
@@ -17,6 +19,20 @@ pub fn resolve_effective_input(
     Ok(ctx.prev.clone())
 }
 
+/// Path carried by an input: a non-empty string or text, a JSON string, or `{"path": ...}`.
+pub fn path_from_input(input: &BlockInput) -> Option<PathBuf> {
+    match input {
+        BlockInput::String(s) | BlockInput::Text(s) if !s.is_empty() => {
+            Some(PathBuf::from(s.as_str()))
+        }
+        BlockInput::Json(v) => v
+            .as_str()
+            .map(PathBuf::from)
+            .or_else(|| v.get("path").and_then(|p| p.as_str()).map(PathBuf::from)),
+        _ => None,
+    }
+}
+
 #[allow(dead_code)]
 pub fn validate_expected_input(
     ctx: &ValidateContext<'_>,
@@ -0,0 +1,323 @@
+//! Join block: SQL-like join of two JSON arrays of objects on the `on` field, in edge order
+//! (first input is the left side). Each match yields one object with the left fields plus the
+//! right fields it lacks. Keys match when their JSON values are equal, so `1` does not match
+//! `"1"`; rows whose key is missing or null never match. `left` keeps unmatched left rows, with
+//! every right field set to null.
+//! Output order follows the left input, then the right input for multiple matches.
+//! Pass your joiner when registering: `register_join(registry, Arc::new(your_joiner))`.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::input_binding::resolve_effective_input;
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind,
+};
+
+/// Error from join operations.
+#[derive(Debug, Clone)]
+pub struct JoinError(pub String);
+
+impl std::fmt::Display for JoinError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for JoinError {}
+
+/// Which left rows are kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JoinMode {
+    /// Only rows with a match on both sides.
+    #[default]
+    Inner,
+    /// Every left row; unmatched ones get null right fields.
+    Left,
+}
+
+/// Joiner abstraction. Implement and pass when registering.
+pub trait Joiner: Send + Sync {
+    fn join(
+        &self,
+        mode: JoinMode,
+        on: &str,
+        left: &[serde_json::Value],
+        right: &[serde_json::Value],
+    ) -> Result<Vec<serde_json::Value>, JoinError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JoinConfig {
+    /// Join key present on both sides; dots address nested objects.
+    pub on: String,
+    #[serde(default)]
+    pub mode: JoinMode,
+}
+
+impl JoinConfig {
+    pub fn new(on: impl Into<String>) -> Self {
+        Self {
+            on: on.into(),
+            mode: JoinMode::default(),
+        }
+    }
+
+    pub fn with_mode(mut self, mode: JoinMode) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
+pub struct JoinBlock {
+    config: JoinConfig,
+    joiner: Arc<dyn Joiner>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl JoinBlock {
+    pub fn new(config: JoinConfig, joiner: Arc<dyn Joiner>) -> Self {
+        Self {
+            config,
+            joiner,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+fn json_array(output: &BlockOutput, side: &str) -> Result<Vec<serde_json::Value>, BlockError> {
+    match output {
+        BlockOutput::Json {
+            value: serde_json::Value::Array(items),
+        } => Ok(items.clone()),
+        BlockOutput::Text { value } | BlockOutput::String { value } => {
+            match serde_json::from_str(value) {
+                Ok(serde_json::Value::Array(items)) => Ok(items),
+                _ => Err(BlockError::Other(format!(
+                    "join {} input is not a JSON array",
+                    side
+                ))),
+            }
+        }
+        _ => Err(BlockError::Other(format!(
+            "join {} input is not a JSON array",
+            side
+        ))),
+    }
+}
+
+impl BlockExecutor for JoinBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        let outputs = match input {
+            BlockInput::Multi { outputs } if outputs.len() == 2 => outputs,
+            BlockInput::Multi { outputs } => {
+                return Err(BlockError::Other(format!(
+                    "join requires exactly two inputs, got {}",
+                    outputs.len()
+                )));
+            }
+            BlockInput::Error { message } => return Err(BlockError::Other(message)),
+            _ => {
+                return Err(BlockError::Other(
+                    "join requires exactly two inputs, got 1".into(),
+                ));
+            }
+        };
+        let left = json_array(&outputs[0], "left")?;
+        let right = json_array(&outputs[1], "right")?;
+        let rows = self
+            .joiner
+            .join(self.config.mode, &self.config.on, &left, &right)
+            .map_err(|e| BlockError::Other(e.0))?;
+        Ok(BlockExecutionResult::Once(BlockOutput::Json {
+            value: serde_json::Value::Array(rows),
+        }))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::Json, OutputMode::Once)
+    }
+}
+
+/// Default hash join over the right input.
+pub struct StdJoiner;
+
+/// The `on` field serialized as JSON, so equal keys are equal JSON values; `None` when the row
+/// lacks it or it is null.
+fn join_key(row: &serde_json::Value, on: &str) -> Option<String> {
+    let value = on
+        .split('.')
+        .try_fold(row, |current, segment| current.get(segment))?;
+    (!value.is_null()).then(|| value.to_string())
+}
+
+fn object<'a>(
+    row: &'a serde_json::Value,
+    side: &str,
+) -> Result<&'a serde_json::Map<String, serde_json::Value>, JoinError> {
+    row.as_object()
+        .ok_or_else(|| JoinError(format!("join {} row is not an object: {}", side, row)))
+}
+
+impl Joiner for StdJoiner {
+    fn join(
+        &self,
+        mode: JoinMode,
+        on: &str,
+        left: &[serde_json::Value],
+        right: &[serde_json::Value],
+    ) -> Result<Vec<serde_json::Value>, JoinError> {
+        let mut index: HashMap<String, Vec<&serde_json::Map<String, serde_json::Value>>> =
+            HashMap::new();
+        let mut right_fields = BTreeSet::new();
+        for row in right {
+            let fields = object(row, "right")?;
+            right_fields.extend(fields.keys().cloned());
+            if let Some(key) = join_key(row, on) {
+                index.entry(key).or_default().push(fields);
+            }
+        }
+
+        let mut rows = Vec::new();
+        for row in left {
+            let fields = object(row, "left")?;
+            let matches = join_key(row, on).and_then(|key| index.get(&key));
+            match matches {
+                Some(matches) => {
+                    for other in matches {
+                        let mut merged = fields.clone();
+                        for (name, value) in other.iter() {
+                            merged.entry(name.clone()).or_insert_with(|| value.clone());
+                        }
+                        rows.push(serde_json::Value::Object(merged));
+                    }
+                }
+                None if mode == JoinMode::Left => {
+                    let mut merged = fields.clone();
+                    for name in &right_fields {
+                        merged
+                            .entry(name.clone())
+                            .or_insert(serde_json::Value::Null);
+                    }
+                    rows.push(serde_json::Value::Object(merged));
+                }
+                None => {}
+            }
+        }
+        Ok(rows)
+    }
+}
+
+/// Register the join block with a joiner.
+pub fn register_join(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    joiner: Arc<dyn Joiner>,
+) {
+    let joiner = Arc::clone(&joiner);
+    registry.register_custom("join", move |payload, input_from| {
+        let config: JoinConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            JoinBlock::new(config, Arc::clone(&joiner)).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use serde_json::json;
+
+    fn inputs() -> BlockInput {
+        BlockInput::Multi {
+            outputs: vec![
+                BlockOutput::Json {
+                    value: json!([
+                        { "id": 1, "title": "Rust 2024" },
+                        { "id": 2, "title": "Orphan story" },
+                        { "id": 3, "title": "Zig release" },
+                    ]),
+                },
+                BlockOutput::Json {
+                    value: json!([
+                        { "id": 3, "score": 7, "title": "ignored" },
+                        { "id": 1, "score": 9 },
+                        { "id": 4, "score": 1 },
+                    ]),
+                },
+            ],
+        }
+    }
+
+    fn run(config: JoinConfig) -> BlockOutput {
+        JoinBlock::new(config, Arc::new(StdJoiner))
+            .execute(test_ctx(inputs()))
+            .unwrap()
+            .into_once()
+    }
+
+    #[test]
+    fn inner_join_keeps_only_matched_rows() {
+        assert_eq!(
+            run(JoinConfig::new("id")),
+            BlockOutput::Json {
+                value: json!([
+                    { "id": 1, "title": "Rust 2024", "score": 9 },
+                    { "id": 3, "title": "Zig release", "score": 7 },
+                ])
+            }
+        );
+    }
+
+    #[test]
+    fn left_join_fills_unmatched_rows_with_nulls() {
+        assert_eq!(
+            run(JoinConfig::new("id").with_mode(JoinMode::Left)),
+            BlockOutput::Json {
+                value: json!([
+                    { "id": 1, "title": "Rust 2024", "score": 9 },
+                    { "id": 2, "title": "Orphan story", "score": null },
+                    { "id": 3, "title": "Zig release", "score": 7 },
+                ])
+            }
+        );
+    }
+
+    #[test]
+    fn join_keys_compare_as_json_values() {
+        let left = [
+            json!({ "id": 1, "name": "number" }),
+            json!({ "id": "1", "name": "string" }),
+        ];
+        let right = [
+            json!({ "id": "1", "score": 5 }),
+            json!({ "id": 1.0, "score": 6 }),
+        ];
+        assert_eq!(
+            StdJoiner.join(JoinMode::Left, "id", &left, &right).unwrap(),
+            vec![
+                json!({ "id": 1, "name": "number", "score": null }),
+                json!({ "id": "1", "name": "string", "score": 5 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn join_requires_two_inputs() {
+        let err = JoinBlock::new(JoinConfig::new("id"), Arc::new(StdJoiner))
+            .execute(test_ctx(BlockInput::Json(json!([]))))
+            .unwrap_err();
+        assert!(err.to_string().contains("exactly two"), "got: {err}");
+    }
+}
@@ -0,0 +1,173 @@
+//! JsonExtract block: apply a JSON Pointer (RFC 6901, e.g. `/data/items/0/title`) to a JSON input
+//! and output the value it points to. An empty pointer selects the whole document. When nothing is
+//! found the block outputs `Empty`, or fails when `missing_is_error` is set.
+//! Register with: `register_json_extract(registry)`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::input_binding::{
+    resolve_effective_input, validate_expected_input, validate_single_input_mode,
+};
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonExtractConfig {
+    /// JSON Pointer; `~1` escapes `/` and `~0` escapes `~` inside a key.
+    pub pointer: String,
+    #[serde(default)]
+    pub missing_is_error: bool,
+}
+
+impl JsonExtractConfig {
+    pub fn new(pointer: impl Into<String>) -> Self {
+        Self {
+            pointer: pointer.into(),
+            missing_is_error: false,
+        }
+    }
+
+    pub fn with_missing_is_error(mut self, missing_is_error: bool) -> Self {
+        self.missing_is_error = missing_is_error;
+        self
+    }
+
+    fn validate_pointer(&self) -> Result<(), BlockError> {
+        if self.pointer.is_empty() || self.pointer.starts_with('/') {
+            Ok(())
+        } else {
+            Err(BlockError::Other(format!(
+                "json_extract pointer must be empty or start with '/', got {:?}",
+                self.pointer
+            )))
+        }
+    }
+}
+
+pub struct JsonExtractBlock {
+    config: JsonExtractConfig,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl JsonExtractBlock {
+    pub fn new(config: JsonExtractConfig) -> Self {
+        Self {
+            config,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+impl BlockExecutor for JsonExtractBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        self.config.validate_pointer()?;
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        let document = match input {
+            BlockInput::Json(value) => value,
+            BlockInput::Error { message } => return Err(BlockError::Other(message)),
+            other => {
+                return Err(BlockError::Other(format!(
+                    "json_extract expects JSON input, got {:?}",
+                    other.value_kind()
+                )));
+            }
+        };
+        let output = match document.pointer(&self.config.pointer) {
+            Some(value) => BlockOutput::Json {
+                value: value.clone(),
+            },
+            None if self.config.missing_is_error => {
+                return Err(BlockError::Other(format!(
+                    "json_extract pointer {:?} did not resolve",
+                    self.config.pointer
+                )));
+            }
+            None => BlockOutput::Empty,
+        };
+        Ok(BlockExecutionResult::Once(output))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        let mut kinds = ValueKindSet::singleton(ValueKind::Json);
+        if !self.config.missing_is_error {
+            kinds |= ValueKindSet::singleton(ValueKind::Empty);
+        }
+        OutputContract {
+            kinds,
+            mode: OutputMode::Once,
+        }
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        self.config.validate_pointer()?;
+        validate_single_input_mode(ctx)?;
+        validate_expected_input(ctx, ValueKindSet::singleton(ValueKind::Json))
+    }
+}
+
+/// Register the json_extract block.
+pub fn register_json_extract(registry: &mut orchestrator_core::block::BlockRegistry) {
+    registry.register_custom("json_extract", |payload, input_from| {
+        let config: JsonExtractConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            JsonExtractBlock::new(config).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use serde_json::json;
+
+    fn response() -> BlockInput {
+        BlockInput::Json(json!({
+            "data": { "items": [{ "title": "Rust 2024" }], "a/b": 1 }
+        }))
+    }
+
+    #[test]
+    fn json_extract_returns_pointed_value() {
+        let out = JsonExtractBlock::new(JsonExtractConfig::new("/data/items/0/title"))
+            .execute(test_ctx(response()))
+            .unwrap()
+            .into_once();
+        assert_eq!(
+            out,
+            BlockOutput::Json {
+                value: json!("Rust 2024")
+            }
+        );
+
+        let out = JsonExtractBlock::new(JsonExtractConfig::new("/data/a~1b"))
+            .execute(test_ctx(response()))
+            .unwrap()
+            .into_once();
+        assert_eq!(out, BlockOutput::Json { value: json!(1) });
+    }
+
+    #[test]
+    fn json_extract_missing_pointer_is_empty_or_error() {
+        let out = JsonExtractBlock::new(JsonExtractConfig::new("/data/missing"))
+            .execute(test_ctx(response()))
+            .unwrap()
+            .into_once();
+        assert_eq!(out, BlockOutput::Empty);
+
+        let err = JsonExtractBlock::new(
+            JsonExtractConfig::new("/data/missing").with_missing_is_error(true),
+        )
+        .execute(test_ctx(response()))
+        .unwrap_err();
+        assert!(err.to_string().contains("/data/missing"), "got: {err}");
+    }
+}
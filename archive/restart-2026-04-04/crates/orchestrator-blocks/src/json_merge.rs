@@ -0,0 +1,277 @@
+//! JsonMerge block: merge the JSON objects from all inputs into one, in edge order, with later
+//! inputs overriding earlier keys. `shallow` replaces top-level values; `deep` merges nested
+//! objects recursively and concatenates arrays. Unlike Combine, keys are not grouped by source.
+//! Pass your merger when registering: `register_json_merge(registry, Arc::new(your_merger))`.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::input_binding::resolve_effective_input;
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind,
+};
+
+/// Error from JSON merge operations.
+#[derive(Debug, Clone)]
+pub struct JsonMergeError(pub String);
+
+impl std::fmt::Display for JsonMergeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for JsonMergeError {}
+
+/// How values under the same key are combined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    /// Later top-level values replace earlier ones.
+    #[default]
+    Shallow,
+    /// Nested objects merge key by key; arrays are concatenated; other values are replaced.
+    #[serde(alias = "deep")]
+    DeepRecursive,
+}
+
+/// JSON merger abstraction. Implement and pass when registering.
+pub trait JsonMerger: Send + Sync {
+    fn merge(
+        &self,
+        strategy: MergeStrategy,
+        objects: Vec<serde_json::Map<String, serde_json::Value>>,
+    ) -> Result<serde_json::Map<String, serde_json::Value>, JsonMergeError>;
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonMergeConfig {
+    #[serde(default)]
+    pub strategy: MergeStrategy,
+}
+
+impl JsonMergeConfig {
+    pub fn new(strategy: MergeStrategy) -> Self {
+        Self { strategy }
+    }
+}
+
+pub struct JsonMergeBlock {
+    config: JsonMergeConfig,
+    merger: Arc<dyn JsonMerger>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl JsonMergeBlock {
+    pub fn new(config: JsonMergeConfig, merger: Arc<dyn JsonMerger>) -> Self {
+        Self {
+            config,
+            merger,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+fn json_object(
+    input: BlockInput,
+    index: usize,
+) -> Result<serde_json::Map<String, serde_json::Value>, BlockError> {
+    let value = match input {
+        BlockInput::Json(value) => value,
+        BlockInput::String(s) | BlockInput::Text(s) => {
+            serde_json::from_str(&s).unwrap_or(serde_json::Value::String(s))
+        }
+        other => {
+            return Err(BlockError::Other(format!(
+                "json_merge input {} is not a JSON object: {:?}",
+                index,
+                other.value_kind()
+            )));
+        }
+    };
+    match value {
+        serde_json::Value::Object(map) => Ok(map),
+        other => Err(BlockError::Other(format!(
+            "json_merge input {} is not a JSON object: {}",
+            index, other
+        ))),
+    }
+}
+
+impl BlockExecutor for JsonMergeBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        let inputs = match input {
+            BlockInput::Multi { outputs } => outputs.into_iter().map(BlockInput::from).collect(),
+            BlockInput::Error { message } => return Err(BlockError::Other(message)),
+            BlockInput::Empty => Vec::new(),
+            other => vec![other],
+        };
+        let objects = inputs
+            .into_iter()
+            .enumerate()
+            .map(|(index, input)| json_object(input, index))
+            .collect::<Result<Vec<_>, _>>()?;
+        let merged = self
+            .merger
+            .merge(self.config.strategy, objects)
+            .map_err(|e| BlockError::Other(e.0))?;
+        Ok(BlockExecutionResult::Once(BlockOutput::Json {
+            value: serde_json::Value::Object(merged),
+        }))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::Json, OutputMode::Once)
+    }
+}
+
+/// Default merger over `serde_json` maps.
+pub struct StdJsonMerger;
+
+fn deep_merge(target: &mut serde_json::Value, source: serde_json::Value) {
+    match (target, source) {
+        (serde_json::Value::Object(target), serde_json::Value::Object(source)) => {
+            for (key, value) in source {
+                match target.get_mut(&key) {
+                    Some(existing) => deep_merge(existing, value),
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (serde_json::Value::Array(target), serde_json::Value::Array(source)) => {
+            target.extend(source);
+        }
+        (target, source) => *target = source,
+    }
+}
+
+impl JsonMerger for StdJsonMerger {
+    fn merge(
+        &self,
+        strategy: MergeStrategy,
+        objects: Vec<serde_json::Map<String, serde_json::Value>>,
+    ) -> Result<serde_json::Map<String, serde_json::Value>, JsonMergeError> {
+        let mut merged = serde_json::Value::Object(serde_json::Map::new());
+        for object in objects {
+            match strategy {
+                MergeStrategy::Shallow => {
+                    if let serde_json::Value::Object(map) = &mut merged {
+                        map.extend(object);
+                    }
+                }
+                MergeStrategy::DeepRecursive => {
+                    deep_merge(&mut merged, serde_json::Value::Object(object))
+                }
+            }
+        }
+        match merged {
+            serde_json::Value::Object(map) => Ok(map),
+            _ => Err(JsonMergeError("json_merge result is not an object".into())),
+        }
+    }
+}
+
+/// Register the json_merge block with a merger.
+pub fn register_json_merge(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    merger: Arc<dyn JsonMerger>,
+) {
+    let merger = Arc::clone(&merger);
+    registry.register_custom("json_merge", move |payload, input_from| {
+        let config: JsonMergeConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            JsonMergeBlock::new(config, Arc::clone(&merger)).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use serde_json::json;
+
+    fn merge(strategy: MergeStrategy, values: Vec<serde_json::Value>) -> serde_json::Value {
+        let input = BlockInput::Multi {
+            outputs: values
+                .into_iter()
+                .map(|value| BlockOutput::Json { value })
+                .collect(),
+        };
+        match JsonMergeBlock::new(JsonMergeConfig::new(strategy), Arc::new(StdJsonMerger))
+            .execute(test_ctx(input))
+            .unwrap()
+            .into_once()
+        {
+            BlockOutput::Json { value } => value,
+            other => panic!("expected Json, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn json_merge_later_inputs_override_overlapping_keys() {
+        let values = vec![
+            json!({ "name": "digest", "count": 1 }),
+            json!({ "count": 2, "ok": true }),
+        ];
+        let expected = json!({ "name": "digest", "count": 2, "ok": true });
+        assert_eq!(merge(MergeStrategy::Shallow, values.clone()), expected);
+        assert_eq!(merge(MergeStrategy::DeepRecursive, values), expected);
+    }
+
+    #[test]
+    fn json_merge_nested_objects_merge_only_when_deep() {
+        let values = vec![
+            json!({ "smtp": { "host": "mail", "port": 25 } }),
+            json!({ "smtp": { "port": 587 } }),
+        ];
+        assert_eq!(
+            merge(MergeStrategy::Shallow, values.clone()),
+            json!({ "smtp": { "port": 587 } })
+        );
+        assert_eq!(
+            merge(MergeStrategy::DeepRecursive, values),
+            json!({ "smtp": { "host": "mail", "port": 587 } })
+        );
+    }
+
+    #[test]
+    fn json_merge_arrays_concatenate_when_deep_and_replace_when_shallow() {
+        let values = vec![
+            json!({ "tags": ["rust"], "meta": { "ids": [1] } }),
+            json!({ "tags": ["zig"], "meta": { "ids": [2] } }),
+        ];
+        assert_eq!(
+            merge(MergeStrategy::Shallow, values.clone()),
+            json!({ "tags": ["zig"], "meta": { "ids": [2] } })
+        );
+        assert_eq!(
+            merge(MergeStrategy::DeepRecursive, values),
+            json!({ "tags": ["rust", "zig"], "meta": { "ids": [1, 2] } })
+        );
+
+        let err = JsonMergeBlock::new(JsonMergeConfig::default(), Arc::new(StdJsonMerger))
+            .execute(test_ctx(BlockInput::Multi {
+                outputs: vec![
+                    BlockOutput::Json { value: json!({}) },
+                    BlockOutput::Json { value: json!([1]) },
+                ],
+            }))
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("input 1 is not a JSON object"),
+            "got: {err}"
+        );
+    }
+}
@@ -0,0 +1,210 @@
+//! JsonSchemaValidate block: check JSON input against a JSON Schema and pass it through unchanged.
+//! String and text inputs are parsed as JSON first. Failures list every violation as
+//! `<instance path>: <message>`, so a bad upstream payload stops the run before email or AI blocks.
+//! Pass your validator when registering: `register_json_schema_validate(registry, Arc::new(your_validator))`.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::input_binding::{resolve_effective_input, validate_expected_input};
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
+};
+
+/// Error from schema validation.
+#[derive(Debug, Clone)]
+pub struct JsonSchemaError(pub String);
+
+impl std::fmt::Display for JsonSchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for JsonSchemaError {}
+
+/// Schema validator abstraction. Implement and pass when registering.
+pub trait SchemaValidator: Send + Sync {
+    fn validate(
+        &self,
+        schema: &serde_json::Value,
+        instance: &serde_json::Value,
+    ) -> Result<(), JsonSchemaError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonSchemaValidateConfig {
+    /// JSON Schema document; the draft is taken from `$schema` (latest when absent).
+    pub schema: serde_json::Value,
+}
+
+impl JsonSchemaValidateConfig {
+    pub fn new(schema: serde_json::Value) -> Self {
+        Self { schema }
+    }
+}
+
+pub struct JsonSchemaValidateBlock {
+    config: JsonSchemaValidateConfig,
+    validator: Arc<dyn SchemaValidator>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl JsonSchemaValidateBlock {
+    pub fn new(config: JsonSchemaValidateConfig, validator: Arc<dyn SchemaValidator>) -> Self {
+        Self {
+            config,
+            validator,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+impl BlockExecutor for JsonSchemaValidateBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        let value = match input {
+            BlockInput::Json(value) => value,
+            BlockInput::String(s) | BlockInput::Text(s) => serde_json::from_str(&s)
+                .map_err(|e| BlockError::Other(format!("json_schema_validate input: {}", e)))?,
+            BlockInput::Error { message } => return Err(BlockError::Other(message)),
+            other => {
+                return Err(BlockError::Other(format!(
+                    "json_schema_validate expects JSON input, got {:?}",
+                    other.value_kind()
+                )));
+            }
+        };
+        self.validator
+            .validate(&self.config.schema, &value)
+            .map_err(|e| BlockError::Other(e.0))?;
+        Ok(BlockExecutionResult::Once(BlockOutput::Json { value }))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::Json, OutputMode::Once)
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        validate_expected_input(
+            ctx,
+            ValueKindSet::singleton(ValueKind::Json)
+                | ValueKindSet::singleton(ValueKind::String)
+                | ValueKindSet::singleton(ValueKind::Text),
+        )
+    }
+}
+
+/// Default implementation using the `jsonschema` crate.
+pub struct JsonschemaValidator;
+
+impl SchemaValidator for JsonschemaValidator {
+    fn validate(
+        &self,
+        schema: &serde_json::Value,
+        instance: &serde_json::Value,
+    ) -> Result<(), JsonSchemaError> {
+        let validator = jsonschema::validator_for(schema)
+            .map_err(|e| JsonSchemaError(format!("invalid JSON schema: {}", e)))?;
+        let violations: Vec<String> = validator
+            .iter_errors(instance)
+            .map(|e| {
+                let path = e.instance_path.to_string();
+                let path = if path.is_empty() { "/" } else { &path };
+                format!("{}: {}", path, e)
+            })
+            .collect();
+        if violations.is_empty() {
+            return Ok(());
+        }
+        Err(JsonSchemaError(format!(
+            "json_schema_validate failed: {}",
+            violations.join("; ")
+        )))
+    }
+}
+
+/// Register the json_schema_validate block with a validator.
+pub fn register_json_schema_validate(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    validator: Arc<dyn SchemaValidator>,
+) {
+    let validator = Arc::clone(&validator);
+    registry.register_custom("json_schema_validate", move |payload, input_from| {
+        let config: JsonSchemaValidateConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            JsonSchemaValidateBlock::new(config, Arc::clone(&validator))
+                .with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use serde_json::json;
+
+    fn block() -> JsonSchemaValidateBlock {
+        JsonSchemaValidateBlock::new(
+            JsonSchemaValidateConfig::new(json!({
+                "type": "object",
+                "required": ["title", "items"],
+                "properties": {
+                    "title": { "type": "string" },
+                    "items": { "type": "array", "items": { "type": "integer" } }
+                }
+            })),
+            Arc::new(JsonschemaValidator),
+        )
+    }
+
+    #[test]
+    fn valid_document_passes_through() {
+        let doc = json!({ "title": "daily", "items": [1, 2] });
+        let out = block()
+            .execute(test_ctx(BlockInput::Json(doc.clone())))
+            .unwrap()
+            .into_once();
+        assert_eq!(out, BlockOutput::Json { value: doc.clone() });
+
+        let out = block()
+            .execute(test_ctx(BlockInput::Text(doc.to_string())))
+            .unwrap()
+            .into_once();
+        assert_eq!(out, BlockOutput::Json { value: doc });
+    }
+
+    #[test]
+    fn missing_required_field_fails_at_root() {
+        let err = block()
+            .execute(test_ctx(BlockInput::Json(json!({ "items": [] }))))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("/: "), "got: {err}");
+        assert!(
+            err.contains("\"title\" is a required property"),
+            "got: {err}"
+        );
+    }
+
+    #[test]
+    fn type_mismatch_reports_instance_path() {
+        let err = block()
+            .execute(test_ctx(BlockInput::Json(
+                json!({ "title": "daily", "items": [1, "two"] }),
+            )))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("/items/1: "), "got: {err}");
+        assert!(err.contains("is not of type \"integer\""), "got: {err}");
+    }
+}
@@ -11,41 +11,114 @@
 //!   calling each `register_XXX(registry, impl)` for the blocks you use. Third-party blocks use
 //!   `BlockConfig::Custom { type_id, payload }` and their own `registry.register_custom(type_id, factory)`.
 
+mod aggregate;
 mod ai_generate;
+mod approval;
+mod base64_codec;
 mod block;
+mod chat_webhook;
 mod combine;
+mod conditional;
 mod cron;
+mod csv_read;
 mod custom_transform;
+mod dedupe;
+mod delay;
+mod env_file;
+mod file_chunk;
 mod file_read;
+mod file_watch;
 mod file_write;
+mod gzip;
 mod http_request;
+mod id;
 mod input_binding;
+mod join;
+mod json_extract;
+mod json_merge;
+mod json_schema_validate;
 mod list_directory;
 mod markdown_to_html;
+mod metrics;
+mod rate_limit;
 mod rss_parse;
 mod select_first;
 mod send_email;
+mod setop;
 mod split_by_keys;
 mod split_lines;
+mod sqlite;
+mod stdin;
+mod switch;
 mod template_handlebars;
+mod textfile_metrics;
+mod xml_parse;
 
+pub use aggregate::{
+    AggregateBlock, AggregateConfig, AggregateError, AggregateOp, Aggregator, StdAggregator,
+    register_aggregate,
+};
 pub use ai_generate::{
     AiGenerateBlock, AiGenerateConfig, AiGenerateError, AiGenerator, StdAiGenerator,
-    register_ai_generate,
+    SummarizeBlock, SummarizeConfig, TranslateBlock, TranslateConfig, register_ai_generate,
+    register_summarize, register_translate,
+};
+pub use approval::{ApprovalBlock, ApprovalConfig, register_approval};
+pub use base64_codec::{
+    Base64Block, Base64Codec, Base64Config, Base64Error, Base64Mode, StdBase64Codec,
+    register_base64,
 };
 pub use block::Block;
+pub use chat_webhook::{
+    ChatWebhookBlock, ChatWebhookConfig, ChatWebhookError, ReqwestWebhookPoster, ThrottlePolicy,
+    WebhookPoster, WebhookRateLimiter, register_chat_webhook,
+};
 pub use combine::{
-    CombineBlock, CombineConfig, CombineError, CombineStrategy, KeyedCombineStrategy,
+    CombineBlock, CombineConfig, CombineError, CombineStrategy, KeyedCombineStrategy, MultiSource,
+};
+pub use conditional::{ConditionalBlock, ConditionalConfig, RuleKind, register_conditional};
+pub use cron::{CronBlock, CronConfig, CronError, CronRunner, StdCronRunner, tick_output};
+pub use csv_read::{
+    CsvReadBlock, CsvReadConfig, CsvReadError, CsvReader, StdCsvReader, register_csv_read,
 };
-pub use cron::{CronBlock, CronConfig, CronError, CronRunner, StdCronRunner};
 pub use custom_transform::{
     CustomTransformBlock, CustomTransformConfig, CustomTransformError, IdentityTransform, Transform,
 };
+pub use dedupe::{
+    DedupeBlock, DedupeConfig, DedupeError, FileSeenStore, SeenStore, register_dedupe,
+};
+pub use delay::{DelayBlock, DelayConfig, register_delay};
+pub use env_file::{
+    DotenvFileLoader, EnvFileBlock, EnvFileConfig, EnvFileError, EnvFileLoader, register_env_file,
+};
+pub use file_chunk::{
+    ChunkBy, FileChunkBlock, FileChunkConfig, FileChunkError, FileChunker, StdFileChunker,
+    register_file_chunk,
+};
 pub use file_read::{FileReadBlock, FileReadConfig, FileReadError, FileReader, StdFileReader};
+pub use file_watch::{
+    FileWatchBlock, FileWatchConfig, FileWatchError, FileWatcher, NotifyFileWatcher,
+    register_file_watch,
+};
 pub use file_write::{FileWriteBlock, FileWriteConfig, FileWriteError, FileWriter, StdFileWriter};
+pub use gzip::{
+    Flate2GzipCodec, GzipBlock, GzipCodec, GzipConfig, GzipError, GzipMode, register_gzip,
+};
 pub use http_request::{
-    HttpRequestBlock, HttpRequestConfig, HttpRequestError, HttpRequester, ReqwestHttpRequester,
-    register_http_request,
+    HttpRequest, HttpRequestBlock, HttpRequestConfig, HttpRequestError, HttpRequester,
+    HttpResponse, PaginatedHttpBlock, PaginatedHttpConfig, Pagination, ReqwestHttpRequester,
+    register_http_request, register_paginated_http,
+};
+pub use id::{IdBlock, IdConfig, IdFormat, register_id};
+pub use join::{JoinBlock, JoinConfig, JoinError, JoinMode, Joiner, StdJoiner, register_join};
+pub use json_extract::{JsonExtractBlock, JsonExtractConfig, register_json_extract};
+pub use json_merge::{
+    JsonMergeBlock, JsonMergeConfig, JsonMergeError, JsonMerger, MergeStrategy, StdJsonMerger,
+    register_json_merge,
+};
+pub use json_schema_validate::{
+    JsonSchemaError, JsonSchemaValidateBlock, JsonSchemaValidateConfig, JsonschemaValidator,
+    SchemaValidator, register_json_schema_validate,
 };
 pub use list_directory::{
     DirectoryLister, ListDirectoryBlock, ListDirectoryConfig, ListDirectoryError,
@@ -55,6 +128,11 @@ pub use markdown_to_html::{
     MarkdownError, MarkdownToHtml, MarkdownToHtmlBlock, MarkdownToHtmlConfig,
     PulldownMarkdownRenderer, register_markdown_to_html,
 };
+pub use metrics::{
+    Metric, MetricKind, MetricsBlock, MetricsConfig, MetricsError, MetricsSink, UdpStatsdSink,
+    register_metrics,
+};
+pub use rate_limit::{RateLimited, RateLimiter};
 pub use rss_parse::{
     FeedRsParser, RssParseBlock, RssParseConfig, RssParseError, RssParser, register_rss_parse,
 };
@@ -65,6 +143,9 @@ pub use send_email::{
     EnvSmtpMailer, SendEmail, SendEmailBlock, SendEmailConfig, SendEmailError, register_send_email,
     register_send_email_env,
 };
+pub use setop::{
+    SetOpBlock, SetOpConfig, SetOpError, SetOpMode, SetOperator, StdSetOperator, register_setop,
+};
 pub use split_by_keys::{
     KeyExtractSplitStrategy, SplitByKeysBlock, SplitByKeysConfig, SplitByKeysError,
     SplitByKeysStrategy,
@@ -72,10 +153,29 @@ pub use split_by_keys::{
 pub use split_lines::{
     LineSplitStrategy, SplitLinesBlock, SplitLinesConfig, SplitLinesError, StdLineSplitter,
 };
+#[cfg(feature = "sqlite")]
+pub use sqlite::RusqliteStore;
+pub use sqlite::{
+    SqliteBlock, SqliteConfig, SqliteError, SqliteOperation, SqliteStore, register_sqlite,
+};
+pub use stdin::{
+    ProcessStdin, StdinBlock, StdinConfig, StdinError, StdinMode, StdinSource, read_text,
+    register_stdin,
+};
+pub use switch::{
+    FieldValueSwitch, SwitchBlock, SwitchConfig, SwitchError, SwitchStrategy, register_switch,
+};
 pub use template_handlebars::{
     HandlebarsTemplateRenderer, TemplateError, TemplateHandlebarsBlock, TemplateHandlebarsConfig,
     TemplateRenderer,
 };
+pub use textfile_metrics::{
+    AtomicTextfileWriter, TextfileMetricsBlock, TextfileMetricsConfig, TextfileMetricsError,
+    TextfileWriter, register_textfile_metrics,
+};
+pub use xml_parse::{
+    QuickXmlParser, XmlParseBlock, XmlParseConfig, XmlParseError, XmlParser, register_xml_parse,
+};
 
 pub use orchestrator_core::{
     BlockConfig, BlockId, BlockOutput, BlockRegistry, RetryPolicy, RunError, Workflow,
@@ -87,7 +187,11 @@ pub use orchestrator_core::{
 pub fn default_registry() -> BlockRegistry {
     let mut r = BlockRegistry::new();
     ai_generate::register_ai_generate(&mut r, std::sync::Arc::new(ai_generate::StdAiGenerator));
+    ai_generate::register_summarize(&mut r, std::sync::Arc::new(ai_generate::StdAiGenerator));
+    ai_generate::register_translate(&mut r, std::sync::Arc::new(ai_generate::StdAiGenerator));
+    approval::register_approval(&mut r);
     cron::register_cron(&mut r, std::sync::Arc::new(cron::StdCronRunner));
+    file_watch::register_file_watch(&mut r, std::sync::Arc::new(file_watch::NotifyFileWatcher));
     list_directory::register_list_directory(
         &mut r,
         std::sync::Arc::new(list_directory::StdDirectoryLister),
@@ -102,18 +206,54 @@ pub fn default_registry() -> BlockRegistry {
         std::sync::Arc::new(split_by_keys::KeyExtractSplitStrategy),
     );
     split_lines::register_split_lines(&mut r, std::sync::Arc::new(split_lines::StdLineSplitter));
+    switch::register_switch(&mut r, std::sync::Arc::new(switch::FieldValueSwitch));
+    conditional::register_conditional(&mut r);
     file_write::register_file_write(&mut r, std::sync::Arc::new(file_write::StdFileWriter));
     markdown_to_html::register_markdown_to_html(
         &mut r,
         std::sync::Arc::new(markdown_to_html::PulldownMarkdownRenderer),
     );
     file_read::register_file_read(&mut r, std::sync::Arc::new(file_read::StdFileReader));
+    csv_read::register_csv_read(&mut r, std::sync::Arc::new(csv_read::StdCsvReader));
     http_request::register_http_request(
         &mut r,
         std::sync::Arc::new(http_request::ReqwestHttpRequester),
     );
+    http_request::register_paginated_http(
+        &mut r,
+        std::sync::Arc::new(http_request::ReqwestHttpRequester),
+    );
     rss_parse::register_rss_parse(&mut r, std::sync::Arc::new(rss_parse::FeedRsParser));
+    xml_parse::register_xml_parse(&mut r, std::sync::Arc::new(xml_parse::QuickXmlParser));
+    aggregate::register_aggregate(&mut r, std::sync::Arc::new(aggregate::StdAggregator));
+    base64_codec::register_base64(&mut r, std::sync::Arc::new(base64_codec::StdBase64Codec));
+    gzip::register_gzip(&mut r, std::sync::Arc::new(gzip::Flate2GzipCodec));
+    dedupe::register_dedupe(&mut r, std::sync::Arc::new(dedupe::FileSeenStore));
+    env_file::register_env_file(&mut r, std::sync::Arc::new(env_file::DotenvFileLoader));
+    metrics::register_metrics(&mut r, std::sync::Arc::new(metrics::UdpStatsdSink));
+    textfile_metrics::register_textfile_metrics(
+        &mut r,
+        std::sync::Arc::new(textfile_metrics::AtomicTextfileWriter),
+    );
+    #[cfg(feature = "sqlite")]
+    sqlite::register_sqlite(&mut r, std::sync::Arc::new(sqlite::RusqliteStore));
+    setop::register_setop(&mut r, std::sync::Arc::new(setop::StdSetOperator));
+    join::register_join(&mut r, std::sync::Arc::new(join::StdJoiner));
+    file_chunk::register_file_chunk(&mut r, std::sync::Arc::new(file_chunk::StdFileChunker));
+    stdin::register_stdin(&mut r, std::sync::Arc::new(stdin::ProcessStdin));
+    id::register_id(&mut r);
+    delay::register_delay(&mut r);
+    json_extract::register_json_extract(&mut r);
+    json_merge::register_json_merge(&mut r, std::sync::Arc::new(json_merge::StdJsonMerger));
+    json_schema_validate::register_json_schema_validate(
+        &mut r,
+        std::sync::Arc::new(json_schema_validate::JsonschemaValidator),
+    );
     select_first::register_select_first(&mut r, std::sync::Arc::new(select_first::StdListSelector));
+    chat_webhook::register_chat_webhook(
+        &mut r,
+        std::sync::Arc::new(chat_webhook::ReqwestWebhookPoster),
+    );
     template_handlebars::register_template_handlebars(
         &mut r,
         std::sync::Arc::new(template_handlebars::HandlebarsTemplateRenderer),
@@ -129,12 +269,49 @@ pub fn registry_with_mailer(mailer: std::sync::Arc<dyn send_email::SendEmail>) -
     r
 }
 
+/// Create a registry with built-in defaults where `send_email`, `chat_webhook` and `http_request`
+/// (non-GET methods) all take a token from `limiter` before acting.
+pub fn registry_with_rate_limiter(limiter: std::sync::Arc<RateLimiter>) -> BlockRegistry {
+    use std::sync::Arc;
+    let mut r = default_registry();
+    send_email::register_send_email(
+        &mut r,
+        Arc::new(RateLimited::new(
+            Arc::new(send_email::EnvSmtpMailer),
+            Arc::clone(&limiter),
+        )),
+    );
+    chat_webhook::register_chat_webhook(
+        &mut r,
+        Arc::new(RateLimited::new(
+            Arc::new(chat_webhook::ReqwestWebhookPoster),
+            Arc::clone(&limiter),
+        )),
+    );
+    http_request::register_http_request(
+        &mut r,
+        Arc::new(RateLimited::new(
+            Arc::new(http_request::ReqwestHttpRequester),
+            limiter,
+        )),
+    );
+    r
+}
+
 /// Create a workflow with the default built-in blocks registry. Equivalent to
 /// `Workflow::with_registry(default_registry())`.
 pub fn new_workflow() -> Workflow {
     Workflow::with_registry(default_registry())
 }
 
+/// Execution context for calling a block directly in unit tests, with `input` as `prev`.
+#[cfg(test)]
+pub(crate) fn test_ctx(
+    input: orchestrator_core::block::BlockInput,
+) -> orchestrator_core::block::BlockExecutionContext {
+    orchestrator_core::block::BlockExecutionContext::new(input)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
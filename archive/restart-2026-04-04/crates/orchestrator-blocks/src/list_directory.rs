@@ -1,4 +1,6 @@
 //! ListDirectory block: Action that lists a directory and outputs paths (List) using an injected lister.
+//! `pattern` keeps entries whose file name matches the glob (e.g. `*.md`); `recursive` also
+//! lists subdirectories. Entries are sorted so downstream blocks see a stable order.
 //! Pass your lister when registering: `register_list_directory(registry, Arc::new(your_lister))`.
 
 use std::path::{Path, PathBuf};
@@ -7,7 +9,7 @@ use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 
 use crate::input_binding::{
-    resolve_effective_input, validate_expected_input, validate_single_input_mode,
+    path_from_input, resolve_effective_input, validate_expected_input, validate_single_input_mode,
 };
 use orchestrator_core::block::{
     BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
@@ -29,6 +31,45 @@ impl std::error::Error for ListDirectoryError {}
 /// Directory lister abstraction. Implement and pass when registering.
 pub trait DirectoryLister: Send + Sync {
     fn list(&self, path: &Path) -> Result<Vec<String>, ListDirectoryError>;
+
+    /// Entries whose file name matches `pattern`, walking subdirectories when `recursive`.
+    /// The default filters `list`; listers that cannot walk reject `recursive`.
+    fn list_matching(
+        &self,
+        path: &Path,
+        pattern: Option<&str>,
+        recursive: bool,
+    ) -> Result<Vec<String>, ListDirectoryError> {
+        if recursive {
+            return Err(ListDirectoryError(
+                "recursive listing is not supported by this lister".into(),
+            ));
+        }
+        let pattern = compile_pattern(pattern)?;
+        Ok(self
+            .list(path)?
+            .into_iter()
+            .filter(|entry| matches_pattern(pattern.as_ref(), Path::new(entry)))
+            .collect())
+    }
+}
+
+fn compile_pattern(pattern: Option<&str>) -> Result<Option<glob::Pattern>, ListDirectoryError> {
+    pattern
+        .map(|p| {
+            glob::Pattern::new(p).map_err(|e| {
+                ListDirectoryError(format!("invalid list_directory pattern {:?}: {}", p, e))
+            })
+        })
+        .transpose()
+}
+
+fn matches_pattern(pattern: Option<&glob::Pattern>, path: &Path) -> bool {
+    pattern.is_none_or(|pattern| {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| pattern.matches(name))
+    })
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -38,6 +79,12 @@ pub struct ListDirectoryConfig {
     /// When true, always use config's path and ignore input (e.g. when upstream is Cron).
     #[serde(default)]
     pub force_config_path: bool,
+    /// Glob matched against each entry's file name.
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Also list the contents of subdirectories.
+    #[serde(default)]
+    pub recursive: bool,
 }
 
 impl ListDirectoryConfig {
@@ -45,9 +92,21 @@ impl ListDirectoryConfig {
         Self {
             path: path.map(Into::into),
             force_config_path: false,
+            pattern: None,
+            recursive: false,
         }
     }
 
+    pub fn with_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.pattern = Some(pattern.into());
+        self
+    }
+
+    pub fn with_recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
     pub fn with_force_config_path(mut self, force: bool) -> Self {
         self.force_config_path = force;
         self
@@ -79,18 +138,6 @@ impl ListDirectoryBlock {
     }
 }
 
-fn path_from_input(input: &BlockInput) -> Option<PathBuf> {
-    match input {
-        BlockInput::String(s) if !s.is_empty() => Some(PathBuf::from(s.as_str())),
-        BlockInput::Text(s) if !s.is_empty() => Some(PathBuf::from(s.as_str())),
-        BlockInput::Json(v) => v
-            .as_str()
-            .map(PathBuf::from)
-            .or_else(|| v.get("path").and_then(|p| p.as_str()).map(PathBuf::from)),
-        _ => None,
-    }
-}
-
 impl BlockExecutor for ListDirectoryBlock {
     fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
         let input = resolve_effective_input(&ctx, &self.input_from, None)?;
@@ -111,10 +158,11 @@ impl BlockExecutor for ListDirectoryBlock {
             path_from_input(&input)
                 .ok_or_else(|| BlockError::Other("path required from input or config".into()))?
         };
-        let entries = self
+        let mut entries = self
             .lister
-            .list(&path)
+            .list_matching(&path, self.config.pattern.as_deref(), self.config.recursive)
             .map_err(|e| BlockError::Other(e.0))?;
+        entries.sort();
         Ok(BlockExecutionResult::Once(BlockOutput::List {
             items: entries,
         }))
@@ -148,19 +196,49 @@ impl BlockExecutor for ListDirectoryBlock {
 /// Default implementation using std::fs::read_dir.
 pub struct StdDirectoryLister;
 
+impl StdDirectoryLister {
+    fn walk(
+        dir: &Path,
+        pattern: Option<&glob::Pattern>,
+        recursive: bool,
+        out: &mut Vec<String>,
+    ) -> Result<(), ListDirectoryError> {
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| ListDirectoryError(format!("{}: {}", dir.display(), e)))?;
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if matches_pattern(pattern, &path) {
+                out.push(path.to_string_lossy().into_owned());
+            }
+            if recursive && entry.file_type().is_ok_and(|t| t.is_dir()) {
+                Self::walk(&path, pattern, recursive, out)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl DirectoryLister for StdDirectoryLister {
     fn list(&self, path: &Path) -> Result<Vec<String>, ListDirectoryError> {
+        self.list_matching(path, None, false)
+    }
+
+    fn list_matching(
+        &self,
+        path: &Path,
+        pattern: Option<&str>,
+        recursive: bool,
+    ) -> Result<Vec<String>, ListDirectoryError> {
+        let pattern = compile_pattern(pattern)?;
         if !path.is_dir() {
             return Err(ListDirectoryError(format!(
                 "not a directory: {}",
                 path.display()
             )));
         }
-        let entries: Vec<String> = std::fs::read_dir(path)
-            .map_err(|e| ListDirectoryError(format!("{}: {}", path.display(), e)))?
-            .filter_map(|e| e.ok())
-            .map(|e| e.path().to_string_lossy().into_owned())
-            .collect();
+        let mut entries = Vec::new();
+        Self::walk(path, pattern.as_ref(), recursive, &mut entries)?;
+        entries.sort();
         Ok(entries)
     }
 }
@@ -180,21 +258,10 @@ pub fn register_list_directory(
     });
 }
 
-#[cfg(test)]
-fn test_ctx(input: BlockInput) -> BlockExecutionContext {
-    BlockExecutionContext {
-        workflow_id: uuid::Uuid::new_v4(),
-        run_id: uuid::Uuid::new_v4(),
-        block_id: uuid::Uuid::new_v4(),
-        attempt: 1,
-        prev: input,
-        store: Default::default(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_ctx;
 
     #[test]
     fn list_directory_executes_and_returns_list() {
@@ -215,6 +282,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn list_directory_pattern_and_recursive_filter_sorted_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("weekly")).unwrap();
+        for name in ["b.md", "a.md", "notes.txt", "weekly/c.md"] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+        let root = dir.path().to_string_lossy().to_string();
+        let list = |config: ListDirectoryConfig| match ListDirectoryBlock::new(
+            config,
+            Arc::new(StdDirectoryLister),
+        )
+        .execute(test_ctx(BlockInput::empty()))
+        .unwrap()
+        .into_once()
+        {
+            BlockOutput::List { items } => items,
+            other => panic!("expected List, got {:?}", other),
+        };
+        let expected = |names: &[&str]| -> Vec<String> {
+            names
+                .iter()
+                .map(|n| dir.path().join(n).to_string_lossy().into_owned())
+                .collect()
+        };
+
+        let flat = ListDirectoryConfig::new(Some(root.clone())).with_pattern("*.md");
+        assert_eq!(list(flat.clone()), expected(&["a.md", "b.md"]));
+        assert_eq!(
+            list(flat.with_recursive(true)),
+            expected(&["a.md", "b.md", "weekly/c.md"])
+        );
+
+        let err = ListDirectoryBlock::new(
+            ListDirectoryConfig::new(Some(root)).with_pattern("[md"),
+            Arc::new(StdDirectoryLister),
+        )
+        .execute(test_ctx(BlockInput::empty()))
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("invalid list_directory pattern"),
+            "got: {err}"
+        );
+    }
+
     #[test]
     fn list_directory_not_a_directory_returns_error() {
         let dir = tempfile::tempdir().unwrap();
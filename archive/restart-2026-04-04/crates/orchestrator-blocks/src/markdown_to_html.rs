@@ -1,4 +1,6 @@
 //! MarkdownToHtml block: Transform that converts Markdown to HTML using an injected renderer.
+//! GitHub-style extensions (tables, strikethrough, footnotes, task lists) are off unless enabled
+//! in the config, so plain CommonMark output is unchanged.
 //! Pass your renderer when registering: `register_markdown_to_html(registry, Arc::new(your_renderer))`.
 
 use std::sync::Arc;
@@ -24,15 +26,56 @@ impl std::fmt::Display for MarkdownError {
 impl std::error::Error for MarkdownError {}
 
 /// Renderer abstraction: convert markdown to HTML. Implement and pass when registering.
+/// `options` carries the extensions enabled for the block.
 pub trait MarkdownToHtml: Send + Sync {
-    fn render(&self, markdown: &str) -> Result<String, MarkdownError>;
+    fn render(
+        &self,
+        markdown: &str,
+        options: &MarkdownToHtmlConfig,
+    ) -> Result<String, MarkdownError>;
 }
 
+/// Markdown extensions; all off by default.
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
-pub struct MarkdownToHtmlConfig;
+pub struct MarkdownToHtmlConfig {
+    #[serde(default)]
+    pub tables: bool,
+    #[serde(default)]
+    pub strikethrough: bool,
+    #[serde(default)]
+    pub footnotes: bool,
+    #[serde(default)]
+    pub tasklists: bool,
+}
+
+impl MarkdownToHtmlConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_tables(mut self, tables: bool) -> Self {
+        self.tables = tables;
+        self
+    }
+
+    pub fn with_strikethrough(mut self, strikethrough: bool) -> Self {
+        self.strikethrough = strikethrough;
+        self
+    }
+
+    pub fn with_footnotes(mut self, footnotes: bool) -> Self {
+        self.footnotes = footnotes;
+        self
+    }
+
+    pub fn with_tasklists(mut self, tasklists: bool) -> Self {
+        self.tasklists = tasklists;
+        self
+    }
+}
 
 pub struct MarkdownToHtmlBlock {
-    _config: MarkdownToHtmlConfig,
+    config: MarkdownToHtmlConfig,
     renderer: Arc<dyn MarkdownToHtml>,
     input_from: Box<[uuid::Uuid]>,
 }
@@ -40,7 +83,7 @@ pub struct MarkdownToHtmlBlock {
 impl MarkdownToHtmlBlock {
     pub fn new(config: MarkdownToHtmlConfig, renderer: Arc<dyn MarkdownToHtml>) -> Self {
         Self {
-            _config: config,
+            config,
             renderer,
             input_from: Box::new([]),
         }
@@ -62,9 +105,10 @@ fn input_to_string(input: &BlockInput) -> Result<String, BlockError> {
             .map(String::from)
             .unwrap_or_else(|| v.to_string())),
         BlockInput::List { items } => Ok(items.join("\n")),
-        BlockInput::Multi { outputs } => {
-            let s: String = outputs
-                .iter()
+        BlockInput::Multi { .. } | BlockInput::MultiNamed { .. } => {
+            let s: String = input
+                .multi_outputs()
+                .into_iter()
                 .filter_map(|o| Option::<String>::from(o.clone()))
                 .collect::<Vec<_>>()
                 .join("\n");
@@ -80,7 +124,7 @@ impl BlockExecutor for MarkdownToHtmlBlock {
         let md = input_to_string(&input)?;
         let html = self
             .renderer
-            .render(&md)
+            .render(&md, &self.config)
             .map_err(|e| BlockError::Other(e.0))?;
         Ok(BlockExecutionResult::Once(BlockOutput::Text {
             value: html,
@@ -96,10 +140,19 @@ impl BlockExecutor for MarkdownToHtmlBlock {
 pub struct PulldownMarkdownRenderer;
 
 impl MarkdownToHtml for PulldownMarkdownRenderer {
-    fn render(&self, markdown: &str) -> Result<String, MarkdownError> {
-        use pulldown_cmark::{Parser, html};
+    fn render(
+        &self,
+        markdown: &str,
+        options: &MarkdownToHtmlConfig,
+    ) -> Result<String, MarkdownError> {
+        use pulldown_cmark::{Options, Parser, html};
+        let mut flags = Options::empty();
+        flags.set(Options::ENABLE_TABLES, options.tables);
+        flags.set(Options::ENABLE_STRIKETHROUGH, options.strikethrough);
+        flags.set(Options::ENABLE_FOOTNOTES, options.footnotes);
+        flags.set(Options::ENABLE_TASKLISTS, options.tasklists);
         let mut out = String::new();
-        html::push_html(&mut out, Parser::new(markdown));
+        html::push_html(&mut out, Parser::new_ext(markdown, flags));
         Ok(out)
     }
 }
@@ -118,32 +171,26 @@ pub fn register_markdown_to_html(
     });
 }
 
-#[cfg(test)]
-fn test_ctx(input: BlockInput) -> BlockExecutionContext {
-    BlockExecutionContext {
-        workflow_id: uuid::Uuid::new_v4(),
-        run_id: uuid::Uuid::new_v4(),
-        block_id: uuid::Uuid::new_v4(),
-        attempt: 1,
-        prev: input,
-        store: Default::default(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_ctx;
 
     struct TestRenderer;
     impl MarkdownToHtml for TestRenderer {
-        fn render(&self, markdown: &str) -> Result<String, MarkdownError> {
+        fn render(
+            &self,
+            markdown: &str,
+            _options: &MarkdownToHtmlConfig,
+        ) -> Result<String, MarkdownError> {
             Ok(markdown.replace('<', "&lt;").replace('>', "&gt;"))
         }
     }
 
     #[test]
     fn markdown_to_html_renders_content() {
-        let block = MarkdownToHtmlBlock::new(MarkdownToHtmlConfig, Arc::new(TestRenderer));
+        let block =
+            MarkdownToHtmlBlock::new(MarkdownToHtmlConfig::default(), Arc::new(TestRenderer));
         let input = BlockInput::String("<script>".into());
         let result = block.execute(test_ctx(input)).unwrap();
         match result {
@@ -156,7 +203,8 @@ mod tests {
 
     #[test]
     fn markdown_to_html_empty_input_returns_empty() {
-        let block = MarkdownToHtmlBlock::new(MarkdownToHtmlConfig, Arc::new(TestRenderer));
+        let block =
+            MarkdownToHtmlBlock::new(MarkdownToHtmlConfig::default(), Arc::new(TestRenderer));
         let result = block.execute(test_ctx(BlockInput::empty())).unwrap();
         match result {
             BlockExecutionResult::Once(BlockOutput::Text { value }) => assert_eq!(value, ""),
@@ -166,7 +214,8 @@ mod tests {
 
     #[test]
     fn markdown_to_html_error_input_returns_error() {
-        let block = MarkdownToHtmlBlock::new(MarkdownToHtmlConfig, Arc::new(TestRenderer));
+        let block =
+            MarkdownToHtmlBlock::new(MarkdownToHtmlConfig::default(), Arc::new(TestRenderer));
         let input = BlockInput::Error {
             message: "upstream error".into(),
         };
@@ -177,8 +226,10 @@ mod tests {
 
     #[test]
     fn pulldown_renderer_produces_html() {
-        let block =
-            MarkdownToHtmlBlock::new(MarkdownToHtmlConfig, Arc::new(PulldownMarkdownRenderer));
+        let block = MarkdownToHtmlBlock::new(
+            MarkdownToHtmlConfig::default(),
+            Arc::new(PulldownMarkdownRenderer),
+        );
         let input = BlockInput::String("# Hi\n**bold**".into());
         let result = block.execute(test_ctx(input)).unwrap();
         match result {
@@ -189,4 +240,24 @@ mod tests {
             _ => panic!("expected Once(Text)"),
         }
     }
+
+    #[test]
+    fn pulldown_renderer_renders_tables_only_when_enabled() {
+        let table = "| feed | items |\n| --- | --- |\n| hn | 30 |\n";
+        let render = |config: MarkdownToHtmlConfig| match MarkdownToHtmlBlock::new(
+            config,
+            Arc::new(PulldownMarkdownRenderer),
+        )
+        .execute(test_ctx(BlockInput::String(table.into())))
+        .unwrap()
+        .into_once()
+        {
+            BlockOutput::Text { value } => value,
+            other => panic!("expected Text, got {:?}", other),
+        };
+        assert!(!render(MarkdownToHtmlConfig::new()).contains("<table>"));
+        let html = render(MarkdownToHtmlConfig::new().with_tables(true));
+        assert!(html.contains("<table>"), "got: {html}");
+        assert!(html.contains("<td>hn</td>"), "got: {html}");
+    }
 }
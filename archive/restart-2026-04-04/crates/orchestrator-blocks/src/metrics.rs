@@ -0,0 +1,328 @@
+//! Metrics block: send a named counter or gauge to a StatsD endpoint, taking the value from input.
+//! Numbers are used as-is, arrays count their items, and empty input counts as 1.
+//! Send failures are logged and reported as `{"sent": false}` unless `fail_on_error` is set.
+//! Pass your sink when registering: `register_metrics(registry, Arc::new(your_sink))`.
+
+use std::net::UdpSocket;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::input_binding::{
+    resolve_effective_input, validate_expected_input, validate_single_input_mode,
+};
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
+};
+
+/// Error from metrics operations.
+#[derive(Debug, Clone)]
+pub struct MetricsError(pub String);
+
+impl std::fmt::Display for MetricsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for MetricsError {}
+
+/// StatsD metric type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricKind {
+    #[default]
+    Counter,
+    Gauge,
+}
+
+/// One metric sample handed to a [`MetricsSink`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metric {
+    pub name: String,
+    pub kind: MetricKind,
+    pub value: f64,
+}
+
+impl Metric {
+    /// StatsD wire format, e.g. `emails.sent:3|c`.
+    pub fn to_statsd_line(&self) -> String {
+        let kind = match self.kind {
+            MetricKind::Counter => "c",
+            MetricKind::Gauge => "g",
+        };
+        format!("{}:{}|{}", self.name, self.value, kind)
+    }
+}
+
+/// Metrics sink abstraction. Implement and pass when registering.
+pub trait MetricsSink: Send + Sync {
+    fn send(&self, endpoint: &str, metric: &Metric) -> Result<(), MetricsError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetricsConfig {
+    pub name: String,
+    #[serde(default)]
+    pub kind: MetricKind,
+    /// StatsD `host:port`.
+    #[serde(default = "default_endpoint")]
+    pub endpoint: String,
+    /// Field holding the value in JSON object input; dots address nested objects.
+    #[serde(default)]
+    pub field: Option<String>,
+    /// Fail the block when the metric cannot be sent.
+    #[serde(default)]
+    pub fail_on_error: bool,
+}
+
+fn default_endpoint() -> String {
+    "127.0.0.1:8125".to_string()
+}
+
+impl MetricsConfig {
+    pub fn new(name: impl Into<String>, kind: MetricKind) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            endpoint: default_endpoint(),
+            field: None,
+            fail_on_error: false,
+        }
+    }
+
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+
+    pub fn with_field(mut self, field: impl Into<String>) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+
+    pub fn with_fail_on_error(mut self, fail_on_error: bool) -> Self {
+        self.fail_on_error = fail_on_error;
+        self
+    }
+}
+
+pub struct MetricsBlock {
+    config: MetricsConfig,
+    sink: Arc<dyn MetricsSink>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl MetricsBlock {
+    pub fn new(config: MetricsConfig, sink: Arc<dyn MetricsSink>) -> Self {
+        Self {
+            config,
+            sink,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+fn json_value(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::Array(items) => Some(items.len() as f64),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn metric_value(input: &BlockInput, field: Option<&str>) -> Result<f64, BlockError> {
+    let value = match input {
+        BlockInput::Empty => Some(1.0),
+        BlockInput::String(s) | BlockInput::Text(s) => s.trim().parse().ok(),
+        BlockInput::List { items } => Some(items.len() as f64),
+        BlockInput::Json(v) => match field {
+            Some(field) => field
+                .split('.')
+                .try_fold(v, |current, segment| current.get(segment))
+                .and_then(json_value),
+            None => json_value(v),
+        },
+        BlockInput::Error { message } => return Err(BlockError::Other(message.clone())),
+        BlockInput::Multi { .. } | BlockInput::MultiNamed { .. } => None,
+    };
+    value.ok_or_else(|| BlockError::Other("metrics input has no numeric value".into()))
+}
+
+impl BlockExecutor for MetricsBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        if self.config.name.trim().is_empty() {
+            return Err(BlockError::Other("metrics requires a name".into()));
+        }
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        let metric = Metric {
+            name: self.config.name.clone(),
+            kind: self.config.kind,
+            value: metric_value(&input, self.config.field.as_deref())?,
+        };
+        let sent = match self.sink.send(&self.config.endpoint, &metric) {
+            Ok(()) => true,
+            Err(e) if self.config.fail_on_error => return Err(BlockError::Other(e.0)),
+            Err(e) => {
+                warn!(
+                    event = "metrics.send_failed",
+                    block_id = %ctx.block_id,
+                    metric = metric.name.as_str(),
+                    error = %e,
+                    "metric not sent"
+                );
+                false
+            }
+        };
+        Ok(BlockExecutionResult::Once(BlockOutput::Json {
+            value: serde_json::json!({
+                "name": metric.name,
+                "value": metric.value,
+                "sent": sent,
+            }),
+        }))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::Json, OutputMode::Once)
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        validate_single_input_mode(ctx)?;
+        validate_expected_input(
+            ctx,
+            ValueKindSet::singleton(ValueKind::Empty)
+                | ValueKindSet::singleton(ValueKind::String)
+                | ValueKindSet::singleton(ValueKind::Text)
+                | ValueKindSet::singleton(ValueKind::Json)
+                | ValueKindSet::singleton(ValueKind::List),
+        )
+    }
+}
+
+/// Default implementation: one StatsD line per UDP datagram.
+pub struct UdpStatsdSink;
+
+impl MetricsSink for UdpStatsdSink {
+    fn send(&self, endpoint: &str, metric: &Metric) -> Result<(), MetricsError> {
+        let socket = UdpSocket::bind("0.0.0.0:0").map_err(|e| MetricsError(e.to_string()))?;
+        socket
+            .send_to(metric.to_statsd_line().as_bytes(), endpoint)
+            .map_err(|e| MetricsError(format!("statsd send to {}: {}", endpoint, e)))?;
+        Ok(())
+    }
+}
+
+/// Register the metrics block with a sink.
+pub fn register_metrics(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    sink: Arc<dyn MetricsSink>,
+) {
+    let sink = Arc::clone(&sink);
+    registry.register_custom("metrics", move |payload, input_from| {
+        let config: MetricsConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            MetricsBlock::new(config, Arc::clone(&sink)).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemorySink {
+        lines: Mutex<Vec<(String, String)>>,
+        fail: bool,
+    }
+
+    impl MetricsSink for MemorySink {
+        fn send(&self, endpoint: &str, metric: &Metric) -> Result<(), MetricsError> {
+            if self.fail {
+                return Err(MetricsError("statsd unreachable".into()));
+            }
+            self.lines
+                .lock()
+                .unwrap()
+                .push((endpoint.to_string(), metric.to_statsd_line()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn metrics_sends_named_counter_and_gauge() {
+        let sink = Arc::new(MemorySink::default());
+        let counter = MetricsBlock::new(
+            MetricsConfig::new("digest.items", MetricKind::Counter).with_endpoint("statsd:8125"),
+            sink.clone(),
+        );
+        let out = counter
+            .execute(test_ctx(BlockInput::Json(json!([1, 2, 3]))))
+            .unwrap()
+            .into_once();
+        assert_eq!(
+            out,
+            BlockOutput::Json {
+                value: json!({ "name": "digest.items", "value": 3.0, "sent": true })
+            }
+        );
+
+        let gauge = MetricsBlock::new(
+            MetricsConfig::new("queue.depth", MetricKind::Gauge).with_field("stats.depth"),
+            sink.clone(),
+        );
+        gauge
+            .execute(test_ctx(BlockInput::Json(
+                json!({ "stats": { "depth": 12.5 } }),
+            )))
+            .unwrap();
+
+        assert_eq!(
+            *sink.lines.lock().unwrap(),
+            vec![
+                ("statsd:8125".to_string(), "digest.items:3|c".to_string()),
+                (
+                    "127.0.0.1:8125".to_string(),
+                    "queue.depth:12.5|g".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn metrics_send_failure_is_non_fatal_by_default() {
+        let sink = Arc::new(MemorySink {
+            fail: true,
+            ..MemorySink::default()
+        });
+        let config = MetricsConfig::new("emails.sent", MetricKind::Counter);
+        let out = MetricsBlock::new(config.clone(), sink.clone())
+            .execute(test_ctx(BlockInput::Empty))
+            .unwrap()
+            .into_once();
+        assert_eq!(
+            out,
+            BlockOutput::Json {
+                value: json!({ "name": "emails.sent", "value": 1.0, "sent": false })
+            }
+        );
+
+        let err = MetricsBlock::new(config.with_fail_on_error(true), sink)
+            .execute(test_ctx(BlockInput::Empty))
+            .unwrap_err();
+        assert!(err.to_string().contains("unreachable"), "got: {err}");
+    }
+}
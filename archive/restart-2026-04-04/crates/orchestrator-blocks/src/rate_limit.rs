@@ -0,0 +1,207 @@
+//! Workflow-wide rate limit for outbound side effects (emails, webhook posts, non-GET HTTP requests).
+//! Unlike ChatWebhook's per-destination `min_interval_ms`, one [`RateLimiter`] is shared by every
+//! wrapped sender in a registry, so a fan-out of side-effecting blocks stays under a single global rate.
+//! Wrap your implementation when registering:
+//! `register_send_email(registry, Arc::new(RateLimited::new(Arc::new(your_mailer), limiter)))`,
+//! or use `registry_with_rate_limiter(limiter)` for the built-in senders.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::debug;
+
+use crate::chat_webhook::{ChatWebhookError, WebhookPoster};
+use crate::http_request::{HttpRequest, HttpRequestError, HttpRequester, HttpResponse};
+use crate::send_email::{SendEmail, SendEmailError};
+
+/// Token bucket: holds up to `permits` tokens and refills `permits` every `per`.
+/// Each side effect takes one token, blocking until one is available.
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// At most `permits` side effects per `per`, with bursts of up to `permits`.
+    /// `permits` is raised to 1 and `per` to 1ms when zero.
+    pub fn new(permits: u32, per: Duration) -> Self {
+        let capacity = f64::from(permits.max(1));
+        let per = per.max(Duration::from_millis(1));
+        Self {
+            capacity,
+            refill_per_sec: capacity / per.as_secs_f64(),
+            bucket: Mutex::new(Bucket {
+                tokens: capacity,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    pub fn per_second(permits: u32) -> Self {
+        Self::new(permits, Duration::from_secs(1))
+    }
+
+    /// Take one token, sleeping until one is available. Returns how long the caller waited.
+    pub fn acquire(&self) -> Duration {
+        let started = Instant::now();
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().expect("rate limiter lock poisoned");
+                self.refill(&mut bucket);
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    return started.elapsed();
+                }
+                Duration::from_secs_f64((1.0 - bucket.tokens) / self.refill_per_sec)
+            };
+            debug!(
+                event = "rate_limit.wait",
+                wait_ms = wait.as_millis() as u64,
+                "waiting for rate limit token"
+            );
+            std::thread::sleep(wait);
+        }
+    }
+
+    /// Take one token if available without waiting.
+    pub fn try_acquire(&self) -> bool {
+        let mut bucket = self.bucket.lock().expect("rate limiter lock poisoned");
+        self.refill(&mut bucket);
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn refill(&self, bucket: &mut Bucket) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        bucket.refilled_at = now;
+    }
+}
+
+/// Wraps a mailer, webhook poster or HTTP requester so each side effect first takes a token
+/// from the shared limiter. HTTP `GET` and `HEAD` requests are not limited.
+pub struct RateLimited<T: ?Sized> {
+    inner: Arc<T>,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<T: ?Sized> RateLimited<T> {
+    pub fn new(inner: Arc<T>, limiter: Arc<RateLimiter>) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+impl<T: SendEmail + ?Sized> SendEmail for RateLimited<T> {
+    fn send_email(
+        &self,
+        subject: &str,
+        to_name: &str,
+        to_email: &str,
+        body: String,
+    ) -> Result<(), SendEmailError> {
+        self.limiter.acquire();
+        self.inner.send_email(subject, to_name, to_email, body)
+    }
+}
+
+impl<T: WebhookPoster + ?Sized> WebhookPoster for RateLimited<T> {
+    fn post_json(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+        timeout: Duration,
+    ) -> Result<(), ChatWebhookError> {
+        self.limiter.acquire();
+        self.inner.post_json(url, body, timeout)
+    }
+}
+
+fn has_side_effects(req: &HttpRequest) -> bool {
+    !matches!(req.method.as_str(), "GET" | "HEAD")
+}
+
+impl<T: HttpRequester + ?Sized> HttpRequester for RateLimited<T> {
+    fn send(&self, req: HttpRequest) -> Result<HttpResponse, HttpRequestError> {
+        if has_side_effects(&req) {
+            self.limiter.acquire();
+        }
+        self.inner.send(req)
+    }
+
+    fn request(&self, req: HttpRequest) -> Result<String, HttpRequestError> {
+        if has_side_effects(&req) {
+            self.limiter.acquire();
+        }
+        self.inner.request(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::send_email::{SendEmailBlock, SendEmailConfig};
+    use crate::test_ctx;
+    use orchestrator_core::block::{BlockExecutor, BlockInput};
+
+    struct RecordingMailer(Mutex<Vec<Instant>>);
+
+    impl SendEmail for RecordingMailer {
+        fn send_email(
+            &self,
+            _subject: &str,
+            _to_name: &str,
+            _to_email: &str,
+            _body: String,
+        ) -> Result<(), SendEmailError> {
+            self.0.lock().unwrap().push(Instant::now());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn second_send_waits_for_a_token() {
+        let mailer = Arc::new(RecordingMailer(Mutex::new(Vec::new())));
+        let limiter = Arc::new(RateLimiter::new(1, Duration::from_millis(200)));
+        let mut config = SendEmailConfig::new("ops@example.com");
+        config.subject = Some("digest".into());
+        let first = SendEmailBlock::new(
+            config.clone(),
+            Arc::new(RateLimited::new(mailer.clone(), Arc::clone(&limiter))),
+        );
+        let second = SendEmailBlock::new(
+            config,
+            Arc::new(RateLimited::new(mailer.clone(), Arc::clone(&limiter))),
+        );
+
+        let started = Instant::now();
+        first
+            .execute(test_ctx(BlockInput::String("one".into())))
+            .unwrap();
+        second
+            .execute(test_ctx(BlockInput::String("two".into())))
+            .unwrap();
+
+        let sent = mailer.0.lock().unwrap();
+        assert_eq!(sent.len(), 2);
+        assert!(sent[0].duration_since(started) < Duration::from_millis(100));
+        assert!(
+            sent[1].duration_since(sent[0]) >= Duration::from_millis(150),
+            "second send did not wait: {:?}",
+            sent[1].duration_since(sent[0])
+        );
+        assert!(!limiter.try_acquire());
+    }
+}
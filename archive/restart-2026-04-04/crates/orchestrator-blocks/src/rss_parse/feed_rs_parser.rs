@@ -31,7 +31,14 @@ impl RssParser for FeedRsParser {
                 .map(|t| t.content.clone())
                 .or_else(|| entry.content.as_ref().and_then(|c| c.body.clone()))
                 .unwrap_or_default();
-            let published_at = entry.published.or(entry.updated).map(|d| d.to_rfc3339());
+            let timestamp = entry.published.or(entry.updated);
+            let published_at = timestamp.map(|d| d.to_rfc3339());
+            let published = timestamp.map(|d| d.to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+            let author = entry
+                .authors
+                .first()
+                .map(|a| a.name.trim().to_string())
+                .filter(|name| !name.is_empty());
             let id = if !entry.id.is_empty() {
                 entry.id
             } else if !url.is_empty() {
@@ -46,6 +53,8 @@ impl RssParser for FeedRsParser {
                 "title": title,
                 "source": source,
                 "published_at": published_at,
+                "published": published,
+                "author": author,
                 "snippet": snippet,
             }));
         }
@@ -1,4 +1,6 @@
 //! RssParse block: parse RSS/Atom XML into normalized JSON items.
+//! Items carry `id`, `url`, `title`, `source`, `snippet`, `published_at`, `published` (UTC
+//! RFC3339, from the published or updated timestamp) and `author`; missing values are null.
 //! Pass your parser when registering: `register_rss_parse(registry, Arc::new(your_parser))`.
 
 mod feed_rs_parser;
@@ -68,7 +70,10 @@ impl BlockExecutor for RssParseBlock {
                 BlockError::Other("rss_parse expects xml string/text input".into())
             })?,
             BlockInput::Error { message } => return Err(BlockError::Other(message)),
-            BlockInput::Empty | BlockInput::List { .. } | BlockInput::Multi { .. } => {
+            BlockInput::Empty
+            | BlockInput::List { .. }
+            | BlockInput::Multi { .. }
+            | BlockInput::MultiNamed { .. } => {
                 return Err(BlockError::Other(
                     "rss_parse expects xml string/text input".into(),
                 ));
@@ -114,21 +119,10 @@ pub fn register_rss_parse(
     });
 }
 
-#[cfg(test)]
-fn test_ctx(input: BlockInput) -> BlockExecutionContext {
-    BlockExecutionContext {
-        workflow_id: uuid::Uuid::new_v4(),
-        run_id: uuid::Uuid::new_v4(),
-        block_id: uuid::Uuid::new_v4(),
-        attempt: 1,
-        prev: input,
-        store: Default::default(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_ctx;
 
     #[test]
     fn rss_parse_parses_basic_rss() {
@@ -163,6 +157,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rss_parse_atom_entry_has_published_and_author() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Atom Feed</title>
+  <id>urn:feed</id>
+  <updated>2026-04-04T08:00:00+02:00</updated>
+  <entry>
+    <title>Release notes</title>
+    <id>urn:entry-1</id>
+    <link href="https://example.com/release"/>
+    <updated>2026-04-04T08:00:00+02:00</updated>
+    <author><name>Jane Doe</name></author>
+  </entry>
+  <entry>
+    <title>Anonymous</title>
+    <id>urn:entry-2</id>
+  </entry>
+</feed>"#;
+        let block = RssParseBlock::new(RssParseConfig::default(), Arc::new(FeedRsParser));
+        let out = block
+            .execute(test_ctx(BlockInput::String(xml.to_string())))
+            .unwrap()
+            .into_once();
+        let BlockOutput::Json { value } = out else {
+            panic!("expected Json, got {:?}", out);
+        };
+        let items = value.as_array().unwrap();
+        assert_eq!(items[0]["published"], "2026-04-04T06:00:00Z");
+        assert_eq!(items[0]["author"], "Jane Doe");
+        assert!(items[1]["published"].is_null());
+        assert!(items[1]["author"].is_null());
+    }
+
     #[test]
     fn rss_parse_invalid_xml_returns_error() {
         let block = RssParseBlock::new(RssParseConfig::default(), Arc::new(FeedRsParser));
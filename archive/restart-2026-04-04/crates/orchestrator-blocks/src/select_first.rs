@@ -68,7 +68,7 @@ fn input_to_items(input: &BlockInput) -> Result<Vec<String>, BlockError> {
         BlockInput::String(s) => Ok(vec![s.clone()]),
         BlockInput::Text(s) => Ok(vec![s.clone()]),
         BlockInput::Empty => Ok(vec![]),
-        BlockInput::Multi { .. } => Err(BlockError::Other(
+        BlockInput::Multi { .. } | BlockInput::MultiNamed { .. } => Err(BlockError::Other(
             "select_first expects List or JSON array, not Multi".into(),
         )),
         BlockInput::Error { message } => Err(BlockError::Other(message.clone())),
@@ -166,21 +166,10 @@ pub fn register_select_first(
     });
 }
 
-#[cfg(test)]
-fn test_ctx(input: BlockInput) -> BlockExecutionContext {
-    BlockExecutionContext {
-        workflow_id: uuid::Uuid::new_v4(),
-        run_id: uuid::Uuid::new_v4(),
-        block_id: uuid::Uuid::new_v4(),
-        attempt: 1,
-        prev: input,
-        store: Default::default(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_ctx;
 
     #[test]
     fn select_first_returns_first_item() {
@@ -1,5 +1,7 @@
 //! SendEmail block: Action that sends email using an injected mailer.
 //! Input may be JSON with `to`/`email`, `name`, `subject`, and `body`, or a plain string as body (config supplies default `to` and `subject`).
+//! With `skip_if_empty`, a blank body (or one equal to `empty_sentinel`) is not sent and the block
+//! outputs `{ "sent": false, "reason": "empty" }`, so an empty digest never reaches the inbox.
 //!
 //! The mailer API matches the poc: `send_email(subject, to_name, to_email, body)`.
 //! Pass your mailer when registering: `register_send_email(registry, Arc::new(your_mailer))`.
@@ -62,6 +64,12 @@ pub struct SendEmailConfig {
     pub timeout_ms: Option<u64>,
     #[serde(default = "default_retry_policy")]
     pub retry_policy: RetryPolicy,
+    /// Skip sending when the body is empty or whitespace.
+    #[serde(default)]
+    pub skip_if_empty: bool,
+    /// Body (after trimming) that also counts as empty, e.g. `No new items`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub empty_sentinel: Option<String>,
 }
 
 fn default_timeout_ms() -> Option<u64> {
@@ -81,8 +89,26 @@ impl SendEmailConfig {
             smtp_port: None,
             timeout_ms: default_timeout_ms(),
             retry_policy: default_retry_policy(),
+            skip_if_empty: false,
+            empty_sentinel: None,
         }
     }
+
+    /// Skip sending empty bodies; `sentinel` is an extra body treated as empty.
+    pub fn with_skip_if_empty(mut self, sentinel: Option<impl Into<String>>) -> Self {
+        self.skip_if_empty = true;
+        self.empty_sentinel = sentinel.map(Into::into);
+        self
+    }
+
+    fn is_empty_body(&self, body: &str) -> bool {
+        let body = body.trim();
+        body.is_empty()
+            || self
+                .empty_sentinel
+                .as_deref()
+                .is_some_and(|sentinel| body == sentinel.trim())
+    }
 }
 
 pub struct SendEmailBlock {
@@ -114,6 +140,7 @@ fn block_input_kind(input: &BlockInput) -> &'static str {
         BlockInput::Json(_) => "json",
         BlockInput::List { .. } => "list",
         BlockInput::Multi { .. } => "multi",
+        BlockInput::MultiNamed { .. } => "multi_named",
         BlockInput::Error { .. } => "error",
     }
 }
@@ -193,9 +220,10 @@ fn parse_input(
             default_subject.to_string(),
             items.join("\n"),
         )),
-        BlockInput::Multi { outputs } => {
-            let body = outputs
-                .iter()
+        BlockInput::Multi { .. } | BlockInput::MultiNamed { .. } => {
+            let body = input
+                .multi_outputs()
+                .into_iter()
                 .filter_map(|o| Option::<String>::from(o.clone()))
                 .collect::<Vec<_>>()
                 .join("\n");
@@ -238,6 +266,17 @@ impl BlockExecutor for SendEmailBlock {
             timeout_ms = self.config.timeout_ms.unwrap_or(30_000),
             max_retries = self.config.retry_policy.max_retries
         );
+        if self.config.skip_if_empty && self.config.is_empty_body(&body) {
+            debug!(
+                event = "email.send_skipped",
+                domain = "email",
+                block_type = "send_email",
+                reason = "empty"
+            );
+            return Ok(BlockExecutionResult::Once(BlockOutput::Json {
+                value: serde_json::json!({ "sent": false, "reason": "empty" }),
+            }));
+        }
         let mut retries_done = 0u32;
         loop {
             let attempt = retries_done + 1;
@@ -269,7 +308,13 @@ impl BlockExecutor for SendEmailBlock {
                 }
                 Err(err) => {
                     let (code, retryable) = classify_email_error(&err.0);
-                    let can_retry = retryable && self.config.retry_policy.can_retry(retries_done);
+                    // A timeout may fire after the mail went out; only retry it if resending is safe.
+                    let can_retry = retryable
+                        && self.config.retry_policy.can_retry_failure(
+                            retries_done,
+                            code == "email.smtp.timeout",
+                            self.idempotent(),
+                        );
                     debug!(
                         event = "email.send_failed",
                         domain = "email",
@@ -416,18 +461,6 @@ pub fn register_send_email(
     });
 }
 
-#[cfg(test)]
-fn test_ctx(input: BlockInput) -> BlockExecutionContext {
-    BlockExecutionContext {
-        workflow_id: uuid::Uuid::new_v4(),
-        run_id: uuid::Uuid::new_v4(),
-        block_id: uuid::Uuid::new_v4(),
-        attempt: 1,
-        prev: input,
-        store: Default::default(),
-    }
-}
-
 /// Register send_email with the built-in env-based SMTP mailer.
 pub fn register_send_email_env(registry: &mut orchestrator_core::block::BlockRegistry) {
     register_send_email(registry, Arc::new(EnvSmtpMailer));
@@ -436,6 +469,7 @@ pub fn register_send_email_env(registry: &mut orchestrator_core::block::BlockReg
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_ctx;
 
     /// No-op mailer for tests only.
     struct NoOpSendEmail;
@@ -537,4 +571,62 @@ mod tests {
             _ => panic!("expected Once(Json)"),
         }
     }
+
+    #[test]
+    fn send_email_skips_empty_body_when_configured() {
+        let mailer = Arc::new(TimingOutMailer {
+            calls: Default::default(),
+        });
+        let config =
+            SendEmailConfig::new("user@example.com").with_skip_if_empty(Some("No new items"));
+        let block = SendEmailBlock::new(config, mailer.clone());
+        for body in ["  \n", "No new items"] {
+            let result = block
+                .execute(test_ctx(BlockInput::String(body.into())))
+                .unwrap();
+            match result {
+                BlockExecutionResult::Once(BlockOutput::Json { value }) => {
+                    assert_eq!(
+                        value,
+                        serde_json::json!({ "sent": false, "reason": "empty" })
+                    );
+                }
+                _ => panic!("expected Once(Json)"),
+            }
+        }
+        assert_eq!(mailer.calls.load(std::sync::atomic::Ordering::SeqCst), 0);
+    }
+
+    struct TimingOutMailer {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl SendEmail for TimingOutMailer {
+        fn send_email(
+            &self,
+            _subject: &str,
+            _to_name: &str,
+            _to_email: &str,
+            _body: String,
+        ) -> Result<(), SendEmailError> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Err(SendEmailError("connection timed out".into()))
+        }
+    }
+
+    #[test]
+    fn send_email_timeout_is_not_retried() {
+        let mailer = Arc::new(TimingOutMailer {
+            calls: Default::default(),
+        });
+        let mut config = SendEmailConfig::new("user@example.com");
+        config.retry_policy = RetryPolicy::exponential(2, 1, 1.0);
+        let block = SendEmailBlock::new(config, mailer.clone());
+        assert!(!block.idempotent());
+        let err = block
+            .execute(test_ctx(BlockInput::String("body".into())))
+            .unwrap_err();
+        assert!(err.to_string().contains("email.smtp.timeout"), "got: {err}");
+        assert_eq!(mailer.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
 }
@@ -0,0 +1,327 @@
+//! SetOp block: union, intersection or difference over two or more list inputs, in edge order.
+//! Output is deduplicated and sorted. `List` inputs give a `List`; JSON array inputs give a JSON
+//! array, compared by `key` field when set (first occurrence wins) or by whole value otherwise.
+//! `difference` keeps the items of the first input that appear in none of the others.
+//! Pass your operator when registering: `register_setop(registry, Arc::new(your_operator))`.
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::input_binding::resolve_effective_input;
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
+};
+
+/// Error from set operations.
+#[derive(Debug, Clone)]
+pub struct SetOpError(pub String);
+
+impl std::fmt::Display for SetOpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for SetOpError {}
+
+/// Set operation applied across the inputs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SetOpMode {
+    #[default]
+    Union,
+    Intersection,
+    Difference,
+}
+
+/// Set operator abstraction. Implement and pass when registering.
+pub trait SetOperator: Send + Sync {
+    fn apply(
+        &self,
+        mode: SetOpMode,
+        key: Option<&str>,
+        inputs: &[BlockOutput],
+    ) -> Result<BlockOutput, SetOpError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetOpConfig {
+    #[serde(default)]
+    pub mode: SetOpMode,
+    /// Field identifying JSON object items; dots address nested objects.
+    #[serde(default)]
+    pub key: Option<String>,
+}
+
+impl SetOpConfig {
+    pub fn new(mode: SetOpMode) -> Self {
+        Self { mode, key: None }
+    }
+
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+}
+
+pub struct SetOpBlock {
+    config: SetOpConfig,
+    operator: Arc<dyn SetOperator>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl SetOpBlock {
+    pub fn new(config: SetOpConfig, operator: Arc<dyn SetOperator>) -> Self {
+        Self {
+            config,
+            operator,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+impl BlockExecutor for SetOpBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        let outputs = match input {
+            BlockInput::Multi { outputs } => outputs,
+            BlockInput::Error { message } => return Err(BlockError::Other(message)),
+            _ => {
+                return Err(BlockError::Other(
+                    "setop requires at least two list inputs".into(),
+                ));
+            }
+        };
+        let output = self
+            .operator
+            .apply(self.config.mode, self.config.key.as_deref(), &outputs)
+            .map_err(|e| BlockError::Other(e.0))?;
+        Ok(BlockExecutionResult::Once(output))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract {
+            kinds: ValueKindSet::singleton(ValueKind::List)
+                | ValueKindSet::singleton(ValueKind::Json),
+            mode: OutputMode::Once,
+        }
+    }
+}
+
+/// Default implementation over `BTreeMap`s keyed by item identity.
+pub struct StdSetOperator;
+
+/// Items of one input keyed by identity, sorted; the first item with a given identity wins.
+type Keyed<T> = BTreeMap<String, T>;
+
+fn identity(item: &serde_json::Value, key: Option<&str>) -> Result<String, SetOpError> {
+    let value = match key {
+        Some(key) => key
+            .split('.')
+            .try_fold(item, |current, segment| current.get(segment))
+            .ok_or_else(|| SetOpError(format!("setop item has no {:?} field: {}", key, item)))?,
+        None => item,
+    };
+    Ok(match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    })
+}
+
+fn combine<T: Clone>(mode: SetOpMode, sets: Vec<Keyed<T>>) -> Keyed<T> {
+    let mut sets = sets.into_iter();
+    let first = sets.next().unwrap_or_default();
+    match mode {
+        SetOpMode::Union => sets.fold(first, |mut acc, set| {
+            for (id, item) in set {
+                acc.entry(id).or_insert(item);
+            }
+            acc
+        }),
+        SetOpMode::Intersection => sets.fold(first, |mut acc, set| {
+            acc.retain(|id, _| set.contains_key(id));
+            acc
+        }),
+        SetOpMode::Difference => {
+            let rest: HashSet<String> = sets.flat_map(|set| set.into_keys()).collect();
+            let mut acc = first;
+            acc.retain(|id, _| !rest.contains(id));
+            acc
+        }
+    }
+}
+
+impl SetOperator for StdSetOperator {
+    fn apply(
+        &self,
+        mode: SetOpMode,
+        key: Option<&str>,
+        inputs: &[BlockOutput],
+    ) -> Result<BlockOutput, SetOpError> {
+        if inputs.len() < 2 {
+            return Err(SetOpError(format!(
+                "setop requires at least two inputs, got {}",
+                inputs.len()
+            )));
+        }
+        if inputs.iter().all(|o| matches!(o, BlockOutput::List { .. })) && key.is_none() {
+            let sets = inputs
+                .iter()
+                .map(|o| match o {
+                    BlockOutput::List { items } => items
+                        .iter()
+                        .map(|item| (item.clone(), item.clone()))
+                        .collect(),
+                    _ => Keyed::new(),
+                })
+                .collect();
+            return Ok(BlockOutput::List {
+                items: combine(mode, sets).into_values().collect(),
+            });
+        }
+        let mut sets = Vec::with_capacity(inputs.len());
+        for (index, output) in inputs.iter().enumerate() {
+            let items: Vec<serde_json::Value> = match output {
+                BlockOutput::Json {
+                    value: serde_json::Value::Array(items),
+                } => items.clone(),
+                BlockOutput::List { items } => items
+                    .iter()
+                    .cloned()
+                    .map(serde_json::Value::String)
+                    .collect(),
+                _ => {
+                    return Err(SetOpError(format!(
+                        "setop input {} is not a list or JSON array",
+                        index
+                    )));
+                }
+            };
+            let mut set = Keyed::new();
+            for item in items {
+                let id = identity(&item, key)?;
+                set.entry(id).or_insert(item);
+            }
+            sets.push(set);
+        }
+        Ok(BlockOutput::Json {
+            value: serde_json::Value::Array(combine(mode, sets).into_values().collect()),
+        })
+    }
+}
+
+/// Register the setop block with an operator.
+pub fn register_setop(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    operator: Arc<dyn SetOperator>,
+) {
+    let operator = Arc::clone(&operator);
+    registry.register_custom("setop", move |payload, input_from| {
+        let config: SetOpConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            SetOpBlock::new(config, Arc::clone(&operator)).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use serde_json::json;
+
+    fn run(config: SetOpConfig, outputs: Vec<BlockOutput>) -> BlockOutput {
+        SetOpBlock::new(config, Arc::new(StdSetOperator))
+            .execute(test_ctx(BlockInput::Multi { outputs }))
+            .unwrap()
+            .into_once()
+    }
+
+    fn list(items: &[&str]) -> BlockOutput {
+        BlockOutput::List {
+            items: items.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn setop_lists_are_sorted_and_deduplicated() {
+        let inputs = || {
+            vec![
+                list(&["rust", "go", "rust", "zig"]),
+                list(&["go", "c", "zig"]),
+                list(&["zig", "go"]),
+            ]
+        };
+        assert_eq!(
+            run(SetOpConfig::new(SetOpMode::Union), inputs()),
+            list(&["c", "go", "rust", "zig"])
+        );
+        assert_eq!(
+            run(SetOpConfig::new(SetOpMode::Intersection), inputs()),
+            list(&["go", "zig"])
+        );
+        assert_eq!(
+            run(SetOpConfig::new(SetOpMode::Difference), inputs()),
+            list(&["rust"])
+        );
+
+        let err = SetOpBlock::new(SetOpConfig::new(SetOpMode::Union), Arc::new(StdSetOperator))
+            .execute(test_ctx(BlockInput::List {
+                items: vec!["a".into()],
+            }))
+            .unwrap_err();
+        assert!(err.to_string().contains("at least two"), "got: {err}");
+    }
+
+    #[test]
+    fn setop_json_items_compare_by_key() {
+        let inputs = || {
+            vec![
+                BlockOutput::Json {
+                    value: json!([
+                        { "link": "b", "title": "B from feed one" },
+                        { "link": "a", "title": "A" },
+                    ]),
+                },
+                BlockOutput::Json {
+                    value: json!([
+                        { "link": "b", "title": "B from feed two" },
+                        { "link": "c", "title": "C" },
+                    ]),
+                },
+            ]
+        };
+        let keyed = |mode| SetOpConfig::new(mode).with_key("link");
+        assert_eq!(
+            run(keyed(SetOpMode::Union), inputs()),
+            BlockOutput::Json {
+                value: json!([
+                    { "link": "a", "title": "A" },
+                    { "link": "b", "title": "B from feed one" },
+                    { "link": "c", "title": "C" },
+                ])
+            }
+        );
+        assert_eq!(
+            run(keyed(SetOpMode::Intersection), inputs()),
+            BlockOutput::Json {
+                value: json!([{ "link": "b", "title": "B from feed one" }])
+            }
+        );
+        assert_eq!(
+            run(keyed(SetOpMode::Difference), inputs()),
+            BlockOutput::Json {
+                value: json!([{ "link": "a", "title": "A" }])
+            }
+        );
+    }
+}
@@ -78,7 +78,7 @@ impl BlockExecutor for SplitByKeysBlock {
                 serde_json::from_str(s).map_err(|e| BlockError::Other(e.to_string()))?
             }
             BlockInput::Empty => serde_json::json!({}),
-            BlockInput::List { .. } | BlockInput::Multi { .. } => {
+            BlockInput::List { .. } | BlockInput::Multi { .. } | BlockInput::MultiNamed { .. } => {
                 return Err(BlockError::Other(
                     "SplitByKeys expects Json or string object".into(),
                 ));
@@ -149,21 +149,10 @@ pub fn register_split_by_keys(
     });
 }
 
-#[cfg(test)]
-fn test_ctx(input: BlockInput) -> BlockExecutionContext {
-    BlockExecutionContext {
-        workflow_id: uuid::Uuid::new_v4(),
-        run_id: uuid::Uuid::new_v4(),
-        block_id: uuid::Uuid::new_v4(),
-        attempt: 1,
-        prev: input,
-        store: Default::default(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_ctx;
 
     #[test]
     fn split_by_keys_outputs_one_per_key() {
@@ -1,4 +1,9 @@
 //! SplitLines block: Control block that splits text into multiple outputs.
+//! With the default `\n` delimiter a trailing `\r` is dropped, so CRLF text splits cleanly.
+//! `trim_each` trims every line and `skip_empty` drops lines left empty; both are on by default.
+//! With `fan_out` (the default) each line is its own output, routed to successors in edge order like
+//! SplitByKeys, so the block needs one successor edge per line. Set it to false to emit a single
+//! `List` for one successor instead.
 //! Pass your strategy when registering: `register_split_lines(registry, Arc::new(your_strategy))`.
 
 use std::sync::Arc;
@@ -40,10 +45,13 @@ pub trait LineSplitStrategy: Send + Sync {
 pub struct SplitLinesConfig {
     #[serde(default = "default_delimiter")]
     pub delimiter: String,
-    #[serde(default = "default_true")]
+    #[serde(default = "default_true", alias = "trim")]
     pub trim_each: bool,
     #[serde(default = "default_true")]
     pub skip_empty: bool,
+    /// Emit `Multiple` (one `String` per line) instead of one `List`.
+    #[serde(default = "default_true")]
+    pub fan_out: bool,
 }
 
 fn default_delimiter() -> String {
@@ -60,6 +68,7 @@ impl Default for SplitLinesConfig {
             delimiter: default_delimiter(),
             trim_each: true,
             skip_empty: true,
+            fan_out: true,
         }
     }
 }
@@ -68,6 +77,26 @@ impl SplitLinesConfig {
     pub fn new() -> Self {
         Self::default()
     }
+
+    pub fn with_delimiter(mut self, delimiter: impl Into<String>) -> Self {
+        self.delimiter = delimiter.into();
+        self
+    }
+
+    pub fn with_trim_each(mut self, trim_each: bool) -> Self {
+        self.trim_each = trim_each;
+        self
+    }
+
+    pub fn with_skip_empty(mut self, skip_empty: bool) -> Self {
+        self.skip_empty = skip_empty;
+        self
+    }
+
+    pub fn with_fan_out(mut self, fan_out: bool) -> Self {
+        self.fan_out = fan_out;
+        self
+    }
 }
 
 pub struct SplitLinesBlock {
@@ -102,7 +131,7 @@ impl BlockExecutor for SplitLinesBlock {
                 .map(String::from)
                 .ok_or_else(|| BlockError::Other("split_lines expects string/text input".into()))?,
             BlockInput::Empty => String::new(),
-            BlockInput::List { .. } | BlockInput::Multi { .. } => {
+            BlockInput::List { .. } | BlockInput::Multi { .. } | BlockInput::MultiNamed { .. } => {
                 return Err(BlockError::Other(
                     "split_lines expects string/text input".into(),
                 ));
@@ -118,6 +147,11 @@ impl BlockExecutor for SplitLinesBlock {
                 self.config.skip_empty,
             )
             .map_err(|e| BlockError::Other(e.0))?;
+        if !self.config.fan_out {
+            return Ok(BlockExecutionResult::Once(BlockOutput::List {
+                items: lines,
+            }));
+        }
         let outputs = lines
             .into_iter()
             .map(|line| BlockOutput::String { value: line })
@@ -126,7 +160,11 @@ impl BlockExecutor for SplitLinesBlock {
     }
 
     fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
-        OutputContract::from_kind(ValueKind::String, OutputMode::Multiple)
+        if self.config.fan_out {
+            OutputContract::from_kind(ValueKind::String, OutputMode::Multiple)
+        } else {
+            OutputContract::from_kind(ValueKind::List, OutputMode::Once)
+        }
     }
 
     fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
@@ -159,6 +197,11 @@ impl LineSplitStrategy for StdLineSplitter {
         };
         let mut out = Vec::new();
         for raw in text.split(delim) {
+            let raw = if delim == "\n" {
+                raw.strip_suffix('\r').unwrap_or(raw)
+            } else {
+                raw
+            };
             let v = if trim_each {
                 raw.trim().to_string()
             } else {
@@ -188,21 +231,10 @@ pub fn register_split_lines(
     });
 }
 
-#[cfg(test)]
-fn test_ctx(input: BlockInput) -> BlockExecutionContext {
-    BlockExecutionContext {
-        workflow_id: uuid::Uuid::new_v4(),
-        run_id: uuid::Uuid::new_v4(),
-        block_id: uuid::Uuid::new_v4(),
-        attempt: 1,
-        prev: input,
-        store: Default::default(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_ctx;
 
     #[test]
     fn split_lines_returns_multiple_outputs() {
@@ -221,6 +253,72 @@ mod tests {
         }
     }
 
+    fn split(config: SplitLinesConfig, text: &str) -> Vec<String> {
+        match SplitLinesBlock::new(config, Arc::new(StdLineSplitter))
+            .execute(test_ctx(BlockInput::Text(text.into())))
+            .unwrap()
+        {
+            BlockExecutionResult::Multiple(outs) => outs
+                .into_iter()
+                .map(|out| match out {
+                    BlockOutput::String { value } => value,
+                    other => panic!("expected String, got {:?}", other),
+                })
+                .collect(),
+            _ => panic!("expected Multiple output"),
+        }
+    }
+
+    #[test]
+    fn split_lines_crlf_input_drops_carriage_returns() {
+        let raw = SplitLinesConfig::new()
+            .with_trim_each(false)
+            .with_skip_empty(false);
+        assert_eq!(split(raw, " a\r\nb \r\n"), vec![" a", "b ", ""]);
+        assert_eq!(
+            split(SplitLinesConfig::new(), " a\r\nb \r\n"),
+            vec!["a", "b"]
+        );
+    }
+
+    #[test]
+    fn split_lines_trailing_newline_yields_no_empty_item() {
+        assert_eq!(split(SplitLinesConfig::new(), "a\nb\n"), vec!["a", "b"]);
+        let keep_empty = SplitLinesConfig::new().with_skip_empty(false);
+        assert_eq!(split(keep_empty, "a\nb\n"), vec!["a", "b", ""]);
+    }
+
+    #[test]
+    fn split_lines_interior_blank_lines_follow_skip_empty_and_trim() {
+        let text = "a\n\n   \nb";
+        assert_eq!(split(SplitLinesConfig::new(), text), vec!["a", "b"]);
+        let untrimmed = SplitLinesConfig::new().with_trim_each(false);
+        assert_eq!(split(untrimmed, text), vec!["a", "   ", "b"]);
+        let keep_empty = SplitLinesConfig::new().with_skip_empty(false);
+        assert_eq!(split(keep_empty, text), vec!["a", "", "", "b"]);
+
+        let config: SplitLinesConfig =
+            serde_json::from_value(serde_json::json!({ "trim": false })).unwrap();
+        assert!(!config.trim_each);
+    }
+
+    #[test]
+    fn split_lines_without_fan_out_returns_one_list() {
+        let config = SplitLinesConfig::new().with_fan_out(false);
+        let out = SplitLinesBlock::new(config, Arc::new(StdLineSplitter))
+            .execute(test_ctx(BlockInput::Text("a\r\n\nb\n".into())))
+            .unwrap();
+        assert_eq!(
+            out.into_once(),
+            BlockOutput::List {
+                items: vec!["a".into(), "b".into()]
+            }
+        );
+
+        let config: SplitLinesConfig = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(config.fan_out);
+    }
+
     #[test]
     fn split_lines_error_input_returns_error() {
         let block = SplitLinesBlock::new(SplitLinesConfig::default(), Arc::new(StdLineSplitter));
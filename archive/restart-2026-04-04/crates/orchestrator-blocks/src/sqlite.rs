@@ -0,0 +1,452 @@
+//! SQLite block: a file-backed state store for workflows without a database server.
+//! `get` and `set` read and write a key-value table; `query` runs SQL and returns rows as JSON objects.
+//! For `set`, the value comes from input (JSON is stored serialized); for `query`, a JSON array input
+//! supplies positional `?` parameters. Writes run in a transaction.
+//! `get` on a missing key outputs `Empty`.
+//! The rusqlite-backed [`RusqliteStore`] is available with the `sqlite` feature (on by default),
+//! which links the system libsqlite3; enable `sqlite-bundled` to compile SQLite from source instead.
+//! Pass your store when registering: `register_sqlite(registry, Arc::new(your_store))`.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::input_binding::{
+    resolve_effective_input, validate_expected_input, validate_single_input_mode,
+};
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
+};
+
+/// Error from SQLite operations.
+#[derive(Debug, Clone)]
+pub struct SqliteError(pub String);
+
+impl std::fmt::Display for SqliteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for SqliteError {}
+
+/// SQLite store abstraction. Implement and pass when registering.
+pub trait SqliteStore: Send + Sync {
+    /// Value stored under `key` in `table`, or `None` when unset.
+    fn get(&self, path: &str, table: &str, key: &str) -> Result<Option<String>, SqliteError>;
+    /// Insert or replace `key` in `table`.
+    fn set(&self, path: &str, table: &str, key: &str, value: &str) -> Result<(), SqliteError>;
+    /// Run `sql` with positional `params`; each returned row is a JSON object keyed by column name.
+    fn query(
+        &self,
+        path: &str,
+        sql: &str,
+        params: &[serde_json::Value],
+    ) -> Result<Vec<serde_json::Value>, SqliteError>;
+}
+
+/// What the block does with the database.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SqliteOperation {
+    /// Read `key`; outputs the stored string, or `Empty` when the key is missing.
+    #[default]
+    Get,
+    /// Store the input under `key`; outputs the stored string.
+    Set,
+    /// Run `sql`; outputs a JSON array of row objects.
+    Query,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SqliteConfig {
+    /// Database file; created on first use.
+    pub path: String,
+    #[serde(default)]
+    pub operation: SqliteOperation,
+    /// Key for `get` and `set`.
+    #[serde(default)]
+    pub key: Option<String>,
+    /// Statement for `query`.
+    #[serde(default)]
+    pub sql: Option<String>,
+    /// Key-value table; created on first use.
+    #[serde(default = "default_table")]
+    pub table: String,
+}
+
+fn default_table() -> String {
+    "kv".to_string()
+}
+
+impl SqliteConfig {
+    pub fn get(path: impl Into<String>, key: impl Into<String>) -> Self {
+        Self::new(path, SqliteOperation::Get).with_key(key)
+    }
+
+    pub fn set(path: impl Into<String>, key: impl Into<String>) -> Self {
+        Self::new(path, SqliteOperation::Set).with_key(key)
+    }
+
+    pub fn query(path: impl Into<String>, sql: impl Into<String>) -> Self {
+        Self {
+            sql: Some(sql.into()),
+            ..Self::new(path, SqliteOperation::Query)
+        }
+    }
+
+    fn new(path: impl Into<String>, operation: SqliteOperation) -> Self {
+        Self {
+            path: path.into(),
+            operation,
+            key: None,
+            sql: None,
+            table: default_table(),
+        }
+    }
+
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    pub fn with_table(mut self, table: impl Into<String>) -> Self {
+        self.table = table.into();
+        self
+    }
+}
+
+pub struct SqliteBlock {
+    config: SqliteConfig,
+    store: Arc<dyn SqliteStore>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl SqliteBlock {
+    pub fn new(config: SqliteConfig, store: Arc<dyn SqliteStore>) -> Self {
+        Self {
+            config,
+            store,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+
+    fn key(&self) -> Result<&str, BlockError> {
+        self.config
+            .key
+            .as_deref()
+            .filter(|k| !k.is_empty())
+            .ok_or_else(|| BlockError::Other("sqlite get/set requires a key".into()))
+    }
+}
+
+/// Table names are interpolated into SQL, so only plain identifiers are accepted.
+fn check_table(table: &str) -> Result<(), BlockError> {
+    let valid = table
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && table.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(BlockError::Other(format!(
+            "sqlite table must be a plain identifier, got {:?}",
+            table
+        )))
+    }
+}
+
+fn err(e: SqliteError) -> BlockError {
+    BlockError::Other(e.0)
+}
+
+impl BlockExecutor for SqliteBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        if self.config.path.trim().is_empty() {
+            return Err(BlockError::Other("sqlite requires a path".into()));
+        }
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        if let BlockInput::Error { message } = &input {
+            return Err(BlockError::Other(message.clone()));
+        }
+        let path = &self.config.path;
+        let output = match self.config.operation {
+            SqliteOperation::Get => {
+                check_table(&self.config.table)?;
+                match self
+                    .store
+                    .get(path, &self.config.table, self.key()?)
+                    .map_err(err)?
+                {
+                    Some(value) => BlockOutput::String { value },
+                    None => BlockOutput::Empty,
+                }
+            }
+            SqliteOperation::Set => {
+                check_table(&self.config.table)?;
+                let value = match input {
+                    BlockInput::String(s) | BlockInput::Text(s) => s,
+                    BlockInput::Json(v) => v.to_string(),
+                    BlockInput::List { items } => serde_json::to_string(&items)
+                        .map_err(|e| BlockError::Other(e.to_string()))?,
+                    _ => return Err(BlockError::Other("sqlite set requires a value".into())),
+                };
+                self.store
+                    .set(path, &self.config.table, self.key()?, &value)
+                    .map_err(err)?;
+                BlockOutput::String { value }
+            }
+            SqliteOperation::Query => {
+                let sql = self
+                    .config
+                    .sql
+                    .as_deref()
+                    .filter(|s| !s.trim().is_empty())
+                    .ok_or_else(|| BlockError::Other("sqlite query requires sql".into()))?;
+                let params = match input {
+                    BlockInput::Json(serde_json::Value::Array(params)) => params,
+                    _ => Vec::new(),
+                };
+                let rows = self.store.query(path, sql, &params).map_err(err)?;
+                BlockOutput::Json {
+                    value: serde_json::Value::Array(rows),
+                }
+            }
+        };
+        Ok(BlockExecutionResult::Once(output))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        let kinds = match self.config.operation {
+            SqliteOperation::Query => ValueKindSet::singleton(ValueKind::Json),
+            SqliteOperation::Get => {
+                ValueKindSet::singleton(ValueKind::String)
+                    | ValueKindSet::singleton(ValueKind::Empty)
+            }
+            SqliteOperation::Set => ValueKindSet::singleton(ValueKind::String),
+        };
+        OutputContract {
+            kinds,
+            mode: OutputMode::Once,
+        }
+    }
+
+    fn idempotent(&self) -> bool {
+        self.config.operation != SqliteOperation::Query
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        validate_single_input_mode(ctx)?;
+        validate_expected_input(
+            ctx,
+            ValueKindSet::singleton(ValueKind::Empty)
+                | ValueKindSet::singleton(ValueKind::String)
+                | ValueKindSet::singleton(ValueKind::Text)
+                | ValueKindSet::singleton(ValueKind::Json)
+                | ValueKindSet::singleton(ValueKind::List),
+        )
+    }
+}
+
+/// Default implementation using rusqlite; opens the file per call.
+#[cfg(feature = "sqlite")]
+pub struct RusqliteStore;
+
+#[cfg(feature = "sqlite")]
+impl RusqliteStore {
+    fn open(path: &str) -> Result<rusqlite::Connection, SqliteError> {
+        rusqlite::Connection::open(path).map_err(|e| SqliteError(format!("open {}: {}", path, e)))
+    }
+
+    fn create_table(conn: &rusqlite::Connection, table: &str) -> Result<(), SqliteError> {
+        conn.execute(
+            &format!(
+                "CREATE TABLE IF NOT EXISTS {} (key TEXT PRIMARY KEY, value TEXT NOT NULL)",
+                table
+            ),
+            [],
+        )
+        .map(|_| ())
+        .map_err(|e| SqliteError(e.to_string()))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+fn sql_param(value: &serde_json::Value) -> rusqlite::types::Value {
+    use rusqlite::types::Value;
+    match value {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(b) => Value::Integer(*b as i64),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Value::Integer(i),
+            None => Value::Real(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(s) => Value::Text(s.clone()),
+        other => Value::Text(other.to_string()),
+    }
+}
+
+#[cfg(feature = "sqlite")]
+fn json_column(value: rusqlite::types::ValueRef<'_>) -> serde_json::Value {
+    use base64::Engine as _;
+    use rusqlite::types::ValueRef;
+    match value {
+        ValueRef::Null => serde_json::Value::Null,
+        ValueRef::Integer(i) => i.into(),
+        ValueRef::Real(f) => f.into(),
+        ValueRef::Text(t) => String::from_utf8_lossy(t).into_owned().into(),
+        ValueRef::Blob(b) => base64::engine::general_purpose::STANDARD.encode(b).into(),
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteStore for RusqliteStore {
+    fn get(&self, path: &str, table: &str, key: &str) -> Result<Option<String>, SqliteError> {
+        use rusqlite::OptionalExtension as _;
+        let conn = Self::open(path)?;
+        Self::create_table(&conn, table)?;
+        conn.query_row(
+            &format!("SELECT value FROM {} WHERE key = ?1", table),
+            [key],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| SqliteError(e.to_string()))
+    }
+
+    fn set(&self, path: &str, table: &str, key: &str, value: &str) -> Result<(), SqliteError> {
+        let mut conn = Self::open(path)?;
+        let tx = conn.transaction().map_err(|e| SqliteError(e.to_string()))?;
+        Self::create_table(&tx, table)?;
+        tx.execute(
+            &format!(
+                "INSERT INTO {} (key, value) VALUES (?1, ?2) \
+                 ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+                table
+            ),
+            [key, value],
+        )
+        .map_err(|e| SqliteError(e.to_string()))?;
+        tx.commit().map_err(|e| SqliteError(e.to_string()))
+    }
+
+    fn query(
+        &self,
+        path: &str,
+        sql: &str,
+        params: &[serde_json::Value],
+    ) -> Result<Vec<serde_json::Value>, SqliteError> {
+        let conn = Self::open(path)?;
+        let mut stmt = conn.prepare(sql).map_err(|e| SqliteError(e.to_string()))?;
+        let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+        let params: Vec<rusqlite::types::Value> = params.iter().map(sql_param).collect();
+        let mut rows = stmt
+            .query(rusqlite::params_from_iter(params))
+            .map_err(|e| SqliteError(e.to_string()))?;
+        let mut out = Vec::new();
+        while let Some(row) = rows.next().map_err(|e| SqliteError(e.to_string()))? {
+            let mut object = serde_json::Map::with_capacity(columns.len());
+            for (i, name) in columns.iter().enumerate() {
+                let value = row.get_ref(i).map_err(|e| SqliteError(e.to_string()))?;
+                object.insert(name.clone(), json_column(value));
+            }
+            out.push(serde_json::Value::Object(object));
+        }
+        Ok(out)
+    }
+}
+
+/// Register the sqlite block with a store.
+pub fn register_sqlite(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    store: Arc<dyn SqliteStore>,
+) {
+    let store = Arc::clone(&store);
+    registry.register_custom("sqlite", move |payload, input_from| {
+        let config: SqliteConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            SqliteBlock::new(config, Arc::clone(&store)).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use serde_json::json;
+
+    fn run(config: SqliteConfig, input: BlockInput) -> BlockOutput {
+        SqliteBlock::new(config, Arc::new(RusqliteStore))
+            .execute(test_ctx(input))
+            .unwrap()
+            .into_once()
+    }
+
+    #[test]
+    fn sqlite_set_then_get_round_trips_value() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = dir.path().join("state.db").to_string_lossy().into_owned();
+
+        assert_eq!(
+            run(SqliteConfig::get(&db, "last_run"), BlockInput::Empty),
+            BlockOutput::Empty
+        );
+        run(
+            SqliteConfig::set(&db, "last_run"),
+            BlockInput::String("2026-04-01".into()),
+        );
+        run(
+            SqliteConfig::set(&db, "last_run"),
+            BlockInput::String("2026-04-02".into()),
+        );
+        assert_eq!(
+            run(SqliteConfig::get(&db, "last_run"), BlockInput::Empty),
+            BlockOutput::String {
+                value: "2026-04-02".into()
+            }
+        );
+    }
+
+    #[test]
+    fn sqlite_query_returns_rows_as_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = dir.path().join("state.db").to_string_lossy().into_owned();
+        for (key, value) in [("a", "1"), ("b", "2"), ("c", "3")] {
+            run(
+                SqliteConfig::set(&db, key).with_table("seen"),
+                BlockInput::String(value.into()),
+            );
+        }
+
+        let out = run(
+            SqliteConfig::query(
+                &db,
+                "SELECT key, CAST(value AS INTEGER) AS n FROM seen WHERE n >= ? ORDER BY key",
+            ),
+            BlockInput::Json(json!([2])),
+        );
+        assert_eq!(
+            out,
+            BlockOutput::Json {
+                value: json!([{ "key": "b", "n": 2 }, { "key": "c", "n": 3 }])
+            }
+        );
+
+        let err = SqliteBlock::new(
+            SqliteConfig::get(&db, "a").with_table("kv; DROP TABLE seen"),
+            Arc::new(RusqliteStore),
+        )
+        .execute(test_ctx(BlockInput::Empty))
+        .unwrap_err();
+        assert!(err.to_string().contains("plain identifier"), "got: {err}");
+    }
+}
@@ -0,0 +1,166 @@
+//! Stdin block: entry block that reads all of standard input and emits it, so command-line
+//! pipelines (`cat data | my-workflow`) can feed a workflow. `as: text` (default) emits
+//! `BlockOutput::Text`; `as: lines` emits a `List` with one item per line, line endings removed.
+//! Pass your source when registering: `register_stdin(registry, Arc::new(your_source))`.
+
+use std::io::Read;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockOutput,
+    OutputContract, OutputMode, ValidateContext, ValueKind,
+};
+
+/// Error from reading stdin.
+#[derive(Debug, Clone)]
+pub struct StdinError(pub String);
+
+impl std::fmt::Display for StdinError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for StdinError {}
+
+/// How stdin is emitted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StdinMode {
+    #[default]
+    Text,
+    Lines,
+}
+
+/// Stdin source abstraction. Implement and pass when registering (e.g. a byte buffer in tests).
+pub trait StdinSource: Send + Sync {
+    /// Read the whole input as UTF-8 text.
+    fn read_all(&self) -> Result<String, StdinError>;
+}
+
+/// Read `reader` to the end as UTF-8 text.
+pub fn read_text(mut reader: impl Read) -> Result<String, StdinError> {
+    let mut text = String::new();
+    reader
+        .read_to_string(&mut text)
+        .map_err(|e| StdinError(format!("stdin: {}", e)))?;
+    Ok(text)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StdinConfig {
+    #[serde(rename = "as", default)]
+    pub mode: StdinMode,
+}
+
+impl StdinConfig {
+    pub fn new(mode: StdinMode) -> Self {
+        Self { mode }
+    }
+}
+
+pub struct StdinBlock {
+    config: StdinConfig,
+    source: Arc<dyn StdinSource>,
+}
+
+impl StdinBlock {
+    pub fn new(config: StdinConfig, source: Arc<dyn StdinSource>) -> Self {
+        Self { config, source }
+    }
+}
+
+impl BlockExecutor for StdinBlock {
+    fn execute(&self, _ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        let text = self.source.read_all().map_err(|e| BlockError::Other(e.0))?;
+        let output = match self.config.mode {
+            StdinMode::Text => BlockOutput::Text { value: text },
+            StdinMode::Lines => BlockOutput::List {
+                items: text.lines().map(String::from).collect(),
+            },
+        };
+        Ok(BlockExecutionResult::Once(output))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        let kind = match self.config.mode {
+            StdinMode::Text => ValueKind::Text,
+            StdinMode::Lines => ValueKind::List,
+        };
+        OutputContract::from_kind(kind, OutputMode::Once)
+    }
+}
+
+/// Default implementation reading the process's standard input.
+pub struct ProcessStdin;
+
+impl StdinSource for ProcessStdin {
+    fn read_all(&self) -> Result<String, StdinError> {
+        read_text(std::io::stdin().lock())
+    }
+}
+
+/// Register the stdin block with a source.
+pub fn register_stdin(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    source: Arc<dyn StdinSource>,
+) {
+    registry.register_custom("stdin", move |payload, _input_from| {
+        let config: StdinConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(StdinBlock::new(config, Arc::clone(&source))))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use orchestrator_core::block::BlockInput;
+
+    struct BytesStdin(Vec<u8>);
+
+    impl StdinSource for BytesStdin {
+        fn read_all(&self) -> Result<String, StdinError> {
+            read_text(self.0.as_slice())
+        }
+    }
+
+    fn emit(mode: StdinMode, bytes: &[u8]) -> BlockOutput {
+        StdinBlock::new(StdinConfig::new(mode), Arc::new(BytesStdin(bytes.to_vec())))
+            .execute(test_ctx(BlockInput::Empty))
+            .unwrap()
+            .into_once()
+    }
+
+    #[test]
+    fn stdin_emits_text_or_lines() {
+        let piped = b"id,name\r\n1,alpha\n2,beta\n";
+        assert_eq!(
+            emit(StdinMode::Text, piped),
+            BlockOutput::Text {
+                value: "id,name\r\n1,alpha\n2,beta\n".into()
+            }
+        );
+        assert_eq!(
+            emit(StdinMode::Lines, piped),
+            BlockOutput::List {
+                items: vec!["id,name".into(), "1,alpha".into(), "2,beta".into()]
+            }
+        );
+        let config: StdinConfig =
+            serde_json::from_value(serde_json::json!({"as": "lines"})).unwrap();
+        assert_eq!(config.mode, StdinMode::Lines);
+    }
+
+    #[test]
+    fn stdin_rejects_invalid_utf8() {
+        let block = StdinBlock::new(
+            StdinConfig::default(),
+            Arc::new(BytesStdin(vec![0xff, 0xfe])),
+        );
+        assert!(block.execute(test_ctx(BlockInput::Empty)).is_err());
+    }
+}
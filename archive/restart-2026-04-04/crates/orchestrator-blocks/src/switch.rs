@@ -0,0 +1,632 @@
+//! Switch block: Control block that routes its JSON input to one successor by a field's value.
+//! Each case maps a field value to a slot; link branches with `Workflow::link_slot(switch, slot, branch)`.
+//! With `match_value` (see `Block::switch_value`) the input value itself is matched (e.g. a
+//! status string), and string input is passed on unchanged.
+//! Unmatched (or missing) values go to `default_slot`; successors on other slots are skipped.
+//! Validation requires a branch linked on every case slot and on the default slot, and no links
+//! on any other slot.
+//! With `fan_out` the switch instead returns `Multiple` like `split_by_keys`: one output per
+//! distinct slot (cases in order, then the default), in edge order. The matched slot's name goes
+//! to its successor and `Empty` to the others; link exactly one branch per slot with `link`.
+//! Pass your strategy when registering: `register_switch(registry, Arc::new(your_strategy))`.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::input_binding::{
+    resolve_effective_input, validate_expected_input, validate_single_input_mode,
+};
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, InputContract, OutputContract, OutputMode, ValidateContext, ValueKind,
+    ValueKindSet,
+};
+
+/// Error from switch routing.
+#[derive(Debug, Clone)]
+pub struct SwitchError(pub String);
+
+impl std::fmt::Display for SwitchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for SwitchError {}
+
+/// Switch strategy abstraction. Implement and pass when registering.
+pub trait SwitchStrategy: Send + Sync {
+    /// Pick the slot `value` is routed to.
+    fn select_slot(
+        &self,
+        config: &SwitchConfig,
+        value: &serde_json::Value,
+    ) -> Result<String, SwitchError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwitchConfig {
+    /// Field to match; dots address nested objects (e.g. `meta.kind`). Required unless
+    /// `match_value` is set.
+    #[serde(default)]
+    pub field: String,
+    /// Match the whole input value instead of a field.
+    #[serde(default)]
+    pub match_value: bool,
+    /// (field value, slot) pairs; the first matching case wins. Non-string values match by
+    /// their JSON text (e.g. `"3"`, `"true"`).
+    #[serde(default)]
+    pub cases: Vec<(String, String)>,
+    /// Slot for values without a case.
+    #[serde(default = "default_slot")]
+    pub default_slot: String,
+    /// Return one output per distinct slot, aligned to successor edges, instead of routing.
+    #[serde(default)]
+    pub fan_out: bool,
+}
+
+fn default_slot() -> String {
+    "default".to_string()
+}
+
+impl SwitchConfig {
+    pub fn new(field: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            match_value: false,
+            cases: Vec::new(),
+            default_slot: default_slot(),
+            fan_out: false,
+        }
+    }
+
+    /// Match the input value itself rather than one of its fields.
+    pub fn value() -> Self {
+        Self {
+            match_value: true,
+            ..Self::new("")
+        }
+    }
+
+    pub fn with_case(mut self, value: impl Into<String>, slot: impl Into<String>) -> Self {
+        self.cases.push((value.into(), slot.into()));
+        self
+    }
+
+    pub fn with_default_slot(mut self, slot: impl Into<String>) -> Self {
+        self.default_slot = slot.into();
+        self
+    }
+
+    pub fn with_fan_out(mut self, fan_out: bool) -> Self {
+        self.fan_out = fan_out;
+        self
+    }
+
+    /// Distinct slots: the case slots in order, then the default slot if no case uses it.
+    pub fn slots(&self) -> Vec<&str> {
+        let mut slots: Vec<&str> = Vec::new();
+        for slot in self
+            .cases
+            .iter()
+            .map(|(_, slot)| slot.as_str())
+            .chain([self.default_slot.as_str()])
+        {
+            if !slots.contains(&slot) {
+                slots.push(slot);
+            }
+        }
+        slots
+    }
+}
+
+pub struct SwitchBlock {
+    config: SwitchConfig,
+    strategy: Arc<dyn SwitchStrategy>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl SwitchBlock {
+    pub fn new(config: SwitchConfig, strategy: Arc<dyn SwitchStrategy>) -> Self {
+        Self {
+            config,
+            strategy,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+
+    /// Send `output` to the slot `value` selects, or with `fan_out` the slot's name to its edge.
+    fn route(
+        &self,
+        value: &serde_json::Value,
+        output: BlockOutput,
+    ) -> Result<BlockExecutionResult, BlockError> {
+        let slot = self
+            .strategy
+            .select_slot(&self.config, value)
+            .map_err(|e| BlockError::Other(e.0))?;
+        if !self.config.fan_out {
+            return Ok(BlockExecutionResult::Routed { slot, output });
+        }
+        let outputs = self
+            .config
+            .slots()
+            .into_iter()
+            .map(|candidate| {
+                if candidate == slot {
+                    BlockOutput::String {
+                        value: slot.clone(),
+                    }
+                } else {
+                    BlockOutput::Empty
+                }
+            })
+            .collect();
+        Ok(BlockExecutionResult::Multiple(outputs))
+    }
+
+    fn check_field(&self) -> Result<(), BlockError> {
+        if !self.config.match_value && self.config.field.trim().is_empty() {
+            return Err(BlockError::Other("switch requires a field".into()));
+        }
+        Ok(())
+    }
+
+    /// Every case slot and the default slot have a linked branch, and every link carries one
+    /// of those slots. With `fan_out`, one plain link per distinct slot.
+    fn check_slots(&self, successor_slots: &[Option<String>]) -> Result<(), BlockError> {
+        if self.config.fan_out {
+            if successor_slots.iter().any(Option::is_some) {
+                return Err(BlockError::Other(
+                    "switch with fan_out links its branches with link, in slot order".into(),
+                ));
+            }
+            let slots = self.config.slots().len();
+            if slots != successor_slots.len() {
+                return Err(BlockError::Other(format!(
+                    "switch has {} distinct slots but {} linked branches",
+                    slots,
+                    successor_slots.len()
+                )));
+            }
+            return Ok(());
+        }
+        let slots: BTreeSet<&str> = self.config.slots().into_iter().collect();
+        let mut linked = BTreeSet::new();
+        for slot in successor_slots {
+            let Some(slot) = slot.as_deref() else {
+                return Err(BlockError::Other(
+                    "switch branches must be linked with link_slot".into(),
+                ));
+            };
+            if !slots.contains(slot) {
+                return Err(BlockError::Other(format!(
+                    "switch has no case for linked slot '{}'",
+                    slot
+                )));
+            }
+            linked.insert(slot);
+        }
+        match slots.difference(&linked).next() {
+            Some(missing) => Err(BlockError::Other(format!(
+                "switch slot '{}' has no linked branch",
+                missing
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl BlockExecutor for SwitchBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        self.check_field()?;
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        if self.config.match_value {
+            let (value, output) = match input {
+                BlockInput::String(s) => (
+                    serde_json::Value::String(s.clone()),
+                    BlockOutput::String { value: s },
+                ),
+                BlockInput::Text(s) => (
+                    serde_json::Value::String(s.clone()),
+                    BlockOutput::Text { value: s },
+                ),
+                BlockInput::Json(v) => (v.clone(), BlockOutput::Json { value: v }),
+                BlockInput::Error { message } => return Err(BlockError::Other(message)),
+                BlockInput::Empty
+                | BlockInput::List { .. }
+                | BlockInput::Multi { .. }
+                | BlockInput::MultiNamed { .. } => {
+                    return Err(BlockError::Other("switch expects a single value".into()));
+                }
+            };
+            return self.route(&value, output);
+        }
+        let value = match input {
+            BlockInput::Json(v) => v,
+            BlockInput::String(s) | BlockInput::Text(s) => {
+                serde_json::from_str(&s).map_err(|e| BlockError::Other(e.to_string()))?
+            }
+            BlockInput::Error { message } => return Err(BlockError::Other(message)),
+            BlockInput::Empty
+            | BlockInput::List { .. }
+            | BlockInput::Multi { .. }
+            | BlockInput::MultiNamed { .. } => {
+                return Err(BlockError::Other("switch expects a JSON object".into()));
+            }
+        };
+        self.route(
+            &value,
+            BlockOutput::Json {
+                value: value.clone(),
+            },
+        )
+    }
+
+    fn infer_output_contract(&self, ctx: &ValidateContext<'_>) -> OutputContract {
+        if self.config.fan_out {
+            return OutputContract {
+                kinds: ValueKindSet::singleton(ValueKind::String)
+                    | ValueKindSet::singleton(ValueKind::Empty),
+                mode: OutputMode::Multiple,
+            };
+        }
+        match &ctx.prev {
+            InputContract::One(kinds) if self.config.match_value => OutputContract {
+                kinds: *kinds,
+                mode: OutputMode::Once,
+            },
+            _ => OutputContract::from_kind(ValueKind::Json, OutputMode::Once),
+        }
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        self.check_field()?;
+        self.check_slots(&ctx.successor_slots)?;
+        validate_single_input_mode(ctx)?;
+        validate_expected_input(
+            ctx,
+            ValueKindSet::singleton(ValueKind::String)
+                | ValueKindSet::singleton(ValueKind::Text)
+                | ValueKindSet::singleton(ValueKind::Json),
+        )
+    }
+}
+
+/// Default implementation: exact match on the field's value.
+pub struct FieldValueSwitch;
+
+impl SwitchStrategy for FieldValueSwitch {
+    fn select_slot(
+        &self,
+        config: &SwitchConfig,
+        value: &serde_json::Value,
+    ) -> Result<String, SwitchError> {
+        let field = if config.match_value {
+            Some(value)
+        } else {
+            config
+                .field
+                .split('.')
+                .try_fold(value, |current, segment| current.get(segment))
+        };
+        let key = match field {
+            Some(serde_json::Value::String(s)) => Some(s.clone()),
+            Some(serde_json::Value::Null) | None => None,
+            Some(other) => Some(other.to_string()),
+        };
+        Ok(key
+            .and_then(|k| {
+                config
+                    .cases
+                    .iter()
+                    .find(|(case, _)| *case == k)
+                    .map(|(_, slot)| slot.clone())
+            })
+            .unwrap_or_else(|| config.default_slot.clone()))
+    }
+}
+
+/// Register the switch block with a strategy.
+pub fn register_switch(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    strategy: Arc<dyn SwitchStrategy>,
+) {
+    let strategy = Arc::clone(&strategy);
+    registry.register_custom("switch", move |payload, input_from| {
+        let config: SwitchConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            SwitchBlock::new(config, Arc::clone(&strategy)).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Block;
+    use orchestrator_core::{BlockConfig, BlockRegistry, Workflow};
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct JsonSource(serde_json::Value);
+    impl BlockExecutor for JsonSource {
+        fn execute(&self, _ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+            Ok(BlockExecutionResult::Once(BlockOutput::Json {
+                value: self.0.clone(),
+            }))
+        }
+    }
+
+    struct Branch {
+        name: String,
+        runs: Arc<AtomicUsize>,
+    }
+    impl BlockExecutor for Branch {
+        fn execute(&self, _ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            Ok(BlockExecutionResult::Once(BlockOutput::Text {
+                value: self.name.clone(),
+            }))
+        }
+    }
+
+    fn route(input: serde_json::Value) -> (BlockOutput, usize) {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let mut registry = BlockRegistry::new();
+        register_switch(&mut registry, Arc::new(FieldValueSwitch));
+        registry.register_custom("json_source", |payload, _| {
+            Ok(Box::new(JsonSource(payload)))
+        });
+        let branch_runs = Arc::clone(&runs);
+        registry.register_custom("branch", move |payload, _| {
+            Ok(Box::new(Branch {
+                name: payload.as_str().unwrap_or_default().to_string(),
+                runs: Arc::clone(&branch_runs),
+            }))
+        });
+        let custom = |type_id: &str, payload: serde_json::Value| BlockConfig::Custom {
+            type_id: type_id.to_string(),
+            payload,
+            input_from: Box::new([]),
+        };
+
+        let mut w = Workflow::with_registry(registry);
+        let source = w.add(custom("json_source", input));
+        let switch = w.add(Block::switch(
+            "event.kind",
+            [("push", "code"), ("issue", "triage"), ("3", "numeric")],
+        ));
+        w.link(source, switch);
+        for (slot, name) in [
+            ("code", "code"),
+            ("triage", "triage"),
+            ("numeric", "numeric"),
+            ("default", "fallthrough"),
+        ] {
+            let branch = w.add(custom("branch", json!(name)));
+            w.link_slot(switch, slot, branch);
+        }
+        let out = w.run().expect("switch workflow runs");
+        (out, runs.load(Ordering::SeqCst))
+    }
+
+    fn text(value: &str) -> BlockOutput {
+        BlockOutput::Text {
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn switch_routes_each_value_to_its_branch_only() {
+        for (input, expected) in [
+            (json!({ "event": { "kind": "push" } }), "code"),
+            (json!({ "event": { "kind": "issue" } }), "triage"),
+            (json!({ "event": { "kind": 3 } }), "numeric"),
+        ] {
+            let (out, branch_runs) = route(input);
+            assert_eq!(out, text(expected));
+            assert_eq!(branch_runs, 1, "only the {expected} branch should run");
+        }
+    }
+
+    #[test]
+    fn switch_unmatched_and_missing_values_fall_through() {
+        for input in [
+            json!({ "event": { "kind": "release" } }),
+            json!({ "event": {} }),
+        ] {
+            let (out, branch_runs) = route(input);
+            assert_eq!(out, text("fallthrough"));
+            assert_eq!(branch_runs, 1);
+        }
+    }
+
+    #[test]
+    fn switch_value_routes_on_the_input_itself() {
+        let mut registry = BlockRegistry::new();
+        register_switch(&mut registry, Arc::new(FieldValueSwitch));
+        crate::register_conditional(&mut registry);
+        registry.register_custom("json_source", |payload, _| {
+            Ok(Box::new(JsonSource(payload)))
+        });
+        let runs = Arc::new(AtomicUsize::new(0));
+        let branch_runs = Arc::clone(&runs);
+        registry.register_custom("branch", move |payload, _| {
+            Ok(Box::new(Branch {
+                name: payload.as_str().unwrap_or_default().to_string(),
+                runs: Arc::clone(&branch_runs),
+            }))
+        });
+        let mut w = Workflow::with_registry(registry);
+        let price = w.add(BlockConfig::Custom {
+            type_id: "json_source".to_string(),
+            payload: json!(42.5),
+            input_from: Box::new([]),
+        });
+        let cheap = w.add(Block::conditional(crate::RuleKind::LessThan, "50"));
+        let switch = w.add(Block::switch_value([("then", "buy"), ("else", "wait")]));
+        w.link(price, cheap);
+        w.link(cheap, switch);
+        for slot in ["buy", "wait", "default"] {
+            let branch = w.add(BlockConfig::Custom {
+                type_id: "branch".to_string(),
+                payload: json!(slot),
+                input_from: Box::new([]),
+            });
+            w.link_slot(switch, slot, branch);
+        }
+        assert_eq!(w.run().unwrap(), text("buy"));
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn switch_slots_must_match_linked_branches() {
+        let mut registry = BlockRegistry::new();
+        register_switch(&mut registry, Arc::new(FieldValueSwitch));
+        registry.register_custom("json_source", |payload, _| {
+            Ok(Box::new(JsonSource(payload)))
+        });
+        let registry = Arc::new(registry);
+        let source = || BlockConfig::Custom {
+            type_id: "json_source".to_string(),
+            payload: json!({ "kind": "push" }),
+            input_from: Box::new([]),
+        };
+        let linkage_error = |switch: Block, slots: &[Option<&str>]| {
+            let mut w = Workflow::with_shared_registry(Arc::clone(&registry));
+            let from = w.add(source());
+            let switch = w.add(switch);
+            w.link(from, switch);
+            for slot in slots {
+                let branch = w.add(source());
+                match slot {
+                    Some(slot) => w.link_slot(switch, *slot, branch),
+                    None => w.link(switch, branch),
+                }
+            }
+            w.validate().err().map(|e| e.to_string())
+        };
+        let push = || Block::switch("kind", [("push", "code")]);
+
+        assert_eq!(
+            linkage_error(push(), &[Some("code"), Some("default")]),
+            None
+        );
+        let missing_default = linkage_error(push(), &[Some("code")]).expect("default unlinked");
+        assert!(
+            missing_default.contains("slot 'default' has no linked branch"),
+            "{missing_default}"
+        );
+        let unknown = linkage_error(push(), &[Some("code"), Some("default"), Some("docs")])
+            .expect("slot without a case");
+        assert!(
+            unknown.contains("no case for linked slot 'docs'"),
+            "{unknown}"
+        );
+        let unslotted =
+            linkage_error(push(), &[Some("code"), Some("default"), None]).expect("plain link");
+        assert!(unslotted.contains("link_slot"), "{unslotted}");
+        let no_field = linkage_error(
+            Block::switch("", [("push", "code")]),
+            &[Some("code"), Some("default")],
+        )
+        .expect("empty field");
+        assert!(no_field.contains("switch requires a field"), "{no_field}");
+    }
+
+    #[test]
+    fn switch_fan_out_emits_the_matched_slot_on_its_edge_only() {
+        let block = SwitchBlock::new(
+            SwitchConfig::new("kind")
+                .with_case("push", "code")
+                .with_case("pr", "code")
+                .with_case("issue", "triage")
+                .with_fan_out(true),
+            Arc::new(FieldValueSwitch),
+        );
+        let slot = |name: &str| BlockOutput::String {
+            value: name.to_string(),
+        };
+        for (kind, expected) in [
+            (
+                "pr",
+                vec![slot("code"), BlockOutput::Empty, BlockOutput::Empty],
+            ),
+            (
+                "issue",
+                vec![BlockOutput::Empty, slot("triage"), BlockOutput::Empty],
+            ),
+            (
+                "release",
+                vec![BlockOutput::Empty, BlockOutput::Empty, slot("default")],
+            ),
+        ] {
+            let result = block
+                .execute(crate::test_ctx(BlockInput::Json(json!({ "kind": kind }))))
+                .unwrap();
+            let BlockExecutionResult::Multiple(outputs) = result else {
+                panic!("expected Multiple for {kind}");
+            };
+            assert_eq!(outputs, expected, "{kind}");
+        }
+    }
+
+    #[test]
+    fn switch_fan_out_requires_one_plain_link_per_distinct_slot() {
+        let mut registry = BlockRegistry::new();
+        register_switch(&mut registry, Arc::new(FieldValueSwitch));
+        registry.register_custom("json_source", |payload, _| {
+            Ok(Box::new(JsonSource(payload)))
+        });
+        let registry = Arc::new(registry);
+        let source = || BlockConfig::Custom {
+            type_id: "json_source".to_string(),
+            payload: json!({ "kind": "push" }),
+            input_from: Box::new([]),
+        };
+        let linkage_error = |slots: &[Option<&str>]| {
+            let mut w = Workflow::with_shared_registry(Arc::clone(&registry));
+            let from = w.add(source());
+            let switch = w.add(
+                Block::switch(
+                    "kind",
+                    [("push", "code"), ("pr", "code"), ("issue", "triage")],
+                )
+                .set_switch_fan_out(true),
+            );
+            w.link(from, switch);
+            for slot in slots {
+                let branch = w.add(source());
+                match slot {
+                    Some(slot) => w.link_slot(switch, *slot, branch),
+                    None => w.link(switch, branch),
+                }
+            }
+            w.validate().err().map(|e| e.to_string())
+        };
+
+        assert_eq!(linkage_error(&[None, None, None]), None);
+        let too_few = linkage_error(&[None, None]).expect("two branches for three slots");
+        assert!(
+            too_few.contains("3 distinct slots but 2 linked branches"),
+            "{too_few}"
+        );
+        let too_many = linkage_error(&[None, None, None, None]).expect("four branches");
+        assert!(
+            too_many.contains("3 distinct slots but 4 linked branches"),
+            "{too_many}"
+        );
+        let slotted =
+            linkage_error(&[Some("code"), Some("triage"), Some("default")]).expect("slotted links");
+        assert!(slotted.contains("fan_out"), "{slotted}");
+    }
+}
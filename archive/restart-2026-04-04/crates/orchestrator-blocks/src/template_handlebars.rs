@@ -1,10 +1,15 @@
 //! TemplateHandlebars block: Renders a template with data using an injected renderer.
 //! Pass your renderer when registering: `register_template_handlebars(registry, Arc::new(your_renderer))`.
 //! Validates: when template has placeholders, requires JSON (or compatible) input; errors on Empty or wrong type.
+//! JSON object input also exposes run context under `ctx`: `{{ctx.run_id}}`, `{{ctx.workflow_id}}`,
+//! and every run var (e.g. `{{ctx.now}}` when the run was started with `run_with_vars`).
+//! `partials` (name -> template) are available as `{{> name}}`; `strict_mode` makes references to
+//! missing fields fail instead of rendering empty.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::input_binding::resolve_effective_input;
 use orchestrator_core::block::{
@@ -25,37 +30,80 @@ impl std::fmt::Display for TemplateError {
 impl std::error::Error for TemplateError {}
 
 /// Template renderer abstraction. Implement and pass when registering.
-/// `partials`: when present, a JSON object mapping partial name -> template string (used by default impl).
+/// `partials` maps partial name -> template string; `strict_mode` asks the renderer to fail on
+/// references to missing fields.
 pub trait TemplateRenderer: Send + Sync {
     fn render(
         &self,
         template: &str,
         data: &serde_json::Value,
-        partials: Option<&serde_json::Value>,
+        partials: &BTreeMap<String, String>,
+        strict_mode: bool,
     ) -> Result<String, TemplateError>;
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// Partials from a JSON object (name -> template string); non-string entries are skipped.
+fn partials_from_json(partials: Option<&serde_json::Value>) -> BTreeMap<String, String> {
+    partials
+        .and_then(|v| v.as_object())
+        .map(|obj| {
+            obj.iter()
+                .filter_map(|(name, val)| Some((name.clone(), val.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Accepts `"partials": null` and skips non-string partials, as payloads did before partials
+/// were a map.
+fn lenient_partials<'de, D>(deserializer: D) -> Result<BTreeMap<String, String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(partials_from_json(
+        Option::<serde_json::Value>::deserialize(deserializer)?.as_ref(),
+    ))
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TemplateHandlebarsConfig {
     #[serde(default)]
     pub template: Option<String>,
-    pub partials: Option<serde_json::Value>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_partials",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub partials: BTreeMap<String, String>,
+    #[serde(default)]
+    pub strict_mode: bool,
 }
 
 impl TemplateHandlebarsConfig {
+    /// `partials`: a JSON object mapping partial name -> template string.
     pub fn new(partials: Option<serde_json::Value>) -> Self {
         Self {
-            template: None,
-            partials,
+            partials: partials_from_json(partials.as_ref()),
+            ..Self::default()
         }
     }
 
     pub fn with_template(template: impl Into<String>, partials: Option<serde_json::Value>) -> Self {
         Self {
             template: Some(template.into()),
-            partials,
+            ..Self::new(partials)
         }
     }
+
+    pub fn with_partial(mut self, name: impl Into<String>, template: impl Into<String>) -> Self {
+        self.partials.insert(name.into(), template.into());
+        self
+    }
+
+    pub fn with_strict_mode(mut self, strict_mode: bool) -> Self {
+        self.strict_mode = strict_mode;
+        self
+    }
 }
 
 fn template_has_placeholders(template: &str) -> bool {
@@ -123,10 +171,25 @@ fn input_to_data(input: &BlockInput, strip_template_field: bool) -> serde_json::
             .first()
             .map(output_to_json)
             .unwrap_or(serde_json::Value::Null),
+        BlockInput::MultiNamed { outputs } => serde_json::Value::Object(
+            outputs
+                .iter()
+                .map(|(label, o)| (label.clone(), output_to_json(o)))
+                .collect(),
+        ),
         BlockInput::Error { message } => serde_json::Value::String(message.clone()),
     }
 }
 
+/// Run vars plus `run_id` / `workflow_id`; vars with the same name take precedence.
+fn context_vars(ctx: &BlockExecutionContext) -> serde_json::Value {
+    let mut vars = serde_json::Map::new();
+    vars.insert("run_id".into(), ctx.run_id.to_string().into());
+    vars.insert("workflow_id".into(), ctx.workflow_id.to_string().into());
+    vars.extend(ctx.vars.iter().map(|(k, v)| (k.clone(), v.clone())));
+    serde_json::Value::Object(vars)
+}
+
 impl BlockExecutor for TemplateHandlebarsBlock {
     fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
         let input = resolve_effective_input(&ctx, &self.input_from, None)?;
@@ -147,7 +210,11 @@ impl BlockExecutor for TemplateHandlebarsBlock {
         };
         let strip_template_field =
             has_input_template && (forced_mode || self.config.template.is_none());
-        let data = input_to_data(&input, strip_template_field);
+        let mut data = input_to_data(&input, strip_template_field);
+        if let Some(obj) = data.as_object_mut() {
+            // Input data wins over the injected context when it already has a `ctx` field.
+            obj.entry("ctx").or_insert_with(|| context_vars(&ctx));
+        }
 
         let needs_data = template_has_placeholders(&template);
         if needs_data && data.is_null() {
@@ -160,7 +227,12 @@ impl BlockExecutor for TemplateHandlebarsBlock {
             data.to_string()
         } else {
             self.renderer
-                .render(&template, &data, self.config.partials.as_ref())
+                .render(
+                    &template,
+                    &data,
+                    &self.config.partials,
+                    self.config.strict_mode,
+                )
                 .map_err(|e| BlockError::Other(e.0))?
         };
         Ok(BlockExecutionResult::Once(BlockOutput::Text { value: out }))
@@ -171,8 +243,8 @@ impl BlockExecutor for TemplateHandlebarsBlock {
     }
 }
 
-/// Default implementation using handlebars crate. Registers partials from
-/// `partials` when present (JSON object: name -> template string).
+/// Default implementation using handlebars crate. Registers `partials` on a fresh
+/// `Handlebars` instance and enables its strict mode when requested.
 pub struct HandlebarsTemplateRenderer;
 
 impl TemplateRenderer for HandlebarsTemplateRenderer {
@@ -180,16 +252,14 @@ impl TemplateRenderer for HandlebarsTemplateRenderer {
         &self,
         template: &str,
         data: &serde_json::Value,
-        partials: Option<&serde_json::Value>,
+        partials: &BTreeMap<String, String>,
+        strict_mode: bool,
     ) -> Result<String, TemplateError> {
         let mut reg = handlebars::Handlebars::new();
-        if let Some(obj) = partials.and_then(|v| v.as_object()) {
-            for (name, val) in obj {
-                if let Some(s) = val.as_str() {
-                    reg.register_partial(name, s)
-                        .map_err(|e| TemplateError(e.to_string()))?;
-                }
-            }
+        reg.set_strict_mode(strict_mode);
+        for (name, partial) in partials {
+            reg.register_partial(name, partial)
+                .map_err(|e| TemplateError(e.to_string()))?;
         }
         reg.render_template(template, data)
             .map_err(|e| TemplateError(e.to_string()))
@@ -211,21 +281,10 @@ pub fn register_template_handlebars(
     });
 }
 
-#[cfg(test)]
-fn test_ctx(input: BlockInput) -> BlockExecutionContext {
-    BlockExecutionContext {
-        workflow_id: uuid::Uuid::new_v4(),
-        run_id: uuid::Uuid::new_v4(),
-        block_id: uuid::Uuid::new_v4(),
-        attempt: 1,
-        prev: input,
-        store: Default::default(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_ctx;
 
     struct TestRenderer;
     impl TemplateRenderer for TestRenderer {
@@ -233,7 +292,8 @@ mod tests {
             &self,
             _template: &str,
             data: &serde_json::Value,
-            _partials: Option<&serde_json::Value>,
+            _partials: &BTreeMap<String, String>,
+            _strict_mode: bool,
         ) -> Result<String, TemplateError> {
             Ok(data.to_string())
         }
@@ -245,7 +305,8 @@ mod tests {
             &self,
             template: &str,
             data: &serde_json::Value,
-            _partials: Option<&serde_json::Value>,
+            _partials: &BTreeMap<String, String>,
+            _strict_mode: bool,
         ) -> Result<String, TemplateError> {
             Ok(format!("template={template};data={data}"))
         }
@@ -299,6 +360,93 @@ mod tests {
         }
     }
 
+    #[test]
+    fn template_handlebars_renders_registered_partials() {
+        let config =
+            TemplateHandlebarsConfig::with_template("{{> header}}: {{body}} {{> footer}}", None)
+                .with_partial("header", "Report for {{team}}")
+                .with_partial("footer", "-- sent by orchestrator");
+        let block = TemplateHandlebarsBlock::new(config, Arc::new(HandlebarsTemplateRenderer));
+        let input = BlockInput::Json(serde_json::json!({"team": "ops", "body": "All green."}));
+        let out = block.execute(test_ctx(input)).unwrap().into_once();
+        assert_eq!(
+            out,
+            BlockOutput::Text {
+                value: "Report for ops: All green. -- sent by orchestrator".into()
+            }
+        );
+    }
+
+    #[test]
+    fn template_handlebars_strict_mode_fails_on_undefined_variable() {
+        let input = || BlockInput::Json(serde_json::json!({"name": "world"}));
+        let lenient = TemplateHandlebarsBlock::new(
+            TemplateHandlebarsConfig::with_template("Hello {{name}}{{missing}}", None),
+            Arc::new(HandlebarsTemplateRenderer),
+        );
+        assert_eq!(
+            lenient.execute(test_ctx(input())).unwrap().into_once(),
+            BlockOutput::Text {
+                value: "Hello world".into()
+            }
+        );
+
+        let renderer = HandlebarsTemplateRenderer;
+        let err = renderer
+            .render(
+                "Hello {{missing}}",
+                &serde_json::json!({"name": "world"}),
+                &BTreeMap::new(),
+                true,
+            )
+            .unwrap_err();
+        assert!(err.0.contains("missing"), "got: {err}");
+
+        let strict = TemplateHandlebarsBlock::new(
+            TemplateHandlebarsConfig::with_template("Hello {{name}}{{missing}}", None)
+                .with_strict_mode(true),
+            Arc::new(HandlebarsTemplateRenderer),
+        );
+        assert!(strict.execute(test_ctx(input())).is_err());
+    }
+
+    #[test]
+    fn template_handlebars_config_accepts_null_partials() {
+        let config: TemplateHandlebarsConfig =
+            serde_json::from_value(serde_json::json!({"template": "x", "partials": null})).unwrap();
+        assert_eq!(config, TemplateHandlebarsConfig::with_template("x", None));
+    }
+
+    #[test]
+    fn template_handlebars_skips_non_string_partials() {
+        let partials = serde_json::json!({"header": "Hi {{name}}", "count": 3, "nested": {}});
+        let config =
+            TemplateHandlebarsConfig::with_template("{{> header}}", Some(partials.clone()));
+        assert_eq!(
+            config.partials,
+            BTreeMap::from([("header".to_string(), "Hi {{name}}".to_string())])
+        );
+        let from_payload: TemplateHandlebarsConfig = serde_json::from_value(
+            serde_json::json!({"template": "{{> header}}", "partials": partials}),
+        )
+        .unwrap();
+        assert_eq!(from_payload, config);
+
+        let block = TemplateHandlebarsBlock::new(config, Arc::new(HandlebarsTemplateRenderer));
+        let out = block
+            .execute(test_ctx(BlockInput::Json(
+                serde_json::json!({"name": "ops"}),
+            )))
+            .unwrap()
+            .into_once();
+        assert_eq!(
+            out,
+            BlockOutput::Text {
+                value: "Hi ops".into()
+            }
+        );
+    }
+
     #[test]
     fn template_handlebars_error_input_is_renderable() {
         let block = TemplateHandlebarsBlock::new(
@@ -317,6 +465,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn template_handlebars_renders_run_context_vars() {
+        struct JsonSource;
+        impl BlockExecutor for JsonSource {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<BlockExecutionResult, BlockError> {
+                Ok(BlockExecutionResult::Once(BlockOutput::Json {
+                    value: serde_json::json!({ "name": "digest" }),
+                }))
+            }
+        }
+
+        let mut registry = orchestrator_core::BlockRegistry::new();
+        registry.register_custom("json_source", |_, _| Ok(Box::new(JsonSource)));
+        register_template_handlebars(&mut registry, Arc::new(HandlebarsTemplateRenderer));
+        let mut workflow = orchestrator_core::Workflow::with_registry(registry);
+        let source = workflow.add(orchestrator_core::BlockConfig::Custom {
+            type_id: "json_source".to_string(),
+            payload: serde_json::json!({}),
+            input_from: Box::new([]),
+        });
+        let template = workflow.add(orchestrator_core::BlockConfig::Custom {
+            type_id: "template_handlebars".to_string(),
+            payload: serde_json::json!({
+                "template": "{{name}} at {{ctx.now}} ({{ctx.correlation_id}})"
+            }),
+            input_from: Box::new([]),
+        });
+        workflow.link(source, template);
+
+        let vars = serde_json::json!({
+            "now": "2026-04-04T08:00:00Z",
+            "correlation_id": "req-42"
+        });
+        let out = workflow
+            .run_with_vars(vars.as_object().unwrap().clone())
+            .unwrap();
+        assert_eq!(
+            out,
+            BlockOutput::Text {
+                value: "digest at 2026-04-04T08:00:00Z (req-42)".into()
+            }
+        );
+    }
+
     #[test]
     fn template_handlebars_precedence_config_over_prev_template() {
         let block = TemplateHandlebarsBlock::new(
@@ -0,0 +1,260 @@
+//! TextfileMetrics block: write a JSON object of metric name → number to `path` in Prometheus text
+//! exposition format, for node_exporter's textfile collector. Each run replaces the file atomically
+//! (temp file in the same directory, then rename), so the collector never reads a partial file.
+//! Names are sorted; characters outside `[a-zA-Z0-9_:]` become `_`. Booleans are written as 1/0.
+//! Pass your writer when registering: `register_textfile_metrics(registry, Arc::new(your_writer))`.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::MetricKind;
+use crate::input_binding::{resolve_effective_input, validate_expected_input};
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
+};
+
+/// Error from textfile metrics operations.
+#[derive(Debug, Clone)]
+pub struct TextfileMetricsError(pub String);
+
+impl std::fmt::Display for TextfileMetricsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for TextfileMetricsError {}
+
+/// Textfile writer abstraction: replace `path` with `content` atomically.
+/// Implement and pass when registering.
+pub trait TextfileWriter: Send + Sync {
+    fn write_atomic(&self, path: &Path, content: &str) -> Result<(), TextfileMetricsError>;
+}
+
+fn default_kind() -> MetricKind {
+    MetricKind::Gauge
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextfileMetricsConfig {
+    /// Output file, e.g. `/var/lib/node_exporter/textfile/digest.prom`.
+    pub path: String,
+    /// `# TYPE` written for every metric.
+    #[serde(default = "default_kind")]
+    pub kind: MetricKind,
+}
+
+impl TextfileMetricsConfig {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            kind: default_kind(),
+        }
+    }
+
+    pub fn with_kind(mut self, kind: MetricKind) -> Self {
+        self.kind = kind;
+        self
+    }
+}
+
+pub struct TextfileMetricsBlock {
+    config: TextfileMetricsConfig,
+    writer: Arc<dyn TextfileWriter>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl TextfileMetricsBlock {
+    pub fn new(config: TextfileMetricsConfig, writer: Arc<dyn TextfileWriter>) -> Self {
+        Self {
+            config,
+            writer,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+fn metric_name(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if out.chars().next().is_none_or(|c| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+/// Prometheus text exposition of `metrics`, sorted by name.
+fn render(
+    metrics: &serde_json::Map<String, serde_json::Value>,
+    kind: MetricKind,
+) -> Result<String, BlockError> {
+    let kind = match kind {
+        MetricKind::Counter => "counter",
+        MetricKind::Gauge => "gauge",
+    };
+    let mut samples = Vec::with_capacity(metrics.len());
+    for (name, value) in metrics {
+        let value = match value {
+            serde_json::Value::Number(n) => n.as_f64(),
+            serde_json::Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            BlockError::Other(format!(
+                "textfile_metrics value for {:?} is not a number: {}",
+                name, value
+            ))
+        })?;
+        samples.push((metric_name(name), value));
+    }
+    samples.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut out = String::new();
+    for (name, value) in samples {
+        out.push_str(&format!("# TYPE {} {}\n{} {}\n", name, kind, name, value));
+    }
+    Ok(out)
+}
+
+impl BlockExecutor for TextfileMetricsBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        let metrics = match input {
+            BlockInput::Json(serde_json::Value::Object(map)) => map,
+            BlockInput::Error { message } => return Err(BlockError::Other(message)),
+            other => {
+                return Err(BlockError::Other(format!(
+                    "textfile_metrics expects a JSON object of name to number, got {:?}",
+                    other.value_kind()
+                )));
+            }
+        };
+        let content = render(&metrics, self.config.kind)?;
+        self.writer
+            .write_atomic(Path::new(&self.config.path), &content)
+            .map_err(|e| BlockError::Other(e.0))?;
+        Ok(BlockExecutionResult::Once(BlockOutput::Json {
+            value: serde_json::json!({
+                "path": self.config.path,
+                "metrics": metrics.len(),
+            }),
+        }))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::Json, OutputMode::Once)
+    }
+
+    /// The file is replaced whole, so writing it again is harmless.
+    fn idempotent(&self) -> bool {
+        true
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        validate_expected_input(ctx, ValueKindSet::singleton(ValueKind::Json))
+    }
+}
+
+/// Default implementation: write a sibling temp file, then rename it over `path`.
+pub struct AtomicTextfileWriter;
+
+impl TextfileWriter for AtomicTextfileWriter {
+    fn write_atomic(&self, path: &Path, content: &str) -> Result<(), TextfileMetricsError> {
+        let err = |e: std::io::Error| TextfileMetricsError(format!("{}: {}", path.display(), e));
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(err)?;
+        }
+        let mut tmp = PathBuf::from(path);
+        tmp.set_file_name(format!(
+            ".{}.{}.tmp",
+            path.file_name()
+                .map(|n| n.to_string_lossy())
+                .unwrap_or_default(),
+            std::process::id()
+        ));
+        std::fs::write(&tmp, content).map_err(err)?;
+        std::fs::rename(&tmp, path).map_err(|e| {
+            let _ = std::fs::remove_file(&tmp);
+            err(e)
+        })
+    }
+}
+
+/// Register the textfile_metrics block with a writer.
+pub fn register_textfile_metrics(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    writer: Arc<dyn TextfileWriter>,
+) {
+    let writer = Arc::clone(&writer);
+    registry.register_custom("textfile_metrics", move |payload, input_from| {
+        let config: TextfileMetricsConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            TextfileMetricsBlock::new(config, Arc::clone(&writer)).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use serde_json::json;
+
+    #[test]
+    fn textfile_metrics_writes_exposition_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("textfile").join("digest.prom");
+        let block = TextfileMetricsBlock::new(
+            TextfileMetricsConfig::new(path.to_string_lossy()),
+            Arc::new(AtomicTextfileWriter),
+        );
+        let out = block
+            .execute(test_ctx(BlockInput::Json(json!({
+                "digest_items": 12,
+                "digest.last_success": true,
+                "digest_duration_seconds": 1.5
+            }))))
+            .unwrap()
+            .into_once();
+        assert_eq!(
+            out,
+            BlockOutput::Json {
+                value: json!({ "path": path.to_string_lossy(), "metrics": 3 })
+            }
+        );
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# TYPE digest_duration_seconds gauge\n\
+             digest_duration_seconds 1.5\n\
+             # TYPE digest_items gauge\n\
+             digest_items 12\n\
+             # TYPE digest_last_success gauge\n\
+             digest_last_success 1\n"
+        );
+        assert_eq!(
+            std::fs::read_dir(path.parent().unwrap()).unwrap().count(),
+            1
+        );
+
+        let err = block
+            .execute(test_ctx(BlockInput::Json(json!({ "status": "ok" }))))
+            .unwrap_err();
+        assert!(err.to_string().contains("not a number"), "got: {err}");
+    }
+}
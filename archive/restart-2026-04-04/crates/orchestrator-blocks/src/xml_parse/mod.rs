@@ -0,0 +1,197 @@
+//! XmlParse block: convert arbitrary XML (SOAP, sitemaps, ...) into a JSON tree.
+//! Attributes become `@attr` keys, element text becomes `#text`, and repeated tags become arrays.
+//! Pass your parser when registering: `register_xml_parse(registry, Arc::new(your_parser))`.
+
+mod quick_xml_parser;
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::input_binding::{
+    resolve_effective_input, validate_expected_input, validate_single_input_mode,
+};
+use orchestrator_core::block::{
+    BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor, BlockInput,
+    BlockOutput, OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
+};
+
+pub use quick_xml_parser::QuickXmlParser;
+
+/// Error from XML parsing operations.
+#[derive(Debug, Clone)]
+pub struct XmlParseError(pub String);
+
+impl std::fmt::Display for XmlParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for XmlParseError {}
+
+/// XML parser abstraction. Implement and pass when registering.
+pub trait XmlParser: Send + Sync {
+    /// Parse `xml` into `{ "<root>": <element> }`.
+    fn parse(&self, xml: &str) -> Result<serde_json::Value, XmlParseError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct XmlParseConfig {}
+
+pub struct XmlParseBlock {
+    _config: XmlParseConfig,
+    parser: Arc<dyn XmlParser>,
+    input_from: Box<[uuid::Uuid]>,
+}
+
+impl XmlParseBlock {
+    pub fn new(config: XmlParseConfig, parser: Arc<dyn XmlParser>) -> Self {
+        Self {
+            _config: config,
+            parser,
+            input_from: Box::new([]),
+        }
+    }
+
+    pub fn with_input_from(mut self, input_from: Box<[uuid::Uuid]>) -> Self {
+        self.input_from = input_from;
+        self
+    }
+}
+
+impl BlockExecutor for XmlParseBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        let input = resolve_effective_input(&ctx, &self.input_from, None)?;
+        let xml = match input {
+            BlockInput::String(s) => s,
+            BlockInput::Text(s) => s,
+            BlockInput::Json(v) => v.as_str().map(String::from).ok_or_else(|| {
+                BlockError::Other("xml_parse expects xml string/text input".into())
+            })?,
+            BlockInput::Error { message } => return Err(BlockError::Other(message)),
+            BlockInput::Empty
+            | BlockInput::List { .. }
+            | BlockInput::Multi { .. }
+            | BlockInput::MultiNamed { .. } => {
+                return Err(BlockError::Other(
+                    "xml_parse expects xml string/text input".into(),
+                ));
+            }
+        };
+
+        let value = self
+            .parser
+            .parse(&xml)
+            .map_err(|e| BlockError::Other(e.0))?;
+        Ok(BlockExecutionResult::Once(BlockOutput::Json { value }))
+    }
+
+    fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+        OutputContract::from_kind(ValueKind::Json, OutputMode::Once)
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        validate_single_input_mode(ctx)?;
+        validate_expected_input(
+            ctx,
+            ValueKindSet::singleton(ValueKind::String)
+                | ValueKindSet::singleton(ValueKind::Text)
+                | ValueKindSet::singleton(ValueKind::Json),
+        )
+    }
+}
+
+/// Register the xml_parse block with a parser.
+pub fn register_xml_parse(
+    registry: &mut orchestrator_core::block::BlockRegistry,
+    parser: Arc<dyn XmlParser>,
+) {
+    let parser = Arc::clone(&parser);
+    registry.register_custom("xml_parse", move |payload, input_from| {
+        let config: XmlParseConfig =
+            serde_json::from_value(payload).map_err(|e| BlockError::Other(e.to_string()))?;
+        Ok(Box::new(
+            XmlParseBlock::new(config, Arc::clone(&parser)).with_input_from(input_from),
+        ))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_ctx;
+    use serde_json::json;
+
+    fn parse(xml: &str) -> Result<serde_json::Value, BlockError> {
+        let block = XmlParseBlock::new(XmlParseConfig::default(), Arc::new(QuickXmlParser));
+        match block.execute(test_ctx(BlockInput::String(xml.to_string())))? {
+            BlockExecutionResult::Once(BlockOutput::Json { value }) => Ok(value),
+            _ => panic!("expected Once(Json)"),
+        }
+    }
+
+    #[test]
+    fn xml_parse_nested_elements() {
+        let value = parse(
+            r#"<?xml version="1.0"?>
+<envelope>
+  <body>
+    <result>
+      <status>ok</status>
+      <note>a &amp; b</note>
+    </result>
+  </body>
+</envelope>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "envelope": {
+                    "body": {
+                        "result": { "status": "ok", "note": "a & b" }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn xml_parse_attributes_and_text() {
+        let value =
+            parse(r#"<price currency="EUR" tax="incl">12.50</price><!-- trailing -->"#).unwrap();
+        assert_eq!(
+            value,
+            json!({ "price": { "@currency": "EUR", "@tax": "incl", "#text": "12.50" } })
+        );
+        let value = parse(r#"<feed><link href="https://example.com"/></feed>"#).unwrap();
+        assert_eq!(
+            value,
+            json!({ "feed": { "link": { "@href": "https://example.com" } } })
+        );
+    }
+
+    #[test]
+    fn xml_parse_repeated_tags_become_arrays() {
+        let value = parse(
+            r#"<urlset>
+  <url><loc>https://example.com/a</loc></url>
+  <url><loc>https://example.com/b</loc></url>
+  <url><loc>https://example.com/c</loc></url>
+</urlset>"#,
+        )
+        .unwrap();
+        let urls = value["urlset"]["url"].as_array().expect("array of urls");
+        assert_eq!(urls.len(), 3);
+        assert_eq!(urls[1], json!({ "loc": "https://example.com/b" }));
+    }
+
+    #[test]
+    fn xml_parse_malformed_reports_position() {
+        let err = parse("<a><b></a>").unwrap_err().to_string();
+        assert!(err.contains("malformed xml at byte"), "got: {err}");
+        let err = parse("<a><b>").unwrap_err().to_string();
+        assert!(err.contains("unclosed element <b>"), "got: {err}");
+    }
+}
@@ -0,0 +1,173 @@
+use quick_xml::XmlVersion;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesStart, Event};
+use serde_json::{Map, Value};
+
+use super::{XmlParseError, XmlParser};
+
+/// Default parser using quick-xml.
+pub struct QuickXmlParser;
+
+struct OpenElement {
+    name: String,
+    fields: Map<String, Value>,
+    text: String,
+}
+
+impl OpenElement {
+    fn from_start(start: &BytesStart<'_>) -> Result<Self, XmlParseError> {
+        let name = String::from_utf8_lossy(start.name().as_ref()).into_owned();
+        let mut fields = Map::new();
+        for attr in start.attributes() {
+            let attr = attr.map_err(|e| XmlParseError(format!("invalid attribute: {}", e)))?;
+            let key = format!("@{}", String::from_utf8_lossy(attr.key.as_ref()));
+            let value = attr
+                .normalized_value(XmlVersion::Implicit1_0)
+                .map_err(|e| XmlParseError(format!("invalid attribute value: {}", e)))?;
+            fields.insert(key, Value::String(value.into_owned()));
+        }
+        Ok(Self {
+            name,
+            fields,
+            text: String::new(),
+        })
+    }
+
+    /// Leaf elements without attributes collapse to their text; everything else is an object
+    /// with `@attr` keys, child keys, and `#text` when there is text content.
+    fn into_value(self) -> Value {
+        let mut fields = self.fields;
+        let text = self.text.trim();
+        if fields.is_empty() {
+            return Value::String(text.to_string());
+        }
+        if !text.is_empty() {
+            fields.insert("#text".to_string(), Value::String(text.to_string()));
+        }
+        Value::Object(fields)
+    }
+}
+
+fn insert_child(fields: &mut Map<String, Value>, name: String, value: Value) {
+    match fields.get_mut(&name) {
+        Some(Value::Array(items)) => items.push(value),
+        Some(existing) => {
+            let first = existing.take();
+            *existing = Value::Array(vec![first, value]);
+        }
+        None => {
+            fields.insert(name, value);
+        }
+    }
+}
+
+impl XmlParser for QuickXmlParser {
+    fn parse(&self, xml: &str) -> Result<Value, XmlParseError> {
+        let mut reader = quick_xml::Reader::from_str(xml);
+        let mut stack: Vec<OpenElement> = Vec::new();
+        let mut root: Option<(String, Value)> = None;
+
+        loop {
+            let event = reader.read_event().map_err(|e| {
+                XmlParseError(format!(
+                    "malformed xml at byte {}: {}",
+                    reader.error_position(),
+                    e
+                ))
+            })?;
+            match event {
+                Event::Start(start) => {
+                    if root.is_some() {
+                        return Err(XmlParseError(format!(
+                            "malformed xml at byte {}: multiple root elements",
+                            reader.buffer_position()
+                        )));
+                    }
+                    stack.push(OpenElement::from_start(&start)?);
+                }
+                Event::Empty(start) => {
+                    let element = OpenElement::from_start(&start)?;
+                    let name = element.name.clone();
+                    let value = element.into_value();
+                    match stack.last_mut() {
+                        Some(parent) => insert_child(&mut parent.fields, name, value),
+                        None if root.is_none() => root = Some((name, value)),
+                        None => {
+                            return Err(XmlParseError(format!(
+                                "malformed xml at byte {}: multiple root elements",
+                                reader.buffer_position()
+                            )));
+                        }
+                    }
+                }
+                Event::End(_) => {
+                    let element = stack.pop().ok_or_else(|| {
+                        XmlParseError(format!(
+                            "malformed xml at byte {}: unexpected closing tag",
+                            reader.buffer_position()
+                        ))
+                    })?;
+                    let name = element.name.clone();
+                    let value = element.into_value();
+                    match stack.last_mut() {
+                        Some(parent) => insert_child(&mut parent.fields, name, value),
+                        None => root = Some((name, value)),
+                    }
+                }
+                Event::Text(text) => {
+                    let content = text
+                        .xml10_content()
+                        .map_err(|e| XmlParseError(e.to_string()))?;
+                    if let Some(open) = stack.last_mut() {
+                        open.text.push_str(&content);
+                    }
+                }
+                Event::CData(cdata) => {
+                    let content = cdata.decode().map_err(|e| XmlParseError(e.to_string()))?;
+                    if let Some(open) = stack.last_mut() {
+                        open.text.push_str(&content);
+                    }
+                }
+                Event::GeneralRef(reference) => {
+                    let resolved = match reference
+                        .resolve_char_ref()
+                        .map_err(|e| XmlParseError(e.to_string()))?
+                    {
+                        Some(ch) => ch.to_string(),
+                        None => {
+                            let name = reference
+                                .decode()
+                                .map_err(|e| XmlParseError(e.to_string()))?;
+                            resolve_predefined_entity(&name)
+                                .ok_or_else(|| {
+                                    XmlParseError(format!(
+                                        "malformed xml at byte {}: unknown entity &{};",
+                                        reader.buffer_position(),
+                                        name
+                                    ))
+                                })?
+                                .to_string()
+                        }
+                    };
+                    if let Some(open) = stack.last_mut() {
+                        open.text.push_str(&resolved);
+                    }
+                }
+                Event::Eof => break,
+                Event::Comment(_) | Event::Decl(_) | Event::PI(_) | Event::DocType(_) => {}
+            }
+        }
+
+        if let Some(open) = stack.last() {
+            return Err(XmlParseError(format!(
+                "malformed xml at byte {}: unclosed element <{}>",
+                reader.buffer_position(),
+                open.name
+            )));
+        }
+        let (name, value) = root.ok_or_else(|| XmlParseError("xml has no root element".into()))?;
+        let mut document = Map::new();
+        document.insert(name, value);
+        Ok(Value::Object(document))
+    }
+}
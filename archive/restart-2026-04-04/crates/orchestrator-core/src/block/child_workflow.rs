@@ -9,22 +9,28 @@ use crate::core::WorkflowDefinition;
 /// Config for the child workflow block: the nested workflow definition.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ChildWorkflowConfig {
-    /// The workflow to run when this node is executed.
-    pub definition: WorkflowDefinition,
+    /// The workflow to run when this node is executed. Boxed to keep `BlockConfig` small.
+    pub definition: Box<WorkflowDefinition>,
     /// Optional timeout for the entire child workflow execution. `None` means infinite.
     #[serde(default)]
     pub timeout_ms: Option<u64>,
     /// Child workflow retry policy at the parent boundary.
     #[serde(default)]
     pub retry_policy: RetryPolicy,
+    /// Map mode: when set, a list or JSON array input runs the child once per item with at
+    /// most this many runs in flight, and the node outputs a JSON array of the child outputs
+    /// in input order.
+    #[serde(default)]
+    pub map_concurrency: Option<usize>,
 }
 
 impl ChildWorkflowConfig {
     pub fn new(definition: WorkflowDefinition) -> Self {
         Self {
-            definition,
+            definition: Box::new(definition),
             timeout_ms: None,
             retry_policy: RetryPolicy::none(),
+            map_concurrency: None,
         }
     }
 
@@ -37,4 +43,9 @@ impl ChildWorkflowConfig {
         self.retry_policy = retry_policy;
         self
     }
+
+    pub fn with_map_concurrency(mut self, map_concurrency: Option<usize>) -> Self {
+        self.map_concurrency = map_concurrency;
+        self
+    }
 }
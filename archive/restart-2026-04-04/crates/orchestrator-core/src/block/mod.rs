@@ -11,6 +11,10 @@
 //! - **Transform**, **Action**, and **Composite** blocks return [`BlockExecutionResult::Once`]
 //!   with a single [`BlockOutput`].
 //! - **Control** blocks may return `Multiple` for blocks like SplitByKeys that fan out.
+//!   `Multiple` outputs are routed to successors in edge order; return `Labeled` instead to
+//!   route each output to the successor linked with the matching slot (`Workflow::link_slot`).
+//!   Return `Routed` to send one output only to the successors linked with its slot; the other
+//!   successors, and nodes reachable only through them, are skipped for that run.
 //!
 //! ## On-error
 //!
@@ -22,6 +26,7 @@
 //! Block authors should validate input and config and return `BlockError` when execution cannot
 //! succeed, so that workflows fail fast and blocks are used correctly.
 
+use std::collections::BTreeMap;
 use std::ops::{BitOr, BitOrAssign};
 use std::sync::Arc;
 
@@ -44,6 +49,11 @@ pub enum BlockInput {
     Multi {
         outputs: Vec<BlockOutput>,
     },
+    /// Predecessor outputs keyed by edge label, when every incoming edge was linked with
+    /// `Workflow::link_as`. A predecessor that produced no output is absent.
+    MultiNamed {
+        outputs: BTreeMap<String, BlockOutput>,
+    },
     Error {
         message: String,
     },
@@ -54,13 +64,24 @@ impl BlockInput {
         BlockInput::Empty
     }
 
+    /// Outputs of a `Multi` or `MultiNamed` input (named ones in label order); empty otherwise.
+    pub fn multi_outputs(&self) -> Vec<&BlockOutput> {
+        match self {
+            BlockInput::Multi { outputs } => outputs.iter().collect(),
+            BlockInput::MultiNamed { outputs } => outputs.values().collect(),
+            _ => Vec::new(),
+        }
+    }
+
     pub fn value_kind(&self) -> ValueKind {
         match self {
             BlockInput::Empty => ValueKind::Empty,
             BlockInput::String(_) => ValueKind::String,
             BlockInput::Text(_) => ValueKind::Text,
             BlockInput::Json(_) => ValueKind::Json,
-            BlockInput::List { .. } | BlockInput::Multi { .. } => ValueKind::List,
+            BlockInput::List { .. } | BlockInput::Multi { .. } | BlockInput::MultiNamed { .. } => {
+                ValueKind::List
+            }
             BlockInput::Error { .. } => ValueKind::Text,
         }
     }
@@ -94,7 +115,10 @@ impl From<BlockInput> for Option<String> {
             BlockInput::String(s) => Some(s),
             BlockInput::Text(s) => Some(s),
             BlockInput::Json(v) => v.as_str().map(String::from).or_else(|| Some(v.to_string())),
-            BlockInput::List { .. } | BlockInput::Multi { .. } | BlockInput::Error { .. } => None,
+            BlockInput::List { .. }
+            | BlockInput::Multi { .. }
+            | BlockInput::MultiNamed { .. }
+            | BlockInput::Error { .. } => None,
         }
     }
 }
@@ -256,11 +280,28 @@ pub enum InputContract {
     Multi(Box<[ValueKindSet]>),
 }
 
+/// What validation knows about a block's place in the graph. Build one outside validation
+/// (e.g. in tests) with [`ValidateContext::new`].
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ValidateContext<'a> {
     pub block_id: Uuid,
     pub prev: InputContract,
     pub forced_refs: &'a [OutputContract],
+    /// Slot of each link out of the block, in link order; `None` for links without a slot.
+    pub successor_slots: Vec<Option<String>>,
+}
+
+impl ValidateContext<'_> {
+    /// A context for a block receiving `prev`, with a fresh id, no forced refs and no successors.
+    pub fn new(prev: InputContract) -> Self {
+        Self {
+            block_id: Uuid::new_v4(),
+            prev,
+            forced_refs: &[],
+            successor_slots: Vec::new(),
+        }
+    }
 }
 
 /// Output values stored in run context.
@@ -300,8 +341,59 @@ impl StoredOutput {
 /// Run-scoped shared output store.
 pub type SharedRunStore = Arc<DashMap<Uuid, StoredOutput>>;
 
-/// Runtime context provided to every block execution.
+/// Run-scoped variables supplied when the run starts (see `Workflow::run_with_vars`).
+pub type RunVars = Arc<serde_json::Map<String, serde_json::Value>>;
+
+/// Caller-owned state shared by every block of a run and kept across Recurring ticks
+/// (see `Workflow::with_tick_state`). Empty unless the workflow was given one.
+#[derive(Clone, Default)]
+pub struct TickState {
+    state: Option<Arc<dyn std::any::Any + Send + Sync>>,
+    /// Puts the state back to `T::default()`; the runtime calls it when a run starts.
+    reset: Option<Arc<dyn Fn() + Send + Sync>>,
+}
+
+impl TickState {
+    pub fn new<T: Default + Send + 'static>(state: Arc<std::sync::Mutex<T>>) -> Self {
+        let for_reset = Arc::clone(&state);
+        Self {
+            state: Some(state),
+            reset: Some(Arc::new(move || {
+                *for_reset
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner) = T::default();
+            })),
+        }
+    }
+
+    /// The state, when one was set and it holds a `T`.
+    pub fn get<T: Send + 'static>(&self) -> Option<Arc<std::sync::Mutex<T>>> {
+        self.state.clone()?.downcast().ok()
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Start over from `T::default()`, so one run does not see the totals of the previous one.
+    pub fn reset(&self) {
+        if let Some(reset) = &self.reset {
+            reset();
+        }
+    }
+}
+
+impl std::fmt::Debug for TickState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TickState").field(&self.is_set()).finish()
+    }
+}
+
+/// Runtime context provided to every block execution. Build one outside a run (e.g. in tests)
+/// with [`BlockExecutionContext::new`]; new fields may be added, so it can't be written as a
+/// struct literal outside this crate.
 #[derive(Clone)]
+#[non_exhaustive]
 pub struct BlockExecutionContext {
     pub workflow_id: Uuid,
     pub run_id: Uuid,
@@ -309,6 +401,30 @@ pub struct BlockExecutionContext {
     pub attempt: u32,
     pub prev: BlockInput,
     pub store: SharedRunStore,
+    pub vars: RunVars,
+    pub tick_state: TickState,
+}
+
+impl BlockExecutionContext {
+    /// A context for executing a block on its own with `prev` as input: fresh ids, attempt 1,
+    /// an empty store, no vars and no tick state. Fields are public to adjust afterwards.
+    pub fn new(prev: BlockInput) -> Self {
+        Self {
+            workflow_id: Uuid::new_v4(),
+            run_id: Uuid::new_v4(),
+            block_id: Uuid::new_v4(),
+            attempt: 1,
+            prev,
+            store: SharedRunStore::default(),
+            vars: RunVars::default(),
+            tick_state: TickState::default(),
+        }
+    }
+
+    /// The workflow's tick state as a `T` (see `Workflow::with_tick_state`).
+    pub fn tick_state<T: Send + 'static>(&self) -> Option<Arc<std::sync::Mutex<T>>> {
+        self.tick_state.get()
+    }
 }
 
 /// Block execution error.
@@ -328,6 +444,9 @@ pub enum BlockError {
         expected: String,
         actual: String,
     },
+    /// The block needs a human decision; the runtime pauses the run until it is resumed.
+    #[error("waiting for approval: {prompt}")]
+    PendingApproval { prompt: String },
 }
 
 /// Result of block execution: single output, recurring stream, or multiple ordered outputs.
@@ -336,6 +455,13 @@ pub enum BlockExecutionResult {
     Once(BlockOutput),
     Recurring(tokio::sync::mpsc::Receiver<BlockOutput>),
     Multiple(Vec<BlockOutput>),
+    /// Outputs keyed by slot label; each successor receives the output whose label matches its edge slot.
+    Labeled(Vec<(String, BlockOutput)>),
+    /// One output for the successors whose edge slot is `slot`; all other successors are skipped.
+    Routed {
+        slot: String,
+        output: BlockOutput,
+    },
 }
 
 impl BlockExecutionResult {
@@ -344,6 +470,8 @@ impl BlockExecutionResult {
             BlockExecutionResult::Once(o) => o,
             BlockExecutionResult::Recurring(_) => panic!("into_once called on Recurring result"),
             BlockExecutionResult::Multiple(_) => panic!("into_once called on Multiple result"),
+            BlockExecutionResult::Labeled(_) => panic!("into_once called on Labeled result"),
+            BlockExecutionResult::Routed { output, .. } => output,
         }
     }
 }
@@ -396,6 +524,19 @@ pub trait BlockExecutor: Send + Sync {
     fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
         OutputContract::any_once()
     }
+
+    /// Whether repeating `execute` with the same input is safe. Retry loops only retry
+    /// ambiguous failures (e.g. a timeout after the side effect may have happened) when true.
+    fn idempotent(&self) -> bool {
+        false
+    }
+
+    /// Variables this block contributes to the run vars before the run starts (e.g. values
+    /// loaded from a file). They override caller vars and are visible to every block via
+    /// `ctx.vars`. `None` (the default) contributes nothing.
+    fn run_vars(&self) -> Result<Option<serde_json::Map<String, serde_json::Value>>, BlockError> {
+        Ok(None)
+    }
 }
 
 #[cfg(test)]
@@ -424,5 +565,5 @@ pub mod registry;
 
 pub use child_workflow::ChildWorkflowConfig;
 pub use config::BlockConfig;
-pub use policy::RetryPolicy;
+pub use policy::{BackoffKind, RetryPolicy};
 pub use registry::BlockRegistry;
@@ -2,9 +2,25 @@ use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
-/// Exponential retry policy used by block-level reliability settings.
+/// How the delay grows between retries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackoffKind {
+    /// `initial_backoff_ms * backoff_factor^retries_done`.
+    #[default]
+    Exponential,
+    /// `initial_backoff_ms` before every retry.
+    Fixed,
+    /// `initial_backoff_ms + backoff_step_ms * retries_done`.
+    Linear,
+}
+
+/// Retry policy used by block-level reliability settings.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RetryPolicy {
+    /// Backoff strategy; configs without `kind` stay exponential.
+    #[serde(default)]
+    pub kind: BackoffKind,
     /// Additional attempts after the first execution.
     #[serde(default)]
     pub max_retries: u32,
@@ -14,9 +30,15 @@ pub struct RetryPolicy {
     /// Exponential multiplier per retry step.
     #[serde(default = "default_backoff_factor")]
     pub backoff_factor: f64,
+    /// Delay added per retry step (linear only).
+    #[serde(default)]
+    pub backoff_step_ms: u64,
     /// Upper bound for computed backoff.
     #[serde(default = "default_max_backoff_ms")]
     pub max_backoff_ms: u64,
+    /// Random spread applied to each backoff, as a fraction of it (0.0 disables jitter).
+    #[serde(default)]
+    pub jitter_fraction: f64,
 }
 
 const fn default_initial_backoff_ms() -> u64 {
@@ -40,10 +62,13 @@ impl Default for RetryPolicy {
 impl RetryPolicy {
     pub const fn none() -> Self {
         Self {
+            kind: BackoffKind::Exponential,
             max_retries: 0,
             initial_backoff_ms: default_initial_backoff_ms(),
             backoff_factor: default_backoff_factor(),
+            backoff_step_ms: 0,
             max_backoff_ms: default_max_backoff_ms(),
+            jitter_fraction: 0.0,
         }
     }
 
@@ -59,13 +84,54 @@ impl RetryPolicy {
             backoff_factor
         };
         Self {
+            kind: BackoffKind::Exponential,
             max_retries,
             initial_backoff_ms: initial,
             backoff_factor: factor,
+            backoff_step_ms: 0,
             max_backoff_ms: default_max_backoff_ms(),
+            jitter_fraction: 0.0,
+        }
+    }
+
+    /// Wait the same `delay_ms` before every retry, e.g. for a rate-limited API.
+    pub fn fixed(max_retries: u32, delay_ms: u64) -> Self {
+        Self {
+            kind: BackoffKind::Fixed,
+            max_retries,
+            initial_backoff_ms: delay_ms,
+            ..Self::none()
         }
     }
 
+    /// Wait `initial_ms` before the first retry and `step_ms` longer before each one after it.
+    pub fn linear(max_retries: u32, initial_ms: u64, step_ms: u64) -> Self {
+        Self {
+            kind: BackoffKind::Linear,
+            max_retries,
+            initial_backoff_ms: initial_ms,
+            backoff_step_ms: step_ms,
+            ..Self::none()
+        }
+    }
+
+    /// Like `exponential`, but each backoff is randomized by up to `± jitter_fraction * base`
+    /// so concurrent retries do not fire in lockstep. The fraction is clamped to `0.0..=1.0`.
+    pub fn exponential_with_jitter(
+        max_retries: u32,
+        initial_backoff_ms: u64,
+        backoff_factor: f64,
+        jitter_fraction: f64,
+    ) -> Self {
+        let mut policy = Self::exponential(max_retries, initial_backoff_ms, backoff_factor);
+        policy.jitter_fraction = if jitter_fraction.is_finite() {
+            jitter_fraction.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        policy
+    }
+
     pub fn with_max_backoff_ms(mut self, max_backoff_ms: u64) -> Self {
         self.max_backoff_ms = max_backoff_ms.max(1);
         self
@@ -75,20 +141,41 @@ impl RetryPolicy {
         retries_done < self.max_retries
     }
 
+    /// Like `can_retry`, but an ambiguous failure (the side effect may already have happened)
+    /// is only retried when the block is idempotent.
+    pub fn can_retry_failure(&self, retries_done: u32, ambiguous: bool, idempotent: bool) -> bool {
+        (idempotent || !ambiguous) && self.can_retry(retries_done)
+    }
+
     pub fn backoff_duration(&self, retries_done: u32) -> Duration {
         if self.max_retries == 0 {
             return Duration::ZERO;
         }
-        let exp = self.backoff_factor.powi(retries_done as i32);
-        let base = (self.initial_backoff_ms as f64 * exp).round() as u64;
-        let clamped = base.min(self.max_backoff_ms.max(1));
-        Duration::from_millis(clamped)
+        let initial = self.initial_backoff_ms as f64;
+        let mut base = match self.kind {
+            BackoffKind::Exponential => initial * self.backoff_factor.powi(retries_done as i32),
+            BackoffKind::Fixed => initial,
+            BackoffKind::Linear => initial + self.backoff_step_ms as f64 * f64::from(retries_done),
+        };
+        if self.jitter_fraction > 0.0 {
+            base += base * self.jitter_fraction * unit_random();
+        }
+        let max = self.max_backoff_ms.max(1) as f64;
+        Duration::from_millis(base.round().clamp(0.0, max) as u64)
     }
 }
 
+/// Uniform sample in `-1.0..1.0`, drawn from the v4 UUID generator. The low half of a v4 UUID
+/// is random apart from its two top (variant) bits, so those are shifted out before taking 53
+/// bits; the version bits live in the high half.
+fn unit_random() -> f64 {
+    let bits = (uuid::Uuid::new_v4().as_u64_pair().1 << 2) >> 11;
+    (bits as f64 / (1u64 << 53) as f64) * 2.0 - 1.0
+}
+
 #[cfg(test)]
 mod tests {
-    use super::RetryPolicy;
+    use super::{BackoffKind, RetryPolicy};
 
     #[test]
     fn none_policy_has_zero_retries() {
@@ -104,4 +191,69 @@ mod tests {
         assert_eq!(p.backoff_duration(1).as_millis(), 200);
         assert_eq!(p.backoff_duration(2).as_millis(), 250);
     }
+
+    #[test]
+    fn fixed_and_linear_policies_compute_delays() {
+        let fixed = RetryPolicy::fixed(3, 500);
+        let linear = RetryPolicy::linear(4, 100, 250).with_max_backoff_ms(700);
+        let millis = |p: &RetryPolicy| {
+            (0..4)
+                .map(|n| p.backoff_duration(n).as_millis())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(millis(&fixed), vec![500, 500, 500, 500]);
+        assert_eq!(millis(&linear), vec![100, 350, 600, 700]);
+        assert!(fixed.can_retry(2) && !fixed.can_retry(3));
+    }
+
+    #[test]
+    fn backoff_kind_round_trips_through_json() {
+        for policy in [
+            RetryPolicy::exponential(2, 100, 3.0),
+            RetryPolicy::fixed(3, 500),
+            RetryPolicy::linear(4, 100, 250),
+        ] {
+            let json = serde_json::to_value(&policy).unwrap();
+            assert_eq!(serde_json::from_value::<RetryPolicy>(json).unwrap(), policy);
+        }
+        let legacy: RetryPolicy =
+            serde_json::from_str(r#"{"max_retries":2,"initial_backoff_ms":100}"#).unwrap();
+        assert_eq!(legacy.kind, BackoffKind::Exponential);
+        let linear: RetryPolicy = serde_json::from_str(
+            r#"{"kind":"linear","max_retries":2,"initial_backoff_ms":100,"backoff_step_ms":50}"#,
+        )
+        .unwrap();
+        assert_eq!(linear.backoff_duration(1).as_millis(), 150);
+    }
+
+    #[test]
+    fn jittered_backoff_stays_within_bounds() {
+        assert_eq!(RetryPolicy::exponential(3, 100, 2.0).jitter_fraction, 0.0);
+        let p = RetryPolicy::exponential_with_jitter(5, 100, 2.0, 0.5).with_max_backoff_ms(300);
+        let mut seen = std::collections::BTreeSet::new();
+        for _ in 0..200 {
+            let first = p.backoff_duration(0).as_millis();
+            assert!((50..=150).contains(&first), "{first}");
+            seen.insert(first);
+            assert!(p.backoff_duration(2).as_millis() <= 300);
+        }
+        assert!(seen.len() > 1);
+        assert!(
+            seen.iter().any(|&ms| ms < 100),
+            "no delay below the base: {seen:?}"
+        );
+        assert!(
+            seen.iter().any(|&ms| ms > 100),
+            "no delay above the base: {seen:?}"
+        );
+    }
+
+    #[test]
+    fn ambiguous_failures_retry_only_when_idempotent() {
+        let p = RetryPolicy::exponential(2, 10, 2.0);
+        assert!(p.can_retry_failure(0, false, false));
+        assert!(!p.can_retry_failure(0, true, false));
+        assert!(p.can_retry_failure(1, true, true));
+        assert!(!p.can_retry_failure(2, true, true));
+    }
 }
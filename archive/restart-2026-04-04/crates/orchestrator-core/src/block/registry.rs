@@ -61,9 +61,7 @@ impl BlockRegistry {
 mod tests {
     use super::*;
     use crate::block::{BlockExecutionContext, BlockExecutor, BlockInput, BlockOutput};
-    use dashmap::DashMap;
     use serde_json::json;
-    use std::sync::Arc;
 
     #[test]
     fn empty_registry_returns_error() {
@@ -95,14 +93,9 @@ mod tests {
             input_from: Box::new([]),
         };
         let block = r.get(&config).unwrap();
-        let out = block.execute(BlockExecutionContext {
-            workflow_id: uuid::Uuid::new_v4(),
-            run_id: uuid::Uuid::new_v4(),
-            block_id: uuid::Uuid::new_v4(),
-            attempt: 1,
-            prev: BlockInput::String("hello".into()),
-            store: Arc::new(DashMap::new()),
-        });
+        let out = block.execute(BlockExecutionContext::new(BlockInput::String(
+            "hello".into(),
+        )));
         assert!(out.is_ok());
         let s: Option<String> = out.unwrap().into_once().into();
         assert_eq!(s, Some("out:HELLO".to_string()));
@@ -122,8 +115,10 @@ mod tests {
                 BlockInput::Empty => String::new(),
                 BlockInput::Json(v) => v.to_string().to_uppercase(),
                 BlockInput::List { items } => items.join(" ").to_uppercase(),
-                BlockInput::Multi { outputs } => outputs
-                    .iter()
+                BlockInput::Multi { .. } | BlockInput::MultiNamed { .. } => ctx
+                    .prev
+                    .multi_outputs()
+                    .into_iter()
                     .filter_map(|o| Option::<String>::from(o.clone()))
                     .collect::<Vec<_>>()
                     .join(" ")
@@ -1,6 +1,6 @@
 use uuid::Uuid;
 
-use super::{NodeDef, WorkflowDefinition};
+use super::{NodeDef, WorkflowDefinition, WorkflowOptions};
 use crate::block::BlockConfig;
 
 /// Fluent builder for WorkflowDefinition. Uses strongly-typed BlockConfig only.
@@ -11,6 +11,10 @@ pub struct WorkflowDefinitionBuilder {
     edges: Vec<(Uuid, Uuid)>,
     error_edges: Vec<(Uuid, Uuid)>,
     entry: Option<Uuid>,
+    options: WorkflowOptions,
+    edge_slots: Vec<(Uuid, Uuid, String)>,
+    cancel_handlers: Vec<Uuid>,
+    edge_labels: Vec<(Uuid, Uuid, String)>,
 }
 
 impl WorkflowDefinitionBuilder {
@@ -21,11 +25,15 @@ impl WorkflowDefinitionBuilder {
             edges: Vec::new(),
             error_edges: Vec::new(),
             entry: None,
+            options: WorkflowOptions::default(),
+            edge_slots: Vec::new(),
+            cancel_handlers: Vec::new(),
+            edge_labels: Vec::new(),
         }
     }
 
     pub fn add_node(mut self, id: Uuid, config: BlockConfig) -> Self {
-        self.nodes.insert(id, NodeDef { config });
+        self.nodes.insert(id, NodeDef::new(config));
         self
     }
 
@@ -34,16 +42,41 @@ impl WorkflowDefinitionBuilder {
         self
     }
 
+    /// Add an edge that receives the `slot`-labeled output of a `Labeled` result from `from`.
+    pub fn add_slot_edge(mut self, from: Uuid, slot: impl Into<String>, to: Uuid) -> Self {
+        self.edges.push((from, to));
+        self.edge_slots.push((from, to, slot.into()));
+        self
+    }
+
+    /// Add an edge whose output reaches `to` under `label` (see `BlockInput::MultiNamed`).
+    pub fn add_labeled_edge(mut self, from: Uuid, to: Uuid, label: impl Into<String>) -> Self {
+        self.edges.push((from, to));
+        self.edge_labels.push((from, to, label.into()));
+        self
+    }
+
     pub fn add_error_edge(mut self, from: Uuid, to: Uuid) -> Self {
         self.error_edges.push((from, to));
         self
     }
 
+    /// Add a cleanup node run when the run is cancelled or times out.
+    pub fn add_cancel_handler(mut self, handler: Uuid) -> Self {
+        self.cancel_handlers.push(handler);
+        self
+    }
+
     pub fn set_entry(mut self, entry: Uuid) -> Self {
         self.entry = Some(entry);
         self
     }
 
+    pub fn set_options(mut self, options: WorkflowOptions) -> Self {
+        self.options = options;
+        self
+    }
+
     pub fn build(self) -> WorkflowDefinition {
         WorkflowDefinition {
             id: self.id,
@@ -51,6 +84,10 @@ impl WorkflowDefinitionBuilder {
             edges: self.edges,
             error_edges: self.error_edges,
             entry: self.entry,
+            options: self.options,
+            edge_slots: self.edge_slots,
+            cancel_handlers: self.cancel_handlers,
+            edge_labels: self.edge_labels,
         }
     }
 }
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::WorkflowRun;
+use crate::block::BlockOutput;
+
+/// Failure to save or load a checkpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("run store: {0}")]
+pub struct RunStoreError(pub String);
+
+/// What a [`RunStore`] keeps for one run: the run itself and the outputs of its completed blocks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub run: WorkflowRun,
+    pub outputs: HashMap<Uuid, BlockOutput>,
+}
+
+/// Durable storage for run progress. The runtime saves after every level so a restarted process
+/// can resume the run without re-executing blocks that already completed.
+pub trait RunStore: Send + Sync {
+    fn save(
+        &self,
+        run: &WorkflowRun,
+        outputs: &HashMap<Uuid, BlockOutput>,
+    ) -> Result<(), RunStoreError>;
+
+    /// The last checkpoint saved for `run_id`, or `None` when the run was never saved.
+    fn load(&self, run_id: Uuid) -> Result<Option<Checkpoint>, RunStoreError>;
+}
+
+/// [`RunStore`] writing one JSON file per run (`<dir>/<run_id>.json`).
+#[derive(Debug, Clone)]
+pub struct FileRunStore {
+    dir: PathBuf,
+}
+
+impl FileRunStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path_for(&self, run_id: Uuid) -> PathBuf {
+        self.dir.join(format!("{}.json", run_id))
+    }
+}
+
+impl RunStore for FileRunStore {
+    fn save(
+        &self,
+        run: &WorkflowRun,
+        outputs: &HashMap<Uuid, BlockOutput>,
+    ) -> Result<(), RunStoreError> {
+        std::fs::create_dir_all(&self.dir).map_err(|e| RunStoreError(e.to_string()))?;
+        let checkpoint = Checkpoint {
+            run: run.clone(),
+            outputs: outputs.clone(),
+        };
+        let bytes =
+            serde_json::to_vec_pretty(&checkpoint).map_err(|e| RunStoreError(e.to_string()))?;
+        // Write then rename so a crash mid-save keeps the previous checkpoint intact.
+        let path = self.path_for(run.id);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, bytes).map_err(|e| RunStoreError(e.to_string()))?;
+        std::fs::rename(&tmp, &path).map_err(|e| RunStoreError(e.to_string()))
+    }
+
+    fn load(&self, run_id: Uuid) -> Result<Option<Checkpoint>, RunStoreError> {
+        let bytes = match std::fs::read(self.path_for(run_id)) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(RunStoreError(e.to_string())),
+        };
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| RunStoreError(e.to_string()))
+    }
+}
+
+/// Store attached to a run plus the outputs restored from its last checkpoint.
+#[derive(Clone)]
+pub(crate) struct RunCheckpoint {
+    pub(crate) store: Arc<dyn RunStore>,
+    pub(crate) restored: HashMap<Uuid, BlockOutput>,
+}
+
+impl std::fmt::Debug for RunCheckpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RunCheckpoint")
+            .field("restored", &self.restored.len())
+            .finish_non_exhaustive()
+    }
+}
@@ -1,12 +1,112 @@
 use crate::block::BlockConfig;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 /// A single node in a workflow: strongly-typed block config (no ad-hoc strings or Value in public API).
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NodeDef {
     pub config: BlockConfig,
+    /// Wall-clock budget for one execution of the block. When it elapses the block fails with
+    /// a `block.timeout` error, which on_error links can handle. `None` waits indefinitely.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+impl NodeDef {
+    /// A node running `config` with no timeout.
+    pub fn new(config: BlockConfig) -> Self {
+        Self {
+            config,
+            timeout_ms: None,
+        }
+    }
+
+    pub fn with_timeout_ms(mut self, timeout_ms: Option<u64>) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+}
+
+impl From<BlockConfig> for NodeDef {
+    fn from(config: BlockConfig) -> Self {
+        Self::new(config)
+    }
+}
+
+/// What a Recurring run does when a tick exceeds `per_tick_timeout_ms`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TickTimeoutPolicy {
+    /// Drop the tick and wait for the next one.
+    #[default]
+    Skip,
+    /// Fail the run with `RuntimeError::TickTimeout`.
+    Fail,
+}
+
+/// What a level does when one of its blocks fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LevelFailure {
+    /// Fail the run with the first failed block, in level order; siblings are not awaited.
+    #[default]
+    FailFast,
+    /// Await every block in the level, run each failure's error handlers, then fail with
+    /// `RuntimeError::LevelFailed` listing all failures.
+    CollectAll,
+}
+
+/// What building a definition does with repeated edges: the same `(from, to)` pair linked
+/// again with the same slot and label, or the same on_error link added twice.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateEdgePolicy {
+    /// Keep the first of each repeated edge, so the target sees the source once.
+    #[default]
+    Dedupe,
+    /// Keep every edge and fail validation with `WorkflowValidationError::DuplicateEdge`.
+    Error,
+}
+
+/// Run-level execution options carried with a workflow definition.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowOptions {
+    /// Recurring entry only: end the run as Completed after this many consecutive ticks
+    /// were skipped with `no_new_items`. `None` keeps consuming ticks until the entry stops.
+    #[serde(default)]
+    pub max_idle_ticks: Option<u32>,
+    /// Recurring entry only: return every tick's sink output as a JSON array when the entry
+    /// stops, instead of only the last one.
+    #[serde(default)]
+    pub collect_ticks: bool,
+    /// Recurring entry only: wall-clock budget for the downstream of each tick.
+    #[serde(default)]
+    pub per_tick_timeout_ms: Option<u64>,
+    /// Recurring entry only: how a tick that exceeds `per_tick_timeout_ms` is handled.
+    #[serde(default)]
+    pub on_tick_timeout: TickTimeoutPolicy,
+    /// How a level with failing blocks fails the run.
+    #[serde(default)]
+    pub on_level_failure: LevelFailure,
+    /// Wall-clock budget for the whole run. When it elapses the run stops, the cancel handlers
+    /// run, and the run fails with `RuntimeError::RunTimeout`.
+    #[serde(default)]
+    pub run_timeout_ms: Option<u64>,
+    /// How repeated `Workflow::link` calls between the same pair are handled.
+    #[serde(default)]
+    pub on_duplicate_edge: DuplicateEdgePolicy,
+    /// Upper bound on blocks of one level executing at once. `None` runs a whole level at once.
+    #[serde(default)]
+    pub max_parallelism: Option<usize>,
+    /// Validate the whole graph before running (see `Workflow::validate_all`): every linkage
+    /// error, unreachable blocks, and more than one sink fail the run up front.
+    #[serde(default)]
+    pub strict_validation: bool,
+    /// Accept graphs with cycles and run them iteratively (see `ExecutionMode::IterativeCycle`)
+    /// instead of failing validation with `CyclicGraph`. Linkage is not checked for such graphs.
+    #[serde(default)]
+    pub allow_cycles: bool,
 }
 
 /// Workflow definition: nodes, edges, and optional entry node.
@@ -23,6 +123,21 @@ pub struct WorkflowDefinition {
     /// Entry node id(s). For single-block workflows, one entry.
     #[serde(default)]
     pub entry: Option<Uuid>,
+    /// Run-level options (idle limits, ...). Defaults when absent.
+    #[serde(default)]
+    pub options: WorkflowOptions,
+    /// Edge slots: (from_id, to_id, label). A `Labeled` result from `from_id` routes the output
+    /// with `label` to `to_id`.
+    #[serde(default)]
+    pub edge_slots: Vec<(Uuid, Uuid, String)>,
+    /// Cleanup nodes run when the run is cancelled or times out. They are not part of the
+    /// graph and receive a JSON summary of the run as input.
+    #[serde(default)]
+    pub cancel_handlers: Vec<Uuid>,
+    /// Edge labels: (from_id, to_id, label). When every edge into a node is labeled, the node
+    /// receives `BlockInput::MultiNamed` keyed by these labels instead of positional `Multi`.
+    #[serde(default)]
+    pub edge_labels: Vec<(Uuid, Uuid, String)>,
 }
 
 impl WorkflowDefinition {
@@ -45,6 +160,101 @@ impl WorkflowDefinition {
     pub fn entry(&self) -> Option<&Uuid> {
         self.entry.as_ref()
     }
+
+    pub fn options(&self) -> &WorkflowOptions {
+        &self.options
+    }
+
+    pub fn edge_slots(&self) -> &[(Uuid, Uuid, String)] {
+        &self.edge_slots
+    }
+
+    pub fn cancel_handlers(&self) -> &[Uuid] {
+        &self.cancel_handlers
+    }
+
+    pub fn edge_labels(&self) -> &[(Uuid, Uuid, String)] {
+        &self.edge_labels
+    }
+
+    /// Slot label on the edge `from -> to`, if it was linked with a slot.
+    pub fn edge_slot(&self, from: Uuid, to: Uuid) -> Option<&str> {
+        self.edge_slots
+            .iter()
+            .find(|(f, t, _)| *f == from && *t == to)
+            .map(|(_, _, label)| label.as_str())
+    }
+
+    /// Input label on the edge `from -> to`, if it was linked with `link_as`.
+    pub fn edge_label(&self, from: Uuid, to: Uuid) -> Option<&str> {
+        self.edge_labels
+            .iter()
+            .find(|(f, t, _)| *f == from && *t == to)
+            .map(|(_, _, label)| label.as_str())
+    }
+
+    /// Edges and on_error edges that repeat an earlier one (see [`DuplicateEdgePolicy`]), in
+    /// the order they were linked.
+    pub fn duplicate_edges(&self) -> Vec<(Uuid, Uuid)> {
+        let mut seen = HashSet::new();
+        let edges = self
+            .edge_keys()
+            .into_iter()
+            .filter(|key| !seen.insert(*key))
+            .map(|(from, to, _, _)| (from, to));
+        let mut seen_error = HashSet::new();
+        let error_edges = self
+            .error_edges
+            .iter()
+            .filter(|edge| !seen_error.insert(**edge))
+            .copied();
+        edges.chain(error_edges).collect()
+    }
+
+    /// Drop every repeated edge, on_error edge, slot and label, keeping the first of each.
+    pub fn dedupe_edges(&mut self) {
+        let mut seen = HashSet::new();
+        let keep: Vec<bool> = self
+            .edge_keys()
+            .into_iter()
+            .map(|key| seen.insert(key))
+            .collect();
+        let mut keep = keep.into_iter();
+        self.edges.retain(|_| keep.next().unwrap_or(true));
+        let mut seen = HashSet::new();
+        self.error_edges.retain(|edge| seen.insert(*edge));
+        let mut seen = HashSet::new();
+        self.edge_slots.retain(|slot| seen.insert(slot.clone()));
+        let mut seen = HashSet::new();
+        self.edge_labels.retain(|label| seen.insert(label.clone()));
+    }
+
+    /// Each edge with the slot and label it was linked with. The n-th `from -> to` edge pairs
+    /// with the n-th slot and the n-th label recorded for that pair.
+    fn edge_keys(&self) -> Vec<(Uuid, Uuid, Option<&str>, Option<&str>)> {
+        fn nth(recorded: &[(Uuid, Uuid, String)], edge: (Uuid, Uuid), n: usize) -> Option<&str> {
+            recorded
+                .iter()
+                .filter(|(from, to, _)| (*from, *to) == edge)
+                .nth(n)
+                .map(|(_, _, name)| name.as_str())
+        }
+        let mut occurrences: HashMap<(Uuid, Uuid), usize> = HashMap::new();
+        self.edges
+            .iter()
+            .map(|&(from, to)| {
+                let n = occurrences.entry((from, to)).or_default();
+                let key = (
+                    from,
+                    to,
+                    nth(&self.edge_slots, (from, to), *n),
+                    nth(&self.edge_labels, (from, to), *n),
+                );
+                *n += 1;
+                key
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -62,17 +272,19 @@ mod tests {
             id,
             nodes: HashMap::from([(
                 node_id,
-                NodeDef {
-                    config: BlockConfig::Custom {
-                        type_id: "file_read".to_string(),
-                        payload: json!({ "path": "README.md" }),
-                        input_from: Box::new([]),
-                    },
-                },
+                NodeDef::new(BlockConfig::Custom {
+                    type_id: "file_read".to_string(),
+                    payload: json!({ "path": "README.md" }),
+                    input_from: Box::new([]),
+                }),
             )]),
             edges: vec![],
             error_edges: vec![],
             entry: Some(node_id),
+            options: WorkflowOptions::default(),
+            edge_slots: Vec::new(),
+            cancel_handlers: Vec::new(),
+            edge_labels: Vec::new(),
         };
         let json = serde_json::to_string(&def).unwrap();
         let restored: WorkflowDefinition = serde_json::from_str(&json).unwrap();
@@ -1,7 +1,16 @@
 mod builder;
+mod checkpoint;
 mod definition;
 mod run;
+mod template;
 
 pub use builder::WorkflowDefinitionBuilder;
-pub use definition::{NodeDef, WorkflowDefinition};
-pub use run::{RunState, WorkflowRun};
+pub(crate) use checkpoint::RunCheckpoint;
+pub use checkpoint::{Checkpoint, FileRunStore, RunStore, RunStoreError};
+pub use definition::{
+    DuplicateEdgePolicy, LevelFailure, NodeDef, TickTimeoutPolicy, WorkflowDefinition,
+    WorkflowOptions,
+};
+pub use run::{BlockReport, BlockStatus, ExecutionMode, RunState, RunSummary, WorkflowRun};
+pub(crate) use run::{BlockReports, PendingApproval};
+pub use template::{WorkflowTemplateError, WorkflowTemplateFn, WorkflowTemplateRegistry};
@@ -1,8 +1,12 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
-use crate::core::WorkflowDefinition;
+use crate::block::{BlockOutput, TickState};
+use crate::core::{Checkpoint, RunCheckpoint, RunStore, WorkflowDefinition};
+use crate::runtime::{HandlerOutput, Suspension};
 
 /// Run state for a workflow execution.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -14,6 +18,60 @@ pub enum RunState {
     Failed(String),
 }
 
+/// How the runtime scheduled a run: level-by-level over a DAG, or ready-set iteration when the
+/// reachable graph has a cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExecutionMode {
+    Topological,
+    IterativeCycle,
+}
+
+/// Final status of one block execution in a [`BlockReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockStatus {
+    Succeeded,
+    Failed,
+    /// Exceeded its node `timeout_ms`; whatever it returns later is discarded.
+    TimedOut,
+    /// Waiting for approval; the run paused here and the block completes on resume.
+    Paused,
+}
+
+/// An approval pause saved with the run's checkpoint, so its token can be resumed after a
+/// restart.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct PendingApproval {
+    pub(crate) token: String,
+    pub(crate) approval_ids: Vec<Uuid>,
+}
+
+/// Timing of one executed block, in completion order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockReport {
+    pub block_id: Uuid,
+    pub block_type: String,
+    /// Executions including retries the runtime scheduled (child workflows); blocks that retry
+    /// internally report 1.
+    pub attempts: u32,
+    pub duration_ms: u64,
+    pub status: BlockStatus,
+}
+
+/// What a successful run produced: the sink block's type and the shape of its output, as logged
+/// with the `run.succeeded` event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunSummary {
+    pub sink_block_type: String,
+    /// `empty`, `string`, `text`, `json` or `list`.
+    pub output_kind: String,
+    /// Characters for text, items for lists and JSON arrays, fields for JSON objects.
+    pub output_units: u64,
+}
+
+/// Block reports shared with the tasks that execute blocks.
+pub(crate) type BlockReports = Arc<Mutex<Vec<BlockReport>>>;
+
 /// A single workflow run: id, definition reference, state, and progress.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowRun {
@@ -24,6 +82,34 @@ pub struct WorkflowRun {
     /// Completed block ids (for progress / cycle handling later).
     #[serde(default)]
     pub completed_block_ids: HashSet<Uuid>,
+    /// Set once the runtime has chosen how to schedule the graph.
+    #[serde(default)]
+    pub execution_mode: Option<ExecutionMode>,
+    /// Run-scoped variables visible to every block through `BlockExecutionContext::vars`.
+    #[serde(default)]
+    pub vars: serde_json::Map<String, serde_json::Value>,
+    /// State visible to every block through `BlockExecutionContext::tick_state`.
+    #[serde(skip)]
+    pub tick_state: TickState,
+    /// Where a paused run stopped; taken by the runtime when the run is resumed.
+    #[serde(skip)]
+    pub(crate) suspension: Option<Box<Suspension>>,
+    /// The approval a checkpointed run is waiting for; the part of `suspension` that survives a
+    /// restart.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) pending_approval: Option<PendingApproval>,
+    /// Filled by the runtime as blocks finish.
+    #[serde(skip)]
+    pub(crate) block_reports: BlockReports,
+    /// Outputs of the on_error and on_cancel handlers that ran, in the order they finished.
+    #[serde(skip)]
+    pub(crate) handled_by: Vec<HandlerOutput>,
+    /// Where the runtime saves progress after each level, if anywhere.
+    #[serde(skip)]
+    pub(crate) checkpoint: Option<RunCheckpoint>,
+    /// Set when the run completes.
+    #[serde(default)]
+    pub summary: Option<RunSummary>,
 }
 
 impl WorkflowRun {
@@ -33,6 +119,15 @@ impl WorkflowRun {
             definition_id: definition.id,
             state: RunState::Created,
             completed_block_ids: HashSet::new(),
+            execution_mode: None,
+            vars: serde_json::Map::new(),
+            tick_state: TickState::default(),
+            suspension: None,
+            pending_approval: None,
+            block_reports: BlockReports::default(),
+            handled_by: Vec::new(),
+            checkpoint: None,
+            summary: None,
         }
     }
 
@@ -52,6 +147,22 @@ impl WorkflowRun {
         &self.completed_block_ids
     }
 
+    pub fn execution_mode(&self) -> Option<ExecutionMode> {
+        self.execution_mode
+    }
+
+    pub fn set_execution_mode(&mut self, mode: ExecutionMode) {
+        self.execution_mode = Some(mode);
+    }
+
+    pub fn vars(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.vars
+    }
+
+    pub fn set_vars(&mut self, vars: serde_json::Map<String, serde_json::Value>) {
+        self.vars = vars;
+    }
+
     pub fn set_state(&mut self, state: RunState) {
         self.state = state;
     }
@@ -59,6 +170,52 @@ impl WorkflowRun {
     pub fn mark_block_completed(&mut self, block_id: Uuid) {
         self.completed_block_ids.insert(block_id);
     }
+
+    /// What the run produced, once it has completed.
+    pub fn summary(&self) -> Option<&RunSummary> {
+        self.summary.as_ref()
+    }
+
+    /// Timings of the blocks executed so far, in completion order.
+    pub fn block_reports(&self) -> Vec<BlockReport> {
+        self.block_reports
+            .lock()
+            .expect("block reports lock")
+            .clone()
+    }
+
+    /// Outputs of the on_error and on_cancel handlers that ran, e.g. partial results a
+    /// recovery handler salvaged from a failed run.
+    pub fn handled_by(&self) -> &[HandlerOutput] {
+        &self.handled_by
+    }
+
+    /// Save completed block outputs to `store` after each level of this run.
+    pub fn set_run_store(&mut self, store: Arc<dyn RunStore>) {
+        self.checkpoint = Some(RunCheckpoint {
+            store,
+            restored: HashMap::new(),
+        });
+    }
+
+    /// Continue the run saved in `checkpoint`: blocks with a saved output are not executed again.
+    /// Block ids must match, so run the same definition the checkpoint was taken from.
+    pub fn from_checkpoint(checkpoint: Checkpoint, store: Arc<dyn RunStore>) -> Self {
+        let mut run = checkpoint.run;
+        run.state = RunState::Created;
+        run.checkpoint = Some(RunCheckpoint {
+            store,
+            restored: checkpoint.outputs,
+        });
+        run
+    }
+
+    /// Output of `block_id` restored from a checkpoint, if the block completed before a restart.
+    pub(crate) fn restored_output(&self, block_id: Uuid) -> Option<BlockOutput> {
+        self.checkpoint
+            .as_ref()
+            .and_then(|c| c.restored.get(&block_id).cloned())
+    }
 }
 
 #[cfg(test)]
@@ -78,17 +235,19 @@ mod tests {
             id: Uuid::new_v4(),
             nodes: HashMap::from([(
                 node_id,
-                NodeDef {
-                    config: BlockConfig::Custom {
-                        type_id: "file_read".to_string(),
-                        payload: json!({ "path": "README.md" }),
-                        input_from: Box::new([]),
-                    },
-                },
+                NodeDef::new(BlockConfig::Custom {
+                    type_id: "file_read".to_string(),
+                    payload: json!({ "path": "README.md" }),
+                    input_from: Box::new([]),
+                }),
             )]),
             edges: vec![],
             error_edges: vec![],
             entry: Some(node_id),
+            options: Default::default(),
+            edge_slots: Vec::new(),
+            cancel_handlers: Vec::new(),
+            edge_labels: Vec::new(),
         };
         let run = WorkflowRun::new(&def);
         assert!(matches!(run.state(), RunState::Created));
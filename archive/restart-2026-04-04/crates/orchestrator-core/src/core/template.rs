@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use super::WorkflowDefinition;
+
+/// Builds a [`WorkflowDefinition`] from JSON params (e.g. recipient, subject, template path).
+pub type WorkflowTemplateFn =
+    Box<dyn Fn(serde_json::Value) -> Result<WorkflowDefinition, String> + Send + Sync>;
+
+/// Failure to instantiate a workflow template.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum WorkflowTemplateError {
+    #[error("unknown workflow template: {0}")]
+    Unknown(String),
+    #[error("workflow template {template} rejected params: {message}")]
+    InvalidParams { template: String, message: String },
+}
+
+/// Registry of reusable sub-workflows: name -> builder. Each instantiation yields a fresh
+/// definition (new ids), ready for `Workflow::add_child_workflow`.
+#[derive(Default)]
+pub struct WorkflowTemplateRegistry {
+    templates: HashMap<String, WorkflowTemplateFn>,
+}
+
+impl WorkflowTemplateRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a template. Registering the same name again replaces the previous builder.
+    pub fn register<E: std::fmt::Display>(
+        &mut self,
+        name: impl Into<String>,
+        build: impl Fn(serde_json::Value) -> Result<WorkflowDefinition, E> + Send + Sync + 'static,
+    ) {
+        self.templates.insert(
+            name.into(),
+            Box::new(move |params| build(params).map_err(|e| e.to_string())),
+        );
+    }
+
+    /// Build the template `name` with `params`.
+    pub fn instantiate(
+        &self,
+        name: &str,
+        params: serde_json::Value,
+    ) -> Result<WorkflowDefinition, WorkflowTemplateError> {
+        let build = self
+            .templates
+            .get(name)
+            .ok_or_else(|| WorkflowTemplateError::Unknown(name.to_string()))?;
+        build(params).map_err(|message| WorkflowTemplateError::InvalidParams {
+            template: name.to_string(),
+            message,
+        })
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.templates.contains_key(name)
+    }
+
+    /// Registered template names, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.templates.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::BlockConfig;
+    use crate::workflow::Workflow;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Deserialize)]
+    struct EmailParams {
+        to: String,
+        subject: String,
+        template_path: String,
+    }
+
+    fn custom(type_id: &str, payload: serde_json::Value) -> BlockConfig {
+        BlockConfig::Custom {
+            type_id: type_id.to_string(),
+            payload,
+            input_from: Box::new([]),
+        }
+    }
+
+    fn email_templates() -> WorkflowTemplateRegistry {
+        let mut templates = WorkflowTemplateRegistry::new();
+        templates.register("email", |params| {
+            let params: EmailParams = serde_json::from_value(params)?;
+            let mut w = Workflow::new();
+            let read = w.add(custom("file_read", json!({ "path": params.template_path })));
+            let render = w.add(custom("template_handlebars", json!({})));
+            let send = w.add(custom(
+                "send_email",
+                json!({ "to": params.to, "subject": params.subject }),
+            ));
+            w.link(read, render);
+            w.link(render, send);
+            Ok::<_, serde_json::Error>(w.into_definition())
+        });
+        templates
+    }
+
+    fn send_email_payload(def: &WorkflowDefinition) -> serde_json::Value {
+        def.nodes()
+            .values()
+            .find_map(|node| match &node.config {
+                BlockConfig::Custom {
+                    type_id, payload, ..
+                } if type_id == "send_email" => Some(payload.clone()),
+                _ => None,
+            })
+            .expect("send_email node")
+    }
+
+    #[test]
+    fn email_template_instantiates_distinct_definitions() {
+        let templates = email_templates();
+        let alice = templates
+            .instantiate(
+                "email",
+                json!({ "to": "alice@example.com", "subject": "Daily", "template_path": "daily.hbs" }),
+            )
+            .unwrap();
+        let bob = templates
+            .instantiate(
+                "email",
+                json!({ "to": "bob@example.com", "subject": "Weekly", "template_path": "weekly.hbs" }),
+            )
+            .unwrap();
+
+        assert_ne!(alice.id(), bob.id());
+        assert_eq!(alice.nodes().len(), 3);
+        assert_eq!(alice.edges().len(), 2);
+        assert!(alice.nodes().keys().all(|id| !bob.nodes().contains_key(id)));
+        assert_eq!(
+            send_email_payload(&alice),
+            json!({ "to": "alice@example.com", "subject": "Daily" })
+        );
+        assert_eq!(
+            send_email_payload(&bob),
+            json!({ "to": "bob@example.com", "subject": "Weekly" })
+        );
+
+        assert_eq!(templates.names(), vec!["email"]);
+        assert!(matches!(
+            templates.instantiate("email", json!({ "to": "x@example.com" })),
+            Err(WorkflowTemplateError::InvalidParams { template, .. }) if template == "email"
+        ));
+        assert_eq!(
+            templates.instantiate("sms", json!({})).unwrap_err(),
+            WorkflowTemplateError::Unknown("sms".into())
+        );
+    }
+}
@@ -4,6 +4,13 @@ pub mod observability;
 pub mod runtime;
 pub mod workflow;
 
-pub use block::{BlockConfig, BlockOutput, BlockRegistry, RetryPolicy};
-pub use core::WorkflowDefinition;
-pub use workflow::{BlockId, RunError, Workflow, WorkflowEndpoint, WorkflowValidationError};
+pub use block::{BackoffKind, BlockConfig, BlockOutput, BlockRegistry, RetryPolicy};
+pub use core::{
+    BlockReport, BlockStatus, Checkpoint, DuplicateEdgePolicy, ExecutionMode, FileRunStore,
+    LevelFailure, RunStore, RunStoreError, RunSummary, TickTimeoutPolicy, WorkflowDefinition,
+    WorkflowTemplateError, WorkflowTemplateRegistry,
+};
+pub use runtime::HandlerOutput;
+pub use workflow::{
+    BlockId, RunError, RunFailure, RunReport, Workflow, WorkflowEndpoint, WorkflowValidationError,
+};
@@ -5,9 +5,21 @@ use uuid::Uuid;
 
 use crate::core::WorkflowDefinition;
 
-/// Error when the graph contains a cycle (no topological order exists).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct CycleDetected;
+/// Error when the graph contains a cycle (no topological order exists). `path` walks one cycle
+/// and ends on the node it started from, e.g. `[a, b, c, a]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleDetected {
+    pub path: Vec<Uuid>,
+}
+
+impl std::fmt::Display for CycleDetected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path: Vec<String> = self.path.iter().map(Uuid::to_string).collect();
+        write!(f, "cycle: {}", path.join(" -> "))
+    }
+}
+
+impl std::error::Error for CycleDetected {}
 
 /// Nodes that have an edge from `from_id`.
 pub fn successors(def: &WorkflowDefinition, from_id: Uuid) -> Vec<Uuid> {
@@ -27,7 +39,9 @@ pub fn error_successors(def: &WorkflowDefinition, from_id: Uuid) -> Vec<Uuid> {
         .collect()
 }
 
-/// Nodes that have an edge to `to_id`.
+/// Nodes that have an edge to `to_id`, in edge insertion order (the order of the `link` calls).
+/// A block with several predecessors receives `BlockInput::Multi` in this order, whichever
+/// predecessor finished first.
 pub fn predecessors(def: &WorkflowDefinition, to_id: Uuid) -> Vec<Uuid> {
     def.edges()
         .iter()
@@ -101,8 +115,71 @@ pub fn topo_order(def: &WorkflowDefinition) -> Result<Vec<Uuid>, CycleDetected>
     if order.len() == nodes.len() {
         Ok(order)
     } else {
-        Err(CycleDetected)
+        let remaining: HashSet<Uuid> = in_degree
+            .into_iter()
+            .filter(|(_, d)| *d > 0)
+            .map(|(id, _)| id)
+            .collect();
+        Err(CycleDetected {
+            path: cycle_path(def, &remaining),
+        })
+    }
+}
+
+/// One cycle among `candidates` (the nodes Kahn's algorithm could not order), found by DFS.
+/// The search starts from the entry when it is on a cycle, so the path reads in link order.
+fn cycle_path(def: &WorkflowDefinition, candidates: &HashSet<Uuid>) -> Vec<Uuid> {
+    let next_of = |id: Uuid| -> Vec<Uuid> {
+        let mut next: Vec<Uuid> = successors(def, id)
+            .into_iter()
+            .filter(|succ| candidates.contains(succ))
+            .collect();
+        // Popped from the back: visit successors in link order.
+        next.reverse();
+        next
+    };
+    let mut starts: Vec<Uuid> = candidates.iter().copied().collect();
+    starts.sort();
+    if let Some(entry) = def.entry()
+        && let Some(pos) = starts.iter().position(|id| id == entry)
+    {
+        let entry = starts.remove(pos);
+        starts.insert(0, entry);
+    }
+    let mut done: HashSet<Uuid> = HashSet::new();
+    for start in starts {
+        if done.contains(&start) {
+            continue;
+        }
+        let mut stack: Vec<(Uuid, Vec<Uuid>)> = vec![(start, next_of(start))];
+        let mut on_path: HashSet<Uuid> = HashSet::from([start]);
+        while let Some((node, next)) = stack.last_mut() {
+            let node = *node;
+            match next.pop() {
+                Some(succ) if on_path.contains(&succ) => {
+                    let mut path: Vec<Uuid> = stack
+                        .iter()
+                        .map(|(id, _)| *id)
+                        .skip_while(|id| *id != succ)
+                        .collect();
+                    path.push(succ);
+                    return path;
+                }
+                Some(succ) => {
+                    if !done.contains(&succ) {
+                        on_path.insert(succ);
+                        stack.push((succ, next_of(succ)));
+                    }
+                }
+                None => {
+                    on_path.remove(&node);
+                    done.insert(node);
+                    stack.pop();
+                }
+            }
+        }
     }
+    Vec::new()
 }
 
 /// Compute the set of node ids that are ready to run: all predecessors are in `completed`.
@@ -151,13 +228,11 @@ mod tests {
     use std::collections::HashMap;
 
     fn node_def(path: &str) -> crate::core::NodeDef {
-        crate::core::NodeDef {
-            config: BlockConfig::Custom {
-                type_id: "file_read".to_string(),
-                payload: json!({ "path": path }),
-                input_from: Box::new([]),
-            },
-        }
+        crate::core::NodeDef::new(BlockConfig::Custom {
+            type_id: "file_read".to_string(),
+            payload: json!({ "path": path }),
+            input_from: Box::new([]),
+        })
     }
 
     fn def_with_chain(a: Uuid, b: Uuid, c: Uuid) -> WorkflowDefinition {
@@ -171,6 +246,10 @@ mod tests {
             edges: vec![(a, b), (b, c)],
             error_edges: vec![],
             entry: Some(a),
+            options: Default::default(),
+            edge_slots: Vec::new(),
+            cancel_handlers: Vec::new(),
+            edge_labels: Vec::new(),
         }
     }
 
@@ -185,6 +264,10 @@ mod tests {
             edges: vec![(entry, left), (entry, right)],
             error_edges: vec![],
             entry: Some(entry),
+            options: Default::default(),
+            edge_slots: Vec::new(),
+            cancel_handlers: Vec::new(),
+            edge_labels: Vec::new(),
         }
     }
 
@@ -199,6 +282,10 @@ mod tests {
             edges: vec![(a, b), (b, c), (c, a)],
             error_edges: vec![],
             entry: Some(a),
+            options: Default::default(),
+            edge_slots: Vec::new(),
+            cancel_handlers: Vec::new(),
+            edge_labels: Vec::new(),
         }
     }
 
@@ -271,6 +358,10 @@ mod tests {
             edges: vec![(entry, left), (entry, right)],
             error_edges: vec![],
             entry: Some(entry),
+            options: Default::default(),
+            edge_slots: Vec::new(),
+            cancel_handlers: Vec::new(),
+            edge_labels: Vec::new(),
         };
         let primary = primary_sink(&def).unwrap();
         assert!(primary == left || primary == right);
@@ -280,6 +371,10 @@ mod tests {
             edges: vec![(entry, left), (entry, right)],
             error_edges: vec![],
             entry: Some(entry),
+            options: Default::default(),
+            edge_slots: Vec::new(),
+            cancel_handlers: Vec::new(),
+            edge_labels: Vec::new(),
         };
         let primary2 = primary_sink(&def_last_link_right).unwrap();
         assert_eq!(primary2, right);
@@ -311,13 +406,45 @@ mod tests {
         assert!(order[1..].contains(&right));
     }
 
+    #[test]
+    fn predecessors_follow_link_order() {
+        let (a, b, c, sink) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        let def = WorkflowDefinition {
+            id: Uuid::new_v4(),
+            nodes: HashMap::from([
+                (a, node_def("a.txt")),
+                (b, node_def("b.txt")),
+                (c, node_def("c.txt")),
+                (sink, node_def("sink.txt")),
+            ]),
+            edges: vec![(c, sink), (a, sink), (b, sink)],
+            error_edges: vec![],
+            entry: Some(a),
+            options: Default::default(),
+            edge_slots: Vec::new(),
+            cancel_handlers: Vec::new(),
+            edge_labels: Vec::new(),
+        };
+        assert_eq!(predecessors(&def, sink), vec![c, a, b]);
+    }
+
     #[test]
     fn topo_order_cycle() {
         let a = Uuid::new_v4();
         let b = Uuid::new_v4();
         let c = Uuid::new_v4();
         let def = def_with_cycle(a, b, c);
-        assert!(topo_order(&def).is_err());
+        let cycle = topo_order(&def).unwrap_err();
+        assert_eq!(cycle.path, vec![a, b, c, a]);
+        assert_eq!(
+            cycle.to_string(),
+            format!("cycle: {a} -> {b} -> {c} -> {a}")
+        );
     }
 
     #[test]
@@ -7,14 +7,19 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use crate::block::{
     BlockConfig, BlockError, BlockExecutionContext, BlockExecutionResult, BlockExecutor,
     BlockInput, BlockOutput, BlockRegistry, ChildWorkflowConfig, InputContract, OutputContract,
-    SharedRunStore, StoredOutput, ValidateContext, ValueKind, ValueKindSet,
-    input_contract_from_predecessors,
+    OutputMode, RunVars, SharedRunStore, StoredOutput, TickState, ValidateContext, ValueKind,
+    ValueKindSet, input_contract_from_predecessors,
+};
+use crate::core::{
+    BlockReport, BlockReports, BlockStatus, DuplicateEdgePolicy, ExecutionMode, LevelFailure,
+    PendingApproval, RunState, RunStoreError, RunSummary, TickTimeoutPolicy, WorkflowDefinition,
+    WorkflowRun,
 };
-use crate::core::{RunState, WorkflowDefinition, WorkflowRun};
 use dashmap::DashMap;
 use futures::future::join_all;
+use futures::{StreamExt as _, TryStreamExt as _};
 use thiserror::Error;
-use tracing::{Instrument, Span, debug, error, info, info_span};
+use tracing::{Instrument, Span, debug, error, info, info_span, warn};
 use uuid::Uuid;
 
 pub use graph::{
@@ -30,6 +35,9 @@ type JoinHandleBlock = tokio::task::JoinHandle<Result<BlockExecutionResult, Bloc
 struct RunLogContext {
     workflow_id: Uuid,
     run_id: Uuid,
+    vars: RunVars,
+    tick_state: TickState,
+    block_reports: BlockReports,
 }
 
 impl RunLogContext {
@@ -37,9 +45,75 @@ impl RunLogContext {
         Self {
             workflow_id: run.definition_id,
             run_id: run.id,
+            vars: Arc::new(run.vars.clone()),
+            tick_state: run.tick_state.clone(),
+            block_reports: run.block_reports.clone(),
         }
     }
 
+    /// Record how long a block took and how it ended, for the run's block reports.
+    fn record_block(
+        &self,
+        block_id: Uuid,
+        block_type: &str,
+        attempts: u32,
+        started: std::time::Instant,
+        succeeded: bool,
+    ) {
+        let status = if succeeded {
+            BlockStatus::Succeeded
+        } else {
+            BlockStatus::Failed
+        };
+        self.record_block_status(block_id, block_type, attempts, started, status);
+    }
+
+    /// Record and log how a block execution ended. An approval pause is reported as
+    /// [`BlockStatus::Paused`] and logged as `block.paused`, not as a failure.
+    fn report_block_result(
+        &self,
+        ctx: &BlockLogContext,
+        started: std::time::Instant,
+        result: &Result<BlockExecutionResult, BlockError>,
+    ) {
+        let status = match result {
+            Ok(exec_result) => {
+                log_block_result_received(ctx, exec_result);
+                log_block_succeeded(ctx);
+                BlockStatus::Succeeded
+            }
+            Err(BlockError::PendingApproval { .. }) => {
+                log_block_paused(ctx);
+                BlockStatus::Paused
+            }
+            Err(err) => {
+                log_block_failed(ctx, &err.to_string());
+                BlockStatus::Failed
+            }
+        };
+        self.record_block_status(ctx.block_id, &ctx.block_type, ctx.attempt, started, status);
+    }
+
+    fn record_block_status(
+        &self,
+        block_id: Uuid,
+        block_type: &str,
+        attempts: u32,
+        started: std::time::Instant,
+        status: BlockStatus,
+    ) {
+        self.block_reports
+            .lock()
+            .expect("block reports lock")
+            .push(BlockReport {
+                block_id,
+                block_type: block_type.to_string(),
+                attempts,
+                duration_ms: started.elapsed().as_millis() as u64,
+                status,
+            });
+    }
+
     fn for_block(
         &self,
         block_id: Uuid,
@@ -104,6 +178,16 @@ fn current_ts_ms() -> u128 {
 
 fn set_run_failed(run: &mut WorkflowRun, err: &RuntimeError) {
     let ctx = RunLogContext::from_run(run);
+    if let RuntimeError::PendingApproval { token } = err {
+        run.set_state(RunState::Paused);
+        info!(
+            event = "run.paused",
+            workflow_id = %ctx.workflow_id,
+            run_id = %ctx.run_id,
+            token = token.as_str()
+        );
+        return;
+    }
     run.set_state(RunState::Failed(err.to_string()));
     error!(
         event = "run.failed",
@@ -129,12 +213,34 @@ fn log_run_started(ctx: &RunLogContext) {
     );
 }
 
-fn log_run_succeeded(ctx: &RunLogContext) {
+/// Log `run.succeeded` with what the run produced and keep the same summary on the run.
+fn log_run_succeeded(
+    run: &mut WorkflowRun,
+    ctx: &RunLogContext,
+    sink_block_type: &str,
+    output: &BlockOutput,
+) {
+    let summary = RunSummary {
+        sink_block_type: sink_block_type.to_string(),
+        output_kind: block_output_kind(output).to_string(),
+        output_units: block_output_units(output),
+    };
     info!(
         event = "run.succeeded",
         workflow_id = %ctx.workflow_id,
-        run_id = %ctx.run_id
+        run_id = %ctx.run_id,
+        sink_block_type = summary.sink_block_type.as_str(),
+        output_kind = summary.output_kind.as_str(),
+        output_units = summary.output_units
     );
+    run.summary = Some(summary);
+}
+
+fn sink_block_type(def: &WorkflowDefinition, sink_id: Uuid) -> &str {
+    def.nodes()
+        .get(&sink_id)
+        .map(|node| node.config.block_type())
+        .unwrap_or_default()
 }
 
 fn log_block_started(ctx: &BlockLogContext) {
@@ -171,6 +277,17 @@ fn log_block_failed(ctx: &BlockLogContext, message: &str) {
     );
 }
 
+fn log_block_paused(ctx: &BlockLogContext) {
+    info!(
+        event = "block.paused",
+        workflow_id = %ctx.workflow_id,
+        run_id = %ctx.run_id,
+        block_id = %ctx.block_id,
+        block_type = ctx.block_type.as_str(),
+        attempt = ctx.attempt
+    );
+}
+
 fn log_block_retry_scheduled(ctx: &BlockLogContext, backoff: Duration) {
     info!(
         event = "block.retry_scheduled",
@@ -191,6 +308,7 @@ fn block_input_kind(input: &BlockInput) -> &'static str {
         BlockInput::Json(_) => "json",
         BlockInput::List { .. } => "list",
         BlockInput::Multi { .. } => "multi",
+        BlockInput::MultiNamed { .. } => "multi_named",
         BlockInput::Error { .. } => "error",
     }
 }
@@ -207,6 +325,7 @@ fn block_input_units(input: &BlockInput) -> u64 {
         },
         BlockInput::List { items } => items.len() as u64,
         BlockInput::Multi { outputs } => outputs.len() as u64,
+        BlockInput::MultiNamed { outputs } => outputs.len() as u64,
         BlockInput::Error { message } => message.len() as u64,
     }
 }
@@ -286,6 +405,30 @@ fn log_block_result_received(ctx: &BlockLogContext, result: &BlockExecutionResul
                 output_count = outputs.len() as u64
             );
         }
+        BlockExecutionResult::Labeled(outputs) => {
+            debug!(
+                event = "block.result_received",
+                workflow_id = %ctx.workflow_id,
+                run_id = %ctx.run_id,
+                block_id = %ctx.block_id,
+                block_type = ctx.block_type.as_str(),
+                attempt = ctx.attempt,
+                result_kind = "labeled",
+                output_count = outputs.len() as u64
+            );
+        }
+        BlockExecutionResult::Routed { slot, .. } => {
+            debug!(
+                event = "block.result_received",
+                workflow_id = %ctx.workflow_id,
+                run_id = %ctx.run_id,
+                block_id = %ctx.block_id,
+                block_type = ctx.block_type.as_str(),
+                attempt = ctx.attempt,
+                result_kind = "routed",
+                slot = slot.as_str()
+            );
+        }
     }
 }
 
@@ -372,6 +515,12 @@ fn reachable_from_entry(def: &WorkflowDefinition, entry_id: Uuid) -> HashSet<Uui
     seen
 }
 
+/// Primary sink among the nodes reachable from the entry, as chosen when the workflow runs.
+pub(crate) fn reachable_primary_sink(def: &WorkflowDefinition) -> Option<Uuid> {
+    let entry_id = *def.entry()?;
+    primary_sink_for_reachable(def, &reachable_from_entry(def, entry_id))
+}
+
 fn primary_sink_for_reachable(def: &WorkflowDefinition, reachable: &HashSet<Uuid>) -> Option<Uuid> {
     let mut sinks: Vec<Uuid> = reachable
         .iter()
@@ -408,6 +557,24 @@ async fn result_to_output_async(result: BlockExecutionResult) -> Result<BlockOut
         BlockExecutionResult::Multiple(outs) => outs.into_iter().next().ok_or_else(|| {
             RuntimeError::Block(BlockError::Other("Multiple with no outputs".into()))
         }),
+        BlockExecutionResult::Labeled(outs) => {
+            outs.into_iter().next().map(|(_, o)| o).ok_or_else(|| {
+                RuntimeError::Block(BlockError::Other("Labeled with no outputs".into()))
+            })
+        }
+        BlockExecutionResult::Routed { output, .. } => Ok(output),
+    }
+}
+
+/// JSON form of one collected tick output.
+fn output_to_json(output: &BlockOutput) -> serde_json::Value {
+    match output {
+        BlockOutput::Empty => serde_json::Value::Null,
+        BlockOutput::String { value } | BlockOutput::Text { value } => {
+            serde_json::Value::String(value.clone())
+        }
+        BlockOutput::Json { value } => value.clone(),
+        BlockOutput::List { items } => serde_json::json!(items),
     }
 }
 
@@ -428,6 +595,40 @@ fn store_multiple(store: &SharedRunStore, block_id: Uuid, outputs: &[BlockOutput
     );
 }
 
+/// Pair each successor of `node_id` with the labeled output matching its edge slot.
+fn route_labeled(
+    def: &WorkflowDefinition,
+    node_id: Uuid,
+    outs: &[(String, BlockOutput)],
+) -> Result<Vec<(Uuid, BlockOutput)>, String> {
+    successors(def, node_id)
+        .into_iter()
+        .map(|succ| {
+            let label = def.edge_slot(node_id, succ).ok_or_else(|| {
+                format!(
+                    "block {} returned Labeled outputs but edge to {} has no slot; use link_slot",
+                    node_id, succ
+                )
+            })?;
+            let output = outs
+                .iter()
+                .find(|(l, _)| l == label)
+                .map(|(_, o)| o.clone())
+                .ok_or_else(|| {
+                    format!("block {} returned no output labeled '{}'", node_id, label)
+                })?;
+            Ok((succ, output))
+        })
+        .collect()
+}
+
+/// Split the successors of a `Routed` node into those linked with `slot` and the rest.
+fn route_slot(def: &WorkflowDefinition, node_id: Uuid, slot: &str) -> (Vec<Uuid>, Vec<Uuid>) {
+    successors(def, node_id)
+        .into_iter()
+        .partition(|succ| def.edge_slot(node_id, *succ) == Some(slot))
+}
+
 /// Resolve one predecessor's output for a node: from outputs (Once) or multi_outputs (Multiple).
 fn output_from_predecessor(
     pred_id: Uuid,
@@ -446,6 +647,8 @@ fn output_from_predecessor(
 
 /// Build BlockInput for a node: empty if no predecessors, single output converted to input if one predecessor,
 /// Multi(ordered_outputs) if multiple predecessors (order by edge order). Uses multi_outputs when a predecessor produced Multiple.
+/// A predecessor without an output for this node (e.g. skipped by a switch) keeps its position as
+/// `BlockOutput::Empty`, so later inputs don't shift; it is left out of `MultiNamed`.
 fn input_for_node(
     def: &WorkflowDefinition,
     node_id: Uuid,
@@ -456,13 +659,32 @@ fn input_for_node(
     if preds.is_empty() {
         return BlockInput::empty();
     }
-    let ordered: Vec<BlockOutput> = preds
+    let labels: Option<Vec<&str>> = preds
         .iter()
-        .filter_map(|pred_id| output_from_predecessor(*pred_id, node_id, outputs, multi_outputs))
+        .map(|pred_id| def.edge_label(*pred_id, node_id))
         .collect();
-    if ordered.is_empty() {
+    if let Some(labels) = labels {
+        let outputs = preds
+            .iter()
+            .zip(labels)
+            .filter_map(|(pred_id, label)| {
+                output_from_predecessor(*pred_id, node_id, outputs, multi_outputs)
+                    .map(|output| (label.to_string(), output))
+            })
+            .collect();
+        return BlockInput::MultiNamed { outputs };
+    }
+    let ordered: Vec<Option<BlockOutput>> = preds
+        .iter()
+        .map(|pred_id| output_from_predecessor(*pred_id, node_id, outputs, multi_outputs))
+        .collect();
+    if ordered.iter().all(Option::is_none) {
         return BlockInput::empty();
     }
+    let ordered: Vec<BlockOutput> = ordered
+        .into_iter()
+        .map(|output| output.unwrap_or_else(BlockOutput::empty))
+        .collect();
     if ordered.len() == 1 {
         let o = ordered.into_iter().next().unwrap();
         return BlockInput::from(o);
@@ -628,18 +850,80 @@ fn execute_block_in_current_task(
         attempt,
         prev: input,
         store,
+        vars: run_ctx.vars.clone(),
+        tick_state: run_ctx.tick_state.clone(),
     };
+    let started = std::time::Instant::now();
     let result = block_span(&ctx).in_scope(|| block.execute(exec_ctx));
-    match &result {
-        Ok(exec_result) => {
-            log_block_result_received(&ctx, exec_result);
-            log_block_succeeded(&ctx);
-        }
-        Err(err) => log_block_failed(&ctx, &err.to_string()),
-    }
+    run_ctx.report_block_result(&ctx, started, &result);
     result
 }
 
+/// Run the entry block: in the current task (so e.g. Cron's producer can use
+/// `Handle::current()`), or on the blocking pool when its node has a `timeout_ms` to bound it.
+async fn execute_entry_block(
+    run_ctx: &RunLogContext,
+    entry_id: Uuid,
+    block_type: &str,
+    block: Box<dyn BlockExecutor>,
+    input: BlockInput,
+    store: SharedRunStore,
+    timeout_ms: Option<u64>,
+) -> Result<BlockExecutionResult, BlockError> {
+    if timeout_ms.is_none() {
+        return execute_block_in_current_task(
+            run_ctx, entry_id, block_type, 1, block, input, store,
+        );
+    }
+    spawn_block_execution(
+        run_ctx.clone(),
+        entry_id,
+        block_type.to_string(),
+        1,
+        block,
+        input,
+        store,
+        timeout_ms,
+    )
+    .await
+    .unwrap_or_else(|e| Err(BlockError::Other(e.to_string())))
+}
+
+/// A block holding one `max_parallelism` permit; the permit is released when the block is
+/// dropped at the end of its execution. Otherwise it behaves exactly like the wrapped block.
+struct PermittedBlock {
+    block: Box<dyn BlockExecutor>,
+    _permit: tokio::sync::OwnedSemaphorePermit,
+}
+
+impl BlockExecutor for PermittedBlock {
+    fn execute(&self, ctx: BlockExecutionContext) -> Result<BlockExecutionResult, BlockError> {
+        self.block.execute(ctx)
+    }
+
+    fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+        self.block.validate_linkage(ctx)
+    }
+
+    fn infer_output_contract(&self, ctx: &ValidateContext<'_>) -> OutputContract {
+        self.block.infer_output_contract(ctx)
+    }
+
+    fn idempotent(&self) -> bool {
+        self.block.idempotent()
+    }
+
+    fn run_vars(&self) -> Result<Option<serde_json::Map<String, serde_json::Value>>, BlockError> {
+        self.block.run_vars()
+    }
+}
+
+/// Run a block on the blocking pool, bounded by its node `timeout_ms` counted from spawn.
+///
+/// On timeout the block fails with a `block.timeout` error and is reported as
+/// [`BlockStatus::TimedOut`]; the blocking task is aborted best-effort (a block already running
+/// keeps its thread until it returns, but its result is discarded and not reported).
+#[allow(clippy::too_many_arguments)]
 fn spawn_block_execution(
     run_ctx: RunLogContext,
     block_id: Uuid,
@@ -648,28 +932,139 @@ fn spawn_block_execution(
     block: Box<dyn BlockExecutor>,
     input: BlockInput,
     store: SharedRunStore,
+    timeout_ms: Option<u64>,
 ) -> JoinHandleBlock {
-    tokio::task::spawn_blocking(move || {
-        let ctx = run_ctx.for_block(block_id, block_type, attempt);
-        log_block_input_prepared(&ctx, &input);
-        log_block_started(&ctx);
-        let exec_ctx = BlockExecutionContext {
-            workflow_id: run_ctx.workflow_id,
-            run_id: run_ctx.run_id,
-            block_id,
-            attempt,
-            prev: input,
-            store,
-        };
-        let result = block_span(&ctx).in_scope(|| block.execute(exec_ctx));
-        match &result {
-            Ok(exec_result) => {
-                log_block_result_received(&ctx, exec_result);
-                log_block_succeeded(&ctx);
+    // Whoever sets this first reports the block: the block when it returns, or the timeout.
+    let reported = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let spawned = std::time::Instant::now();
+    let handle = {
+        let run_ctx = run_ctx.clone();
+        let block_type = block_type.clone();
+        let reported = Arc::clone(&reported);
+        tokio::task::spawn_blocking(move || {
+            let ctx = run_ctx.for_block(block_id, block_type, attempt);
+            log_block_input_prepared(&ctx, &input);
+            log_block_started(&ctx);
+            let exec_ctx = BlockExecutionContext {
+                workflow_id: run_ctx.workflow_id,
+                run_id: run_ctx.run_id,
+                block_id,
+                attempt,
+                prev: input,
+                store,
+                vars: run_ctx.vars.clone(),
+                tick_state: run_ctx.tick_state.clone(),
+            };
+            let started = std::time::Instant::now();
+            let result = block_span(&ctx).in_scope(|| block.execute(exec_ctx));
+            if reported.swap(true, std::sync::atomic::Ordering::SeqCst) {
+                return result;
             }
-            Err(err) => log_block_failed(&ctx, &err.to_string()),
+            run_ctx.report_block_result(&ctx, started, &result);
+            result
+        })
+    };
+    let Some(ms) = timeout_ms else {
+        return handle;
+    };
+    tokio::spawn(async move {
+        let mut handle = handle;
+        let timed_out = match tokio::time::timeout(Duration::from_millis(ms.max(1)), &mut handle)
+            .await
+        {
+            Ok(joined) => return joined.unwrap_or_else(|e| Err(BlockError::Other(e.to_string()))),
+            Err(_) => !reported.swap(true, std::sync::atomic::Ordering::SeqCst),
+        };
+        if !timed_out {
+            // The block returned right at the deadline and has already been reported.
+            return handle
+                .await
+                .unwrap_or_else(|e| Err(BlockError::Other(e.to_string())));
         }
-        result
+        handle.abort();
+        run_ctx.record_block_status(
+            block_id,
+            &block_type,
+            attempt,
+            spawned,
+            BlockStatus::TimedOut,
+        );
+        Err(BlockError::Other(
+            serde_json::json!({
+                "origin": "block",
+                "domain": "runtime",
+                "code": "block.timeout",
+                "message": format!("block timed out after {}ms", ms),
+                "attempt": attempt,
+                "retry_disposition": "never",
+                "severity": "error"
+            })
+            .to_string(),
+        ))
+    })
+}
+
+/// Items a map-mode child workflow runs over: list items as strings, JSON array elements as-is.
+fn map_items(input: BlockInput) -> Result<Vec<BlockInput>, RuntimeError> {
+    match input {
+        BlockInput::List { items } => Ok(items.into_iter().map(BlockInput::String).collect()),
+        BlockInput::Json(serde_json::Value::Array(values)) => Ok(values
+            .into_iter()
+            .map(|value| match value {
+                serde_json::Value::String(s) => BlockInput::String(s),
+                other => BlockInput::Json(other),
+            })
+            .collect()),
+        BlockInput::Error { message } => Err(RuntimeError::Block(BlockError::Other(message))),
+        other => Err(RuntimeError::Block(BlockError::Other(format!(
+            "child workflow map expects a list or JSON array input, got {:?}",
+            other.value_kind()
+        )))),
+    }
+}
+
+/// Run a child workflow node: once, or once per item in map mode.
+async fn run_child_workflow_node(
+    cfg: &ChildWorkflowConfig,
+    run_ctx: &RunLogContext,
+    block_id: Uuid,
+    block_type: &str,
+    registry: &BlockRegistry,
+    input: BlockInput,
+    store: SharedRunStore,
+) -> Result<BlockOutput, RuntimeError> {
+    let Some(concurrency) = cfg.map_concurrency else {
+        return run_child_workflow_with_policy(
+            cfg, run_ctx, block_id, block_type, registry, input, store,
+        )
+        .await;
+    };
+    let items = map_items(input)?;
+    debug!(
+        event = "child_workflow.map_started",
+        workflow_id = %run_ctx.workflow_id,
+        run_id = %run_ctx.run_id,
+        block_id = %block_id,
+        block_type = block_type,
+        items = items.len() as u64,
+        max_concurrency = concurrency as u64
+    );
+    let outputs: Vec<BlockOutput> = futures::stream::iter(items.into_iter().map(|item| {
+        run_child_workflow_with_policy(
+            cfg,
+            run_ctx,
+            block_id,
+            block_type,
+            registry,
+            item,
+            store.clone(),
+        )
+    }))
+    .buffered(concurrency.max(1))
+    .try_collect()
+    .await?;
+    Ok(BlockOutput::Json {
+        value: serde_json::Value::Array(outputs.iter().map(output_to_json).collect()),
     })
 }
 
@@ -683,6 +1078,7 @@ async fn run_child_workflow_with_policy(
     store: SharedRunStore,
 ) -> Result<BlockOutput, RuntimeError> {
     let mut retries_done = 0u32;
+    let started = std::time::Instant::now();
     loop {
         let attempt = retries_done + 1;
         let block_ctx = run_ctx.for_block(block_id, block_type, attempt);
@@ -703,33 +1099,41 @@ async fn run_child_workflow_with_policy(
         log_block_started(&block_ctx);
         let run_result = async {
             let mut child_run = WorkflowRun::new(&cfg.definition);
-            let run_future = Box::pin(run_workflow(
+            child_run.set_vars((*run_ctx.vars).clone());
+            child_run.tick_state = run_ctx.tick_state.clone();
+            // The node's timeout cancels the child run, so the child's own run_timeout_ms and
+            // cancel handlers apply just as they do to a top-level run.
+            let deadline = async {
+                match cfg.timeout_ms {
+                    Some(ms) => tokio::time::sleep(Duration::from_millis(ms.max(1))).await,
+                    None => std::future::pending().await,
+                }
+            };
+            let result = Box::pin(run_workflow_within(
                 &cfg.definition,
                 &mut child_run,
                 registry,
                 Some(input.clone()),
-                Some(store.clone()),
-            ));
-            match cfg.timeout_ms {
-                Some(ms) => {
-                    let timeout = Duration::from_millis(ms.max(1));
-                    match tokio::time::timeout(timeout, run_future).await {
-                        Ok(r) => r,
-                        Err(_) => Err(RuntimeError::Block(BlockError::Other(
-                            serde_json::json!({
-                                "origin": "block",
-                                "domain": "child_workflow",
-                                "code": "child.timeout",
-                                "message": format!("child workflow timed out after {}ms", ms),
-                                "attempt": attempt,
-                                "retry_disposition": "never",
-                                "severity": "error"
-                            })
-                            .to_string(),
-                        ))),
-                    }
+                store.clone(),
+                deadline,
+            ))
+            .await;
+            match (result, cfg.timeout_ms) {
+                (Err(RuntimeError::Cancelled), Some(ms)) => {
+                    Err(RuntimeError::Block(BlockError::Other(
+                        serde_json::json!({
+                            "origin": "block",
+                            "domain": "child_workflow",
+                            "code": "child.timeout",
+                            "message": format!("child workflow timed out after {}ms", ms),
+                            "attempt": attempt,
+                            "retry_disposition": "never",
+                            "severity": "error"
+                        })
+                        .to_string(),
+                    )))
                 }
-                None => run_future.await,
+                (result, _) => result,
             }
         }
         .instrument(block_span(&block_ctx))
@@ -747,6 +1151,7 @@ async fn run_child_workflow_with_policy(
                     output_units = block_output_units(&out)
                 );
                 log_block_succeeded(&block_ctx);
+                run_ctx.record_block(block_id, block_type, attempt, started, true);
                 return Ok(out);
             }
             Err(err) => {
@@ -773,6 +1178,7 @@ async fn run_child_workflow_with_policy(
                     retries_done += 1;
                     continue;
                 }
+                run_ctx.record_block(block_id, block_type, attempt, started, false);
                 return Err(RuntimeError::Block(BlockError::Other(
                     child_workflow_error_payload(
                         &message,
@@ -792,18 +1198,17 @@ async fn run_error_handler_node(
     registry: &BlockRegistry,
     store: SharedRunStore,
     handler_id: Uuid,
-    message: String,
-) -> Result<Uuid, RuntimeError> {
+    input: BlockInput,
+) -> Result<BlockOutput, RuntimeError> {
     let node_def = def
         .nodes()
         .get(&handler_id)
         .ok_or(RuntimeError::EntryNodeNotFound(handler_id))?
         .clone();
-    let input = BlockInput::Error { message };
 
-    match &node_def.config {
+    let output = match &node_def.config {
         BlockConfig::ChildWorkflow(cfg) => {
-            let _ = run_child_workflow_with_policy(
+            run_child_workflow_node(
                 cfg,
                 run_ctx,
                 handler_id,
@@ -812,7 +1217,7 @@ async fn run_error_handler_node(
                 input,
                 store.clone(),
             )
-            .await?;
+            .await?
         }
         _ => {
             let block = registry.get(&node_def.config)?;
@@ -824,21 +1229,35 @@ async fn run_error_handler_node(
                 block,
                 input,
                 store.clone(),
+                node_def.timeout_ms,
             )
             .await
             .map_err(|e| RuntimeError::Block(BlockError::Other(e.to_string())))??;
-            if let BlockExecutionResult::Recurring(_) = result {
-                return Err(RuntimeError::Block(BlockError::Other(
-                    "error handler must not return Recurring".into(),
-                )));
+            match result {
+                BlockExecutionResult::Once(output) => output,
+                BlockExecutionResult::Multiple(outputs) => outputs
+                    .into_iter()
+                    .last()
+                    .unwrap_or_else(BlockOutput::empty),
+                BlockExecutionResult::Labeled(outputs) => outputs
+                    .into_iter()
+                    .last()
+                    .map(|(_, o)| o)
+                    .unwrap_or_else(BlockOutput::empty),
+                BlockExecutionResult::Routed { output, .. } => output,
+                BlockExecutionResult::Recurring(_) => {
+                    return Err(RuntimeError::Block(BlockError::Other(
+                        "error handler must not return Recurring".into(),
+                    )));
+                }
             }
         }
-    }
+    };
 
-    Ok(handler_id)
+    Ok(output)
 }
 
-/// Run error handlers linked from `node_id`. Returns true when at least one handler executed.
+/// Run error handlers linked from `node_id`. Returns the outputs of the handlers that succeeded.
 async fn run_error_handlers(
     def: &WorkflowDefinition,
     run: &mut WorkflowRun,
@@ -846,10 +1265,10 @@ async fn run_error_handlers(
     store: SharedRunStore,
     node_id: Uuid,
     message: &str,
-) -> bool {
+) -> Vec<HandlerOutput> {
     let handlers = error_successors(def, node_id);
     if handlers.is_empty() {
-        return false;
+        return Vec::new();
     }
     let source_block_type = block_type_for(def, node_id).to_string();
     let handlers_with_types: Vec<(Uuid, String)> = handlers
@@ -882,17 +1301,17 @@ async fn run_error_handlers(
             registry,
             store.clone(),
             *handler_id,
-            envelope.clone(),
+            BlockInput::Error {
+                message: envelope.clone(),
+            },
         )
     });
     let results = join_all(futures).await;
-    let mut success_count = 0u64;
+    let mut handled_by = Vec::new();
     let mut failure_count = 0u64;
-    for ((handler_id, handler_block_type), result) in
-        handlers_with_types.into_iter().zip(results.into_iter())
-    {
+    for ((handler_id, handler_block_type), result) in handlers_with_types.into_iter().zip(results) {
         match result {
-            Ok(handler_id) => {
+            Ok(output) => {
                 run.mark_block_completed(handler_id);
                 log_on_error_handler_succeeded(
                     &run_ctx,
@@ -901,7 +1320,11 @@ async fn run_error_handlers(
                     handler_id,
                     handler_block_type.as_str(),
                 );
-                success_count += 1;
+                handled_by.push(HandlerOutput {
+                    handler_id,
+                    block_type: handler_block_type,
+                    output,
+                });
             }
             Err(err) => {
                 log_on_error_handler_failed(
@@ -922,10 +1345,109 @@ async fn run_error_handlers(
         run_id = %run_ctx.run_id,
         source_block_id = %node_id,
         source_block_type = source_block_type.as_str(),
-        success_count = success_count,
+        success_count = handled_by.len() as u64,
         failure_count = failure_count
     );
-    true
+    handled_by
+}
+
+/// Run the cancel handlers of `def` after the run stopped early. Each receives a JSON summary:
+/// `reason` (`cancelled` or `timeout`), the run ids, and the blocks that completed before the stop.
+async fn run_cancel_handlers(
+    def: &WorkflowDefinition,
+    run: &mut WorkflowRun,
+    registry: &BlockRegistry,
+    store: SharedRunStore,
+    reason: &str,
+) -> Vec<HandlerOutput> {
+    let handlers = def.cancel_handlers();
+    if handlers.is_empty() {
+        return Vec::new();
+    }
+    let run_ctx = RunLogContext::from_run(run);
+    let mut completed: Vec<String> = run
+        .completed_block_ids()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    completed.sort();
+    let summary = serde_json::json!({
+        "reason": reason,
+        "workflow_id": run_ctx.workflow_id.to_string(),
+        "run_id": run_ctx.run_id.to_string(),
+        "completed_block_ids": completed,
+        "ts": current_ts_ms()
+    });
+    info!(
+        event = "on_cancel.dispatch_started",
+        workflow_id = %run_ctx.workflow_id,
+        run_id = %run_ctx.run_id,
+        reason = reason,
+        handler_count = handlers.len() as u64
+    );
+    let futures = handlers.iter().map(|handler_id| {
+        run_error_handler_node(
+            def,
+            &run_ctx,
+            registry,
+            store.clone(),
+            *handler_id,
+            BlockInput::Json(summary.clone()),
+        )
+    });
+    let results = join_all(futures).await;
+    let mut handled_by = Vec::new();
+    for (handler_id, result) in handlers.iter().copied().zip(results) {
+        let block_type = block_type_for(def, handler_id).to_string();
+        match result {
+            Ok(output) => {
+                run.mark_block_completed(handler_id);
+                handled_by.push(HandlerOutput {
+                    handler_id,
+                    block_type,
+                    output,
+                });
+            }
+            Err(err) => {
+                error!(
+                    event = "on_cancel.handler_failed",
+                    workflow_id = %run_ctx.workflow_id,
+                    run_id = %run_ctx.run_id,
+                    handler_block_id = %handler_id,
+                    handler_block_type = block_type.as_str(),
+                    error = %err
+                );
+            }
+        }
+    }
+    handled_by
+}
+
+/// `failures` rendered one after another, for [`RuntimeError::LevelFailed`]'s message.
+fn join_errors(failures: &[RuntimeError]) -> String {
+    failures
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Keep the outputs of the handlers that ran for `err` on `run`, and return `err` unchanged.
+fn record_handled_by(
+    run: &mut WorkflowRun,
+    err: RuntimeError,
+    handled_by: Vec<HandlerOutput>,
+) -> RuntimeError {
+    run.handled_by.extend(handled_by);
+    err
+}
+
+/// Output produced by an on_error or on_cancel handler of a failed run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HandlerOutput {
+    pub handler_id: Uuid,
+    pub block_type: String,
+    pub output: BlockOutput,
 }
 
 /// Runtime execution error.
@@ -943,12 +1465,33 @@ pub enum RuntimeError {
     NoSink,
     #[error("iteration budget exceeded (cycle or too many steps)")]
     IterationBudgetExceeded,
+    #[error("recurring tick exceeded its {timeout_ms}ms budget")]
+    TickTimeout { timeout_ms: u64 },
+    /// The run was cancelled before it finished; the cancel handlers have run.
+    #[error("run cancelled")]
+    Cancelled,
+    /// The run exceeded `WorkflowOptions::run_timeout_ms`; the cancel handlers have run.
+    #[error("run exceeded its {timeout_ms}ms budget")]
+    RunTimeout { timeout_ms: u64 },
+    /// An approval block is waiting for a decision; resume the run with this token.
+    #[error("run paused for approval (token {token})")]
+    PendingApproval { token: String },
+    /// The token is unknown, was already resumed, or belonged to a process that exited without
+    /// a run store.
+    #[error("no paused run for approval token {0}")]
+    UnknownApprovalToken(String),
+    /// Saving or loading the run's checkpoint failed.
+    #[error(transparent)]
+    Checkpoint(#[from] RunStoreError),
+    /// Several blocks in one level failed under [`LevelFailure::CollectAll`], in level order.
+    #[error("{} blocks failed: {}", failures.len(), join_errors(failures))]
+    LevelFailed { failures: Vec<RuntimeError> },
 }
 
 #[derive(Debug, Clone, Error)]
 pub enum WorkflowValidationError {
-    #[error("workflow graph has a cycle; validation requires DAG topology")]
-    CyclicGraph,
+    #[error("workflow graph has a {0}; validation requires DAG topology")]
+    CyclicGraph(CycleDetected),
     #[error("validation node not found: {0}")]
     NodeNotFound(Uuid),
     #[error("missing input source for block {block_id}: ref_key={source_ref_key}")]
@@ -969,140 +1512,376 @@ pub enum WorkflowValidationError {
     MissingForcedRefContract { block_id: Uuid, source_id: Uuid },
     #[error("block {block_id} failed linkage validation: {message}")]
     BlockLinkage { block_id: Uuid, message: String },
+    #[error("duplicate edge from {from} to {to}")]
+    DuplicateEdge { from: Uuid, to: Uuid },
+    #[error("block {block_id} has both labeled and unlabeled incoming links")]
+    MixedEdgeLabels { block_id: Uuid },
+    #[error("block {block_id} has more than one incoming link labeled '{label}'")]
+    DuplicateEdgeLabel { block_id: Uuid, label: String },
+    #[error("blocks unreachable from the entry: {0:?}")]
+    UnreachableNodes(Vec<Uuid>),
+    #[error("workflow has more than one sink: {0:?}")]
+    MultipleSinks(Vec<Uuid>),
+    #[error("workflow id must be set before adding blocks ({block_count} already added)")]
+    IdAfterBlocks { block_count: usize },
+    #[error("{} validation errors: {}", .0.len(), join_validation_errors(.0))]
+    Multiple(Vec<WorkflowValidationError>),
 }
 
-pub fn validate_workflow(
+fn join_validation_errors(errors: &[WorkflowValidationError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Output contract of every node in topological order. `entry_prev` overrides the entry
+/// node's input contract (a child workflow's entry receives the parent node's input).
+fn infer_output_contracts(
     def: &WorkflowDefinition,
     registry: &BlockRegistry,
-) -> Result<(), WorkflowValidationError> {
-    let order = topo_order(def).map_err(|_| WorkflowValidationError::CyclicGraph)?;
+    entry_prev: Option<InputContract>,
+) -> Result<HashMap<Uuid, OutputContract>, WorkflowValidationError> {
+    let order = topo_order(def).map_err(WorkflowValidationError::CyclicGraph)?;
     let mut contracts: HashMap<Uuid, OutputContract> = HashMap::new();
     for node_id in order {
-        let node_def = def
-            .nodes()
-            .get(&node_id)
-            .ok_or(WorkflowValidationError::NodeNotFound(node_id))?;
-        let predecessors = predecessors(def, node_id);
-        let pred_contracts: Result<Vec<OutputContract>, WorkflowValidationError> = predecessors
-            .iter()
-            .map(|pred_id| {
-                contracts.get(pred_id).copied().ok_or(
-                    WorkflowValidationError::MissingPredecessorContract {
-                        block_id: node_id,
-                        predecessor_id: *pred_id,
-                    },
-                )
-            })
-            .collect();
-        let prev = input_contract_from_predecessors(&pred_contracts?);
-        let forced_ids: &[Uuid] = match &node_def.config {
-            BlockConfig::Custom { input_from, .. } => input_from,
-            _ => &[],
-        };
-        let mut forced_refs = Vec::with_capacity(forced_ids.len());
-        for source_id in forced_ids {
-            if !def.nodes().contains_key(source_id) {
-                return Err(WorkflowValidationError::InputSourceNodeMissing {
-                    block_id: node_id,
-                    source_id: *source_id,
-                });
-            }
-            if !is_upstream(def, *source_id, node_id) {
-                return Err(WorkflowValidationError::InputSourceNotUpstream {
-                    block_id: node_id,
-                    source_id: *source_id,
-                });
-            }
-            let contract = contracts.get(source_id).copied().ok_or(
-                WorkflowValidationError::MissingForcedRefContract {
-                    block_id: node_id,
-                    source_id: *source_id,
-                },
-            )?;
-            forced_refs.push(contract);
-        }
-        let ctx = ValidateContext {
-            block_id: node_id,
-            prev,
-            forced_refs: &forced_refs,
-        };
-        let output = match &node_def.config {
-            BlockConfig::ChildWorkflow(_) => OutputContract::any_once(),
-            _ => {
-                let block = registry.get(&node_def.config).map_err(|e| {
-                    WorkflowValidationError::BlockLinkage {
-                        block_id: node_id,
-                        message: e.to_string(),
-                    }
-                })?;
-                block.validate_linkage(&ctx).map_err(|e| {
-                    WorkflowValidationError::BlockLinkage {
-                        block_id: node_id,
-                        message: e.to_string(),
-                    }
-                })?;
-                block.infer_output_contract(&ctx)
-            }
-        };
+        let output = node_output_contract(def, registry, node_id, &contracts, &entry_prev)?;
         contracts.insert(node_id, output);
     }
+    Ok(contracts)
+}
 
-    let error_prev = InputContract::One(ValueKindSet::singleton(ValueKind::Text));
-    for (_, handler_id) in def.error_edges() {
-        let handler = def
-            .nodes()
-            .get(handler_id)
-            .ok_or(WorkflowValidationError::NodeNotFound(*handler_id))?;
-        let forced_ids: &[Uuid] = match &handler.config {
-            BlockConfig::Custom { input_from, .. } => input_from,
-            _ => &[],
-        };
-        let mut forced_refs = Vec::with_capacity(forced_ids.len());
-        for source_id in forced_ids {
-            if !def.nodes().contains_key(source_id) {
-                return Err(WorkflowValidationError::InputSourceNodeMissing {
-                    block_id: *handler_id,
-                    source_id: *source_id,
-                });
-            }
-            if !is_upstream(def, *source_id, *handler_id) {
-                return Err(WorkflowValidationError::InputSourceNotUpstream {
-                    block_id: *handler_id,
-                    source_id: *source_id,
-                });
-            }
-            let contract = contracts.get(source_id).copied().ok_or(
-                WorkflowValidationError::MissingForcedRefContract {
-                    block_id: *handler_id,
-                    source_id: *source_id,
+/// Whether `node_id` receives its inputs by label (see `input_for_node`): every incoming link is
+/// labeled, with distinct labels. Mixing labeled and unlabeled links is rejected.
+fn has_labeled_inputs(
+    def: &WorkflowDefinition,
+    node_id: Uuid,
+    predecessors: &[Uuid],
+) -> Result<bool, WorkflowValidationError> {
+    let labels: Vec<Option<&str>> = predecessors
+        .iter()
+        .map(|pred_id| def.edge_label(*pred_id, node_id))
+        .collect();
+    if labels.iter().all(Option::is_none) {
+        return Ok(false);
+    }
+    if labels.iter().any(Option::is_none) {
+        return Err(WorkflowValidationError::MixedEdgeLabels { block_id: node_id });
+    }
+    let mut seen = HashSet::new();
+    if let Some(label) = labels
+        .into_iter()
+        .flatten()
+        .find(|label| !seen.insert(*label))
+    {
+        return Err(WorkflowValidationError::DuplicateEdgeLabel {
+            block_id: node_id,
+            label: label.to_string(),
+        });
+    }
+    Ok(true)
+}
+
+/// Slot of each link out of `node_id`, for [`ValidateContext::successor_slots`].
+fn successor_slots(def: &WorkflowDefinition, node_id: Uuid) -> Vec<Option<String>> {
+    successors(def, node_id)
+        .into_iter()
+        .map(|succ| def.edge_slot(node_id, succ).map(str::to_string))
+        .collect()
+}
+
+/// Validate one node's linkage against its predecessors' contracts and infer its output contract.
+fn node_output_contract(
+    def: &WorkflowDefinition,
+    registry: &BlockRegistry,
+    node_id: Uuid,
+    contracts: &HashMap<Uuid, OutputContract>,
+    entry_prev: &Option<InputContract>,
+) -> Result<OutputContract, WorkflowValidationError> {
+    let node_def = def
+        .nodes()
+        .get(&node_id)
+        .ok_or(WorkflowValidationError::NodeNotFound(node_id))?;
+    let predecessors = predecessors(def, node_id);
+    let pred_contracts: Result<Vec<OutputContract>, WorkflowValidationError> = predecessors
+        .iter()
+        .map(|pred_id| {
+            contracts.get(pred_id).copied().ok_or(
+                WorkflowValidationError::MissingPredecessorContract {
+                    block_id: node_id,
+                    predecessor_id: *pred_id,
                 },
-            )?;
-            forced_refs.push(contract);
+            )
+        })
+        .collect();
+    let pred_contracts = pred_contracts?;
+    let prev = match entry_prev {
+        Some(entry_prev) if pred_contracts.is_empty() && def.entry() == Some(&node_id) => {
+            entry_prev.clone()
         }
-        let ctx = ValidateContext {
-            block_id: *handler_id,
-            prev: error_prev.clone(),
-            forced_refs: &forced_refs,
-        };
-        if let BlockConfig::Custom { .. } = &handler.config {
-            let block = registry.get(&handler.config).map_err(|e| {
+        // Labeled inputs arrive as `MultiNamed`, even from a single predecessor.
+        _ if has_labeled_inputs(def, node_id, &predecessors)? => {
+            InputContract::Multi(pred_contracts.iter().map(|c| c.kinds).collect())
+        }
+        _ => input_contract_from_predecessors(&pred_contracts),
+    };
+    let forced_ids: &[Uuid] = match &node_def.config {
+        BlockConfig::Custom { input_from, .. } => input_from,
+        _ => &[],
+    };
+    let mut forced_refs = Vec::with_capacity(forced_ids.len());
+    for source_id in forced_ids {
+        if !def.nodes().contains_key(source_id) {
+            return Err(WorkflowValidationError::InputSourceNodeMissing {
+                block_id: node_id,
+                source_id: *source_id,
+            });
+        }
+        if !is_upstream(def, *source_id, node_id) {
+            return Err(WorkflowValidationError::InputSourceNotUpstream {
+                block_id: node_id,
+                source_id: *source_id,
+            });
+        }
+        let contract = contracts.get(source_id).copied().ok_or(
+            WorkflowValidationError::MissingForcedRefContract {
+                block_id: node_id,
+                source_id: *source_id,
+            },
+        )?;
+        forced_refs.push(contract);
+    }
+    let ctx = ValidateContext {
+        block_id: node_id,
+        prev,
+        forced_refs: &forced_refs,
+        successor_slots: successor_slots(def, node_id),
+    };
+    let output = match &node_def.config {
+        BlockConfig::ChildWorkflow(cfg) => child_output_contract(cfg, registry, &ctx.prev)
+            .map_err(|e| WorkflowValidationError::BlockLinkage {
+                block_id: node_id,
+                message: format!("child workflow: {e}"),
+            })?,
+        _ => {
+            let block = registry.get(&node_def.config).map_err(|e| {
                 WorkflowValidationError::BlockLinkage {
-                    block_id: *handler_id,
+                    block_id: node_id,
                     message: e.to_string(),
                 }
             })?;
             block
                 .validate_linkage(&ctx)
                 .map_err(|e| WorkflowValidationError::BlockLinkage {
-                    block_id: *handler_id,
+                    block_id: node_id,
                     message: e.to_string(),
                 })?;
+            block.infer_output_contract(&ctx)
         }
+    };
+    Ok(output)
+}
+
+/// Output contract of a child workflow node: its primary sink's kinds, delivered once.
+/// Map mode always yields a JSON array; cyclic children run in iteration mode and stay untyped.
+fn child_output_contract(
+    cfg: &ChildWorkflowConfig,
+    registry: &BlockRegistry,
+    prev: &InputContract,
+) -> Result<OutputContract, WorkflowValidationError> {
+    if cfg.map_concurrency.is_some() {
+        return Ok(OutputContract::from_kind(ValueKind::Json, OutputMode::Once));
     }
+    let contracts = match infer_output_contracts(&cfg.definition, registry, Some(prev.clone())) {
+        Ok(contracts) => contracts,
+        Err(WorkflowValidationError::CyclicGraph(_)) => return Ok(OutputContract::any_once()),
+        Err(e) => return Err(e),
+    };
+    Ok(primary_sink(&cfg.definition)
+        .and_then(|sink| contracts.get(&sink))
+        .map(|sink| OutputContract {
+            kinds: sink.kinds,
+            mode: OutputMode::Once,
+        })
+        .unwrap_or_else(OutputContract::any_once))
+}
 
+pub fn validate_workflow(
+    def: &WorkflowDefinition,
+    registry: &BlockRegistry,
+) -> Result<(), WorkflowValidationError> {
+    if def.options().on_duplicate_edge == DuplicateEdgePolicy::Error
+        && let Some(&(from, to)) = def.duplicate_edges().first()
+    {
+        return Err(WorkflowValidationError::DuplicateEdge { from, to });
+    }
+    let contracts = match infer_output_contracts(def, registry, None) {
+        Err(WorkflowValidationError::CyclicGraph(_)) if def.options().allow_cycles => {
+            return Ok(());
+        }
+        contracts => contracts?,
+    };
+
+    for (handler_id, prev) in error_handler_inputs(def) {
+        validate_handler(def, registry, &contracts, handler_id, &prev)?;
+    }
     Ok(())
 }
 
+/// Error edges and cancel handlers: the node's input is the failure message (Text) or the
+/// cancellation payload (Json) rather than its graph predecessors.
+fn error_handler_inputs(def: &WorkflowDefinition) -> Vec<(Uuid, InputContract)> {
+    let error_prev = InputContract::One(ValueKindSet::singleton(ValueKind::Text));
+    let cancel_prev = InputContract::One(ValueKindSet::singleton(ValueKind::Json));
+    def.error_edges()
+        .iter()
+        .map(|(_, handler_id)| (*handler_id, error_prev.clone()))
+        .chain(
+            def.cancel_handlers()
+                .iter()
+                .map(|id| (*id, cancel_prev.clone())),
+        )
+        .collect()
+}
+
+fn validate_handler(
+    def: &WorkflowDefinition,
+    registry: &BlockRegistry,
+    contracts: &HashMap<Uuid, OutputContract>,
+    handler_id: Uuid,
+    prev: &InputContract,
+) -> Result<(), WorkflowValidationError> {
+    let handler = def
+        .nodes()
+        .get(&handler_id)
+        .ok_or(WorkflowValidationError::NodeNotFound(handler_id))?;
+    let forced_ids: &[Uuid] = match &handler.config {
+        BlockConfig::Custom { input_from, .. } => input_from,
+        _ => &[],
+    };
+    let mut forced_refs = Vec::with_capacity(forced_ids.len());
+    for source_id in forced_ids {
+        if !def.nodes().contains_key(source_id) {
+            return Err(WorkflowValidationError::InputSourceNodeMissing {
+                block_id: handler_id,
+                source_id: *source_id,
+            });
+        }
+        if !is_upstream(def, *source_id, handler_id) {
+            return Err(WorkflowValidationError::InputSourceNotUpstream {
+                block_id: handler_id,
+                source_id: *source_id,
+            });
+        }
+        let contract = contracts.get(source_id).copied().ok_or(
+            WorkflowValidationError::MissingForcedRefContract {
+                block_id: handler_id,
+                source_id: *source_id,
+            },
+        )?;
+        forced_refs.push(contract);
+    }
+    let ctx = ValidateContext {
+        block_id: handler_id,
+        prev: prev.clone(),
+        forced_refs: &forced_refs,
+        successor_slots: successor_slots(def, handler_id),
+    };
+    if let BlockConfig::Custom { .. } = &handler.config {
+        let block =
+            registry
+                .get(&handler.config)
+                .map_err(|e| WorkflowValidationError::BlockLinkage {
+                    block_id: handler_id,
+                    message: e.to_string(),
+                })?;
+        block
+            .validate_linkage(&ctx)
+            .map_err(|e| WorkflowValidationError::BlockLinkage {
+                block_id: handler_id,
+                message: e.to_string(),
+            })?;
+    }
+    Ok(())
+}
+
+/// Full validation: every linkage error across the DAG rather than the first, plus blocks
+/// unreachable from the entry and more than one reachable sink. Downstream of a block that
+/// failed validation is skipped since its input contract is unknown.
+pub fn validate_workflow_all(
+    def: &WorkflowDefinition,
+    registry: &BlockRegistry,
+) -> Result<(), WorkflowValidationError> {
+    let mut errors = Vec::new();
+    if def.options().on_duplicate_edge == DuplicateEdgePolicy::Error {
+        for (from, to) in def.duplicate_edges() {
+            errors.push(WorkflowValidationError::DuplicateEdge { from, to });
+        }
+    }
+    match topo_order(def) {
+        Err(_) if def.options().allow_cycles => {}
+        Err(cycle) => errors.push(WorkflowValidationError::CyclicGraph(cycle)),
+        Ok(order) => {
+            let mut contracts: HashMap<Uuid, OutputContract> = HashMap::new();
+            for node_id in order {
+                if predecessors(def, node_id)
+                    .iter()
+                    .any(|pred_id| !contracts.contains_key(pred_id))
+                {
+                    continue;
+                }
+                match node_output_contract(def, registry, node_id, &contracts, &None) {
+                    Ok(output) => {
+                        contracts.insert(node_id, output);
+                    }
+                    Err(e) => errors.push(e),
+                }
+            }
+            for (handler_id, prev) in error_handler_inputs(def) {
+                if let Err(e) = validate_handler(def, registry, &contracts, handler_id, &prev) {
+                    errors.push(e);
+                }
+            }
+        }
+    }
+    if let Some(&entry_id) = def.entry() {
+        let reachable = reachable_from_entry(def, entry_id);
+        let handlers: HashSet<Uuid> = error_handler_inputs(def)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        let mut unreachable: Vec<Uuid> = def
+            .nodes()
+            .keys()
+            .filter(|id| !reachable.contains(id) && !handlers.contains(id))
+            .copied()
+            .collect();
+        if !unreachable.is_empty() {
+            unreachable.sort();
+            errors.push(WorkflowValidationError::UnreachableNodes(unreachable));
+        }
+        let mut sinks: Vec<Uuid> = reachable
+            .iter()
+            .filter(|id| {
+                !successors(def, **id)
+                    .iter()
+                    .any(|succ| reachable.contains(succ))
+            })
+            .copied()
+            .collect();
+        if sinks.len() > 1 {
+            sinks.sort();
+            errors.push(WorkflowValidationError::MultipleSinks(sinks));
+        }
+    }
+    match errors.len() {
+        0 => Ok(()),
+        1 => Err(errors.remove(0)),
+        _ => Err(WorkflowValidationError::Multiple(errors)),
+    }
+}
+
 fn is_no_new_items_runtime_error(err: &RuntimeError) -> bool {
     match err {
         RuntimeError::Block(BlockError::Other(message)) => parse_json_payload(message)
@@ -1116,6 +1895,54 @@ fn is_no_new_items_runtime_error(err: &RuntimeError) -> bool {
     }
 }
 
+/// Run a workflow until it finishes, `cancel` resolves, or `run_timeout_ms` elapses. A run that
+/// stops early runs its cancel handlers and fails with [`RuntimeError::Cancelled`] or
+/// [`RuntimeError::RunTimeout`]; blocks already executing are abandoned, not awaited.
+pub async fn run_workflow_until(
+    def: &WorkflowDefinition,
+    run: &mut WorkflowRun,
+    registry: &BlockRegistry,
+    cancel: impl std::future::Future<Output = ()>,
+) -> Result<BlockOutput, RuntimeError> {
+    run_workflow_within(def, run, registry, None, Arc::new(DashMap::new()), cancel).await
+}
+
+/// [`run_workflow_until`] with an entry input and a store shared with the caller, as a child
+/// workflow runs inside its parent.
+async fn run_workflow_within(
+    def: &WorkflowDefinition,
+    run: &mut WorkflowRun,
+    registry: &BlockRegistry,
+    entry_input: Option<BlockInput>,
+    store: SharedRunStore,
+    cancel: impl std::future::Future<Output = ()>,
+) -> Result<BlockOutput, RuntimeError> {
+    let timeout_ms = def.options().run_timeout_ms;
+    let (reason, err) = {
+        let work = run_workflow(def, run, registry, entry_input, Some(store.clone()));
+        let deadline = async {
+            match timeout_ms {
+                Some(ms) => tokio::time::sleep(Duration::from_millis(ms)).await,
+                None => std::future::pending().await,
+            }
+        };
+        tokio::select! {
+            result = work => return result,
+            _ = cancel => ("cancelled", RuntimeError::Cancelled),
+            _ = deadline => (
+                "timeout",
+                RuntimeError::RunTimeout {
+                    timeout_ms: timeout_ms.unwrap_or_default(),
+                },
+            ),
+        }
+    };
+    let handled_by = run_cancel_handlers(def, run, registry, store, reason).await;
+    let err = record_handled_by(run, err, handled_by);
+    set_run_failed(run, &err);
+    Err(err)
+}
+
 /// Run a workflow (single-block or multi-block DAG). Async entrypoint used by run() and run_async().
 /// When `entry_input` is Some, the entry node receives that input instead of empty.
 pub async fn run_workflow(
@@ -1145,6 +1972,7 @@ pub async fn run_workflow(
     );
 
     if nodes.len() == 1 && edges.is_empty() {
+        run.set_execution_mode(ExecutionMode::Topological);
         let entry_id = *def.entry().unwrap();
         let node_def = nodes
             .get(&entry_id)
@@ -1153,7 +1981,7 @@ pub async fn run_workflow(
         let input = entry_input.unwrap_or_else(BlockInput::empty);
         match &node_def.config {
             BlockConfig::ChildWorkflow(cfg) => {
-                let output = match run_child_workflow_with_policy(
+                let output = match run_child_workflow_node(
                     cfg,
                     &run_ctx,
                     entry_id,
@@ -1166,7 +1994,7 @@ pub async fn run_workflow(
                 {
                     Ok(out) => out,
                     Err(err) => {
-                        run_error_handlers(
+                        let handled_by = run_error_handlers(
                             def,
                             run,
                             registry,
@@ -1175,6 +2003,7 @@ pub async fn run_workflow(
                             &err.to_string(),
                         )
                         .await;
+                        let err = record_handled_by(run, err, handled_by);
                         set_run_failed(run, &err);
                         return Err(err);
                     }
@@ -1182,7 +2011,7 @@ pub async fn run_workflow(
                 store_once(&store, entry_id, &output);
                 run.mark_block_completed(entry_id);
                 run.set_state(RunState::Completed);
-                log_run_succeeded(&run_ctx);
+                log_run_succeeded(run, &run_ctx, node_def.config.block_type(), &output);
                 return Ok(output);
             }
             _ => {
@@ -1194,18 +2023,33 @@ pub async fn run_workflow(
                         return Err(err);
                     }
                 };
-                let result = match execute_block_in_current_task(
+                let result = match execute_entry_block(
                     &run_ctx,
                     entry_id,
                     node_def.config.block_type(),
-                    1,
                     block,
                     input,
                     store.clone(),
-                ) {
+                    node_def.timeout_ms,
+                )
+                .await
+                {
                     Ok(r) => r,
+                    Err(BlockError::PendingApproval { prompt }) => {
+                        let err = suspend_at_entry(
+                            run,
+                            &run_ctx,
+                            entry_id,
+                            &prompt,
+                            entry_id,
+                            &[],
+                            store,
+                        );
+                        set_run_failed(run, &err);
+                        return Err(err);
+                    }
                     Err(err) => {
-                        run_error_handlers(
+                        let handled_by = run_error_handlers(
                             def,
                             run,
                             registry,
@@ -1214,7 +2058,8 @@ pub async fn run_workflow(
                             &err.to_string(),
                         )
                         .await;
-                        let runtime_err = RuntimeError::Block(err);
+                        let runtime_err =
+                            record_handled_by(run, RuntimeError::Block(err), handled_by);
                         set_run_failed(run, &runtime_err);
                         return Err(runtime_err);
                     }
@@ -1229,7 +2074,7 @@ pub async fn run_workflow(
                 store_once(&store, entry_id, &output);
                 run.mark_block_completed(entry_id);
                 run.set_state(RunState::Completed);
-                log_run_succeeded(&run_ctx);
+                log_run_succeeded(run, &run_ctx, node_def.config.block_type(), &output);
                 return Ok(output);
             }
         }
@@ -1254,6 +2099,7 @@ pub async fn run_workflow(
                 .filter(|id| reachable.contains(id))
                 .collect();
             let levels = group_by_level(def, &order, entry_id);
+            run.set_execution_mode(ExecutionMode::Topological);
             debug!(
                 event = "run.execution_mode_selected",
                 workflow_id = %run_ctx.workflow_id,
@@ -1271,68 +2117,94 @@ pub async fn run_workflow(
                 .ok_or(RuntimeError::EntryNodeNotFound(entry_id))?
                 .clone();
             let input = entry_input.unwrap_or_else(BlockInput::empty);
-            let result = match &node_def.config {
-                BlockConfig::ChildWorkflow(cfg) => {
-                    match run_child_workflow_with_policy(
-                        cfg,
-                        &run_ctx,
-                        entry_id,
-                        node_def.config.block_type(),
-                        registry,
-                        input,
-                        store.clone(),
-                    )
-                    .await
-                    {
-                        Ok(out) => BlockExecutionResult::Once(out),
-                        Err(err) => {
-                            run_error_handlers(
-                                def,
-                                run,
-                                registry,
-                                store.clone(),
-                                entry_id,
-                                &err.to_string(),
-                            )
-                            .await;
-                            set_run_failed(run, &err);
-                            return Err(err);
+            let result = if let Some(output) = run.restored_output(entry_id) {
+                debug!(
+                    event = "block.restored",
+                    workflow_id = %run_ctx.workflow_id,
+                    run_id = %run_ctx.run_id,
+                    block_id = %entry_id
+                );
+                BlockExecutionResult::Once(output)
+            } else {
+                match &node_def.config {
+                    BlockConfig::ChildWorkflow(cfg) => {
+                        match run_child_workflow_node(
+                            cfg,
+                            &run_ctx,
+                            entry_id,
+                            node_def.config.block_type(),
+                            registry,
+                            input,
+                            store.clone(),
+                        )
+                        .await
+                        {
+                            Ok(out) => BlockExecutionResult::Once(out),
+                            Err(err) => {
+                                let handled_by = run_error_handlers(
+                                    def,
+                                    run,
+                                    registry,
+                                    store.clone(),
+                                    entry_id,
+                                    &err.to_string(),
+                                )
+                                .await;
+                                let err = record_handled_by(run, err, handled_by);
+                                set_run_failed(run, &err);
+                                return Err(err);
+                            }
                         }
                     }
-                }
-                _ => {
-                    let block = match registry.get(&node_def.config) {
-                        Ok(b) => b,
-                        Err(e) => {
-                            let err = RuntimeError::Block(e);
-                            set_run_failed(run, &err);
-                            return Err(err);
-                        }
-                    };
-                    // Run entry block in current task so Cron's spawned thread can use Handle::current().
-                    match execute_block_in_current_task(
-                        &run_ctx,
-                        entry_id,
-                        node_def.config.block_type(),
-                        1,
-                        block,
-                        input,
-                        store.clone(),
-                    ) {
-                        Ok(r) => r,
-                        Err(err) => {
-                            run_error_handlers(
-                                def,
-                                run,
-                                registry,
-                                store.clone(),
-                                entry_id,
-                                &err.to_string(),
-                            )
-                            .await;
-                            let runtime_err = RuntimeError::Block(err);
-                            set_run_failed(run, &runtime_err);
-                            return Err(runtime_err);
+                    _ => {
+                        let block = match registry.get(&node_def.config) {
+                            Ok(b) => b,
+                            Err(e) => {
+                                let err = RuntimeError::Block(e);
+                                set_run_failed(run, &err);
+                                return Err(err);
+                            }
+                        };
+                        match execute_entry_block(
+                            &run_ctx,
+                            entry_id,
+                            node_def.config.block_type(),
+                            block,
+                            input,
+                            store.clone(),
+                            node_def.timeout_ms,
+                        )
+                        .await
+                        {
+                            Ok(r) => r,
+                            Err(BlockError::PendingApproval { prompt }) => {
+                                let err = suspend_at_entry(
+                                    run,
+                                    &run_ctx,
+                                    entry_id,
+                                    &prompt,
+                                    sink_id,
+                                    &levels[1..],
+                                    store,
+                                );
+                                set_run_failed(run, &err);
+                                return Err(err);
+                            }
+                            Err(err) => {
+                                let handled_by = run_error_handlers(
+                                    def,
+                                    run,
+                                    registry,
+                                    store.clone(),
+                                    entry_id,
+                                    &err.to_string(),
+                                )
+                                .await;
+                                let runtime_err =
+                                    record_handled_by(run, RuntimeError::Block(err), handled_by);
+                                set_run_failed(run, &runtime_err);
+                                return Err(runtime_err);
+                            }
                         }
                     }
                 }
@@ -1343,46 +2215,31 @@ pub async fn run_workflow(
             let remaining_levels = &levels[1..];
 
             match result {
-                BlockExecutionResult::Once(o) => {
-                    store_once(&store, entry_id, &o);
-                    outputs.insert(entry_id, o);
-                    run.mark_block_completed(entry_id);
-                    let sink_output = match run_remaining_levels(RemainingLevelsContext {
-                        def,
-                        run,
-                        registry,
-                        run_ctx: &run_ctx,
-                        store: store.clone(),
-                        sink_id,
-                        levels: remaining_levels,
-                        outputs: &mut outputs,
-                        multi_outputs: &mut multi_outputs,
-                    })
-                    .await
-                    {
-                        Ok(o) => o,
-                        Err(err) => {
-                            set_run_failed(run, &err);
-                            return Err(err);
-                        }
-                    };
-                    run.set_state(RunState::Completed);
-                    log_run_succeeded(&run_ctx);
-                    Ok(sink_output)
-                }
                 BlockExecutionResult::Recurring(mut rx) => {
                     let mut last_sink_output: Option<BlockOutput> = None;
+                    let collect_ticks = def.options().collect_ticks;
+                    let mut collected: Vec<serde_json::Value> = Vec::new();
+                    let max_idle_ticks = def.options().max_idle_ticks;
+                    let per_tick_timeout = def.options().per_tick_timeout_ms;
+                    let on_tick_timeout = def.options().on_tick_timeout;
+                    let mut idle_ticks = 0u32;
+                    let mut idle_limit_reached = false;
+                    // Every tick re-runs the whole graph; restored outputs only cover one pass.
+                    if let Some(checkpoint) = run.checkpoint.as_mut() {
+                        checkpoint.restored.clear();
+                    }
                     debug!(
                         event = "entry.recurring_stream_started",
                         workflow_id = %run_ctx.workflow_id,
                         run_id = %run_ctx.run_id,
-                        block_id = %entry_id
+                        block_id = %entry_id,
+                        max_idle_ticks = ?max_idle_ticks
                     );
                     while let Some(o) = rx.recv().await {
                         store_once(&store, entry_id, &o);
                         outputs.insert(entry_id, o);
                         run.mark_block_completed(entry_id);
-                        let sink_output = match run_remaining_levels(RemainingLevelsContext {
+                        let tick = run_remaining_levels(RemainingLevelsContext {
                             def,
                             run,
                             registry,
@@ -1392,24 +2249,76 @@ pub async fn run_workflow(
                             levels: remaining_levels,
                             outputs: &mut outputs,
                             multi_outputs: &mut multi_outputs,
-                        })
-                        .await
-                        {
+                            inactive_edges: HashSet::new(),
+                        });
+                        let tick_result = match per_tick_timeout {
+                            // Blocks already running on the blocking pool finish in the
+                            // background; their outputs are discarded.
+                            Some(ms) => {
+                                match tokio::time::timeout(Duration::from_millis(ms), tick).await {
+                                    Ok(result) => result,
+                                    Err(_) => {
+                                        warn!(
+                                            event = "run.tick_timed_out",
+                                            workflow_id = %run_ctx.workflow_id,
+                                            run_id = %run_ctx.run_id,
+                                            block_id = %entry_id,
+                                            timeout_ms = ms,
+                                            policy = ?on_tick_timeout
+                                        );
+                                        if on_tick_timeout == TickTimeoutPolicy::Skip {
+                                            continue;
+                                        }
+                                        let err = RuntimeError::TickTimeout { timeout_ms: ms };
+                                        set_run_failed(run, &err);
+                                        return Err(err);
+                                    }
+                                }
+                            }
+                            None => tick.await,
+                        };
+                        let sink_output = match tick_result {
                             Ok(out) => out,
                             Err(err) => {
                                 if is_no_new_items_runtime_error(&err) {
+                                    idle_ticks = idle_ticks.saturating_add(1);
+                                    if max_idle_ticks.is_some_and(|max| idle_ticks >= max) {
+                                        info!(
+                                            event = "run.idle_limit_reached",
+                                            workflow_id = %run_ctx.workflow_id,
+                                            run_id = %run_ctx.run_id,
+                                            block_id = %entry_id,
+                                            idle_ticks = idle_ticks
+                                        );
+                                        idle_limit_reached = true;
+                                        break;
+                                    }
                                     continue;
                                 }
                                 set_run_failed(run, &err);
                                 return Err(err);
                             }
                         };
+                        idle_ticks = 0;
+                        if collect_ticks {
+                            collected.push(output_to_json(&sink_output));
+                        }
                         last_sink_output = Some(sink_output);
                     }
+                    // Dropping the receiver stops the entry's producer on its next send.
+                    drop(rx);
+                    if collect_ticks {
+                        last_sink_output = Some(BlockOutput::Json {
+                            value: serde_json::Value::Array(collected),
+                        });
+                    }
+                    if idle_limit_reached && last_sink_output.is_none() {
+                        last_sink_output = Some(BlockOutput::empty());
+                    }
                     match last_sink_output.ok_or(RuntimeError::EntryNodeNotFound(sink_id)) {
                         Ok(out) => {
                             run.set_state(RunState::Completed);
-                            log_run_succeeded(&run_ctx);
+                            log_run_succeeded(run, &run_ctx, sink_block_type(def, sink_id), &out);
                             Ok(out)
                         }
                         Err(err) => {
@@ -1418,21 +2327,68 @@ pub async fn run_workflow(
                         }
                     }
                 }
-                BlockExecutionResult::Multiple(_) => {
-                    let err = RuntimeError::Block(BlockError::Other(
-                        "entry block must not return Multiple".into(),
-                    ));
-                    set_run_failed(run, &err);
-                    Err(err)
+                result => {
+                    let mut inactive_edges = HashSet::new();
+                    if let Err(msg) = record_result(
+                        def,
+                        &run_ctx,
+                        &store,
+                        entry_id,
+                        result,
+                        &mut outputs,
+                        &mut multi_outputs,
+                        &mut inactive_edges,
+                    ) {
+                        let handled_by =
+                            run_error_handlers(def, run, registry, store.clone(), entry_id, &msg)
+                                .await;
+                        let err = record_handled_by(
+                            run,
+                            RuntimeError::Block(BlockError::Other(msg)),
+                            handled_by,
+                        );
+                        set_run_failed(run, &err);
+                        return Err(err);
+                    }
+                    run.mark_block_completed(entry_id);
+                    if let Err(err) = save_checkpoint(run, &outputs) {
+                        set_run_failed(run, &err);
+                        return Err(err);
+                    }
+                    let sink_output = match run_remaining_levels(RemainingLevelsContext {
+                        def,
+                        run,
+                        registry,
+                        run_ctx: &run_ctx,
+                        store: store.clone(),
+                        sink_id,
+                        levels: remaining_levels,
+                        outputs: &mut outputs,
+                        multi_outputs: &mut multi_outputs,
+                        inactive_edges,
+                    })
+                    .await
+                    {
+                        Ok(o) => o,
+                        Err(err) => {
+                            set_run_failed(run, &err);
+                            return Err(err);
+                        }
+                    };
+                    run.set_state(RunState::Completed);
+                    log_run_succeeded(run, &run_ctx, sink_block_type(def, sink_id), &sink_output);
+                    Ok(sink_output)
                 }
             }
         }
-        Err(CycleDetected) => {
+        Err(cycle) => {
+            run.set_execution_mode(ExecutionMode::IterativeCycle);
             debug!(
                 event = "run.execution_mode_selected",
                 workflow_id = %run_ctx.workflow_id,
                 run_id = %run_ctx.run_id,
                 mode = "iterative_cycle",
+                cycle = %cycle,
                 reachable_count = reachable.len() as u64,
                 iteration_budget = ITERATION_BUDGET
             );
@@ -1443,11 +2399,15 @@ pub async fn run_workflow(
                 &run_ctx,
                 store.clone(),
                 sink_id,
-                entry_input,
+                IterationState {
+                    outputs: HashMap::new(),
+                    budget: ITERATION_BUDGET,
+                    entry_input,
+                },
             )
             .await;
             match &out {
-                Ok(_) => log_run_succeeded(&run_ctx),
+                Ok(o) => log_run_succeeded(run, &run_ctx, sink_block_type(def, sink_id), o),
                 Err(err) => set_run_failed(run, err),
             }
             out
@@ -1455,6 +2415,17 @@ pub async fn run_workflow(
     }
 }
 
+/// Persist the completed outputs when the run has a [`RunStore`](crate::core::RunStore).
+fn save_checkpoint(
+    run: &WorkflowRun,
+    outputs: &HashMap<Uuid, BlockOutput>,
+) -> Result<(), RuntimeError> {
+    if let Some(checkpoint) = &run.checkpoint {
+        checkpoint.store.save(run, outputs)?;
+    }
+    Ok(())
+}
+
 struct RemainingLevelsContext<'a> {
     def: &'a WorkflowDefinition,
     run: &'a mut WorkflowRun,
@@ -1465,6 +2436,113 @@ struct RemainingLevelsContext<'a> {
     levels: &'a [Vec<Uuid>],
     outputs: &'a mut HashMap<Uuid, BlockOutput>,
     multi_outputs: &'a mut MultiOutputs,
+    /// Edges that carry nothing this run: from a `Routed` node to an unmatched successor, or out
+    /// of a skipped node. A node whose incoming edges are all inactive is skipped.
+    inactive_edges: HashSet<(Uuid, Uuid)>,
+}
+
+/// Where a run paused for approval: the levels still to run and everything they read.
+#[derive(Debug, Clone)]
+pub(crate) struct Suspension {
+    token: String,
+    approval_ids: Vec<Uuid>,
+    sink_id: Uuid,
+    levels: Vec<Vec<Uuid>>,
+    outputs: HashMap<Uuid, BlockOutput>,
+    multi_outputs: MultiOutputs,
+    inactive_edges: HashSet<(Uuid, Uuid)>,
+    store: SharedRunStore,
+    /// Budget left when an iterative (cyclic) run paused; such a run resumes by iterating again
+    /// from `outputs` instead of running `levels`.
+    iteration_budget: Option<u32>,
+}
+
+/// A fresh approval token for a pause of `run_id`; the run id prefix lets
+/// [`approval_token_run_id`] find the run's checkpoint after a restart.
+fn approval_token(run_id: Uuid) -> String {
+    format!("{}.{}", run_id, Uuid::new_v4().simple())
+}
+
+/// The run an approval token was issued for, or `None` when `token` is not one.
+pub(crate) fn approval_token_run_id(token: &str) -> Option<Uuid> {
+    Uuid::parse_str(token.split_once('.')?.0).ok()
+}
+
+/// Park `suspension` on the run. Level-by-level runs also save the pause with their checkpoint,
+/// so the token can be resumed from the run store after a restart; iterative runs are not
+/// checkpointed and can only be resumed in this process.
+fn suspend(run: &mut WorkflowRun, suspension: Suspension) -> RuntimeError {
+    let token = suspension.token.clone();
+    if suspension.iteration_budget.is_none() {
+        run.pending_approval = Some(PendingApproval {
+            token: token.clone(),
+            approval_ids: suspension.approval_ids.clone(),
+        });
+        if let Err(err) = save_checkpoint(run, &suspension.outputs) {
+            return err;
+        }
+    }
+    run.suspension = Some(Box::new(suspension));
+    RuntimeError::PendingApproval { token }
+}
+
+/// Complete the approval blocks of the checkpointed pause `token` with `decision`, so a run
+/// restored from its checkpoint continues past them. The token is used up.
+pub(crate) fn restore_approval(
+    run: &mut WorkflowRun,
+    token: &str,
+    decision: BlockOutput,
+) -> Result<(), RuntimeError> {
+    let pending = match run.pending_approval.take() {
+        Some(pending) if pending.token == token => pending,
+        other => {
+            run.pending_approval = other;
+            return Err(RuntimeError::UnknownApprovalToken(token.to_string()));
+        }
+    };
+    let Some(checkpoint) = run.checkpoint.as_mut() else {
+        return Err(RuntimeError::UnknownApprovalToken(token.to_string()));
+    };
+    for id in &pending.approval_ids {
+        checkpoint.restored.insert(*id, decision.clone());
+    }
+    let outputs = checkpoint.restored.clone();
+    for id in pending.approval_ids {
+        run.mark_block_completed(id);
+    }
+    save_checkpoint(run, &outputs)
+}
+
+/// Park a run whose entry block asked for approval: nothing else has run yet, so every level
+/// after the entry is still to come.
+fn suspend_at_entry(
+    run: &mut WorkflowRun,
+    run_ctx: &RunLogContext,
+    entry_id: Uuid,
+    prompt: &str,
+    sink_id: Uuid,
+    levels: &[Vec<Uuid>],
+    store: SharedRunStore,
+) -> RuntimeError {
+    info!(
+        event = "block.pending_approval",
+        workflow_id = %run_ctx.workflow_id,
+        run_id = %run_ctx.run_id,
+        block_id = %entry_id,
+        prompt = prompt
+    );
+    let suspension = Suspension {
+        token: approval_token(run.id),
+        approval_ids: vec![entry_id],
+        sink_id,
+        levels: levels.to_vec(),
+        outputs: HashMap::new(),
+        multi_outputs: HashMap::new(),
+        inactive_edges: HashSet::new(),
+        store,
+        iteration_budget: None,
+    };
+    suspend(run, suspension)
 }
 
 /// Run levels from a slice (non-entry levels). Returns the sink output if any.
@@ -1482,9 +2560,15 @@ async fn run_remaining_levels(
         levels,
         outputs,
         multi_outputs,
+        mut inactive_edges,
     } = ctx;
     let nodes = def.nodes();
     let mut last_completed_id: Option<Uuid> = None;
+    let collect_all = def.options().on_level_failure == LevelFailure::CollectAll;
+    let limiter = def
+        .options()
+        .max_parallelism
+        .map(|limit| Arc::new(tokio::sync::Semaphore::new(limit.max(1))));
     for (level_idx, level_nodes) in levels.iter().enumerate() {
         debug!(
             event = "level.started",
@@ -1494,14 +2578,48 @@ async fn run_remaining_levels(
             block_count = level_nodes.len() as u64
         );
         let mut joins: Vec<(Uuid, Option<JoinHandleBlock>)> = Vec::with_capacity(level_nodes.len());
+        let mut approval_ids: Vec<Uuid> = Vec::new();
+        let mut failures: Vec<RuntimeError> = Vec::new();
         for node_id in level_nodes {
             let node_def = nodes
                 .get(node_id)
                 .ok_or(RuntimeError::EntryNodeNotFound(*node_id))?
                 .clone();
+            let preds = predecessors(def, *node_id);
+            if !preds.is_empty()
+                && preds
+                    .iter()
+                    .all(|pred| inactive_edges.contains(&(*pred, *node_id)))
+            {
+                debug!(
+                    event = "block.skipped",
+                    workflow_id = %run_ctx.workflow_id,
+                    run_id = %run_ctx.run_id,
+                    block_id = %node_id
+                );
+                outputs.remove(node_id);
+                multi_outputs.remove(node_id);
+                for succ in successors(def, *node_id) {
+                    inactive_edges.insert((*node_id, succ));
+                }
+                continue;
+            }
+            if let Some(output) = run.restored_output(*node_id) {
+                debug!(
+                    event = "block.restored",
+                    workflow_id = %run_ctx.workflow_id,
+                    run_id = %run_ctx.run_id,
+                    block_id = %node_id
+                );
+                store_once(&store, *node_id, &output);
+                outputs.insert(*node_id, output);
+                run.mark_block_completed(*node_id);
+                last_completed_id = Some(*node_id);
+                continue;
+            }
             let input = input_for_node(def, *node_id, outputs, multi_outputs);
             if let BlockConfig::ChildWorkflow(cfg) = &node_def.config {
-                let output = match run_child_workflow_with_policy(
+                let output = match run_child_workflow_node(
                     cfg,
                     run_ctx,
                     *node_id,
@@ -1515,8 +2633,19 @@ async fn run_remaining_levels(
                     Ok(out) => out,
                     Err(e) => {
                         let msg = e.to_string();
-                        run_error_handlers(def, run, registry, store.clone(), *node_id, &msg).await;
-                        return Err(RuntimeError::Block(BlockError::Other(msg)));
+                        let handled_by =
+                            run_error_handlers(def, run, registry, store.clone(), *node_id, &msg)
+                                .await;
+                        let err = record_handled_by(
+                            run,
+                            RuntimeError::Block(BlockError::Other(msg)),
+                            handled_by,
+                        );
+                        if !collect_all {
+                            return Err(err);
+                        }
+                        failures.push(err);
+                        continue;
                     }
                 };
                 store_once(&store, *node_id, &output);
@@ -1525,7 +2654,17 @@ async fn run_remaining_levels(
                 last_completed_id = Some(*node_id);
                 joins.push((*node_id, None));
             } else {
-                let block = registry.get(&node_def.config)?;
+                let mut block = registry.get(&node_def.config)?;
+                if let Some(limiter) = &limiter {
+                    let permit = Arc::clone(limiter)
+                        .acquire_owned()
+                        .await
+                        .expect("parallelism semaphore is never closed");
+                    block = Box::new(PermittedBlock {
+                        block,
+                        _permit: permit,
+                    });
+                }
                 let join_handle = spawn_block_execution(
                     run_ctx.clone(),
                     *node_id,
@@ -1534,58 +2673,116 @@ async fn run_remaining_levels(
                     block,
                     input,
                     store.clone(),
+                    node_def.timeout_ms,
                 );
                 joins.push((*node_id, Some(join_handle)));
             }
         }
         for (node_id, join_handle_opt) in joins {
-            if let Some(join_handle) = join_handle_opt {
-                let result = match join_handle.await {
-                    Ok(Ok(result)) => result,
-                    Ok(Err(err)) => {
-                        let msg = err.to_string();
-                        run_error_handlers(def, run, registry, store.clone(), node_id, &msg).await;
-                        return Err(RuntimeError::Block(err));
-                    }
-                    Err(e) => {
-                        let block_err = BlockError::Other(e.to_string());
-                        let msg = block_err.to_string();
-                        run_error_handlers(def, run, registry, store.clone(), node_id, &msg).await;
-                        return Err(RuntimeError::Block(block_err));
-                    }
-                };
-                match result {
-                    BlockExecutionResult::Once(o) => {
-                        store_once(&store, node_id, &o);
-                        outputs.insert(node_id, o);
-                        run.mark_block_completed(node_id);
-                        last_completed_id = Some(node_id);
-                    }
-                    BlockExecutionResult::Multiple(outs) => {
-                        let succs = successors(def, node_id);
-                        debug!(
-                            event = "block.multiple_routed",
-                            workflow_id = %run_ctx.workflow_id,
-                            run_id = %run_ctx.run_id,
-                            block_id = %node_id,
-                            output_count = outs.len() as u64,
-                            successor_count = succs.len() as u64
-                        );
-                        store_multiple(&store, node_id, &outs);
-                        let list: Vec<(Uuid, BlockOutput)> =
-                            succs.into_iter().zip(outs.into_iter()).collect();
-                        multi_outputs.insert(node_id, list);
-                        run.mark_block_completed(node_id);
-                        last_completed_id = Some(node_id);
-                    }
-                    BlockExecutionResult::Recurring(_) => {
-                        let msg = "Recurring only supported for entry block".to_string();
-                        run_error_handlers(def, run, registry, store.clone(), node_id, &msg).await;
-                        return Err(RuntimeError::Block(BlockError::Other(msg)));
+            let outcome: Result<(), RuntimeError> = 'join: {
+                if let Some(join_handle) = join_handle_opt {
+                    let result = match join_handle.await {
+                        Ok(Ok(result)) => result,
+                        Ok(Err(BlockError::PendingApproval { prompt })) => {
+                            info!(
+                                event = "block.pending_approval",
+                                workflow_id = %run_ctx.workflow_id,
+                                run_id = %run_ctx.run_id,
+                                block_id = %node_id,
+                                prompt = prompt.as_str()
+                            );
+                            approval_ids.push(node_id);
+                            break 'join Ok(());
+                        }
+                        Ok(Err(err)) => {
+                            let msg = err.to_string();
+                            let handled_by = run_error_handlers(
+                                def,
+                                run,
+                                registry,
+                                store.clone(),
+                                node_id,
+                                &msg,
+                            )
+                            .await;
+                            break 'join Err(record_handled_by(
+                                run,
+                                RuntimeError::Block(err),
+                                handled_by,
+                            ));
+                        }
+                        Err(e) => {
+                            let block_err = BlockError::Other(e.to_string());
+                            let msg = block_err.to_string();
+                            let handled_by = run_error_handlers(
+                                def,
+                                run,
+                                registry,
+                                store.clone(),
+                                node_id,
+                                &msg,
+                            )
+                            .await;
+                            break 'join Err(record_handled_by(
+                                run,
+                                RuntimeError::Block(block_err),
+                                handled_by,
+                            ));
+                        }
+                    };
+                    if let Err(msg) = record_result(
+                        def,
+                        run_ctx,
+                        &store,
+                        node_id,
+                        result,
+                        outputs,
+                        multi_outputs,
+                        &mut inactive_edges,
+                    ) {
+                        let handled_by =
+                            run_error_handlers(def, run, registry, store.clone(), node_id, &msg)
+                                .await;
+                        break 'join Err(record_handled_by(
+                            run,
+                            RuntimeError::Block(BlockError::Other(msg)),
+                            handled_by,
+                        ));
                     }
+                    run.mark_block_completed(node_id);
+                    last_completed_id = Some(node_id);
                 }
+                Ok(())
+            };
+            if let Err(err) = outcome {
+                if !collect_all {
+                    return Err(err);
+                }
+                failures.push(err);
             }
         }
+        // Saved before failures surface so completed siblings of a failed block are kept.
+        save_checkpoint(run, outputs)?;
+        match failures.len() {
+            0 => {}
+            1 => return Err(failures.remove(0)),
+            _ => return Err(RuntimeError::LevelFailed { failures }),
+        }
+        if !approval_ids.is_empty() {
+            // The rest of this level has finished; park everything the later levels need.
+            let suspension = Suspension {
+                token: approval_token(run.id),
+                approval_ids,
+                sink_id,
+                levels: levels[level_idx + 1..].to_vec(),
+                outputs: outputs.clone(),
+                multi_outputs: multi_outputs.clone(),
+                inactive_edges,
+                store,
+                iteration_budget: None,
+            };
+            return Err(suspend(run, suspension));
+        }
         debug!(
             event = "level.completed",
             workflow_id = %run_ctx.workflow_id,
@@ -1599,9 +2796,171 @@ async fn run_remaining_levels(
         .ok_or(RuntimeError::EntryNodeNotFound(sink_id))
 }
 
+/// Record a finished block's result for its successors: a single output in `outputs`, fanned-out
+/// outputs in `multi_outputs` keyed by successor, and the edges a `Routed` result did not take in
+/// `inactive_edges`. Fails with the error message for unroutable labels and `Recurring` results,
+/// which only the entry block may return.
+#[allow(clippy::too_many_arguments)]
+fn record_result(
+    def: &WorkflowDefinition,
+    run_ctx: &RunLogContext,
+    store: &SharedRunStore,
+    node_id: Uuid,
+    result: BlockExecutionResult,
+    outputs: &mut HashMap<Uuid, BlockOutput>,
+    multi_outputs: &mut MultiOutputs,
+    inactive_edges: &mut HashSet<(Uuid, Uuid)>,
+) -> Result<(), String> {
+    match result {
+        BlockExecutionResult::Once(o) => {
+            store_once(store, node_id, &o);
+            outputs.insert(node_id, o);
+        }
+        BlockExecutionResult::Multiple(outs) => {
+            let succs = successors(def, node_id);
+            debug!(
+                event = "block.multiple_routed",
+                workflow_id = %run_ctx.workflow_id,
+                run_id = %run_ctx.run_id,
+                block_id = %node_id,
+                output_count = outs.len() as u64,
+                successor_count = succs.len() as u64
+            );
+            store_multiple(store, node_id, &outs);
+            let list: Vec<(Uuid, BlockOutput)> = succs.into_iter().zip(outs).collect();
+            multi_outputs.insert(node_id, list);
+        }
+        BlockExecutionResult::Labeled(outs) => {
+            let list = route_labeled(def, node_id, &outs)?;
+            debug!(
+                event = "block.multiple_routed",
+                workflow_id = %run_ctx.workflow_id,
+                run_id = %run_ctx.run_id,
+                block_id = %node_id,
+                output_count = outs.len() as u64,
+                successor_count = list.len() as u64
+            );
+            let ordered: Vec<BlockOutput> = outs.into_iter().map(|(_, o)| o).collect();
+            store_multiple(store, node_id, &ordered);
+            multi_outputs.insert(node_id, list);
+        }
+        BlockExecutionResult::Routed { slot, output } => {
+            let (matched, unmatched) = route_slot(def, node_id, &slot);
+            debug!(
+                event = "block.routed",
+                workflow_id = %run_ctx.workflow_id,
+                run_id = %run_ctx.run_id,
+                block_id = %node_id,
+                slot = slot.as_str(),
+                matched_count = matched.len() as u64,
+                skipped_count = unmatched.len() as u64
+            );
+            for succ in unmatched {
+                inactive_edges.insert((node_id, succ));
+            }
+            store_once(store, node_id, &output);
+            let list = matched.into_iter().map(|s| (s, output.clone())).collect();
+            multi_outputs.insert(node_id, list);
+        }
+        BlockExecutionResult::Recurring(_) => {
+            return Err("Recurring only supported for entry block".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Resume a run paused by [`RuntimeError::PendingApproval`]. The waiting approval blocks complete
+/// with `decision` as their output, then the remaining levels run as usual.
+pub async fn resume_workflow(
+    def: &WorkflowDefinition,
+    run: &mut WorkflowRun,
+    registry: &BlockRegistry,
+    token: &str,
+    decision: BlockOutput,
+) -> Result<BlockOutput, RuntimeError> {
+    if run.suspension.as_ref().is_none_or(|s| s.token != token) {
+        return Err(RuntimeError::UnknownApprovalToken(token.to_string()));
+    }
+    let mut suspension = *run.suspension.take().expect("checked above");
+    run.pending_approval = None;
+    let run_ctx = RunLogContext::from_run(run);
+    let _run_guard = run_span(&run_ctx).entered();
+    run.set_state(RunState::Running);
+    info!(
+        event = "run.resumed",
+        workflow_id = %run_ctx.workflow_id,
+        run_id = %run_ctx.run_id,
+        approval_count = suspension.approval_ids.len() as u64
+    );
+    for id in &suspension.approval_ids {
+        store_once(&suspension.store, *id, &decision);
+        suspension.outputs.insert(*id, decision.clone());
+        run.mark_block_completed(*id);
+    }
+    if suspension.iteration_budget.is_none() {
+        save_checkpoint(run, &suspension.outputs)?;
+    }
+    let result = match suspension.iteration_budget {
+        Some(budget) => {
+            run_workflow_iteration(
+                def,
+                run,
+                registry,
+                &run_ctx,
+                suspension.store.clone(),
+                suspension.sink_id,
+                IterationState {
+                    outputs: suspension.outputs,
+                    budget,
+                    entry_input: None,
+                },
+            )
+            .await
+        }
+        None => {
+            run_remaining_levels(RemainingLevelsContext {
+                def,
+                run,
+                registry,
+                run_ctx: &run_ctx,
+                store: suspension.store.clone(),
+                sink_id: suspension.sink_id,
+                levels: &suspension.levels,
+                outputs: &mut suspension.outputs,
+                multi_outputs: &mut suspension.multi_outputs,
+                inactive_edges: suspension.inactive_edges,
+            })
+            .await
+        }
+    };
+    match result {
+        Ok(out) => {
+            run.set_state(RunState::Completed);
+            log_run_succeeded(
+                run,
+                &run_ctx,
+                sink_block_type(def, suspension.sink_id),
+                &out,
+            );
+            Ok(out)
+        }
+        Err(err) => {
+            set_run_failed(run, &err);
+            Err(err)
+        }
+    }
+}
+
+/// Where an iterative run starts: from scratch, or from the outputs a paused run had.
+struct IterationState {
+    outputs: HashMap<Uuid, BlockOutput>,
+    budget: u32,
+    /// Input for the entry node's first run only.
+    entry_input: Option<BlockInput>,
+}
+
 /// Run workflow in iteration mode (graph has a cycle). Uses ready set and iteration budget.
 /// Nodes can run multiple times; ready = all predecessors have produced output.
-/// When entry_input is Some, the entry node receives it on first run only.
 async fn run_workflow_iteration(
     def: &WorkflowDefinition,
     run: &mut WorkflowRun,
@@ -1609,13 +2968,16 @@ async fn run_workflow_iteration(
     run_ctx: &RunLogContext,
     store: SharedRunStore,
     sink_id: Uuid,
-    mut entry_input: Option<BlockInput>,
+    state: IterationState,
 ) -> Result<BlockOutput, RuntimeError> {
     let nodes = def.nodes();
     let entry_id = *def.entry().unwrap();
-    let mut outputs: HashMap<Uuid, BlockOutput> = HashMap::new();
+    let IterationState {
+        mut outputs,
+        mut budget,
+        mut entry_input,
+    } = state;
     let multi_outputs: MultiOutputs = HashMap::new();
-    let mut budget = ITERATION_BUDGET;
     let mut last_completed_id: Option<Uuid> = None;
 
     loop {
@@ -1658,7 +3020,7 @@ async fn run_workflow_iteration(
                 input_for_node(def, node_id, &outputs, &multi_outputs)
             };
             if let BlockConfig::ChildWorkflow(cfg) = &node_def.config {
-                let output = match run_child_workflow_with_policy(
+                let output = match run_child_workflow_node(
                     cfg,
                     run_ctx,
                     node_id,
@@ -1672,8 +3034,14 @@ async fn run_workflow_iteration(
                     Ok(out) => out,
                     Err(e) => {
                         let msg = e.to_string();
-                        run_error_handlers(def, run, registry, store.clone(), node_id, &msg).await;
-                        return Err(RuntimeError::Block(BlockError::Other(msg)));
+                        let handled_by =
+                            run_error_handlers(def, run, registry, store.clone(), node_id, &msg)
+                                .await;
+                        return Err(record_handled_by(
+                            run,
+                            RuntimeError::Block(BlockError::Other(msg)),
+                            handled_by,
+                        ));
                     }
                 };
                 store_once(&store, node_id, &output);
@@ -1685,8 +3053,10 @@ async fn run_workflow_iteration(
                     Ok(b) => b,
                     Err(err) => {
                         let msg = err.to_string();
-                        run_error_handlers(def, run, registry, store.clone(), node_id, &msg).await;
-                        return Err(RuntimeError::Block(err));
+                        let handled_by =
+                            run_error_handlers(def, run, registry, store.clone(), node_id, &msg)
+                                .await;
+                        return Err(record_handled_by(run, RuntimeError::Block(err), handled_by));
                     }
                 };
                 let result = match spawn_block_execution(
@@ -1697,28 +3067,60 @@ async fn run_workflow_iteration(
                     block,
                     input,
                     store.clone(),
+                    node_def.timeout_ms,
                 )
                 .await
                 {
                     Ok(Ok(r)) => r,
+                    Ok(Err(BlockError::PendingApproval { prompt })) => {
+                        info!(
+                            event = "block.pending_approval",
+                            workflow_id = %run_ctx.workflow_id,
+                            run_id = %run_ctx.run_id,
+                            block_id = %node_id,
+                            prompt = prompt.as_str()
+                        );
+                        let suspension = Suspension {
+                            token: approval_token(run.id),
+                            approval_ids: vec![node_id],
+                            sink_id,
+                            levels: Vec::new(),
+                            outputs,
+                            multi_outputs,
+                            inactive_edges: HashSet::new(),
+                            store,
+                            iteration_budget: Some(budget),
+                        };
+                        return Err(suspend(run, suspension));
+                    }
                     Ok(Err(err)) => {
                         let msg = err.to_string();
-                        run_error_handlers(def, run, registry, store.clone(), node_id, &msg).await;
-                        return Err(RuntimeError::Block(err));
+                        let handled_by =
+                            run_error_handlers(def, run, registry, store.clone(), node_id, &msg)
+                                .await;
+                        return Err(record_handled_by(run, RuntimeError::Block(err), handled_by));
                     }
                     Err(e) => {
                         let block_err = BlockError::Other(e.to_string());
                         let msg = block_err.to_string();
-                        run_error_handlers(def, run, registry, store.clone(), node_id, &msg).await;
-                        return Err(RuntimeError::Block(block_err));
+                        let handled_by =
+                            run_error_handlers(def, run, registry, store.clone(), node_id, &msg)
+                                .await;
+                        return Err(record_handled_by(
+                            run,
+                            RuntimeError::Block(block_err),
+                            handled_by,
+                        ));
                     }
                 };
                 let output = match result_to_output_async(result).await {
                     Ok(out) => out,
                     Err(err) => {
                         let msg = err.to_string();
-                        run_error_handlers(def, run, registry, store.clone(), node_id, &msg).await;
-                        return Err(err);
+                        let handled_by =
+                            run_error_handlers(def, run, registry, store.clone(), node_id, &msg)
+                                .await;
+                        return Err(record_handled_by(run, err, handled_by));
                     }
                 };
                 store_once(&store, node_id, &output);
@@ -1,12 +1,16 @@
 //! Minimal user-facing API: Workflow, BlockId, add/link/run. Use [`Workflow::with_registry`] to supply a block registry (e.g. from orchestrator-blocks). Use [`Workflow::add_custom`] to add custom blocks.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 
 use serde::Serialize;
 use uuid::Uuid;
 
-use crate::block::{BlockConfig, BlockOutput, BlockRegistry};
-use crate::core::{NodeDef, WorkflowDefinition, WorkflowRun};
+use crate::block::{BlockConfig, BlockOutput, BlockRegistry, TickState};
+use crate::core::{
+    BlockReport, DuplicateEdgePolicy, ExecutionMode, LevelFailure, NodeDef, RunStore,
+    RunStoreError, RunSummary, TickTimeoutPolicy, WorkflowDefinition, WorkflowOptions, WorkflowRun,
+};
 use crate::runtime;
 
 /// Opaque ID for a block in a workflow. Returned by [`Workflow::add`] and used in [`Workflow::link`].
@@ -44,6 +48,34 @@ pub type RunError = runtime::RuntimeError;
 /// Public validation failure type.
 pub type WorkflowValidationError = runtime::WorkflowValidationError;
 
+type TickSummary = Arc<dyn Fn(&TickState) -> Option<BlockOutput> + Send + Sync>;
+
+/// Result of [`Workflow::run_detailed`]: the sink output plus how the run was scheduled and
+/// how long each executed block took.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunReport {
+    pub run_id: Uuid,
+    pub output: BlockOutput,
+    pub execution_mode: ExecutionMode,
+    /// One entry per executed block, in completion order.
+    pub blocks: Vec<BlockReport>,
+    /// Wall-clock duration of the whole run.
+    pub duration_ms: u64,
+    /// Sink block type and output shape, as logged with `run.succeeded`.
+    pub summary: Option<RunSummary>,
+}
+
+/// Error of [`Workflow::run_recovering`]: why the run failed, plus what its on_error and
+/// on_cancel handlers produced.
+#[derive(Debug)]
+pub struct RunFailure {
+    /// Nil when the run failed validation before it started.
+    pub run_id: Uuid,
+    pub error: RunError,
+    /// Outputs of the handlers that succeeded, in the order they finished.
+    pub handled_by: Vec<runtime::HandlerOutput>,
+}
+
 /// Workflow: add blocks, link them, then run. First block added is the entry block.
 pub struct Workflow {
     def_id: Uuid,
@@ -52,8 +84,21 @@ pub struct Workflow {
     node_input_sources: HashMap<Uuid, Vec<usize>>,
     edges: Vec<(Uuid, Uuid)>,
     error_edges: Vec<(Uuid, Uuid)>,
+    edge_slots: Vec<(Uuid, Uuid, String)>,
+    edge_labels: Vec<(Uuid, Uuid, String)>,
+    cancel_handlers: Vec<Uuid>,
+    tags: HashMap<Uuid, Vec<String>>,
+    block_timeouts: HashMap<Uuid, u64>,
     entry: Option<Uuid>,
-    registry: BlockRegistry,
+    options: WorkflowOptions,
+    /// Shared, never cloned: child workflows at any depth borrow it from the runtime.
+    registry: Arc<BlockRegistry>,
+    /// Runs paused by an approval block, keyed by approval token.
+    paused: Mutex<HashMap<String, (WorkflowDefinition, WorkflowRun)>>,
+    tick_state: TickState,
+    /// Turns the final tick state into the run's output (see [`Workflow::with_tick_summary`]).
+    tick_summary: Option<TickSummary>,
+    run_store: Option<Arc<dyn RunStore>>,
 }
 
 impl Workflow {
@@ -66,13 +111,29 @@ impl Workflow {
             node_input_sources: HashMap::new(),
             edges: Vec::new(),
             error_edges: Vec::new(),
+            edge_slots: Vec::new(),
+            edge_labels: Vec::new(),
+            cancel_handlers: Vec::new(),
+            tags: HashMap::new(),
+            block_timeouts: HashMap::new(),
             entry: None,
-            registry: BlockRegistry::new(),
+            options: WorkflowOptions::default(),
+            registry: Arc::new(BlockRegistry::new()),
+            paused: Mutex::new(HashMap::new()),
+            tick_state: TickState::default(),
+            tick_summary: None,
+            run_store: None,
         }
     }
 
     /// Create an empty workflow using the given registry (e.g. builtins from orchestrator-blocks plus custom blocks).
     pub fn with_registry(registry: BlockRegistry) -> Self {
+        Self::with_shared_registry(Arc::new(registry))
+    }
+
+    /// Create an empty workflow using a registry shared with other workflows. Factories and the
+    /// state they capture are reused as-is; the registry is never copied.
+    pub fn with_shared_registry(registry: Arc<BlockRegistry>) -> Self {
         Self {
             def_id: Uuid::new_v4(),
             nodes: HashMap::new(),
@@ -80,15 +141,46 @@ impl Workflow {
             node_input_sources: HashMap::new(),
             edges: Vec::new(),
             error_edges: Vec::new(),
+            edge_slots: Vec::new(),
+            edge_labels: Vec::new(),
+            cancel_handlers: Vec::new(),
+            tags: HashMap::new(),
+            block_timeouts: HashMap::new(),
             entry: None,
+            options: WorkflowOptions::default(),
             registry,
+            paused: Mutex::new(HashMap::new()),
+            tick_state: TickState::default(),
+            tick_summary: None,
+            run_store: None,
+        }
+    }
+
+    /// Give the workflow a stable id instead of a random one. Block ids are derived from the
+    /// workflow id and the order blocks are added, so a workflow rebuilt the same way under the
+    /// same id (e.g. after a restart) gets the same block ids and can resume the checkpoints
+    /// of [`run_checkpointed`](Workflow::run_checkpointed). Call before adding blocks, since
+    /// their ids are already derived; otherwise fails with
+    /// [`WorkflowValidationError::IdAfterBlocks`].
+    pub fn with_id(mut self, id: Uuid) -> Result<Self, WorkflowValidationError> {
+        if !self.nodes.is_empty() {
+            return Err(WorkflowValidationError::IdAfterBlocks {
+                block_count: self.nodes.len(),
+            });
         }
+        self.def_id = id;
+        Ok(self)
+    }
+
+    /// Id for the next block added: the `nodes.len()`-th block of this workflow.
+    fn next_block_id(&self) -> Uuid {
+        Uuid::new_v5(&self.def_id, &(self.nodes.len() as u64).to_le_bytes())
     }
 
     /// Add a block to the workflow. Returns its [`BlockId`] for linking. First block added becomes the entry.
     /// Pass a [`BlockConfig`] or any type that implements `Into<BlockConfig>` (e.g. `orchestrator_blocks::Block`).
     pub fn add(&mut self, config: impl Into<BlockConfig>) -> BlockId {
-        let id = Uuid::new_v4();
+        let id = self.next_block_id();
         if self.entry.is_none() {
             self.entry = Some(id);
         }
@@ -139,6 +231,26 @@ impl Workflow {
         self.add(crate::block::ChildWorkflowConfig::new(definition))
     }
 
+    /// Add a child workflow node that is re-run as a whole per `retry_policy` when it fails.
+    pub fn add_child_workflow_with_retry(
+        &mut self,
+        definition: WorkflowDefinition,
+        retry_policy: crate::block::RetryPolicy,
+    ) -> BlockId {
+        self.add(crate::block::ChildWorkflowConfig::new(definition).with_retry_policy(retry_policy))
+    }
+
+    /// Add a child workflow node whose whole run (each attempt) must finish within `timeout_ms`.
+    pub fn add_child_workflow_with_timeout(
+        &mut self,
+        definition: WorkflowDefinition,
+        timeout_ms: u64,
+    ) -> BlockId {
+        self.add(
+            crate::block::ChildWorkflowConfig::new(definition).with_timeout_ms(Some(timeout_ms)),
+        )
+    }
+
     /// Add a custom block (registered in the registry). Pass the same `type_id` used in [`BlockRegistry::register_custom`](crate::block::BlockRegistry::register_custom) and a config that implements `Serialize`.
     pub fn add_custom(
         &mut self,
@@ -152,7 +264,7 @@ impl Workflow {
         }
         let payload = serde_json::to_value(config)
             .map_err(|e| crate::block::BlockError::Other(e.to_string()))?;
-        let id = Uuid::new_v4();
+        let id = self.next_block_id();
         if self.entry.is_none() {
             self.entry = Some(id);
         }
@@ -179,6 +291,38 @@ impl Workflow {
         self.edges.push((from.0, to.0));
     }
 
+    /// Link the `slot`-labeled output of `from` to `to`. Use when `from` returns
+    /// [`BlockExecutionResult::Labeled`](crate::block::BlockExecutionResult::Labeled): each
+    /// successor receives the output whose label matches its slot, regardless of link order.
+    /// A [`BlockExecutionResult::Routed`](crate::block::BlockExecutionResult::Routed) result
+    /// reaches only the successors linked with its slot; the others are skipped.
+    pub fn link_slot<F, T>(&mut self, from: F, slot: impl Into<String>, to: T)
+    where
+        F: WorkflowEndpoint,
+        T: WorkflowEndpoint,
+    {
+        let from = from.resolve(self);
+        let to = to.resolve(self);
+        self.edges.push((from.0, to.0));
+        self.edge_slots.push((from.0, to.0, slot.into()));
+    }
+
+    /// Link output of `from` to input of `to` under `label`. When every edge into `to` is
+    /// labeled, `to` receives [`BlockInput::MultiNamed`](crate::block::BlockInput::MultiNamed)
+    /// keyed by label, so it can read its inputs by name instead of trusting link order.
+    /// Validation rejects a block whose incoming links mix labeled and unlabeled ones, or repeat
+    /// a label.
+    pub fn link_as<F, T>(&mut self, from: F, to: T, label: impl Into<String>)
+    where
+        F: WorkflowEndpoint,
+        T: WorkflowEndpoint,
+    {
+        let from = from.resolve(self);
+        let to = to.resolve(self);
+        self.edges.push((from.0, to.0));
+        self.edge_labels.push((from.0, to.0, label.into()));
+    }
+
     /// Link error of `from` to `to`. When `from` returns an error at runtime, `to` receives
     /// `BlockInput::Error { message }`.
     pub fn on_error<F, T>(&mut self, from: F, to: T)
@@ -200,38 +344,489 @@ impl Workflow {
         self.on_error(from, to);
     }
 
+    /// Run `handler` as a cleanup node when the run is cancelled or exceeds its
+    /// [run timeout](Workflow::set_run_timeout). It is not linked into the graph; its input is a
+    /// JSON object with `reason` (`cancelled` or `timeout`), `workflow_id`, `run_id` and
+    /// `completed_block_ids`, so it can roll back or notify.
+    pub fn on_cancel<T>(&mut self, handler: T) -> BlockId
+    where
+        T: WorkflowEndpoint,
+    {
+        let handler = handler.resolve(self);
+        self.cancel_handlers.push(handler.0);
+        handler
+    }
+
+    /// Tag `block` so [`run_only`](Workflow::run_only) can select it. A block may carry several tags.
+    pub fn tag(&mut self, block: BlockId, tag: impl Into<String>) {
+        let tags = self.tags.entry(block.0).or_default();
+        let tag = tag.into();
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    /// Fail `block` with a `block.timeout` error when one execution takes longer than `timeout`.
+    /// The error follows the block's [`on_error`](Workflow::on_error) links like any other failure.
+    /// The timeout applies to every block, including the entry block.
+    pub fn set_block_timeout(&mut self, block: BlockId, timeout: std::time::Duration) {
+        self.block_timeouts
+            .insert(block.0, (timeout.as_millis() as u64).max(1));
+    }
+
+    /// Stop the run after `timeout` of wall-clock time, run the cancel handlers, and fail with
+    /// [`RunError::RunTimeout`].
+    pub fn set_run_timeout(&mut self, timeout: std::time::Duration) {
+        self.options.run_timeout_ms = Some(timeout.as_millis() as u64);
+    }
+
+    /// End a Recurring-entry run as Completed after `ticks` consecutive ticks were skipped
+    /// with `no_new_items`, instead of waiting on the entry forever.
+    pub fn set_max_idle_ticks(&mut self, ticks: u32) {
+        self.options.max_idle_ticks = Some(ticks);
+    }
+
+    /// Choose how a level with failing blocks fails the run. [`LevelFailure::CollectAll`] awaits
+    /// every sibling and reports all failures as [`RunError::LevelFailed`].
+    pub fn set_level_failure(&mut self, policy: LevelFailure) {
+        self.options.on_level_failure = policy;
+    }
+
+    /// Choose what happens when the same pair is linked more than once: drop the repeats
+    /// ([`DuplicateEdgePolicy::Dedupe`], the default) or fail validation
+    /// ([`DuplicateEdgePolicy::Error`]).
+    pub fn set_duplicate_edge_policy(&mut self, policy: DuplicateEdgePolicy) {
+        self.options.on_duplicate_edge = policy;
+    }
+
+    /// Execute at most `limit` blocks of a level at once, e.g. to avoid hammering a server with
+    /// a level of fifty http requests. Unlimited by default.
+    pub fn set_max_parallelism(&mut self, limit: usize) {
+        self.options.max_parallelism = Some(limit.max(1));
+    }
+
+    /// Run [`Workflow::validate_all`] instead of the first-error check before every run, so a
+    /// workflow with unreachable blocks or several sinks fails before anything executes.
+    pub fn set_strict_validation(&mut self, strict: bool) {
+        self.options.strict_validation = strict;
+    }
+
+    /// Let the workflow contain cycles. Such a run executes iteratively, re-running blocks whose
+    /// predecessors all have output until nothing is ready or the iteration budget runs out.
+    pub fn set_allow_cycles(&mut self, allow: bool) {
+        self.options.allow_cycles = allow;
+    }
+
+    /// Give each Recurring tick's downstream at most `timeout` of wall-clock time. A tick that
+    /// runs over is dropped ([`TickTimeoutPolicy::Skip`]) or fails the run ([`TickTimeoutPolicy::Fail`]).
+    pub fn set_per_tick_timeout(
+        &mut self,
+        timeout: std::time::Duration,
+        policy: TickTimeoutPolicy,
+    ) {
+        self.options.per_tick_timeout_ms = Some(timeout.as_millis() as u64);
+        self.options.on_tick_timeout = policy;
+    }
+
+    /// Share `state` with every block of each run; blocks read it with
+    /// `BlockExecutionContext::tick_state::<T>()`. It survives across Recurring ticks, so a
+    /// recurring digest can keep totals, and is put back to `T::default()` when a new run
+    /// starts. Resuming a paused run keeps it.
+    pub fn with_tick_state<T: Default + Send + 'static>(mut self, state: Arc<Mutex<T>>) -> Self {
+        self.tick_state = TickState::new(state);
+        self
+    }
+
+    /// Once a run completes, replace its output with `summary` of the tick state (see
+    /// [`with_tick_state`](Workflow::with_tick_state)), e.g. the totals a recurring run kept.
+    /// Has no effect when the tick state is unset or not a `T`.
+    pub fn with_tick_summary<T, F>(mut self, summary: F) -> Self
+    where
+        T: Send + 'static,
+        F: Fn(&T) -> BlockOutput + Send + Sync + 'static,
+    {
+        self.tick_summary = Some(Arc::new(move |state: &TickState| {
+            let state = state.get::<T>()?;
+            let state = state
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            Some(summary(&state))
+        }));
+        self
+    }
+
+    /// Save every run's completed block outputs to `store` after each level, so
+    /// [`run_checkpointed`](Workflow::run_checkpointed) can resume it after a restart.
+    pub fn set_run_store(&mut self, store: Arc<dyn RunStore>) {
+        self.run_store = Some(store);
+    }
+
+    /// A fresh run of `def` whose vars are the caller's `vars` overlaid with every block's
+    /// [`run_vars`](crate::block::BlockExecutor::run_vars). The tick state starts over.
+    fn new_run(
+        &self,
+        def: &WorkflowDefinition,
+        vars: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> Result<WorkflowRun, RunError> {
+        let mut run = WorkflowRun::new(def);
+        self.tick_state.reset();
+        run.tick_state = self.tick_state.clone();
+        if let Some(store) = &self.run_store {
+            run.set_run_store(Arc::clone(store));
+        }
+        let mut vars = vars.unwrap_or_default();
+        for node_def in def.nodes.values() {
+            if let Ok(block) = self.registry.get(&node_def.config)
+                && let Some(block_vars) = block.run_vars()?
+            {
+                vars.extend(block_vars);
+            }
+        }
+        run.set_vars(vars);
+        Ok(run)
+    }
+
+    /// Run (sync) under `run_id`, resuming from its last checkpoint in the
+    /// [run store](Workflow::set_run_store) when there is one: blocks whose outputs were saved
+    /// are not executed again. Without a checkpoint the run starts fresh under `run_id`. Block ids
+    /// are part of the checkpoint, so resume with the same `Workflow`, or one rebuilt the same way
+    /// under the same [`with_id`](Workflow::with_id); a checkpoint taken from another workflow
+    /// fails with [`RunError::Checkpoint`]. Only single outputs are restored; blocks that fanned
+    /// out, cyclic graphs, and the ticks of a Recurring entry run again.
+    pub fn run_checkpointed(&self, run_id: Uuid) -> Result<BlockOutput, RunError> {
+        let options = RunOptions {
+            run_id: Some(run_id),
+            ..RunOptions::default()
+        };
+        block_on(self.run_with(options, std::future::pending())).map(|report| report.output)
+    }
+
     /// Run the workflow (sync). Blocks until complete. Returns the sink block's output or [`RunError`].
+    /// A run that reaches an approval block returns [`RunError::PendingApproval`]; continue it with
+    /// [`resume_with_input`](Workflow::resume_with_input).
     pub fn run(&self) -> Result<BlockOutput, RunError> {
+        block_on(self.run_with(RunOptions::default(), std::future::pending()))
+            .map(|report| report.output)
+    }
+
+    /// The path every public run method takes: validate the definition once, set up the run,
+    /// execute it until it ends or `cancel` resolves, and keep it for
+    /// [`resume_with_input`](Workflow::resume_with_input) if it paused for approval.
+    async fn run_with(
+        &self,
+        options: RunOptions,
+        cancel: impl std::future::Future<Output = ()>,
+    ) -> Result<RunReport, RunError> {
+        self.run_with_handlers(options, cancel)
+            .await
+            .map_err(|failure| failure.error)
+    }
+
+    /// [`run_with`](Workflow::run_with), keeping the handler outputs of a failed run.
+    async fn run_with_handlers(
+        &self,
+        options: RunOptions,
+        cancel: impl std::future::Future<Output = ()>,
+    ) -> Result<RunReport, RunFailure> {
+        crate::observability::init_observability();
+        let def = match options.definition {
+            Some(def) => def,
+            None => self.build_definition(),
+        };
+        let mut run = self
+            .validate_definition(&def)
+            .map_err(RunError::from)
+            .and_then(|()| match options.run_id {
+                Some(run_id) => self.checkpointed_run(&def, run_id, options.vars),
+                None => self.new_run(&def, options.vars),
+            })
+            .map_err(|error| RunFailure {
+                run_id: Uuid::nil(),
+                error,
+                handled_by: Vec::new(),
+            })?;
+        let started = std::time::Instant::now();
+        let result = runtime::run_workflow_until(&def, &mut run, &self.registry, cancel)
+            .await
+            .map(|output| RunReport {
+                run_id: run.id,
+                output: self.summarize(output),
+                execution_mode: run.execution_mode().unwrap_or(ExecutionMode::Topological),
+                blocks: run.block_reports(),
+                duration_ms: started.elapsed().as_millis() as u64,
+                summary: run.summary().cloned(),
+            });
+        let run_id = run.id;
+        let handled_by = std::mem::take(&mut run.handled_by);
+        self.park_if_paused(def, run, result)
+            .map_err(|error| RunFailure {
+                run_id,
+                error,
+                handled_by,
+            })
+    }
+
+    /// `run_id` restored from its checkpoint in the run store, or a fresh run under that id.
+    fn checkpointed_run(
+        &self,
+        def: &WorkflowDefinition,
+        run_id: Uuid,
+        vars: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> Result<WorkflowRun, RunError> {
+        if let Some(store) = &self.run_store
+            && let Some(checkpoint) = store.load(run_id)?
+        {
+            if checkpoint.run.definition_id() != &def.id {
+                return Err(RunStoreError(format!(
+                    "checkpoint of run {} was taken from workflow {}, not {}",
+                    run_id,
+                    checkpoint.run.definition_id(),
+                    def.id
+                ))
+                .into());
+            }
+            let mut run = WorkflowRun::from_checkpoint(checkpoint, Arc::clone(store));
+            run.tick_state = self.tick_state.clone();
+            if let Some(vars) = vars {
+                run.set_vars(vars);
+            }
+            return Ok(run);
+        }
+        let mut run = self.new_run(def, vars)?;
+        run.id = run_id;
+        Ok(run)
+    }
+
+    /// Resume a run paused by an approval block. `decision` (e.g. `"approve"`) becomes the
+    /// approval block's string output and the rest of the workflow runs from there.
+    ///
+    /// Runs paused by this `Workflow` resume from memory. With a [run store](Workflow::set_run_store)
+    /// the pause is also saved in the run's checkpoint, so a `Workflow` rebuilt the same way (see
+    /// [`run_checkpointed`](Workflow::run_checkpointed)) can resume the token after a restart,
+    /// with the same restore limits. Without a run store, and for cyclic graphs, paused runs live
+    /// only in this process. A token that is unknown, already used, or lost with its process fails
+    /// with [`RunError::UnknownApprovalToken`].
+    pub fn resume_with_input(
+        &self,
+        token: &str,
+        decision: impl Into<String>,
+    ) -> Result<BlockOutput, RunError> {
         crate::observability::init_observability();
-        self.validate()?;
+        let decision = BlockOutput::String {
+            value: decision.into(),
+        };
+        let paused = self.paused.lock().expect("paused runs lock").remove(token);
+        let Some((def, mut run)) = paused else {
+            return self.resume_checkpointed(token, decision);
+        };
+        let result = block_on(runtime::resume_workflow(
+            &def,
+            &mut run,
+            &self.registry,
+            token,
+            decision,
+        ))
+        .map(|output| self.summarize(output));
+        self.park_if_paused(def, run, result)
+    }
+
+    /// Resume `token` from the checkpoint its run saved in the run store when it paused.
+    fn resume_checkpointed(
+        &self,
+        token: &str,
+        decision: BlockOutput,
+    ) -> Result<BlockOutput, RunError> {
+        let unknown = || RunError::UnknownApprovalToken(token.to_string());
+        let run_id = runtime::approval_token_run_id(token).ok_or_else(unknown)?;
+        let Some(store) = &self.run_store else {
+            return Err(unknown());
+        };
+        if store.load(run_id)?.is_none() {
+            return Err(unknown());
+        }
         let def = self.build_definition();
-        let mut run = WorkflowRun::new(&def);
-        let rt = tokio::runtime::Builder::new_current_thread()
-            .enable_all()
-            .build()
-            .expect("tokio runtime");
-        rt.block_on(runtime::run_workflow(
+        self.validate_definition(&def)?;
+        let mut run = self.checkpointed_run(&def, run_id, None)?;
+        runtime::restore_approval(&mut run, token, decision)?;
+        let result = block_on(runtime::run_workflow_until(
             &def,
             &mut run,
             &self.registry,
-            None,
-            None,
+            std::future::pending(),
         ))
+        .map(|output| self.summarize(output));
+        self.park_if_paused(def, run, result)
+    }
+
+    /// `output`, or the tick summary when the workflow has one.
+    fn summarize(&self, output: BlockOutput) -> BlockOutput {
+        self.tick_summary
+            .as_ref()
+            .and_then(|summary| summary(&self.tick_state))
+            .unwrap_or(output)
+    }
+
+    /// Keep a run that paused for approval so it can be resumed by token.
+    fn park_if_paused<T>(
+        &self,
+        def: WorkflowDefinition,
+        run: WorkflowRun,
+        result: Result<T, RunError>,
+    ) -> Result<T, RunError> {
+        if let Err(RunError::PendingApproval { token }) = &result {
+            self.paused
+                .lock()
+                .expect("paused runs lock")
+                .insert(token.clone(), (def, run));
+        }
+        result
+    }
+
+    /// Run (sync) only the blocks carrying any of `tags`, plus the blocks they depend on through
+    /// links or forced input sources. Everything downstream of the selection is pruned, so the
+    /// run returns the output of the last tagged block; the on_error handlers of the blocks that
+    /// run, and the on_cancel handlers, are kept. No matching tag fails with
+    /// [`RunError::NoEntryNode`].
+    pub fn run_only<I, S>(&self, tags: I) -> Result<BlockOutput, RunError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let tags: Vec<S> = tags.into_iter().collect();
+        let options = RunOptions {
+            definition: Some(prune_to(self.build_definition(), &self.tagged_with(&tags))),
+            ..RunOptions::default()
+        };
+        block_on(self.run_with(options, std::future::pending())).map(|report| report.output)
+    }
+
+    /// Run (sync) with some block configs replaced for this run only, e.g. for parameter sweeps.
+    /// Each override is deep-merged into the block's payload: objects merge key by key at every
+    /// depth, and any other value (arrays included) replaces what it overrides. The workflow
+    /// itself is unchanged.
+    pub fn run_with_overrides(
+        &self,
+        overrides: HashMap<BlockId, serde_json::Value>,
+    ) -> Result<BlockOutput, RunError> {
+        let mut def = self.build_definition();
+        for (block, value) in overrides {
+            let node = def
+                .nodes
+                .get_mut(&block.0)
+                .ok_or(WorkflowValidationError::NodeNotFound(block.0))?;
+            let BlockConfig::Custom { payload, .. } = &mut node.config else {
+                return Err(RunError::Block(crate::block::BlockError::Other(format!(
+                    "block {} has no payload to override",
+                    block.0
+                ))));
+            };
+            merge_payload(payload, value);
+        }
+        let options = RunOptions {
+            definition: Some(def),
+            ..RunOptions::default()
+        };
+        block_on(self.run_with(options, std::future::pending())).map(|report| report.output)
+    }
+
+    fn tagged_with<S: AsRef<str>>(&self, tags: &[S]) -> Vec<Uuid> {
+        self.tags
+            .iter()
+            .filter(|(_, block_tags)| {
+                tags.iter()
+                    .any(|tag| block_tags.iter().any(|t| t == tag.as_ref()))
+            })
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Run the workflow (sync) with run-scoped variables. Every block sees `vars` through
+    /// `BlockExecutionContext::vars` (e.g. a correlation id or the run timestamp for templates).
+    pub fn run_with_vars(
+        &self,
+        vars: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<BlockOutput, RunError> {
+        let options = RunOptions {
+            vars: Some(vars),
+            ..RunOptions::default()
+        };
+        block_on(self.run_with(options, std::future::pending())).map(|report| report.output)
+    }
+
+    /// Run the workflow (sync) and return a [`RunReport`] with the sink output and the
+    /// execution mode the runtime selected.
+    pub fn run_detailed(&self) -> Result<RunReport, RunError> {
+        block_on(self.run_with(RunOptions::default(), std::future::pending()))
+    }
+
+    /// Run the workflow (sync) like [`run_detailed`](Workflow::run_detailed); when it fails, the
+    /// [`RunFailure`] also carries the outputs of the on_error and on_cancel handlers that ran,
+    /// e.g. partial results a recovery handler salvaged.
+    pub fn run_recovering(&self) -> Result<RunReport, RunFailure> {
+        block_on(self.run_with_handlers(RunOptions::default(), std::future::pending()))
+    }
+
+    /// Run the workflow (sync) and return the sink output with a [`RunReport`] holding
+    /// per-block timings and the total duration, e.g. to print a table after each run.
+    pub fn run_with_report(&self) -> Result<(BlockOutput, RunReport), RunError> {
+        let report = self.run_detailed()?;
+        Ok((report.output.clone(), report))
+    }
+
+    /// Run the workflow (sync) and, for a Recurring entry, return every tick's sink output as a
+    /// JSON array once the entry stops. Skipped (`no_new_items`) ticks are not collected.
+    pub fn run_collect_ticks(&self) -> Result<BlockOutput, RunError> {
+        let mut def = self.build_definition();
+        def.options.collect_ticks = true;
+        let options = RunOptions {
+            definition: Some(def),
+            ..RunOptions::default()
+        };
+        block_on(self.run_with(options, std::future::pending())).map(|report| report.output)
     }
 
     /// Run the workflow (async). Returns the sink block's output or [`RunError`]. Call with `.await`.
     pub async fn run_async(&self) -> Result<BlockOutput, RunError> {
-        crate::observability::init_observability();
-        self.validate()?;
-        let def = self.build_definition();
-        let mut run = WorkflowRun::new(&def);
-        runtime::run_workflow(&def, &mut run, &self.registry, None, None).await
+        self.run_with(RunOptions::default(), std::future::pending())
+            .await
+            .map(|report| report.output)
+    }
+
+    /// Run the workflow (async) until it finishes or `cancel` resolves. A cancelled run executes
+    /// the [`on_cancel`](Workflow::on_cancel) handlers and fails with [`RunError::Cancelled`].
+    pub async fn run_until_cancelled(
+        &self,
+        cancel: impl std::future::Future<Output = ()>,
+    ) -> Result<BlockOutput, RunError> {
+        self.run_with(RunOptions::default(), cancel)
+            .await
+            .map(|report| report.output)
+    }
+
+    /// Entry block: the first block added, or `None` for an empty workflow.
+    pub fn entry_id(&self) -> Option<BlockId> {
+        self.entry.map(BlockId)
+    }
+
+    /// Block whose output a run returns: the primary sink among blocks reachable from the entry.
+    /// `None` when the workflow is empty or the reachable graph has no sink (a pure cycle).
+    pub fn sink_id(&self) -> Option<BlockId> {
+        runtime::reachable_primary_sink(&self.build_definition()).map(BlockId)
     }
 
     /// Validate workflow graph and block I/O contracts without executing the workflow.
+    /// Stops at the first error; with [`Workflow::set_strict_validation`] this is
+    /// [`Workflow::validate_all`].
     pub fn validate(&self) -> Result<(), WorkflowValidationError> {
-        let def = self.build_definition();
-        for (node_id, ref_keys) in &self.node_input_sources {
+        self.validate_definition(&self.build_definition())
+    }
+
+    /// [`Workflow::validate`] for `def`, this workflow's definition as adjusted for one run.
+    fn validate_definition(&self, def: &WorkflowDefinition) -> Result<(), WorkflowValidationError> {
+        if self.options.strict_validation {
+            return self.validate_definition_all(def);
+        }
+        for (node_id, ref_keys) in self.input_sources_in(def) {
             for ref_key in ref_keys {
                 if !self.ref_index.contains_key(ref_key) {
                     return Err(WorkflowValidationError::MissingInputSource {
@@ -241,7 +836,51 @@ impl Workflow {
                 }
             }
         }
-        runtime::validate_workflow(&def, &self.registry)
+        runtime::validate_workflow(def, &self.registry)
+    }
+
+    /// Validate the whole graph and report every problem at once: each block's linkage, blocks
+    /// unreachable from the entry (error and cancel handlers excepted), and more than one sink.
+    /// Several problems come back as [`WorkflowValidationError::Multiple`].
+    pub fn validate_all(&self) -> Result<(), WorkflowValidationError> {
+        self.validate_definition_all(&self.build_definition())
+    }
+
+    fn validate_definition_all(
+        &self,
+        def: &WorkflowDefinition,
+    ) -> Result<(), WorkflowValidationError> {
+        let mut errors = Vec::new();
+        for (node_id, ref_keys) in self.input_sources_in(def) {
+            for ref_key in ref_keys {
+                if !self.ref_index.contains_key(ref_key) {
+                    errors.push(WorkflowValidationError::MissingInputSource {
+                        block_id: *node_id,
+                        source_ref_key: *ref_key,
+                    });
+                }
+            }
+        }
+        match runtime::validate_workflow_all(def, &self.registry) {
+            Ok(()) => {}
+            Err(WorkflowValidationError::Multiple(rest)) => errors.extend(rest),
+            Err(e) => errors.push(e),
+        }
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            _ => Err(WorkflowValidationError::Multiple(errors)),
+        }
+    }
+
+    /// Forced input source refs of the blocks `def` still contains.
+    fn input_sources_in<'a>(
+        &'a self,
+        def: &'a WorkflowDefinition,
+    ) -> impl Iterator<Item = (&'a Uuid, &'a Vec<usize>)> {
+        self.node_input_sources
+            .iter()
+            .filter(|(id, _)| def.nodes.contains_key(id))
     }
 
     /// Consume this workflow and return a [`WorkflowDefinition`] suitable for use as a child workflow
@@ -251,6 +890,7 @@ impl Workflow {
     pub fn into_definition(self) -> WorkflowDefinition {
         let ref_index = self.ref_index;
         let node_input_sources = self.node_input_sources;
+        let block_timeouts = self.block_timeouts;
         let nodes: HashMap<Uuid, NodeDef> = self
             .nodes
             .into_iter()
@@ -265,16 +905,21 @@ impl Workflow {
                     })
                     .unwrap_or_else(|| Box::new([]));
                 let config = with_resolved_input_from(config, input_from);
-                (id, NodeDef { config })
+                let timeout_ms = block_timeouts.get(&id).copied();
+                (id, NodeDef::new(config).with_timeout_ms(timeout_ms))
             })
             .collect();
-        WorkflowDefinition {
+        with_edge_policy(WorkflowDefinition {
             id: self.def_id,
             nodes,
             edges: self.edges,
             error_edges: self.error_edges,
+            edge_slots: self.edge_slots,
+            edge_labels: self.edge_labels,
+            cancel_handlers: self.cancel_handlers,
             entry: self.entry,
-        }
+            options: self.options,
+        })
     }
 
     fn build_definition(&self) -> WorkflowDefinition {
@@ -294,22 +939,113 @@ impl Workflow {
                     .unwrap_or_else(|| Box::new([]));
                 (
                     *id,
-                    NodeDef {
-                        config: with_resolved_input_from(config.clone(), input_from),
-                    },
+                    NodeDef::new(with_resolved_input_from(config.clone(), input_from))
+                        .with_timeout_ms(self.block_timeouts.get(id).copied()),
                 )
             })
             .collect();
-        WorkflowDefinition {
+        with_edge_policy(WorkflowDefinition {
             id: self.def_id,
             nodes,
             edges: self.edges.clone(),
             error_edges: self.error_edges.clone(),
+            edge_slots: self.edge_slots.clone(),
+            edge_labels: self.edge_labels.clone(),
+            cancel_handlers: self.cancel_handlers.clone(),
             entry: self.entry,
+            options: self.options.clone(),
+        })
+    }
+}
+
+/// What one run of [`Workflow::run_with`] uses besides the workflow itself.
+#[derive(Default)]
+struct RunOptions {
+    /// Run this definition instead of the workflow's own, e.g. one pruned or overridden.
+    definition: Option<WorkflowDefinition>,
+    /// Resume this run from the run store's checkpoint, or start fresh under this id.
+    run_id: Option<Uuid>,
+    vars: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+/// Drive `future` on a fresh current-thread runtime, for the sync run methods.
+fn block_on<T>(future: impl std::future::Future<Output = T>) -> T {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("tokio runtime")
+        .block_on(future)
+}
+
+/// `def` with repeated edges dropped under [`DuplicateEdgePolicy::Dedupe`], so a target never
+/// sees the same source twice in its `Multi`; kept for validation to reject otherwise.
+fn with_edge_policy(mut def: WorkflowDefinition) -> WorkflowDefinition {
+    if def.options.on_duplicate_edge == DuplicateEdgePolicy::Dedupe {
+        def.dedupe_edges();
+    }
+    def
+}
+
+/// Deep-merge `value` into `payload`: objects merge recursively, anything else replaces.
+fn merge_payload(payload: &mut serde_json::Value, value: serde_json::Value) {
+    match (payload, value) {
+        (serde_json::Value::Object(current), serde_json::Value::Object(patch)) => {
+            for (key, value) in patch {
+                match current.get_mut(&key) {
+                    Some(existing) => merge_payload(existing, value),
+                    None => {
+                        current.insert(key, value);
+                    }
+                }
+            }
         }
+        (payload, value) => *payload = value,
     }
 }
 
+/// Keep `selected` and every block it depends on (predecessors and forced input sources), plus
+/// the on_error handlers of those blocks and the on_cancel handlers; drop all other nodes and
+/// the edges touching them. Handlers kept only as handlers get no normal edges.
+fn prune_to(mut def: WorkflowDefinition, selected: &[Uuid]) -> WorkflowDefinition {
+    let mut keep: HashSet<Uuid> = HashSet::new();
+    let mut stack: Vec<Uuid> = selected.to_vec();
+    while let Some(id) = stack.pop() {
+        if !keep.insert(id) {
+            continue;
+        }
+        stack.extend(
+            def.edges
+                .iter()
+                .filter(|(_, to)| *to == id)
+                .map(|(from, _)| *from),
+        );
+        if let Some(NodeDef {
+            config: BlockConfig::Custom { input_from, .. },
+            ..
+        }) = def.nodes.get(&id)
+        {
+            stack.extend(input_from.iter().copied());
+        }
+    }
+    def.error_edges.retain(|(from, _)| keep.contains(from));
+    let handlers: HashSet<Uuid> = def
+        .error_edges
+        .iter()
+        .map(|(_, handler)| *handler)
+        .chain(def.cancel_handlers.iter().copied())
+        .collect();
+    def.nodes
+        .retain(|id, _| keep.contains(id) || handlers.contains(id));
+    def.edges
+        .retain(|(from, to)| keep.contains(from) && keep.contains(to));
+    def.edge_slots
+        .retain(|(from, to, _)| keep.contains(from) && keep.contains(to));
+    def.edge_labels
+        .retain(|(from, to, _)| keep.contains(from) && keep.contains(to));
+    def.entry = def.entry.filter(|id| keep.contains(id));
+    def
+}
+
 fn with_resolved_input_from(config: BlockConfig, input_from: Box<[Uuid]>) -> BlockConfig {
     match config {
         BlockConfig::Custom {
@@ -350,6 +1086,7 @@ mod tests {
         BlockError, BlockExecutionContext, BlockExecutor, BlockInput, BlockOutput, InputContract,
         OutputContract, OutputMode, ValidateContext, ValueKind, ValueKindSet,
     };
+    use crate::core::FileRunStore;
     use serde::Serialize;
     use serde_json::json;
     use std::path::PathBuf;
@@ -554,32 +1291,355 @@ mod tests {
         }
     }
 
-    struct TestPassthroughBlock;
-    impl BlockExecutor for TestPassthroughBlock {
-        fn execute(
-            &self,
-            ctx: BlockExecutionContext,
-        ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
-            let input = ctx.prev;
-            let output = match input {
-                BlockInput::Empty => BlockOutput::empty(),
-                BlockInput::String(s) => BlockOutput::String { value: s },
-                BlockInput::Text(s) => BlockOutput::Text { value: s },
-                BlockInput::Json(v) => BlockOutput::Json { value: v },
-                BlockInput::List { items } => BlockOutput::List { items },
-                BlockInput::Multi { outputs } => BlockOutput::Json {
-                    value: serde_json::to_value(&outputs).unwrap_or(serde_json::Value::Null),
-                },
-                BlockInput::Error { message } => {
-                    return Err(crate::block::BlockError::Other(message));
+    fn json_to_string_registry() -> BlockRegistry {
+        struct JsonProducer;
+        impl BlockExecutor for JsonProducer {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::Json {
+                        value: json!({"ok": true}),
+                    },
+                ))
+            }
+
+            fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+                OutputContract::from_kind(ValueKind::Json, OutputMode::Once)
+            }
+        }
+
+        struct StringConsumer;
+        impl BlockExecutor for StringConsumer {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Ok(crate::block::BlockExecutionResult::Once(BlockOutput::Empty))
+            }
+
+            fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+                match &ctx.prev {
+                    InputContract::One(kinds)
+                        if kinds.intersects(ValueKindSet::singleton(ValueKind::String)) =>
+                    {
+                        Ok(())
+                    }
+                    _ => Err(BlockError::Other("string input required".into())),
                 }
-            };
-            Ok(crate::block::BlockExecutionResult::Once(output))
+            }
         }
+
+        let mut registry = BlockRegistry::new();
+        registry.register_custom("producer", |_, _input_from| Ok(Box::new(JsonProducer)));
+        registry.register_custom("consumer", |_, _input_from| Ok(Box::new(StringConsumer)));
+        registry
     }
 
-    #[test]
-    fn workflow_add_file_read_run_returns_content() {
+    fn custom(type_id: &str) -> BlockConfig {
+        BlockConfig::Custom {
+            type_id: type_id.to_string(),
+            payload: json!({}),
+            input_from: Box::new([]),
+        }
+    }
+
+    #[test]
+    fn validate_all_collects_every_linkage_error_and_unreachable_block() {
+        let mut w = Workflow::with_registry(json_to_string_registry());
+        let producer = w.add(custom("producer"));
+        let first = w.add(custom("consumer"));
+        let second = w.add(custom("consumer"));
+        let orphan = w.add(custom("producer"));
+        w.link(producer, first);
+        w.link(producer, second);
+
+        let WorkflowValidationError::Multiple(errors) = w.validate_all().unwrap_err() else {
+            panic!("expected every problem to be reported");
+        };
+        let mut mismatched: Vec<Uuid> = errors
+            .iter()
+            .filter_map(|e| match e {
+                WorkflowValidationError::BlockLinkage { block_id, .. } => Some(*block_id),
+                _ => None,
+            })
+            .collect();
+        mismatched.sort();
+        let mut expected = vec![first.0, second.0];
+        expected.sort();
+        assert_eq!(mismatched, expected);
+        assert!(errors.iter().any(|e| matches!(
+            e,
+            WorkflowValidationError::UnreachableNodes(ids) if ids == &vec![orphan.0]
+        )));
+        assert!(
+            errors.iter().any(
+                |e| matches!(e, WorkflowValidationError::MultipleSinks(ids) if ids.len() == 2)
+            )
+        );
+        // The default check still stops at the first mismatch.
+        assert!(matches!(
+            w.validate().unwrap_err(),
+            WorkflowValidationError::BlockLinkage { .. }
+        ));
+    }
+
+    #[test]
+    fn strict_validation_fails_run_on_unreachable_block() {
+        let mut w = Workflow::with_registry(json_to_string_registry());
+        let entry = w.add(custom("producer"));
+        let next = w.add(custom("producer"));
+        let orphan = w.add(custom("producer"));
+        w.link(entry, next);
+        w.run().expect("unreachable blocks are ignored by default");
+
+        w.set_strict_validation(true);
+        match w.run().unwrap_err() {
+            RunError::WorkflowValidation(WorkflowValidationError::UnreachableNodes(ids)) => {
+                assert_eq!(ids, vec![orphan.0]);
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[test]
+    fn validate_reports_cycle_path() {
+        let mut w = Workflow::with_registry(json_to_string_registry());
+        let a = w.add(custom("producer"));
+        let b = w.add(custom("producer"));
+        let c = w.add(custom("producer"));
+        w.link(a, b);
+        w.link(b, c);
+        w.link(c, a);
+
+        let err = w.validate().unwrap_err();
+        let WorkflowValidationError::CyclicGraph(cycle) = &err else {
+            panic!("unexpected validation error: {err}");
+        };
+        assert_eq!(cycle.path, vec![a.0, b.0, c.0, a.0]);
+        assert!(
+            err.to_string()
+                .contains(&format!("cycle: {} -> {} -> {} -> {}", a.0, b.0, c.0, a.0))
+        );
+    }
+
+    #[test]
+    fn link_as_delivers_named_inputs() {
+        struct Named(&'static str);
+        impl BlockExecutor for Named {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::String {
+                        value: self.0.to_string(),
+                    },
+                ))
+            }
+        }
+
+        /// Combine-like: one JSON key per input label.
+        struct ByLabel;
+        impl BlockExecutor for ByLabel {
+            fn execute(
+                &self,
+                ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                let BlockInput::MultiNamed { outputs } = ctx.prev else {
+                    return Err(BlockError::Other(format!("not named: {:?}", ctx.prev)));
+                };
+                let value = outputs
+                    .into_iter()
+                    .map(|(label, output)| (label, json!(Option::<String>::from(output))))
+                    .collect();
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::Json {
+                        value: serde_json::Value::Object(value),
+                    },
+                ))
+            }
+        }
+
+        let mut registry = BlockRegistry::new();
+        registry.register_custom("start", |_, _| Ok(Box::new(Named("start"))));
+        registry.register_custom("prices", |_, _| Ok(Box::new(Named("12.50"))));
+        registry.register_custom("stock", |_, _| Ok(Box::new(Named("in stock"))));
+        registry.register_custom("by_label", |_, _| Ok(Box::new(ByLabel)));
+
+        let mut w = Workflow::with_registry(registry);
+        let start = w.add(custom("start"));
+        let prices = w.add(custom("prices"));
+        let stock = w.add(custom("stock"));
+        let by_label = w.add(custom("by_label"));
+        w.link(start, prices);
+        w.link(start, stock);
+        w.link_as(stock, by_label, "availability");
+        w.link_as(prices, by_label, "price");
+
+        assert_eq!(
+            w.run().unwrap(),
+            BlockOutput::Json {
+                value: json!({"price": "12.50", "availability": "in stock"})
+            }
+        );
+    }
+
+    #[test]
+    fn link_as_rejects_mixed_and_repeated_labels() {
+        let transform = || BlockConfig::Custom {
+            type_id: "custom_transform".to_string(),
+            payload: json!({}),
+            input_from: Box::new([]),
+        };
+        let mut w = Workflow::with_registry(passthrough_registry());
+        let start = w.add(transform());
+        let left = w.add(transform());
+        let right = w.add(transform());
+        let join = w.add(transform());
+        w.link(start, left);
+        w.link(start, right);
+        w.link_as(left, join, "left");
+        w.link(right, join);
+        assert!(matches!(
+            w.validate(),
+            Err(WorkflowValidationError::MixedEdgeLabels { block_id }) if block_id == join.0
+        ));
+
+        let mut w = Workflow::with_registry(passthrough_registry());
+        let start = w.add(transform());
+        let left = w.add(transform());
+        let right = w.add(transform());
+        let join = w.add(transform());
+        w.link(start, left);
+        w.link(start, right);
+        w.link_as(left, join, "side");
+        w.link_as(right, join, String::from("side"));
+        let err = w.validate().unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                WorkflowValidationError::DuplicateEdgeLabel { block_id, label }
+                    if *block_id == join.0 && label == "side"
+            ),
+            "got: {err}"
+        );
+    }
+
+    #[test]
+    fn child_workflow_output_contract_follows_child_sink() {
+        struct JsonProducer;
+        impl BlockExecutor for JsonProducer {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::Json { value: json!([]) },
+                ))
+            }
+
+            fn infer_output_contract(&self, _ctx: &ValidateContext<'_>) -> OutputContract {
+                OutputContract::from_kind(ValueKind::Json, OutputMode::Once)
+            }
+        }
+
+        struct JsonOnlyConsumer;
+        impl BlockExecutor for JsonOnlyConsumer {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Ok(crate::block::BlockExecutionResult::Once(BlockOutput::Empty))
+            }
+
+            fn validate_linkage(&self, ctx: &ValidateContext<'_>) -> Result<(), BlockError> {
+                match &ctx.prev {
+                    InputContract::One(kinds)
+                        if *kinds == ValueKindSet::singleton(ValueKind::Json) =>
+                    {
+                        Ok(())
+                    }
+                    other => Err(BlockError::Other(format!(
+                        "json input required, got {other:?}"
+                    ))),
+                }
+            }
+        }
+
+        let registry = || {
+            let mut registry = BlockRegistry::new();
+            registry.register_custom("json_producer", |_, _input_from| Ok(Box::new(JsonProducer)));
+            registry.register_custom("json_only", |_, _input_from| Ok(Box::new(JsonOnlyConsumer)));
+            registry.register_custom("passthrough", |_, _input_from| {
+                Ok(Box::new(TestPassthroughBlock))
+            });
+            registry
+        };
+        let custom = |type_id: &str| BlockConfig::Custom {
+            type_id: type_id.to_string(),
+            payload: json!({}),
+            input_from: Box::new([]),
+        };
+        let child_with_sink = |sink_type: &str| {
+            let entry = Uuid::new_v4();
+            let sink = Uuid::new_v4();
+            WorkflowDefinition::builder()
+                .add_node(entry, custom("passthrough"))
+                .add_node(sink, custom(sink_type))
+                .set_entry(entry)
+                .add_edge(entry, sink)
+                .build()
+        };
+
+        let mut w = Workflow::with_registry(registry());
+        let child = w.add_child_workflow(child_with_sink("json_producer"));
+        let consumer = w.add(custom("json_only"));
+        w.link(child, consumer);
+        w.validate().expect("child sink contract is Json");
+
+        let mut w = Workflow::with_registry(registry());
+        let child = w.add_child_workflow(child_with_sink("passthrough"));
+        let consumer = w.add(custom("json_only"));
+        w.link(child, consumer);
+        match w.validate().expect_err("untyped child sink is not Json") {
+            WorkflowValidationError::BlockLinkage { block_id, .. } => {
+                assert_eq!(block_id, consumer.0);
+            }
+            other => panic!("unexpected validation error: {other}"),
+        }
+    }
+
+    struct TestPassthroughBlock;
+    impl BlockExecutor for TestPassthroughBlock {
+        fn execute(
+            &self,
+            ctx: BlockExecutionContext,
+        ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+            let input = ctx.prev;
+            let output = match input {
+                BlockInput::Empty => BlockOutput::empty(),
+                BlockInput::String(s) => BlockOutput::String { value: s },
+                BlockInput::Text(s) => BlockOutput::Text { value: s },
+                BlockInput::Json(v) => BlockOutput::Json { value: v },
+                BlockInput::List { items } => BlockOutput::List { items },
+                BlockInput::Multi { outputs } => BlockOutput::Json {
+                    value: serde_json::to_value(&outputs).unwrap_or(serde_json::Value::Null),
+                },
+                BlockInput::MultiNamed { outputs } => BlockOutput::Json {
+                    value: serde_json::to_value(&outputs).unwrap_or(serde_json::Value::Null),
+                },
+                BlockInput::Error { message } => {
+                    return Err(crate::block::BlockError::Other(message));
+                }
+            };
+            Ok(crate::block::BlockExecutionResult::Once(output))
+        }
+    }
+
+    #[test]
+    fn workflow_add_file_read_run_returns_content() {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("sample.txt");
         std::fs::write(&path, "hello from workflow test").unwrap();
@@ -636,6 +1696,145 @@ mod tests {
         assert_eq!(s, Some("hello from chain".to_string()));
     }
 
+    #[test]
+    fn entry_and_sink_ids_follow_reachable_graph() {
+        let transform = || BlockConfig::Custom {
+            type_id: "custom_transform".to_string(),
+            payload: json!({}),
+            input_from: Box::new([]),
+        };
+        let mut w = Workflow::with_registry(passthrough_registry());
+        assert_eq!(w.entry_id(), None);
+        assert_eq!(w.sink_id(), None);
+
+        let entry = w.add(transform());
+        let middle = w.add(transform());
+        let sink = w.add(transform());
+        let unreachable = w.add(transform());
+        w.link(entry, middle);
+        w.link(middle, sink);
+        w.link(unreachable, sink);
+        assert_eq!(w.entry_id(), Some(entry));
+        assert_eq!(w.sink_id(), Some(sink));
+
+        // A sink only reachable from outside the entry's graph is not the run's output.
+        let stray_sink = w.add(transform());
+        w.link(unreachable, stray_sink);
+        assert_eq!(w.sink_id(), Some(sink));
+
+        // A reachable block added after the old sink becomes the sink.
+        let last = w.add(transform());
+        w.link(sink, last);
+        assert_eq!(w.sink_id(), Some(last));
+    }
+
+    #[test]
+    fn run_only_executes_tagged_blocks_and_their_dependencies() {
+        struct RecordBlock {
+            name: String,
+            log: Arc<Mutex<Vec<String>>>,
+        }
+        impl BlockExecutor for RecordBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, BlockError> {
+                self.log.lock().unwrap().push(self.name.clone());
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::String {
+                        value: self.name.clone(),
+                    },
+                ))
+            }
+        }
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = BlockRegistry::new();
+        let factory_log = Arc::clone(&log);
+        registry.register_custom("record", move |payload, _input_from| {
+            Ok(Box::new(RecordBlock {
+                name: payload["name"].as_str().unwrap_or_default().to_string(),
+                log: Arc::clone(&factory_log),
+            }))
+        });
+        let mut w = Workflow::with_registry(registry);
+        let fetch = w.add_custom("record", json!({"name": "fetch"})).unwrap();
+        let render = w.add_custom("record", json!({"name": "render"})).unwrap();
+        let email = w.add_custom("record", json!({"name": "email"})).unwrap();
+        let archive = w.add_custom("record", json!({"name": "archive"})).unwrap();
+        w.link(fetch, render);
+        w.link(render, email);
+        w.link(fetch, archive);
+        w.tag(render, "email");
+        w.tag(email, "email");
+        w.tag(archive, "storage");
+
+        let output = w.run_only(["email"]).unwrap();
+        assert_eq!(
+            output,
+            BlockOutput::String {
+                value: "email".into()
+            }
+        );
+        assert_eq!(*log.lock().unwrap(), vec!["fetch", "render", "email"]);
+
+        log.lock().unwrap().clear();
+        assert!(matches!(
+            w.run_only(["missing"]),
+            Err(RunError::NoEntryNode)
+        ));
+        assert!(log.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn run_only_keeps_error_handlers_of_selected_blocks() {
+        struct AlwaysFailBlock;
+        impl BlockExecutor for AlwaysFailBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, BlockError> {
+                Err(BlockError::Other("publish failed".into()))
+            }
+        }
+        struct CaptureError(Arc<Mutex<Option<String>>>);
+        impl BlockExecutor for CaptureError {
+            fn execute(
+                &self,
+                ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, BlockError> {
+                if let BlockInput::Error { message } = ctx.prev {
+                    *self.0.lock().unwrap() = Some(message);
+                }
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::empty(),
+                ))
+            }
+        }
+        let handled = Arc::new(Mutex::new(None));
+        let mut registry = passthrough_registry();
+        registry.register_custom("fail", |_, _input_from| Ok(Box::new(AlwaysFailBlock)));
+        let handler_seen = Arc::clone(&handled);
+        registry.register_custom("capture_error", move |_, _input_from| {
+            Ok(Box::new(CaptureError(Arc::clone(&handler_seen))))
+        });
+        let mut w = Workflow::with_registry(registry);
+        let entry = w.add_custom("custom_transform", json!({})).unwrap();
+        let publish = w.add_custom("fail", json!({})).unwrap();
+        let handler = w.add_custom("capture_error", json!({})).unwrap();
+        let archive = w.add_custom("custom_transform", json!({})).unwrap();
+        w.link(entry, publish);
+        w.link(entry, archive);
+        w.on_error(publish, handler);
+        w.tag(publish, "publish");
+
+        assert!(w.run_only(["publish"]).is_err());
+        let message = handled.lock().unwrap().clone();
+        assert!(
+            message.is_some_and(|m| m.contains("publish failed")),
+            "on_error handler of the selected block ran"
+        );
+    }
+
     #[test]
     fn workflow_with_registry_add_custom_runs() {
         #[derive(Serialize)]
@@ -658,8 +1857,9 @@ mod tests {
                     BlockInput::Empty => String::new(),
                     BlockInput::Json(v) => v.to_string().to_uppercase(),
                     BlockInput::List { items } => items.join(" ").to_uppercase(),
-                    BlockInput::Multi { outputs } => outputs
-                        .iter()
+                    BlockInput::Multi { .. } | BlockInput::MultiNamed { .. } => input
+                        .multi_outputs()
+                        .into_iter()
                         .filter_map(|o| Option::<String>::from(o.clone()))
                         .collect::<Vec<_>>()
                         .join(" ")
@@ -819,6 +2019,91 @@ mod tests {
         assert_eq!(s.unwrap(), "passthrough");
     }
 
+    #[test]
+    fn nested_child_workflows_share_one_registry() {
+        use crate::block::ChildWorkflowConfig;
+        use crate::core::WorkflowDefinition;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use uuid::Uuid;
+
+        struct ProbeBlock {
+            probe: Arc<()>,
+            seen: Arc<AtomicUsize>,
+        }
+        impl BlockExecutor for ProbeBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                self.seen
+                    .store(Arc::strong_count(&self.probe), Ordering::SeqCst);
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::String {
+                        value: "leaf".into(),
+                    },
+                ))
+            }
+        }
+
+        let probe = Arc::new(());
+        let builds = Arc::new(AtomicUsize::new(0));
+        let seen = Arc::new(AtomicUsize::new(0));
+        let mut registry = BlockRegistry::new();
+        let (factory_probe, factory_builds, factory_seen) =
+            (Arc::clone(&probe), Arc::clone(&builds), Arc::clone(&seen));
+        registry.register_custom("probe", move |_, _input_from| {
+            factory_builds.fetch_add(1, Ordering::SeqCst);
+            Ok(Box::new(ProbeBlock {
+                probe: Arc::clone(&factory_probe),
+                seen: Arc::clone(&factory_seen),
+            }))
+        });
+        let registry = Arc::new(registry);
+
+        let leaf = Uuid::new_v4();
+        let mut definition = WorkflowDefinition::builder()
+            .add_node(
+                leaf,
+                BlockConfig::Custom {
+                    type_id: "probe".to_string(),
+                    payload: json!(null),
+                    input_from: Box::new([]),
+                },
+            )
+            .set_entry(leaf)
+            .build();
+        for _ in 0..6 {
+            let node = Uuid::new_v4();
+            definition = WorkflowDefinition::builder()
+                .add_node(
+                    node,
+                    BlockConfig::ChildWorkflow(ChildWorkflowConfig::new(definition)),
+                )
+                .set_entry(node)
+                .build();
+        }
+
+        let mut first = Workflow::with_shared_registry(Arc::clone(&registry));
+        first.add_child_workflow(definition.clone());
+        let mut second = Workflow::with_shared_registry(Arc::clone(&registry));
+        second.add_child_workflow(definition);
+        assert_eq!(Arc::strong_count(&registry), 3);
+
+        for w in [&first, &second] {
+            let out: Option<String> = w.run().unwrap().into();
+            assert_eq!(out.as_deref(), Some("leaf"));
+            // Test + factory closure + the live block: no per-level copy of the factory state.
+            assert_eq!(seen.load(Ordering::SeqCst), 3);
+        }
+        assert_eq!(
+            builds.load(Ordering::SeqCst),
+            4,
+            "validation and execution build the leaf once each, at any depth"
+        );
+        assert_eq!(Arc::strong_count(&registry), 3);
+        assert_eq!(Arc::strong_count(&probe), 2);
+    }
+
     #[test]
     fn into_definition_produces_child_workflow_that_runs() {
         let dir = tempfile::tempdir().unwrap();
@@ -849,20 +2134,368 @@ mod tests {
     }
 
     #[test]
-    fn link_on_error_runs_handler_and_run_still_fails() {
-        struct AlwaysFailBlock;
-        impl BlockExecutor for AlwaysFailBlock {
+    fn run_with_overrides_changes_block_payload_per_run() {
+        struct FetchBlock(String);
+        impl BlockExecutor for FetchBlock {
             fn execute(
                 &self,
                 _ctx: BlockExecutionContext,
-            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
-                Err(crate::block::BlockError::Other("boom".into()))
+            ) -> Result<crate::block::BlockExecutionResult, BlockError> {
+                if self.0.contains("unreachable") {
+                    return Err(BlockError::Other(format!("connect failed: {}", self.0)));
+                }
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::String {
+                        value: format!("fetched {}", self.0),
+                    },
+                ))
             }
         }
-
-        struct ErrorToFileBlock {
-            path: String,
-        }
+        let mut registry = passthrough_registry();
+        registry.register_custom("fetch", |payload, _input_from| {
+            Ok(Box::new(FetchBlock(
+                payload["url"].as_str().unwrap_or_default().to_string(),
+            )))
+        });
+        let mut w = Workflow::with_registry(registry);
+        let entry = w.add_custom("custom_transform", json!({})).unwrap();
+        let fetch = w
+            .add_custom(
+                "fetch",
+                json!({ "url": "https://a.example", "method": "GET" }),
+            )
+            .unwrap();
+        w.link(entry, fetch);
+
+        let output = w
+            .run_with_overrides(HashMap::from([(
+                fetch,
+                json!({ "url": "https://b.example" }),
+            )]))
+            .unwrap();
+        assert_eq!(
+            output,
+            BlockOutput::String {
+                value: "fetched https://b.example".into()
+            }
+        );
+        let err = w
+            .run_with_overrides(HashMap::from([(
+                fetch,
+                json!({ "url": "https://unreachable.example" }),
+            )]))
+            .unwrap_err();
+        assert!(err.to_string().contains("connect failed"), "got: {err}");
+        assert_eq!(
+            w.run().unwrap(),
+            BlockOutput::String {
+                value: "fetched https://a.example".into()
+            }
+        );
+    }
+
+    #[test]
+    fn merge_payload_merges_nested_objects_and_replaces_other_values() {
+        let mut payload = json!({
+            "url": "https://a.example",
+            "headers": { "accept": "text/plain", "x-trace": "1" },
+            "tags": ["a", "b"]
+        });
+        merge_payload(
+            &mut payload,
+            json!({ "headers": { "accept": "application/json" }, "tags": ["c"] }),
+        );
+        assert_eq!(
+            payload,
+            json!({
+                "url": "https://a.example",
+                "headers": { "accept": "application/json", "x-trace": "1" },
+                "tags": ["c"]
+            })
+        );
+    }
+
+    #[test]
+    fn run_with_report_has_one_entry_per_executed_block() {
+        let mut w = Workflow::with_registry(passthrough_registry());
+        let entry = w.add_custom("custom_transform", json!({})).unwrap();
+        let left = w.add_custom("custom_transform", json!({})).unwrap();
+        let right = w.add_custom("custom_transform", json!({})).unwrap();
+        let sink = w.add_custom("custom_transform", json!({})).unwrap();
+        w.link(entry, left);
+        w.link(entry, right);
+        w.link(left, sink);
+        w.link(right, sink);
+
+        let (output, report) = w.run_with_report().unwrap();
+        assert_eq!(output, report.output);
+        let mut ids: Vec<Uuid> = report.blocks.iter().map(|b| b.block_id).collect();
+        ids.sort();
+        let mut expected = vec![entry.0, left.0, right.0, sink.0];
+        expected.sort();
+        assert_eq!(ids, expected);
+        assert_eq!(report.blocks[0].block_id, entry.0);
+        assert_eq!(report.blocks[3].block_id, sink.0);
+        for block in &report.blocks {
+            assert_eq!(block.block_type, "custom_transform");
+            assert_eq!(block.attempts, 1);
+            assert_eq!(block.status, crate::core::BlockStatus::Succeeded);
+            assert!(block.duration_ms <= report.duration_ms);
+        }
+    }
+
+    #[test]
+    fn timed_out_block_is_reported_once_as_timed_out() {
+        struct SleepBlock;
+        impl BlockExecutor for SleepBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, BlockError> {
+                std::thread::sleep(std::time::Duration::from_millis(100));
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::empty(),
+                ))
+            }
+        }
+        let mut registry = passthrough_registry();
+        registry.register_custom("sleep", |_, _input_from| Ok(Box::new(SleepBlock)));
+        let mut w = Workflow::with_registry(registry);
+        let entry = w.add_custom("custom_transform", json!({})).unwrap();
+        let slow = w.add_custom("sleep", json!({})).unwrap();
+        w.link(entry, slow);
+        w.set_block_timeout(slow, std::time::Duration::from_millis(10));
+
+        let def = w.build_definition();
+        let mut run = WorkflowRun::new(&def);
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let result = rt.block_on(runtime::run_workflow(
+            &def,
+            &mut run,
+            &w.registry,
+            None,
+            None,
+        ));
+        assert!(result.unwrap_err().to_string().contains("block.timeout"));
+        // Let the abandoned block return; its late success must not be reported.
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        let slow_reports: Vec<_> = run
+            .block_reports()
+            .into_iter()
+            .filter(|b| b.block_id == slow.0)
+            .collect();
+        assert_eq!(slow_reports.len(), 1);
+        assert_eq!(slow_reports[0].status, crate::core::BlockStatus::TimedOut);
+    }
+
+    #[test]
+    fn run_succeeded_summary_carries_sink_output_kind_and_units() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("digest.md");
+        std::fs::write(&path, "weekly digest").unwrap();
+        let mut w = Workflow::with_registry(passthrough_registry());
+        let read = w
+            .add_custom("file_read", json!({"path": path.to_str().unwrap()}))
+            .unwrap();
+        let sink = w.add_custom("custom_transform", json!({})).unwrap();
+        w.link(read, sink);
+
+        let report = w.run_detailed().unwrap();
+        assert_eq!(
+            report.summary,
+            Some(RunSummary {
+                sink_block_type: "custom_transform".into(),
+                output_kind: "string".into(),
+                output_units: "weekly digest".len() as u64,
+            })
+        );
+    }
+
+    #[test]
+    fn max_parallelism_caps_concurrent_blocks_in_a_level() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Default)]
+        struct Gauge {
+            running: AtomicUsize,
+            peak: AtomicUsize,
+            done: AtomicUsize,
+        }
+        struct CountingBlock(Arc<Gauge>);
+        impl BlockExecutor for CountingBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, BlockError> {
+                let running = self.0.running.fetch_add(1, Ordering::SeqCst) + 1;
+                self.0.peak.fetch_max(running, Ordering::SeqCst);
+                std::thread::sleep(std::time::Duration::from_millis(20));
+                self.0.running.fetch_sub(1, Ordering::SeqCst);
+                self.0.done.fetch_add(1, Ordering::SeqCst);
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::empty(),
+                ))
+            }
+        }
+
+        let gauge = Arc::new(Gauge::default());
+        let mut registry = passthrough_registry();
+        let factory_gauge = Arc::clone(&gauge);
+        registry.register_custom("counting", move |_, _input_from| {
+            Ok(Box::new(CountingBlock(Arc::clone(&factory_gauge))))
+        });
+        let mut w = Workflow::with_registry(registry);
+        let source = w.add_custom("custom_transform", json!({})).unwrap();
+        let sink = w.add_custom("custom_transform", json!({})).unwrap();
+        for _ in 0..6 {
+            let branch = w.add_custom("counting", json!({})).unwrap();
+            w.link(source, branch);
+            w.link(branch, sink);
+        }
+        w.set_max_parallelism(2);
+        w.run().unwrap();
+        assert_eq!(gauge.done.load(Ordering::SeqCst), 6);
+        let peak = gauge.peak.load(Ordering::SeqCst);
+        assert!((1..=2).contains(&peak), "peak concurrency {peak}");
+    }
+
+    #[test]
+    fn collect_all_reports_every_failed_sibling() {
+        struct FailWith(String);
+        impl BlockExecutor for FailWith {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Err(crate::block::BlockError::Other(self.0.clone()))
+            }
+        }
+
+        let build = |policy: LevelFailure| {
+            let mut registry = passthrough_registry();
+            registry.register_custom("fail_with", |payload, _input_from| {
+                Ok(Box::new(FailWith(
+                    payload.as_str().unwrap_or_default().to_string(),
+                )))
+            });
+            let mut w = Workflow::with_registry(registry);
+            let source = w
+                .add_custom("custom_transform", serde_json::json!({}))
+                .expect("add source");
+            for message in ["disk full", "quota exceeded"] {
+                let branch = w
+                    .add_custom("fail_with", serde_json::json!(message))
+                    .expect("add failing branch");
+                w.link(source, branch);
+            }
+            w.set_level_failure(policy);
+            w
+        };
+
+        let err = build(LevelFailure::FailFast).run().unwrap_err();
+        assert!(matches!(err, RunError::Block(_)), "got: {err}");
+
+        let err = build(LevelFailure::CollectAll).run().unwrap_err();
+        let RunError::LevelFailed { failures } = &err else {
+            panic!("expected LevelFailed, got: {err}");
+        };
+        assert_eq!(failures.len(), 2);
+        let message = err.to_string();
+        assert!(message.starts_with("2 blocks failed"), "got: {message}");
+        assert!(message.contains("disk full"), "got: {message}");
+        assert!(message.contains("quota exceeded"), "got: {message}");
+    }
+
+    #[test]
+    fn block_timeout_fails_block_and_routes_to_on_error() {
+        struct SleepBlock;
+        impl BlockExecutor for SleepBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, BlockError> {
+                std::thread::sleep(std::time::Duration::from_millis(300));
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::empty(),
+                ))
+            }
+        }
+        struct CaptureError(Arc<Mutex<Option<String>>>);
+        impl BlockExecutor for CaptureError {
+            fn execute(
+                &self,
+                ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, BlockError> {
+                if let BlockInput::Error { message } = ctx.prev {
+                    *self.0.lock().unwrap() = Some(message);
+                }
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::empty(),
+                ))
+            }
+        }
+
+        let captured = Arc::new(Mutex::new(None));
+        let mut registry = BlockRegistry::new();
+        registry.register_custom("sleep", |_, _input_from| Ok(Box::new(SleepBlock)));
+        let handler_captured = Arc::clone(&captured);
+        registry.register_custom("capture_error", move |_, _input_from| {
+            Ok(Box::new(CaptureError(Arc::clone(&handler_captured))))
+        });
+        registry.register_custom("custom_transform", |_, _input_from| {
+            Ok(Box::new(TestPassthroughBlock))
+        });
+        let registry = Arc::new(registry);
+        let mut w = Workflow::with_shared_registry(Arc::clone(&registry));
+        let entry = w.add_custom("custom_transform", json!({})).unwrap();
+        let slow = w.add_custom("sleep", json!({})).unwrap();
+        let handler = w.add_custom("capture_error", json!({})).unwrap();
+        w.link(entry, slow);
+        w.on_error(slow, handler);
+        w.set_block_timeout(slow, std::time::Duration::from_millis(30));
+
+        let err = w.run().unwrap_err();
+        assert!(err.to_string().contains("block.timeout"), "got: {err}");
+        let message = captured
+            .lock()
+            .unwrap()
+            .take()
+            .expect("on_error handler ran");
+        let envelope: serde_json::Value = serde_json::from_str(&message).unwrap();
+        assert_eq!(envelope["code"], "block.timeout");
+
+        // The entry block is bounded too.
+        let mut w = Workflow::with_shared_registry(registry);
+        let slow_entry = w.add_custom("sleep", json!({})).unwrap();
+        let after = w.add_custom("custom_transform", json!({})).unwrap();
+        let handler = w.add_custom("capture_error", json!({})).unwrap();
+        w.link(slow_entry, after);
+        w.on_error(slow_entry, handler);
+        w.set_block_timeout(slow_entry, std::time::Duration::from_millis(30));
+
+        let err = w.run().unwrap_err();
+        assert!(err.to_string().contains("block.timeout"), "got: {err}");
+        assert!(captured.lock().unwrap().is_some(), "on_error handler ran");
+    }
+
+    #[test]
+    fn link_on_error_runs_handler_and_run_still_fails() {
+        struct AlwaysFailBlock;
+        impl BlockExecutor for AlwaysFailBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Err(crate::block::BlockError::Other("boom".into()))
+            }
+        }
+
+        struct ErrorToFileBlock {
+            path: String,
+        }
         impl BlockExecutor for ErrorToFileBlock {
             fn execute(
                 &self,
@@ -1048,110 +2681,638 @@ mod tests {
     }
 
     #[test]
-    fn link_with_blockconfig_reference_reuses_registered_block() {
-        let mut w = Workflow::new();
-        let a = BlockConfig::Custom {
-            type_id: "a".to_string(),
-            payload: json!({"k":"a"}),
-            input_from: Box::new([]),
-        };
-        let b = BlockConfig::Custom {
-            type_id: "b".to_string(),
-            payload: json!({"k":"b"}),
-            input_from: Box::new([]),
+    fn recurring_entry_ends_after_max_idle_ticks() {
+        use std::sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
         };
 
-        w.link(&a, &b);
-        w.link(&a, &b);
+        struct EndlessTickEntryBlock;
+        impl BlockExecutor for EndlessTickEntryBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                let (tx, rx) = tokio::sync::mpsc::channel(4);
+                tokio::runtime::Handle::current().spawn(async move {
+                    while tx
+                        .send(BlockOutput::Text {
+                            value: "tick".to_string(),
+                        })
+                        .await
+                        .is_ok()
+                    {}
+                });
+                Ok(crate::block::BlockExecutionResult::Recurring(rx))
+            }
+        }
 
-        assert_eq!(w.nodes.len(), 2, "expected reference endpoints to dedupe");
-        assert_eq!(w.edges.len(), 2, "expected both links to be recorded");
-    }
+        struct AlwaysSkipBlock {
+            calls: Arc<AtomicUsize>,
+        }
+        impl BlockExecutor for AlwaysSkipBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                Err(crate::block::BlockError::Other(
+                    serde_json::json!({ "kind": "no_new_items" }).to_string(),
+                ))
+            }
+        }
 
-    #[test]
-    fn link_with_inline_blockconfig_values_is_one_shot() {
-        let mut w = Workflow::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut registry = BlockRegistry::new();
+        registry.register_custom("endless_entry", |_, _input_from| {
+            Ok(Box::new(EndlessTickEntryBlock))
+        });
+        let calls_for_block = Arc::clone(&calls);
+        registry.register_custom("always_skip", move |_, _input_from| {
+            Ok(Box::new(AlwaysSkipBlock {
+                calls: Arc::clone(&calls_for_block),
+            }))
+        });
 
-        w.link(
-            BlockConfig::Custom {
-                type_id: "a".to_string(),
-                payload: json!({"k":"a"}),
-                input_from: Box::new([]),
-            },
-            BlockConfig::Custom {
-                type_id: "b".to_string(),
-                payload: json!({"k":"b"}),
-                input_from: Box::new([]),
-            },
-        );
-        w.link(
-            BlockConfig::Custom {
-                type_id: "a".to_string(),
-                payload: json!({"k":"a"}),
-                input_from: Box::new([]),
-            },
-            BlockConfig::Custom {
-                type_id: "b".to_string(),
-                payload: json!({"k":"b"}),
-                input_from: Box::new([]),
-            },
-        );
+        let mut w = Workflow::with_registry(registry);
+        let entry_id = w
+            .add_custom("endless_entry", serde_json::json!({}))
+            .expect("add endless_entry");
+        let sink_id = w
+            .add_custom("always_skip", serde_json::json!({}))
+            .expect("add always_skip");
+        w.link(entry_id, sink_id);
+        w.set_max_idle_ticks(3);
 
-        assert_eq!(w.nodes.len(), 4, "expected inline endpoints to be one-shot");
-        assert_eq!(w.edges.len(), 2);
+        let out = w.run().expect("idle limit should complete the run");
+        assert!(matches!(out, BlockOutput::Empty));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
     }
 
     #[test]
-    fn on_error_and_link_on_error_both_add_error_edges() {
-        let mut w = Workflow::new();
-        let src = BlockConfig::Custom {
-            type_id: "src".to_string(),
-            payload: json!({}),
-            input_from: Box::new([]),
-        };
-        let handler = BlockConfig::Custom {
-            type_id: "handler".to_string(),
+    fn run_detailed_reports_execution_mode() {
+        let transform = || BlockConfig::Custom {
+            type_id: "custom_transform".to_string(),
             payload: json!({}),
             input_from: Box::new([]),
         };
 
-        w.on_error(&src, &handler);
-        w.link_on_error(&src, &handler);
+        let mut acyclic = Workflow::with_registry(passthrough_registry());
+        let a = acyclic.add(transform());
+        let b = acyclic.add(transform());
+        acyclic.link(a, b);
+        let report = acyclic.run_detailed().expect("acyclic run");
+        assert_eq!(report.execution_mode, ExecutionMode::Topological);
 
-        assert_eq!(w.error_edges.len(), 2);
-        assert_eq!(
-            w.nodes.len(),
-            2,
-            "expected handler/src refs to resolve to stable block ids"
-        );
+        // The cycle is unreachable from the entry, so the iterative run completes.
+        let mut cyclic = Workflow::with_registry(passthrough_registry());
+        cyclic.add(transform());
+        let x = cyclic.add(transform());
+        let y = cyclic.add(transform());
+        cyclic.link(x, y);
+        cyclic.link(y, x);
+        assert!(matches!(
+            cyclic.run_detailed(),
+            Err(RunError::WorkflowValidation(
+                WorkflowValidationError::CyclicGraph(_)
+            ))
+        ));
+        cyclic.set_allow_cycles(true);
+        let report = cyclic.run_detailed().expect("cyclic run");
+        assert_eq!(report.execution_mode, ExecutionMode::IterativeCycle);
     }
 
     #[test]
-    fn multiple_on_error_handlers_execute_in_parallel() {
-        use std::sync::{
-            Arc,
-            atomic::{AtomicUsize, Ordering},
-        };
-        use std::time::Duration;
-
-        struct AlwaysFailBlock;
-        impl BlockExecutor for AlwaysFailBlock {
+    fn paused_run_reports_approval_block_as_paused_not_failed() {
+        struct AskBlock;
+        impl BlockExecutor for AskBlock {
             fn execute(
                 &self,
                 _ctx: BlockExecutionContext,
             ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
-                Err(crate::block::BlockError::Other("boom".into()))
+                Err(crate::block::BlockError::PendingApproval {
+                    prompt: "ship it?".into(),
+                })
             }
         }
 
-        struct ParallelProbeHandler {
-            active: Arc<AtomicUsize>,
-            max_active: Arc<AtomicUsize>,
-        }
-        impl BlockExecutor for ParallelProbeHandler {
-            fn execute(
-                &self,
-                _ctx: BlockExecutionContext,
+        let registry = || {
+            let mut registry = passthrough_registry();
+            registry.register_custom("ask", |_, _| Ok(Box::new(AskBlock)));
+            registry
+        };
+        let ask = || BlockConfig::Custom {
+            type_id: "ask".to_string(),
+            payload: json!({}),
+            input_from: Box::new([]),
+        };
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        // The approval block runs on the blocking pool after the entry, and in the current task
+        // as the entry itself.
+        let mut mid = Workflow::with_registry(registry());
+        let entry = mid.add_custom("custom_transform", json!({})).unwrap();
+        let approval = mid.add(ask());
+        mid.link(entry, approval);
+        let mut at_entry = Workflow::with_registry(registry());
+        let entry_approval = at_entry.add(ask());
+        let tail = at_entry.add_custom("custom_transform", json!({})).unwrap();
+        at_entry.link(entry_approval, tail);
+
+        for (w, approval) in [(&mid, approval), (&at_entry, entry_approval)] {
+            let def = w.build_definition();
+            let mut run = WorkflowRun::new(&def);
+            let result = rt.block_on(runtime::run_workflow(
+                &def,
+                &mut run,
+                &w.registry,
+                None,
+                None,
+            ));
+            assert!(matches!(result, Err(RunError::PendingApproval { .. })));
+            assert_eq!(run.state, crate::core::RunState::Paused);
+            let reports = run.block_reports();
+            let paused: Vec<_> = reports
+                .iter()
+                .filter(|r| r.status == crate::core::BlockStatus::Paused)
+                .map(|r| r.block_id)
+                .collect();
+            assert_eq!(paused, vec![approval.0]);
+            assert!(
+                reports
+                    .iter()
+                    .all(|r| r.status != crate::core::BlockStatus::Failed),
+                "{reports:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn iterative_run_pauses_for_approval_and_resumes() {
+        struct AskBlock;
+        impl BlockExecutor for AskBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Err(crate::block::BlockError::PendingApproval {
+                    prompt: "continue?".into(),
+                })
+            }
+        }
+
+        let mut registry = passthrough_registry();
+        registry.register_custom("ask", |_, _| Ok(Box::new(AskBlock)));
+        let mut w = Workflow::with_registry(registry);
+        w.add(BlockConfig::Custom {
+            type_id: "ask".to_string(),
+            payload: json!({}),
+            input_from: Box::new([]),
+        });
+        let transform = || BlockConfig::Custom {
+            type_id: "custom_transform".to_string(),
+            payload: json!({}),
+            input_from: Box::new([]),
+        };
+        let x = w.add(transform());
+        let y = w.add(transform());
+        w.link(x, y);
+        w.link(y, x);
+
+        let def = w.build_definition();
+        let mut run = WorkflowRun::new(&def);
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let token = match rt.block_on(runtime::run_workflow(
+            &def,
+            &mut run,
+            &w.registry,
+            None,
+            None,
+        )) {
+            Err(RunError::PendingApproval { token }) => token,
+            other => panic!("expected pending approval, got {other:?}"),
+        };
+        assert_eq!(run.execution_mode(), Some(ExecutionMode::IterativeCycle));
+        let out = rt
+            .block_on(runtime::resume_workflow(
+                &def,
+                &mut run,
+                &w.registry,
+                &token,
+                BlockOutput::String {
+                    value: "approve".into(),
+                },
+            ))
+            .expect("resumed run");
+        assert_eq!(
+            out,
+            BlockOutput::String {
+                value: "approve".into()
+            }
+        );
+    }
+
+    #[test]
+    fn run_collect_ticks_returns_every_tick_output() {
+        struct ThreeTickEntryBlock;
+        impl BlockExecutor for ThreeTickEntryBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                let (tx, rx) = tokio::sync::mpsc::channel(4);
+                tokio::runtime::Handle::current().spawn(async move {
+                    for tick in 1..=3 {
+                        let value = format!("t{tick}");
+                        if tx.send(BlockOutput::Text { value }).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+                Ok(crate::block::BlockExecutionResult::Recurring(rx))
+            }
+        }
+
+        struct EchoBlock;
+        impl BlockExecutor for EchoBlock {
+            fn execute(
+                &self,
+                ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                let value = match ctx.prev {
+                    BlockInput::Text(v) | BlockInput::String(v) => v,
+                    other => format!("{:?}", other),
+                };
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::Text {
+                        value: format!("seen:{value}"),
+                    },
+                ))
+            }
+        }
+
+        let mut registry = BlockRegistry::new();
+        registry.register_custom("three_ticks", |_, _input_from| {
+            Ok(Box::new(ThreeTickEntryBlock))
+        });
+        registry.register_custom("echo", |_, _input_from| Ok(Box::new(EchoBlock)));
+
+        let mut w = Workflow::with_registry(registry);
+        let entry_id = w.add_custom("three_ticks", json!({})).unwrap();
+        let sink_id = w.add_custom("echo", json!({})).unwrap();
+        w.link(entry_id, sink_id);
+
+        let collected = w.run_collect_ticks().expect("collect ticks");
+        assert_eq!(
+            collected,
+            BlockOutput::Json {
+                value: json!(["seen:t1", "seen:t2", "seen:t3"])
+            }
+        );
+        let last = w.run().expect("plain run");
+        assert_eq!(
+            last,
+            BlockOutput::Text {
+                value: "seen:t3".into()
+            }
+        );
+    }
+
+    #[test]
+    fn tick_state_accumulates_across_recurring_ticks() {
+        struct ThreeTickEntryBlock;
+        impl BlockExecutor for ThreeTickEntryBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                let (tx, rx) = tokio::sync::mpsc::channel(4);
+                tokio::runtime::Handle::current().spawn(async move {
+                    for items in [2u64, 3, 5] {
+                        let value = json!({ "items": items });
+                        if tx.send(BlockOutput::Json { value }).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+                Ok(crate::block::BlockExecutionResult::Recurring(rx))
+            }
+        }
+
+        /// Adds each tick's `items` to the shared total and emits the running total.
+        struct CountBlock;
+        impl BlockExecutor for CountBlock {
+            fn execute(
+                &self,
+                ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                let items = match &ctx.prev {
+                    BlockInput::Json(v) => v["items"].as_u64().unwrap_or(0),
+                    _ => 0,
+                };
+                let state = ctx
+                    .tick_state::<u64>()
+                    .ok_or_else(|| BlockError::Other("tick state missing".into()))?;
+                let mut total = state.lock().unwrap();
+                *total += items;
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::Json {
+                        value: json!({ "total": *total }),
+                    },
+                ))
+            }
+        }
+
+        let mut registry = BlockRegistry::new();
+        registry.register_custom("three_ticks", |_, _input_from| {
+            Ok(Box::new(ThreeTickEntryBlock))
+        });
+        registry.register_custom("count", |_, _input_from| Ok(Box::new(CountBlock)));
+
+        let total = Arc::new(Mutex::new(0u64));
+        let mut w = Workflow::with_registry(registry).with_tick_state(total.clone());
+        let entry_id = w.add_custom("three_ticks", json!({})).unwrap();
+        let sink_id = w.add_custom("count", json!({})).unwrap();
+        w.link(entry_id, sink_id);
+
+        let last = w.run().expect("run");
+        assert_eq!(
+            last,
+            BlockOutput::Json {
+                value: json!({ "total": 10 })
+            }
+        );
+        assert_eq!(*total.lock().unwrap(), 10);
+
+        // A second run starts from zero instead of adding to the first run's total.
+        w.run().expect("second run");
+        assert_eq!(*total.lock().unwrap(), 10);
+
+        let w = w.with_tick_summary(|total: &u64| BlockOutput::Text {
+            value: format!("{total} items"),
+        });
+        assert_eq!(
+            w.run().expect("summarized run"),
+            BlockOutput::Text {
+                value: "10 items".into()
+            }
+        );
+    }
+
+    #[test]
+    fn slow_tick_is_skipped_and_later_ticks_proceed() {
+        struct ThreeTickEntryBlock;
+        impl BlockExecutor for ThreeTickEntryBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                let (tx, rx) = tokio::sync::mpsc::channel(4);
+                tokio::runtime::Handle::current().spawn(async move {
+                    for tick in 1..=3 {
+                        let value = format!("t{tick}");
+                        if tx.send(BlockOutput::Text { value }).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+                Ok(crate::block::BlockExecutionResult::Recurring(rx))
+            }
+        }
+
+        struct SlowOnSecondTickBlock;
+        impl BlockExecutor for SlowOnSecondTickBlock {
+            fn execute(
+                &self,
+                ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                let value = match ctx.prev {
+                    BlockInput::Text(v) => v,
+                    other => format!("{:?}", other),
+                };
+                if value == "t2" {
+                    std::thread::sleep(std::time::Duration::from_millis(300));
+                }
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::Text {
+                        value: format!("seen:{value}"),
+                    },
+                ))
+            }
+        }
+
+        let workflow = |policy| {
+            let mut registry = BlockRegistry::new();
+            registry.register_custom("three_ticks", |_, _input_from| {
+                Ok(Box::new(ThreeTickEntryBlock))
+            });
+            registry.register_custom("slow_on_t2", |_, _input_from| {
+                Ok(Box::new(SlowOnSecondTickBlock))
+            });
+            let mut w = Workflow::with_registry(registry);
+            let entry_id = w.add_custom("three_ticks", json!({})).unwrap();
+            let sink_id = w.add_custom("slow_on_t2", json!({})).unwrap();
+            w.link(entry_id, sink_id);
+            w.set_per_tick_timeout(std::time::Duration::from_millis(50), policy);
+            w
+        };
+
+        let collected = workflow(TickTimeoutPolicy::Skip)
+            .run_collect_ticks()
+            .expect("slow tick is skipped");
+        assert_eq!(
+            collected,
+            BlockOutput::Json {
+                value: json!(["seen:t1", "seen:t3"])
+            }
+        );
+
+        let err = workflow(TickTimeoutPolicy::Fail)
+            .run()
+            .expect_err("slow tick fails the run");
+        assert!(matches!(err, RunError::TickTimeout { timeout_ms: 50 }));
+    }
+
+    #[test]
+    fn link_with_blockconfig_reference_reuses_registered_block() {
+        let mut w = Workflow::new();
+        let a = BlockConfig::Custom {
+            type_id: "a".to_string(),
+            payload: json!({"k":"a"}),
+            input_from: Box::new([]),
+        };
+        let b = BlockConfig::Custom {
+            type_id: "b".to_string(),
+            payload: json!({"k":"b"}),
+            input_from: Box::new([]),
+        };
+
+        w.link(&a, &b);
+        w.link(&a, &b);
+
+        assert_eq!(w.nodes.len(), 2, "expected reference endpoints to dedupe");
+        assert_eq!(w.edges.len(), 2, "expected both links to be recorded");
+    }
+
+    #[test]
+    fn duplicate_links_are_deduped_or_rejected_when_strict() {
+        let build = |policy: DuplicateEdgePolicy| {
+            let mut w = Workflow::with_registry(passthrough_registry());
+            let source = w
+                .add_custom("custom_transform", json!({}))
+                .expect("add source");
+            let sink = w
+                .add_custom("custom_transform", json!({}))
+                .expect("add sink");
+            w.link(source, sink);
+            w.link(source, sink);
+            w.set_duplicate_edge_policy(policy);
+            (w, source, sink)
+        };
+
+        let (w, source, sink) = build(DuplicateEdgePolicy::Dedupe);
+        assert_eq!(w.build_definition().edges(), &[(source.0, sink.0)]);
+        w.validate().expect("deduped graph is valid");
+
+        let (w, source, sink) = build(DuplicateEdgePolicy::Error);
+        assert_eq!(w.build_definition().edges().len(), 2);
+        assert!(matches!(
+            w.validate().unwrap_err(),
+            WorkflowValidationError::DuplicateEdge { from, to } if from == source.0 && to == sink.0
+        ));
+    }
+
+    #[test]
+    fn duplicate_policy_keys_on_slot_and_covers_error_links() {
+        let build = |policy: DuplicateEdgePolicy| {
+            let mut w = Workflow::with_registry(passthrough_registry());
+            let source = w
+                .add_custom("custom_transform", json!({}))
+                .expect("add source");
+            let sink = w
+                .add_custom("custom_transform", json!({}))
+                .expect("add sink");
+            let handler = w
+                .add_custom("custom_transform", json!({}))
+                .expect("add handler");
+            w.link_slot(source, "a", sink);
+            w.link_slot(source, "a", sink);
+            w.link_slot(source, "b", sink);
+            w.on_error(source, handler);
+            w.on_error(source, handler);
+            w.set_duplicate_edge_policy(policy);
+            (w, source, sink, handler)
+        };
+
+        let (w, source, sink, handler) = build(DuplicateEdgePolicy::Dedupe);
+        let def = w.build_definition();
+        assert_eq!(def.edges(), &[(source.0, sink.0), (source.0, sink.0)]);
+        assert_eq!(
+            def.edge_slots(),
+            &[
+                (source.0, sink.0, "a".to_string()),
+                (source.0, sink.0, "b".to_string())
+            ]
+        );
+        assert_eq!(def.error_edges(), &[(source.0, handler.0)]);
+
+        let (w, source, sink, handler) = build(DuplicateEdgePolicy::Error);
+        assert_eq!(
+            w.build_definition().duplicate_edges(),
+            vec![(source.0, sink.0), (source.0, handler.0)]
+        );
+    }
+
+    #[test]
+    fn link_with_inline_blockconfig_values_is_one_shot() {
+        let mut w = Workflow::new();
+
+        w.link(
+            BlockConfig::Custom {
+                type_id: "a".to_string(),
+                payload: json!({"k":"a"}),
+                input_from: Box::new([]),
+            },
+            BlockConfig::Custom {
+                type_id: "b".to_string(),
+                payload: json!({"k":"b"}),
+                input_from: Box::new([]),
+            },
+        );
+        w.link(
+            BlockConfig::Custom {
+                type_id: "a".to_string(),
+                payload: json!({"k":"a"}),
+                input_from: Box::new([]),
+            },
+            BlockConfig::Custom {
+                type_id: "b".to_string(),
+                payload: json!({"k":"b"}),
+                input_from: Box::new([]),
+            },
+        );
+
+        assert_eq!(w.nodes.len(), 4, "expected inline endpoints to be one-shot");
+        assert_eq!(w.edges.len(), 2);
+    }
+
+    #[test]
+    fn on_error_and_link_on_error_both_add_error_edges() {
+        let mut w = Workflow::new();
+        let src = BlockConfig::Custom {
+            type_id: "src".to_string(),
+            payload: json!({}),
+            input_from: Box::new([]),
+        };
+        let handler = BlockConfig::Custom {
+            type_id: "handler".to_string(),
+            payload: json!({}),
+            input_from: Box::new([]),
+        };
+
+        w.on_error(&src, &handler);
+        w.link_on_error(&src, &handler);
+
+        assert_eq!(w.error_edges.len(), 2);
+        assert_eq!(
+            w.nodes.len(),
+            2,
+            "expected handler/src refs to resolve to stable block ids"
+        );
+    }
+
+    #[test]
+    fn multiple_on_error_handlers_execute_in_parallel() {
+        use std::sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        };
+        use std::time::Duration;
+
+        struct AlwaysFailBlock;
+        impl BlockExecutor for AlwaysFailBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Err(crate::block::BlockError::Other("boom".into()))
+            }
+        }
+
+        struct ParallelProbeHandler {
+            active: Arc<AtomicUsize>,
+            max_active: Arc<AtomicUsize>,
+        }
+        impl BlockExecutor for ParallelProbeHandler {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
             ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
                 let now_active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
                 let mut prev_max = self.max_active.load(Ordering::SeqCst);
@@ -1232,50 +3393,540 @@ mod tests {
                 &self,
                 _ctx: BlockExecutionContext,
             ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
-                Err(crate::block::BlockError::Other("handler boom".into()))
+                Err(crate::block::BlockError::Other("handler boom".into()))
+            }
+        }
+
+        struct HandlerOk;
+        impl BlockExecutor for HandlerOk {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Ok(crate::block::BlockExecutionResult::Once(BlockOutput::Empty))
+            }
+        }
+
+        let mut registry = BlockRegistry::new();
+        registry.register_custom("always_fail", |_, _input_from| {
+            Ok(Box::new(AlwaysFailBlock))
+        });
+        registry.register_custom("handler_fail", |_, _input_from| Ok(Box::new(HandlerFail)));
+        registry.register_custom("handler_ok", |_, _input_from| Ok(Box::new(HandlerOk)));
+
+        let mut w = Workflow::with_registry(registry);
+        let fail_id = w
+            .add_custom("always_fail", serde_json::json!({}))
+            .expect("add always_fail");
+        let handler_fail = w
+            .add_custom("handler_fail", serde_json::json!({}))
+            .expect("add handler_fail");
+        let handler_ok = w
+            .add_custom("handler_ok", serde_json::json!({}))
+            .expect("add handler_ok");
+
+        w.on_error(fail_id, handler_fail);
+        w.on_error(fail_id, handler_ok);
+
+        let err = w.run().expect_err("source failure should fail workflow");
+        let msg = err.to_string();
+        assert!(
+            msg.contains("source boom"),
+            "expected source error to be preserved, got: {msg}"
+        );
+        assert!(
+            !msg.contains("handler boom"),
+            "handler failure should not replace source error: {msg}"
+        );
+    }
+
+    #[test]
+    fn failed_run_reports_on_error_handler_outputs() {
+        struct AlwaysFailBlock;
+        impl BlockExecutor for AlwaysFailBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Err(crate::block::BlockError::Other("source boom".into()))
+            }
+        }
+
+        struct RecoveryHandler;
+        impl BlockExecutor for RecoveryHandler {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::Text {
+                        value: "partial results".into(),
+                    },
+                ))
+            }
+        }
+
+        let mut registry = BlockRegistry::new();
+        registry.register_custom("always_fail", |_, _input_from| {
+            Ok(Box::new(AlwaysFailBlock))
+        });
+        registry.register_custom("recovery", |_, _input_from| Ok(Box::new(RecoveryHandler)));
+
+        let mut w = Workflow::with_registry(registry);
+        let fail_id = w
+            .add_custom("always_fail", serde_json::json!({}))
+            .expect("add always_fail");
+        let handler_id = w
+            .add_custom("recovery", serde_json::json!({}))
+            .expect("add recovery");
+        w.on_error(fail_id, handler_id);
+
+        // Callers that only need the error still match on it directly.
+        let err = w.run().expect_err("source failure should fail workflow");
+        assert!(err.to_string().contains("source boom"));
+        assert!(matches!(err, RunError::Block(_)));
+
+        let failure = w
+            .run_recovering()
+            .expect_err("source failure should fail workflow");
+        assert!(matches!(failure.error, RunError::Block(_)));
+        assert!(!failure.run_id.is_nil());
+        let handled_by = failure.handled_by;
+        assert_eq!(handled_by.len(), 1);
+        assert_eq!(handled_by[0].handler_id, handler_id.0);
+        assert_eq!(handled_by[0].block_type, "recovery");
+        assert_eq!(
+            handled_by[0].output,
+            BlockOutput::Text {
+                value: "partial results".into()
+            }
+        );
+    }
+
+    #[test]
+    fn run_recovering_reports_on_cancel_handler_outputs() {
+        struct SlowBlock;
+        impl BlockExecutor for SlowBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                std::thread::sleep(std::time::Duration::from_millis(300));
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::empty(),
+                ))
+            }
+        }
+
+        let mut registry = BlockRegistry::new();
+        registry.register_custom("slow", |_, _input_from| Ok(Box::new(SlowBlock)));
+        registry.register_custom("custom_transform", |_, _input_from| {
+            Ok(Box::new(TestPassthroughBlock))
+        });
+        let mut w = Workflow::with_registry(registry);
+        let entry = w.add_custom("custom_transform", json!({})).unwrap();
+        let slow = w.add_custom("slow", json!({})).unwrap();
+        w.link(entry, slow);
+        let cleanup = w.add_custom("custom_transform", json!({})).unwrap();
+        w.on_cancel(cleanup);
+        w.set_run_timeout(std::time::Duration::from_millis(50));
+
+        let failure = w.run_recovering().expect_err("timed-out run should fail");
+        assert!(matches!(
+            failure.error,
+            RunError::RunTimeout { timeout_ms: 50 }
+        ));
+        assert_eq!(failure.handled_by.len(), 1);
+        assert_eq!(failure.handled_by[0].handler_id, cleanup.0);
+    }
+
+    #[tokio::test]
+    async fn cancelled_run_executes_on_cancel_handler() {
+        use std::sync::{Arc, Mutex};
+
+        struct StepBlock;
+        impl BlockExecutor for StepBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::Text {
+                        value: "sent".into(),
+                    },
+                ))
+            }
+        }
+
+        struct SlowBlock;
+        impl BlockExecutor for SlowBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                std::thread::sleep(std::time::Duration::from_millis(300));
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::empty(),
+                ))
+            }
+        }
+
+        struct CleanupBlock(Arc<Mutex<Vec<BlockInput>>>);
+        impl BlockExecutor for CleanupBlock {
+            fn execute(
+                &self,
+                ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                self.0.lock().unwrap().push(ctx.prev);
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::Text {
+                        value: "rolled back".into(),
+                    },
+                ))
+            }
+        }
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = BlockRegistry::new();
+        registry.register_custom("step", |_, _input_from| Ok(Box::new(StepBlock)));
+        registry.register_custom("slow", |_, _input_from| Ok(Box::new(SlowBlock)));
+        let cleanup_seen = Arc::clone(&seen);
+        registry.register_custom("cleanup", move |_, _input_from| {
+            Ok(Box::new(CleanupBlock(Arc::clone(&cleanup_seen))))
+        });
+
+        let mut w = Workflow::with_registry(registry);
+        let step_id = w.add_custom("step", json!({})).expect("add step");
+        let slow_id = w.add_custom("slow", json!({})).expect("add slow");
+        w.link(step_id, slow_id);
+        let cleanup_id = w.add_custom("cleanup", json!({})).expect("add cleanup");
+        assert_eq!(w.on_cancel(cleanup_id), cleanup_id);
+
+        let err = w
+            .run_until_cancelled(tokio::time::sleep(std::time::Duration::from_millis(100)))
+            .await
+            .expect_err("cancelled run should fail");
+        assert!(matches!(err, RunError::Cancelled));
+        let inputs = seen.lock().unwrap().clone();
+        let [BlockInput::Json(summary)] = inputs.as_slice() else {
+            panic!("expected one JSON summary, got {:?}", inputs);
+        };
+        assert_eq!(summary["reason"], json!("cancelled"));
+        assert_eq!(
+            summary["completed_block_ids"],
+            json!([step_id.0.to_string()])
+        );
+
+        seen.lock().unwrap().clear();
+        w.set_run_timeout(std::time::Duration::from_millis(50));
+        let err = w.run_async().await.expect_err("timed-out run should fail");
+        assert!(matches!(err, RunError::RunTimeout { timeout_ms: 50 }));
+        assert_eq!(seen.lock().unwrap().len(), 1);
+
+        // A run that finishes in time leaves the cleanup node alone.
+        w.set_run_timeout(std::time::Duration::from_secs(5));
+        seen.lock().unwrap().clear();
+        w.run_async().await.expect("run within budget");
+        assert!(seen.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn labeled_outputs_route_by_slot_not_link_order() {
+        use std::sync::{Arc, Mutex};
+
+        struct EmitBlock;
+        impl BlockExecutor for EmitBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::empty(),
+                ))
+            }
+        }
+
+        struct LabeledSplitBlock;
+        impl BlockExecutor for LabeledSplitBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Ok(crate::block::BlockExecutionResult::Labeled(vec![
+                    (
+                        "a".into(),
+                        BlockOutput::Text {
+                            value: "alpha".into(),
+                        },
+                    ),
+                    (
+                        "b".into(),
+                        BlockOutput::Text {
+                            value: "beta".into(),
+                        },
+                    ),
+                ]))
+            }
+        }
+
+        struct RecordBlock {
+            name: String,
+            seen: Arc<Mutex<Vec<(String, String)>>>,
+        }
+        impl BlockExecutor for RecordBlock {
+            fn execute(
+                &self,
+                ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                let value = match ctx.prev {
+                    BlockInput::Text(v) | BlockInput::String(v) => v,
+                    other => format!("{:?}", other),
+                };
+                self.seen
+                    .lock()
+                    .unwrap()
+                    .push((self.name.clone(), value.clone()));
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::Text { value },
+                ))
+            }
+        }
+
+        let seen: Arc<Mutex<Vec<(String, String)>>> = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = BlockRegistry::new();
+        registry.register_custom("emit", |_, _input_from| Ok(Box::new(EmitBlock)));
+        registry.register_custom("labeled_split", |_, _input_from| {
+            Ok(Box::new(LabeledSplitBlock))
+        });
+        let seen_for_registry = Arc::clone(&seen);
+        registry.register_custom("record", move |payload, _input_from| {
+            Ok(Box::new(RecordBlock {
+                name: payload["name"].as_str().unwrap_or_default().to_string(),
+                seen: Arc::clone(&seen_for_registry),
+            }))
+        });
+
+        let mut w = Workflow::with_registry(registry);
+        let entry = w.add_custom("emit", serde_json::json!({})).unwrap();
+        let split = w
+            .add_custom("labeled_split", serde_json::json!({}))
+            .unwrap();
+        let sink_a = w
+            .add_custom("record", serde_json::json!({ "name": "sink_a" }))
+            .unwrap();
+        let sink_b = w
+            .add_custom("record", serde_json::json!({ "name": "sink_b" }))
+            .unwrap();
+        w.link(entry, split);
+        // Link "b" first so positional routing would hand "alpha" to sink_b.
+        w.link_slot(split, "b", sink_b);
+        w.link_slot(split, "a", sink_a);
+
+        w.run().expect("labeled routing should succeed");
+        let mut seen = seen.lock().unwrap().clone();
+        seen.sort();
+        assert_eq!(
+            seen,
+            vec![
+                ("sink_a".to_string(), "alpha".to_string()),
+                ("sink_b".to_string(), "beta".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn entry_block_labeled_outputs_route_by_slot() {
+        struct LabeledEntry;
+        impl BlockExecutor for LabeledEntry {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Ok(crate::block::BlockExecutionResult::Labeled(vec![
+                    (
+                        "a".into(),
+                        BlockOutput::Text {
+                            value: "alpha".into(),
+                        },
+                    ),
+                    (
+                        "b".into(),
+                        BlockOutput::Text {
+                            value: "beta".into(),
+                        },
+                    ),
+                ]))
+            }
+        }
+
+        let mut registry = passthrough_registry();
+        registry.register_custom("labeled_entry", |_, _input_from| Ok(Box::new(LabeledEntry)));
+        let mut w = Workflow::with_registry(registry);
+        let split = w.add_custom("labeled_entry", json!({})).unwrap();
+        let sink_b = w.add_custom("custom_transform", json!({})).unwrap();
+        let sink_a = w.add_custom("custom_transform", json!({})).unwrap();
+        let join = w.add_custom("custom_transform", json!({})).unwrap();
+        w.link_slot(split, "b", sink_b);
+        w.link_slot(split, "a", sink_a);
+        w.link(sink_a, join);
+        w.link(sink_b, join);
+
+        let out = w.run().expect("labeled entry routing should succeed");
+        assert_eq!(
+            out,
+            BlockOutput::Json {
+                value: json!([
+                    { "v": "text", "value": "alpha" },
+                    { "v": "text", "value": "beta" }
+                ])
+            }
+        );
+    }
+
+    #[test]
+    fn entry_block_routed_result_skips_other_branches() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct RouteToB;
+        impl BlockExecutor for RouteToB {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Ok(crate::block::BlockExecutionResult::Routed {
+                    slot: "b".into(),
+                    output: BlockOutput::Text {
+                        value: "routed".into(),
+                    },
+                })
+            }
+        }
+        struct Counted(Arc<AtomicUsize>);
+        impl BlockExecutor for Counted {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::empty(),
+                ))
+            }
+        }
+
+        let skipped_runs = Arc::new(AtomicUsize::new(0));
+        let taken_runs = Arc::new(AtomicUsize::new(0));
+        let mut registry = BlockRegistry::new();
+        registry.register_custom("route_to_b", |_, _input_from| Ok(Box::new(RouteToB)));
+        let counter = Arc::clone(&skipped_runs);
+        registry.register_custom("counted", move |_, _input_from| {
+            Ok(Box::new(Counted(Arc::clone(&counter))))
+        });
+        let counter = Arc::clone(&taken_runs);
+        registry.register_custom("taken", move |_, _input_from| {
+            Ok(Box::new(Counted(Arc::clone(&counter))))
+        });
+        let mut w = Workflow::with_registry(registry);
+        let switch = w.add_custom("route_to_b", json!({})).unwrap();
+        let branch_a = w.add_custom("counted", json!({})).unwrap();
+        let after_a = w.add_custom("counted", json!({})).unwrap();
+        let branch_b = w.add_custom("taken", json!({})).unwrap();
+        w.link_slot(switch, "a", branch_a);
+        w.link(branch_a, after_a);
+        w.link_slot(switch, "b", branch_b);
+
+        w.run().expect("routed entry should succeed");
+        assert_eq!(skipped_runs.load(Ordering::SeqCst), 0);
+        assert_eq!(taken_runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn skipped_predecessor_keeps_its_position_in_multi_input() {
+        struct RouteOn;
+        impl BlockExecutor for RouteOn {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Ok(crate::block::BlockExecutionResult::Routed {
+                    slot: "on".into(),
+                    output: BlockOutput::Text { value: "x".into() },
+                })
+            }
+        }
+
+        let mut registry = passthrough_registry();
+        registry.register_custom("route_on", |_, _input_from| Ok(Box::new(RouteOn)));
+        let mut w = Workflow::with_registry(registry);
+        let switch = w.add_custom("route_on", json!({})).unwrap();
+        let first = w.add_custom("custom_transform", json!({})).unwrap();
+        let skipped = w.add_custom("custom_transform", json!({})).unwrap();
+        let third = w.add_custom("custom_transform", json!({})).unwrap();
+        let join = w.add_custom("custom_transform", json!({})).unwrap();
+        w.link_slot(switch, "on", first);
+        w.link_slot(switch, "off", skipped);
+        w.link_slot(switch, "on", third);
+        w.link(first, join);
+        w.link(skipped, join);
+        w.link(third, join);
+
+        assert_eq!(
+            w.run().unwrap(),
+            BlockOutput::Json {
+                value: json!([
+                    { "v": "text", "value": "x" },
+                    { "v": "empty" },
+                    { "v": "text", "value": "x" }
+                ])
+            }
+        );
+    }
+
+    #[test]
+    fn labeled_outputs_fail_when_slot_has_no_output() {
+        struct LabeledOnlyA;
+        impl BlockExecutor for LabeledOnlyA {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Ok(crate::block::BlockExecutionResult::Labeled(vec![(
+                    "a".into(),
+                    BlockOutput::empty(),
+                )]))
             }
         }
-
-        struct HandlerOk;
-        impl BlockExecutor for HandlerOk {
+        struct PassBlock;
+        impl BlockExecutor for PassBlock {
             fn execute(
                 &self,
                 _ctx: BlockExecutionContext,
             ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
-                Ok(crate::block::BlockExecutionResult::Once(BlockOutput::Empty))
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::empty(),
+                ))
             }
         }
 
         let mut registry = BlockRegistry::new();
-        registry.register_custom("always_fail", |_, _input_from| {
-            Ok(Box::new(AlwaysFailBlock))
+        registry.register_custom("pass", |_, _input_from| Ok(Box::new(PassBlock)));
+        registry.register_custom("labeled_only_a", |_, _input_from| {
+            Ok(Box::new(LabeledOnlyA))
         });
-        registry.register_custom("handler_fail", |_, _input_from| Ok(Box::new(HandlerFail)));
-        registry.register_custom("handler_ok", |_, _input_from| Ok(Box::new(HandlerOk)));
-
         let mut w = Workflow::with_registry(registry);
-        let fail_id = w
-            .add_custom("always_fail", serde_json::json!({}))
-            .expect("add always_fail");
-        let handler_fail = w
-            .add_custom("handler_fail", serde_json::json!({}))
-            .expect("add handler_fail");
-        let handler_ok = w
-            .add_custom("handler_ok", serde_json::json!({}))
-            .expect("add handler_ok");
-
-        w.on_error(fail_id, handler_fail);
-        w.on_error(fail_id, handler_ok);
+        let entry = w.add_custom("pass", serde_json::json!({})).unwrap();
+        let split = w
+            .add_custom("labeled_only_a", serde_json::json!({}))
+            .unwrap();
+        let sink = w.add_custom("pass", serde_json::json!({})).unwrap();
+        w.link(entry, split);
+        w.link_slot(split, "missing", sink);
 
-        let err = w.run().expect_err("source failure should fail workflow");
-        let msg = err.to_string();
-        assert!(
-            msg.contains("source boom"),
-            "expected source error to be preserved, got: {msg}"
-        );
+        let err = w.run().expect_err("missing slot output should fail");
         assert!(
-            !msg.contains("handler boom"),
-            "handler failure should not replace source error: {msg}"
+            err.to_string().contains("no output labeled 'missing'"),
+            "got: {err}"
         );
     }
 
@@ -1338,4 +3989,445 @@ mod tests {
         assert_eq!(calls.load(Ordering::SeqCst), 2);
         let _ = child_id; // keep explicit id usage in test for readability.
     }
+
+    #[test]
+    fn child_workflow_builder_helpers_set_retry_and_timeout() {
+        use std::sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        };
+
+        struct FlakyBlock {
+            calls: Arc<AtomicUsize>,
+        }
+        impl BlockExecutor for FlakyBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                let call = self.calls.fetch_add(1, Ordering::SeqCst);
+                if call == 0 {
+                    return Err(crate::block::BlockError::Other("first failure".into()));
+                }
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::String { value: "ok".into() },
+                ))
+            }
+        }
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut registry = BlockRegistry::new();
+        let calls_for_flaky = Arc::clone(&calls);
+        registry.register_custom("flaky", move |_, _input_from| {
+            Ok(Box::new(FlakyBlock {
+                calls: Arc::clone(&calls_for_flaky),
+            }))
+        });
+
+        let child_entry = Uuid::new_v4();
+        let child_def = WorkflowDefinition::builder()
+            .add_node(
+                child_entry,
+                BlockConfig::Custom {
+                    type_id: "flaky".to_string(),
+                    payload: json!({}),
+                    input_from: Box::new([]),
+                },
+            )
+            .set_entry(child_entry)
+            .build();
+
+        let mut w = Workflow::with_registry(registry);
+        let child_id =
+            w.add_child_workflow_with_retry(child_def.clone(), RetryPolicy::exponential(1, 1, 1.0));
+        let output = w
+            .run()
+            .expect("helper-built child should succeed after one retry");
+        let out: Option<String> = output.into();
+        assert_eq!(out.as_deref(), Some("ok"));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        match &w.nodes[&child_id.0] {
+            BlockConfig::ChildWorkflow(config) => {
+                assert_eq!(config.retry_policy.max_retries, 1);
+                assert_eq!(config.timeout_ms, None);
+            }
+            other => panic!("expected child workflow node, got {:?}", other),
+        }
+
+        let timed_id = w.add_child_workflow_with_timeout(child_def, 250);
+        match &w.nodes[&timed_id.0] {
+            BlockConfig::ChildWorkflow(config) => assert_eq!(config.timeout_ms, Some(250)),
+            other => panic!("expected child workflow node, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn child_workflow_honours_its_run_timeout_and_cancel_handlers() {
+        use std::sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        };
+
+        struct StepBlock;
+        impl BlockExecutor for StepBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::Text {
+                        value: "sent".into(),
+                    },
+                ))
+            }
+        }
+
+        struct SlowBlock;
+        impl BlockExecutor for SlowBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                std::thread::sleep(std::time::Duration::from_millis(300));
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::empty(),
+                ))
+            }
+        }
+
+        struct CleanupBlock(Arc<AtomicUsize>);
+        impl BlockExecutor for CleanupBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::empty(),
+                ))
+            }
+        }
+
+        let cleanups = Arc::new(AtomicUsize::new(0));
+        let registry = {
+            let mut registry = BlockRegistry::new();
+            registry.register_custom("step", |_, _input_from| Ok(Box::new(StepBlock)));
+            registry.register_custom("slow", |_, _input_from| Ok(Box::new(SlowBlock)));
+            let cleanups = Arc::clone(&cleanups);
+            registry.register_custom("cleanup", move |_, _input_from| {
+                Ok(Box::new(CleanupBlock(Arc::clone(&cleanups))))
+            });
+            Arc::new(registry)
+        };
+        let child = |run_timeout: Option<std::time::Duration>| {
+            let mut child = Workflow::with_shared_registry(Arc::clone(&registry));
+            let step_id = child.add_custom("step", json!({})).expect("add step");
+            let slow_id = child.add_custom("slow", json!({})).expect("add slow");
+            child.link(step_id, slow_id);
+            let cleanup_id = child.add_custom("cleanup", json!({})).expect("add cleanup");
+            child.on_cancel(cleanup_id);
+            if let Some(timeout) = run_timeout {
+                child.set_run_timeout(timeout);
+            }
+            child.into_definition()
+        };
+
+        // The child's own run timeout stops it and runs its cancel handler.
+        let mut w = Workflow::with_shared_registry(Arc::clone(&registry));
+        w.add_child_workflow(child(Some(std::time::Duration::from_millis(50))));
+        let err = w
+            .run()
+            .expect_err("child run timeout should fail the parent");
+        assert!(err.to_string().contains("50ms budget"), "{err}");
+        assert_eq!(cleanups.load(Ordering::SeqCst), 1);
+
+        // So does the parent's timeout on the child node.
+        let mut w = Workflow::with_shared_registry(Arc::clone(&registry));
+        w.add_child_workflow_with_timeout(child(None), 50);
+        let err = w
+            .run()
+            .expect_err("child node timeout should fail the parent");
+        assert!(err.to_string().contains("child.timeout"), "{err}");
+        assert_eq!(cleanups.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn map_child_workflow_runs_child_per_item_and_collects_outputs() {
+        struct ListSourceBlock;
+        impl BlockExecutor for ListSourceBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::Json {
+                        value: json!(["alpha", "beta", "gamma"]),
+                    },
+                ))
+            }
+        }
+
+        struct UppercaseBlock;
+        impl BlockExecutor for UppercaseBlock {
+            fn execute(
+                &self,
+                ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, crate::block::BlockError> {
+                let value: Option<String> = ctx.prev.into();
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::String {
+                        value: value.unwrap_or_default().to_uppercase(),
+                    },
+                ))
+            }
+        }
+
+        let mut registry = BlockRegistry::new();
+        registry.register_custom("list_source", |_, _input_from| {
+            Ok(Box::new(ListSourceBlock))
+        });
+        registry.register_custom("uppercase", |_, _input_from| Ok(Box::new(UppercaseBlock)));
+
+        let child_entry = Uuid::new_v4();
+        let child_def = WorkflowDefinition::builder()
+            .add_node(
+                child_entry,
+                BlockConfig::Custom {
+                    type_id: "uppercase".to_string(),
+                    payload: json!({}),
+                    input_from: Box::new([]),
+                },
+            )
+            .set_entry(child_entry)
+            .build();
+
+        let mut w = Workflow::with_registry(registry);
+        let source = w.add_custom("list_source", json!({})).unwrap();
+        let map =
+            w.add(crate::block::ChildWorkflowConfig::new(child_def).with_map_concurrency(Some(2)));
+        w.link(source, map);
+
+        let output = w.run().expect("map child workflow runs");
+        assert_eq!(
+            output,
+            BlockOutput::Json {
+                value: json!(["ALPHA", "BETA", "GAMMA"])
+            }
+        );
+    }
+
+    #[test]
+    fn run_checkpointed_resumes_without_re_executing_completed_blocks() {
+        struct StepBlock {
+            name: String,
+            log: Arc<Mutex<Vec<String>>>,
+            fail: Arc<std::sync::atomic::AtomicBool>,
+        }
+        impl BlockExecutor for StepBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, BlockError> {
+                self.log.lock().unwrap().push(self.name.clone());
+                if self.name == "load" && self.fail.load(std::sync::atomic::Ordering::SeqCst) {
+                    return Err(BlockError::Other("process killed".into()));
+                }
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::String {
+                        value: self.name.clone(),
+                    },
+                ))
+            }
+        }
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let fail = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let mut registry = BlockRegistry::new();
+        let (factory_log, factory_fail) = (Arc::clone(&log), Arc::clone(&fail));
+        registry.register_custom("step", move |payload, _input_from| {
+            Ok(Box::new(StepBlock {
+                name: payload["name"].as_str().unwrap_or_default().to_string(),
+                log: Arc::clone(&factory_log),
+                fail: Arc::clone(&factory_fail),
+            }))
+        });
+        let mut w = Workflow::with_registry(registry);
+        let fetch = w.add_custom("step", json!({"name": "fetch"})).unwrap();
+        let transform = w.add_custom("step", json!({"name": "transform"})).unwrap();
+        let load = w.add_custom("step", json!({"name": "load"})).unwrap();
+        w.link(fetch, transform);
+        w.link(transform, load);
+        let dir = tempfile::tempdir().unwrap();
+        w.set_run_store(Arc::new(FileRunStore::new(dir.path())));
+        let run_id = Uuid::new_v4();
+
+        assert!(w.run_checkpointed(run_id).is_err());
+        assert_eq!(*log.lock().unwrap(), vec!["fetch", "transform", "load"]);
+        assert!(dir.path().join(format!("{}.json", run_id)).exists());
+
+        // Restart: a fresh store over the same directory, and the failure is gone.
+        log.lock().unwrap().clear();
+        fail.store(false, std::sync::atomic::Ordering::SeqCst);
+        w.set_run_store(Arc::new(FileRunStore::new(dir.path())));
+        let output = w.run_checkpointed(run_id).unwrap();
+        assert_eq!(
+            output,
+            BlockOutput::String {
+                value: "load".into()
+            }
+        );
+        assert_eq!(*log.lock().unwrap(), vec!["load"]);
+    }
+
+    #[test]
+    fn run_checkpointed_resumes_a_workflow_rebuilt_under_the_same_id() {
+        struct StepBlock {
+            name: String,
+            log: Arc<Mutex<Vec<String>>>,
+            fail: bool,
+        }
+        impl BlockExecutor for StepBlock {
+            fn execute(
+                &self,
+                _ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, BlockError> {
+                self.log.lock().unwrap().push(self.name.clone());
+                if self.name == "load" && self.fail {
+                    return Err(BlockError::Other("process killed".into()));
+                }
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::String {
+                        value: self.name.clone(),
+                    },
+                ))
+            }
+        }
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let dir = tempfile::tempdir().unwrap();
+        // A fresh registry, workflow and store each time, as after a process restart.
+        let build = |id: Uuid, fail: bool| {
+            let mut registry = BlockRegistry::new();
+            let factory_log = Arc::clone(&log);
+            registry.register_custom("step", move |payload, _input_from| {
+                Ok(Box::new(StepBlock {
+                    name: payload["name"].as_str().unwrap_or_default().to_string(),
+                    log: Arc::clone(&factory_log),
+                    fail,
+                }))
+            });
+            let mut w = Workflow::with_registry(registry).with_id(id).unwrap();
+            let fetch = w.add_custom("step", json!({"name": "fetch"})).unwrap();
+            let load = w.add_custom("step", json!({"name": "load"})).unwrap();
+            w.link(fetch, load);
+            w.set_run_store(Arc::new(FileRunStore::new(dir.path())));
+            w
+        };
+        let workflow_id = Uuid::new_v4();
+        let run_id = Uuid::new_v4();
+
+        assert!(build(workflow_id, true).run_checkpointed(run_id).is_err());
+        assert_eq!(*log.lock().unwrap(), vec!["fetch", "load"]);
+
+        log.lock().unwrap().clear();
+        let err = build(Uuid::new_v4(), false)
+            .run_checkpointed(run_id)
+            .unwrap_err();
+        assert!(matches!(err, RunError::Checkpoint(_)), "got: {err}");
+        assert!(log.lock().unwrap().is_empty());
+
+        let output = build(workflow_id, false).run_checkpointed(run_id).unwrap();
+        assert_eq!(
+            output,
+            BlockOutput::String {
+                value: "load".into()
+            }
+        );
+        assert_eq!(*log.lock().unwrap(), vec!["load"]);
+
+        // Block ids are already derived once a block is added, so the id cannot change.
+        let mut w = Workflow::new();
+        w.add_custom("step", json!({"name": "fetch"})).unwrap();
+        assert!(matches!(
+            w.with_id(workflow_id),
+            Err(WorkflowValidationError::IdAfterBlocks { block_count: 1 })
+        ));
+    }
+
+    #[test]
+    fn approval_token_resumes_from_the_run_store_after_a_restart() {
+        struct StepBlock {
+            name: String,
+            log: Arc<Mutex<Vec<String>>>,
+        }
+        impl BlockExecutor for StepBlock {
+            fn execute(
+                &self,
+                ctx: BlockExecutionContext,
+            ) -> Result<crate::block::BlockExecutionResult, BlockError> {
+                self.log.lock().unwrap().push(self.name.clone());
+                if self.name == "ask" {
+                    return Err(BlockError::PendingApproval {
+                        prompt: "publish?".into(),
+                    });
+                }
+                let prev = match ctx.prev {
+                    BlockInput::String(s) => s,
+                    _ => String::new(),
+                };
+                Ok(crate::block::BlockExecutionResult::Once(
+                    BlockOutput::String {
+                        value: format!("{}:{}", self.name, prev),
+                    },
+                ))
+            }
+        }
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let dir = tempfile::tempdir().unwrap();
+        let workflow_id = Uuid::new_v4();
+        // A fresh registry, workflow and store each time, as after a process restart.
+        let build = || {
+            let mut registry = BlockRegistry::new();
+            let factory_log = Arc::clone(&log);
+            registry.register_custom("step", move |payload, _input_from| {
+                Ok(Box::new(StepBlock {
+                    name: payload["name"].as_str().unwrap_or_default().to_string(),
+                    log: Arc::clone(&factory_log),
+                }))
+            });
+            let mut w = Workflow::with_registry(registry)
+                .with_id(workflow_id)
+                .unwrap();
+            let fetch = w.add_custom("step", json!({"name": "fetch"})).unwrap();
+            let ask = w.add_custom("step", json!({"name": "ask"})).unwrap();
+            let publish = w.add_custom("step", json!({"name": "publish"})).unwrap();
+            w.link(fetch, ask);
+            w.link(ask, publish);
+            w.set_run_store(Arc::new(FileRunStore::new(dir.path())));
+            w
+        };
+
+        let token = match build().run() {
+            Err(RunError::PendingApproval { token }) => token,
+            other => panic!("expected pending approval, got {other:?}"),
+        };
+        assert_eq!(*log.lock().unwrap(), vec!["fetch", "ask"]);
+
+        log.lock().unwrap().clear();
+        assert!(matches!(
+            build().resume_with_input("not-a-token", "approve"),
+            Err(RunError::UnknownApprovalToken(_))
+        ));
+        let output = build().resume_with_input(&token, "approve").unwrap();
+        assert_eq!(
+            output,
+            BlockOutput::String {
+                value: "publish:approve".into()
+            }
+        );
+        assert_eq!(*log.lock().unwrap(), vec!["publish"]);
+
+        assert!(matches!(
+            build().resume_with_input(&token, "approve"),
+            Err(RunError::UnknownApprovalToken(_))
+        ));
+    }
 }
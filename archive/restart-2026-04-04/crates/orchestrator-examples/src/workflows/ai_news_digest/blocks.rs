@@ -175,14 +175,7 @@ mod tests {
     use super::*;
 
     fn test_ctx(input: BlockInput) -> BlockExecutionContext {
-        BlockExecutionContext {
-            workflow_id: Default::default(),
-            run_id: Default::default(),
-            block_id: Default::default(),
-            attempt: 1,
-            prev: input,
-            store: Default::default(),
-        }
+        BlockExecutionContext::new(input)
     }
 
     #[test]